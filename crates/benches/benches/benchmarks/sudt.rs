@@ -5,9 +5,8 @@ use gw_common::{
 };
 use gw_config::BackendConfig;
 use gw_generator::{
-    account_lock_manage::AccountLockManage, backend_manage::BackendManage,
-    constants::L2TX_MAX_CYCLES, dummy_state::DummyState, error::TransactionError, traits::StateExt,
-    Generator,
+    account_lock_manage::AccountLockManage, backend_manage::BackendManage, dummy_state::DummyState,
+    error::TransactionError, traits::StateExt, Generator,
 };
 use gw_traits::{ChainStore, CodeStore};
 use gw_types::{
@@ -40,11 +39,15 @@ fn build_backend_manage(rollup_config: &RollupConfig) -> BackendManage {
             validator_path: META_VALIDATOR_PATH.into(),
             generator_path: META_GENERATOR_PATH.into(),
             validator_script_type_hash: META_VALIDATOR_SCRIPT_TYPE_HASH.into(),
+            activation_block_number: 0,
+            native_generator: None,
         },
         BackendConfig {
             validator_path: SUDT_VALIDATOR_PATH.into(),
             generator_path: SUDT_GENERATOR_PATH.into(),
             validator_script_type_hash: sudt_validator_script_type_hash.into(),
+            activation_block_number: 0,
+            native_generator: None,
         },
     ];
     BackendManage::from_config(configs).expect("default backend")
@@ -90,10 +93,12 @@ fn run_contract_get_result<S: State + CodeStore>(
         account_lock_manage,
         rollup_ctx,
         Default::default(),
+        Default::default(),
     );
     let chain_view = DummyChainStore;
+    let max_cycles = generator.execution_limits().l2tx_max_cycles;
     let run_result =
-        generator.execute_transaction(&chain_view, tree, block_info, &raw_tx, L2TX_MAX_CYCLES)?;
+        generator.execute_transaction(&chain_view, tree, block_info, &raw_tx, max_cycles)?;
     tree.apply_run_result(&run_result).expect("update state");
     Ok(run_result)
 }
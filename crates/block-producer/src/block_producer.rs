@@ -1,24 +1,27 @@
 #![allow(clippy::mutable_key_type)]
 
 use crate::{
+    dry_run,
     produce_block::{produce_block, ProduceBlockParam, ProduceBlockResult},
     test_mode_control::TestModeControl,
     types::ChainEvent,
     utils,
 };
-use anyhow::{anyhow, bail, Context, Result};
+use anyhow::{anyhow, bail, Result};
 use ckb_chain_spec::consensus::MAX_BLOCK_BYTES;
 use ckb_types::prelude::Unpack as CKBUnpack;
 use futures::{future::select_all, FutureExt};
 use gw_chain::chain::{Chain, SyncEvent};
+use gw_challenge::offchain::OffChainMockContext;
 use gw_common::{h256_ext::H256Ext, H256};
-use gw_config::{BlockProducerConfig, DebugConfig};
+use gw_config::{BlockProducerConfig, DebugConfig, NodeMode};
 use gw_generator::Generator;
 use gw_jsonrpc_types::test_mode::TestModePayload;
 use gw_mem_pool::{
     custodian::to_custodian_cell,
     pool::{MemPool, OutputParam},
 };
+use gw_metrics::GLOBAL_METRICS;
 use gw_poa::{PoA, ShouldIssueBlock};
 use gw_rpc_client::rpc_client::RPCClient;
 use gw_store::Store;
@@ -27,7 +30,7 @@ use gw_types::{
     core::{DepType, ScriptHashType, Status},
     offchain::{
         global_state_from_slice, CellInfo, CollectedCustodianCells, DepositInfo, InputCellInfo,
-        RollupContext,
+        RollupContext, TxStatus,
     },
     packed::{
         CellDep, CellInput, CellOutput, GlobalState, L2Block, OutPoint, OutPointVec, RollupAction,
@@ -36,7 +39,9 @@ use gw_types::{
     prelude::*,
 };
 use gw_utils::{
-    fee::fill_tx_fee, genesis_info::CKBGenesisInfo, transaction_skeleton::TransactionSkeleton,
+    fee::{fill_tx_fee_with_rate, FeeRateEstimator},
+    genesis_info::CKBGenesisInfo,
+    transaction_skeleton::TransactionSkeleton,
     wallet::Wallet,
 };
 use smol::lock::Mutex;
@@ -44,7 +49,7 @@ use std::{
     collections::{HashMap, HashSet},
     convert::TryFrom,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 const MAX_BLOCK_OUTPUT_PARAM_RETRY_COUNT: usize = 5;
@@ -139,6 +144,27 @@ pub struct BlockProducer {
     rpc_client: RPCClient,
     ckb_genesis_info: CKBGenesisInfo,
     tests_control: Option<TestModeControl>,
+    node_mode: NodeMode,
+    offchain_mock_context: OffChainMockContext,
+    fee_estimator: FeeRateEstimator,
+    pending_block_tx: Option<PendingBlockTx>,
+}
+
+/// Signed block tx with its unsigned skeleton, which is used to bump fee
+pub struct ProducedBlockTx {
+    pub block_number: u64,
+    pub tx: Transaction,
+    pub fee_rate: u64,
+    // tx skeleton without fee cell
+    tx_skeleton: TransactionSkeleton,
+}
+
+struct PendingBlockTx {
+    block_number: u64,
+    tx_hash: H256,
+    fee_rate: u64,
+    tx_skeleton: TransactionSkeleton,
+    submitted_at: Instant,
 }
 
 impl BlockProducer {
@@ -152,10 +178,12 @@ impl BlockProducer {
         rpc_client: RPCClient,
         ckb_genesis_info: CKBGenesisInfo,
         config: BlockProducerConfig,
+        wallet: Wallet,
         debug_config: DebugConfig,
         tests_control: Option<TestModeControl>,
+        node_mode: NodeMode,
+        offchain_mock_context: OffChainMockContext,
     ) -> Result<Self> {
-        let wallet = Wallet::from_config(&config.wallet_config).with_context(|| "init wallet")?;
         let poa = PoA::new(
             rpc_client.clone(),
             wallet.lock_script().clone(),
@@ -163,6 +191,8 @@ impl BlockProducer {
             config.poa_state_dep.clone().into(),
         );
 
+        let fee_estimator = FeeRateEstimator::new(config.fee_config.clone());
+
         let block_producer = BlockProducer {
             rollup_config_hash,
             store,
@@ -176,6 +206,10 @@ impl BlockProducer {
             config,
             debug_config,
             tests_control,
+            node_mode,
+            offchain_mock_context,
+            fee_estimator,
+            pending_block_tx: None,
         };
         Ok(block_producer)
     }
@@ -220,6 +254,11 @@ impl BlockProducer {
             return Ok(());
         }
 
+        // wait pending block tx, which consumes the rollup cell too
+        if self.pending_block_tx.is_some() && self.check_pending_block_tx().await? {
+            return Ok(());
+        }
+
         let median_time = self.rpc_client.get_block_median_time(tip_hash).await?;
         let poa_cell_input = InputCellInfo {
             input: CellInput::new_builder()
@@ -234,8 +273,13 @@ impl BlockProducer {
             .should_issue_next_block(median_time, &poa_cell_input)
             .await?
         {
-            let (block_number, tx) = self.produce_next_block(median_time, rollup_cell).await?;
-            self.submit_block_tx(block_number, tx).await?;
+            let block_tx = self.produce_next_block(median_time, rollup_cell).await?;
+            if NodeMode::DryRun == self.node_mode {
+                self.dry_run_block_tx(block_tx.block_number, block_tx.tx)
+                    .await?;
+            } else {
+                self.submit_block_tx(block_tx).await?;
+            }
         }
         Ok(())
     }
@@ -244,7 +288,7 @@ impl BlockProducer {
         &mut self,
         median_time: Duration,
         rollup_cell: CellInfo,
-    ) -> Result<(u64, Transaction)> {
+    ) -> Result<ProducedBlockTx> {
         if let Some(ref tests_control) = self.tests_control {
             match tests_control.payload().await {
                 Some(TestModePayload::None) => tests_control.clear_none().await?,
@@ -253,6 +297,7 @@ impl BlockProducer {
             }
         }
 
+        let now = Instant::now();
         let mut retry_count = 0;
         while retry_count <= MAX_BLOCK_OUTPUT_PARAM_RETRY_COUNT {
             // get txs & withdrawal requests from mem pool
@@ -279,23 +324,31 @@ impl BlockProducer {
             let ProduceBlockResult {
                 mut block,
                 mut global_state,
+                collected_fees,
             } = block_result;
 
             let number: u64 = block.raw().number().unpack();
             let block_txs = block.transactions().len();
             let block_withdrawals = block.withdrawals().len();
             log::info!(
-                "produce new block #{} (txs: {}, deposits: {}, withdrawals: {})",
+                "produce new block #{} (txs: {}, deposits: {}, withdrawals: {}, fees: {:?})",
                 number,
                 block_txs,
                 deposit_cells.len(),
                 block_withdrawals,
+                collected_fees,
             );
             if !block.withdrawals().is_empty() && opt_finalized_custodians.is_none() {
                 bail!("unexpected none custodians for withdrawals ",);
             }
             let finalized_custodians = opt_finalized_custodians.unwrap_or_default();
 
+            // Merge custodians periodically if they aren't consumed by withdrawals
+            let merge_custodians = {
+                let interval_blocks = self.config.custodian_merge.interval_blocks;
+                block_withdrawals == 0 && interval_blocks != 0 && number % interval_blocks == 0
+            };
+
             if let Some(ref tests_control) = self.tests_control {
                 if let Some(TestModePayload::BadBlock { .. }) = tests_control.payload().await {
                     let (bad_block, bad_global_state) = tests_control
@@ -308,17 +361,28 @@ impl BlockProducer {
             }
 
             // composite tx
-            let tx = match self
-                .complete_tx_skeleton(
-                    deposit_cells,
-                    finalized_custodians,
-                    block,
-                    global_state,
-                    median_time,
-                    rollup_cell.clone(),
-                )
-                .await
-            {
+            let fee_rate = match self.fee_estimator.estimate(&self.rpc_client).await {
+                Ok(fee_rate) => fee_rate,
+                Err(err) => {
+                    log::warn!("[produce_next_block] Failed to estimate fee rate: {}", err);
+                    self.fee_estimator.last_estimated()
+                }
+            };
+            let composite_tx = async {
+                let tx_skeleton = self
+                    .complete_tx_skeleton(
+                        deposit_cells,
+                        finalized_custodians,
+                        block,
+                        global_state,
+                        median_time,
+                        rollup_cell.clone(),
+                    )
+                    .await?;
+                let tx = self.sign_block_tx(tx_skeleton.clone(), fee_rate).await?;
+                Ok::<_, anyhow::Error>((tx, tx_skeleton))
+            };
+            let (tx, tx_skeleton) = match composite_tx.await {
                 Ok(tx) => tx,
                 Err(err) => {
                     log::error!(
@@ -331,16 +395,45 @@ impl BlockProducer {
             };
 
             if tx.as_slice().len() <= MAX_BLOCK_BYTES as usize {
-                return Ok((number, tx));
+                let (tx, tx_skeleton) = if merge_custodians {
+                    match self.merge_custodians(number, &tx_skeleton, fee_rate).await {
+                        Ok(Some(merged)) => merged,
+                        Ok(None) => (tx, tx_skeleton),
+                        Err(err) => {
+                            log::warn!("[produce_next_block] Failed to merge custodians: {}", err);
+                            (tx, tx_skeleton)
+                        }
+                    }
+                } else {
+                    (tx, tx_skeleton)
+                };
+
+                GLOBAL_METRICS
+                    .produce_block_duration
+                    .observe_duration(now.elapsed());
+                return Ok(ProducedBlockTx {
+                    block_number: number,
+                    tx,
+                    fee_rate,
+                    tx_skeleton,
+                });
             }
 
             retry_count += 1;
+            GLOBAL_METRICS.produce_block_retries.inc();
         }
 
         Err(anyhow!("[produce_next_block] package reach max retry"))
     }
 
-    async fn submit_block_tx(&mut self, block_number: u64, tx: Transaction) -> Result<()> {
+    async fn submit_block_tx(&mut self, block_tx: ProducedBlockTx) -> Result<()> {
+        let ProducedBlockTx {
+            block_number,
+            tx,
+            fee_rate,
+            tx_skeleton,
+        } = block_tx;
+
         let cycles = utils::dry_run_transaction(
             &self.debug_config,
             &self.rpc_client,
@@ -348,6 +441,9 @@ impl BlockProducer {
             format!("L2 block {}", block_number).as_str(),
         )
         .await;
+        if let Some(cycles) = cycles {
+            GLOBAL_METRICS.submit_block_cycles.observe(cycles as f64);
+        }
 
         if cycles.is_none()
             || cycles.unwrap_or(0) > self.debug_config.expected_l1_tx_upper_bound_cycles
@@ -368,13 +464,23 @@ impl BlockProducer {
         // send transaction
         match self.rpc_client.send_transaction(tx.clone()).await {
             Ok(tx_hash) => {
+                GLOBAL_METRICS.produced_blocks.inc();
                 log::info!(
-                    "Submitted l2 block {} in tx {}",
+                    "Submitted l2 block {} in tx {}, fee rate {}",
                     block_number,
-                    hex::encode(tx_hash.as_slice())
+                    hex::encode(tx_hash.as_slice()),
+                    fee_rate
                 );
+                self.pending_block_tx = Some(PendingBlockTx {
+                    block_number,
+                    tx_hash,
+                    fee_rate,
+                    tx_skeleton,
+                    submitted_at: Instant::now(),
+                });
             }
             Err(err) => {
+                GLOBAL_METRICS.submit_block_failures.inc();
                 log::error!("Submitting l2 block error: {}", err);
                 self.poa.reset_current_round();
 
@@ -403,6 +509,104 @@ impl BlockProducer {
         Ok(())
     }
 
+    // Return true if the submitted block tx is still pending. Re-submit it with bumped
+    // fee rate if it has been pending longer than `bump_timeout_secs`.
+    async fn check_pending_block_tx(&mut self) -> Result<bool> {
+        let pending = match self.pending_block_tx {
+            Some(ref pending) => pending,
+            None => return Ok(false),
+        };
+
+        match self
+            .rpc_client
+            .get_transaction_status(pending.tx_hash)
+            .await?
+        {
+            Some(TxStatus::Pending) => (),
+            Some(TxStatus::Proposed) => return Ok(true),
+            Some(TxStatus::Committed) => {
+                self.pending_block_tx = None;
+                return Ok(false);
+            }
+            None => {
+                log::warn!(
+                    "Submitted l2 block {} tx {} is dropped",
+                    pending.block_number,
+                    hex::encode(pending.tx_hash.as_slice())
+                );
+                self.pending_block_tx = None;
+                return Ok(false);
+            }
+        }
+
+        let bump_timeout = Duration::from_secs(self.config.fee_config.bump_timeout_secs);
+        if bump_timeout.as_secs() == 0 || pending.submitted_at.elapsed() < bump_timeout {
+            return Ok(true);
+        }
+        let fee_rate = match self.fee_estimator.bump(pending.fee_rate) {
+            Some(fee_rate) => fee_rate,
+            None => {
+                log::warn!(
+                    "Pending l2 block {} tx {} already reach max fee rate {}",
+                    pending.block_number,
+                    hex::encode(pending.tx_hash.as_slice()),
+                    pending.fee_rate
+                );
+                return Ok(true);
+            }
+        };
+
+        let block_number = pending.block_number;
+        let tx_skeleton = pending.tx_skeleton.clone();
+        let tx = self.sign_block_tx(tx_skeleton.clone(), fee_rate).await?;
+        match self.rpc_client.send_transaction(tx).await {
+            Ok(tx_hash) => {
+                log::info!(
+                    "Re-submitted l2 block {} in tx {}, bump fee rate to {}",
+                    block_number,
+                    hex::encode(tx_hash.as_slice()),
+                    fee_rate
+                );
+                self.pending_block_tx = Some(PendingBlockTx {
+                    block_number,
+                    tx_hash,
+                    fee_rate,
+                    tx_skeleton,
+                    submitted_at: Instant::now(),
+                });
+            }
+            Err(err) => log::error!(
+                "Re-submitting l2 block {} with fee rate {} error: {}",
+                block_number,
+                fee_rate,
+                err
+            ),
+        }
+
+        Ok(true)
+    }
+
+    // Verify block tx by dry run and offchain validator, never send it
+    async fn dry_run_block_tx(&mut self, block_number: u64, tx: Transaction) -> Result<()> {
+        let report = dry_run::dry_run_block_tx(
+            &self.rpc_client,
+            &self.offchain_mock_context.rollup_cell_deps,
+            &self.debug_config.debug_tx_dump_path,
+            block_number,
+            tx,
+        )
+        .await;
+        report.log();
+        if let Ok(cycles) = report.dry_run_cycles {
+            GLOBAL_METRICS.submit_block_cycles.observe(cycles as f64);
+        }
+        if report.verify_cycles.is_err() {
+            GLOBAL_METRICS.submit_block_failures.inc();
+        }
+
+        Ok(())
+    }
+
     async fn complete_tx_skeleton(
         &self,
         deposit_cells: Vec<DepositInfo>,
@@ -411,7 +615,7 @@ impl BlockProducer {
         global_state: GlobalState,
         median_time: Duration,
         rollup_cell: CellInfo,
-    ) -> Result<Transaction> {
+    ) -> Result<TransactionSkeleton> {
         let rollup_context = self.generator.rollup_context();
         let mut tx_skeleton = TransactionSkeleton::default();
         let rollup_cell_input_index = tx_skeleton.inputs().len();
@@ -425,11 +629,11 @@ impl BlockProducer {
         // rollup deps
         tx_skeleton
             .cell_deps_mut()
-            .push(self.config.rollup_cell_type_dep.clone().into());
+            .push(self.config.watchtower.rollup_cell_type_dep.clone().into());
         // rollup config cell
         tx_skeleton
             .cell_deps_mut()
-            .push(self.config.rollup_config_cell_dep.clone().into());
+            .push(self.config.watchtower.rollup_config_cell_dep.clone().into());
         // deposit lock dep
         if !deposit_cells.is_empty() {
             let cell_dep: CellDep = self.config.deposit_cell_lock_dep.clone().into();
@@ -596,6 +800,7 @@ impl BlockProducer {
             rollup_context,
             &block,
             &self.config,
+            self.offchain_mock_context.mock_rollup.sudt_bond.as_ref(),
             &self.rpc_client,
             self.wallet.lock_script().to_owned(),
         )
@@ -605,6 +810,9 @@ impl BlockProducer {
         tx_skeleton
             .outputs_mut()
             .push((generated_stake.output, generated_stake.output_data));
+        tx_skeleton
+            .outputs_mut()
+            .extend(generated_stake.change_outputs);
 
         // withdrawal cells
         if let Some(generated_withdrawal_cells) =
@@ -682,11 +890,73 @@ impl BlockProducer {
             *tx_skeleton.cell_deps_mut() = deps.into_iter().cloned().collect();
         }
 
+        Ok(tx_skeleton)
+    }
+
+    // Custodian lock requires the rollup cell in inputs, so fragmented custodians can only
+    // be merged by the block tx. Merging is done on a block tx which already fits in a
+    // layer1 block, and skipped if the merged tx doesn't fit, so it never shrinks the block.
+    async fn merge_custodians(
+        &self,
+        block_number: u64,
+        tx_skeleton: &TransactionSkeleton,
+        fee_rate: u64,
+    ) -> Result<Option<(Transaction, TransactionSkeleton)>> {
+        let rollup_context = self.generator.rollup_context();
+        let merge_config = &self.config.custodian_merge;
+        let last_finalized_block_number =
+            rollup_context.last_finalized_block_number(block_number.saturating_sub(1));
+        let mergeable = self
+            .rpc_client
+            .query_mergeable_custodian_cells(merge_config.max_cells, last_finalized_block_number)
+            .await?;
+        if mergeable.cells_info.len() < merge_config.min_cells {
+            return Ok(None);
+        }
+        let merged_custodians =
+            match crate::withdrawal::merge_custodians(rollup_context, mergeable, &self.config)? {
+                Some(merged_custodians) => merged_custodians,
+                None => return Ok(None),
+            };
+
+        let merged_count = merged_custodians.inputs.len();
+        let mut tx_skeleton = tx_skeleton.clone();
+        for dep in merged_custodians.deps {
+            let cell_deps = tx_skeleton.cell_deps_mut();
+            if !cell_deps.iter().any(|d| d.as_slice() == dep.as_slice()) {
+                cell_deps.push(dep);
+            }
+        }
+        tx_skeleton.inputs_mut().extend(merged_custodians.inputs);
+        tx_skeleton.outputs_mut().extend(merged_custodians.outputs);
+
+        let tx = self.sign_block_tx(tx_skeleton.clone(), fee_rate).await?;
+        let tx_size = tx.as_slice().len();
+        if tx_size > MAX_BLOCK_BYTES as usize {
+            log::warn!(
+                "skip merging {} custodians, block tx size {} exceeds {}",
+                merged_count,
+                tx_size,
+                MAX_BLOCK_BYTES
+            );
+            return Ok(None);
+        }
+
+        log::info!("merge custodians {}", merged_count);
+        Ok(Some((tx, tx_skeleton)))
+    }
+
+    async fn sign_block_tx(
+        &self,
+        mut tx_skeleton: TransactionSkeleton,
+        fee_rate: u64,
+    ) -> Result<Transaction> {
         // tx fee cell
-        fill_tx_fee(
+        fill_tx_fee_with_rate(
             &mut tx_skeleton,
             &self.rpc_client.indexer,
             self.wallet.lock_script().to_owned(),
+            fee_rate,
         )
         .await?;
         debug_assert_eq!(
@@ -695,7 +965,10 @@ impl BlockProducer {
             "check duplicated inputs"
         );
         // sign
-        let tx = self.wallet.sign_tx_skeleton(tx_skeleton)?;
+        let tx = self
+            .wallet
+            .sign_tx_skeleton_with_fee_rate(tx_skeleton, fee_rate)
+            .await?;
         log::debug!("final tx size: {}", tx.as_slice().len());
         Ok(tx)
     }
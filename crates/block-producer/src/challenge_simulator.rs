@@ -0,0 +1,414 @@
+//! Challenge dispute simulator
+//!
+//! Simulate a dispute against a block in local store: enter challenge, cancel
+//! challenge through offchain mock tx and revert. No tx is sent and the store
+//! is opened read-only, nothing is ever committed.
+//!
+//! The simulation runs offline by default. Verifying the cancel challenge tx
+//! requires the rollup cell deps from layer1, it's opt-in and uses an
+//! ephemeral wallet, no wallet config is needed.
+
+use anyhow::{anyhow, bail, Context, Result};
+use ckb_chain_spec::consensus::MAX_BLOCK_BYTES;
+use ckb_crypto::secp::Generator as KeyGenerator;
+use gw_challenge::{
+    cancel_challenge::LoadDataStrategy,
+    context::{build_challenge_context, build_revert_context, build_verify_context},
+    enter_challenge::EnterChallenge,
+    offchain::{
+        mock_cancel_challenge_tx,
+        verify_tx::{verify_tx, TxWithContext},
+        OffChainMockContext,
+    },
+    revert::Revert,
+};
+use gw_common::{blake2b::new_blake2b, H256};
+use gw_config::Config;
+use gw_db::{config::Config as DBConfig, schema::COLUMNS, RocksDB};
+use gw_generator::Generator;
+use gw_store::Store;
+use gw_types::{
+    bytes::Bytes,
+    core::{ChallengeTargetType, ScriptHashType, Status},
+    offchain::{CellInfo, RollupContext},
+    packed::{L2Block, Script},
+    prelude::{Builder, Entity, Pack, Unpack},
+};
+use gw_utils::{sudt::SUDTBond, wallet::Wallet};
+
+use std::sync::Arc;
+
+use crate::{
+    db_block_validator::build_challenge_target,
+    runner::{
+        build_builtin_load_data, build_generator, build_rpc_client, query_ckb_genesis_info,
+        query_secp_data,
+    },
+};
+
+pub struct ChallengeSimulator {
+    pub rollup_context: RollupContext,
+    pub generator: Arc<Generator>,
+    pub sudt_bond: Option<SUDTBond>,
+    pub burn_lock: Script,
+    pub verify_max_cycles: u64,
+    /// Cancel challenge txs are only verified with mock context
+    pub mock_ctx: Option<OffChainMockContext>,
+}
+
+pub struct CancelAttempt {
+    pub load_data_strategy: LoadDataStrategy,
+    pub tx_size: u64,
+    /// Verified cycles, error if mock tx failed to build or verify
+    pub cycles: Result<u64>,
+    /// Cycles and tx size are within limits
+    pub ok: bool,
+}
+
+pub struct RevertReport {
+    pub reverted_blocks: usize,
+    pub rollup_witness_size: usize,
+}
+
+pub struct SimulationReport {
+    pub block_hash: H256,
+    pub tip_block_number: u64,
+    pub enter_challenge_witness_size: usize,
+    pub challenge_capacity: u64,
+    /// None if cancel challenge isn't verified
+    pub cancel_attempts: Option<Vec<CancelAttempt>>,
+    /// None if the challenge would be cancelled
+    pub revert: Option<RevertReport>,
+}
+
+impl SimulationReport {
+    /// None if unknown, cancel challenge isn't verified
+    pub fn cancelable(&self) -> Option<bool> {
+        let attempts = self.cancel_attempts.as_ref()?;
+        Some(attempts.iter().any(|attempt| attempt.ok))
+    }
+
+    pub fn print(&self, block_number: u64, target_index: u32, target_type: ChallengeTargetType) {
+        println!(
+            "simulate challenge block #{} 0x{} target {:?} #{}",
+            block_number,
+            hex::encode(self.block_hash.as_slice()),
+            target_type,
+            target_index
+        );
+        println!(
+            "enter challenge at tip #{}: rollup witness {} bytes, challenge cell capacity {}",
+            self.tip_block_number, self.enter_challenge_witness_size, self.challenge_capacity
+        );
+        match self.cancel_attempts {
+            Some(ref attempts) => {
+                for attempt in attempts {
+                    match attempt.cycles {
+                        Ok(cycles) => println!(
+                            "cancel challenge ({:?} load data): tx {} bytes, cycles {}, {}",
+                            attempt.load_data_strategy,
+                            attempt.tx_size,
+                            cycles,
+                            if attempt.ok { "ok" } else { "exceeded limit" }
+                        ),
+                        Err(ref err) => println!(
+                            "cancel challenge ({:?} load data): tx {} bytes, {}",
+                            attempt.load_data_strategy, attempt.tx_size, err
+                        ),
+                    }
+                }
+            }
+            None => println!("cancel challenge: not verified, run with --verify-cancel"),
+        }
+        if let Some(ref revert) = self.revert {
+            println!(
+                "revert {} blocks: rollup witness {} bytes",
+                revert.reverted_blocks, revert.rollup_witness_size
+            );
+        }
+        match self.cancelable() {
+            Some(true) => println!("result: challenge would be cancelled"),
+            Some(false) => {
+                println!("result: challenge can't be cancelled, blocks would be reverted")
+            }
+            None => println!("result: unknown, blocks would be reverted if cancel fails"),
+        }
+    }
+}
+
+impl ChallengeSimulator {
+    /// Simulate a challenge against `target_index` of block `block_number`.
+    /// Reads `store` in a transaction which is always rolled back.
+    pub fn simulate(
+        &self,
+        store: &Store,
+        block_number: u64,
+        target_index: u32,
+        target_type: ChallengeTargetType,
+    ) -> Result<SimulationReport> {
+        let db = store.begin_transaction();
+        let to_block = |number: u64| -> Result<L2Block> {
+            let hash = db.get_block_hash_by_number(number)?;
+            let block = hash.map(|h| db.get_block(&h)).transpose()?.flatten();
+            block.ok_or_else(|| anyhow!("block #{} not found", number))
+        };
+
+        let block = to_block(block_number)?;
+        let block_hash: H256 = block.hash().into();
+        let target_count = match target_type {
+            ChallengeTargetType::Withdrawal => block.withdrawals().len(),
+            ChallengeTargetType::TxSignature | ChallengeTargetType::TxExecution => {
+                block.transactions().len()
+            }
+        };
+        if target_index as usize >= target_count {
+            bail!(
+                "block #{} has {} {:?} targets, index {} out of range",
+                block_number,
+                target_count,
+                target_type,
+                target_index
+            );
+        }
+        let target = build_challenge_target(block_hash, target_index, target_type);
+
+        // Enter challenge from tip
+        let tip_block = db.get_tip_block()?;
+        let tip_block_number: u64 = tip_block.raw().number().unpack();
+        let tip_global_state = {
+            let state = db.get_block_post_global_state(&tip_block.hash().into())?;
+            state
+                .ok_or_else(|| anyhow!("tip block #{} global state not found", tip_block_number))?
+        };
+        let challenge_context = build_challenge_context(&db, target.clone())?;
+        let owner_lock = match self.mock_ctx {
+            Some(ref mock_ctx) => mock_ctx.mock_rollup.wallet.lock_script().to_owned(),
+            None => Script::default(),
+        };
+        let enter_challenge = EnterChallenge::new(
+            tip_global_state,
+            &self.rollup_context,
+            challenge_context,
+            owner_lock,
+            self.sudt_bond.clone(),
+        );
+        let challenge_output = enter_challenge.build_output();
+        let challenge_cell = {
+            let (output, data) = challenge_output.challenge_cell;
+            CellInfo {
+                output,
+                data,
+                ..Default::default()
+            }
+        };
+        let challenge_capacity: u64 = challenge_cell.output.capacity().unpack();
+
+        // Cancel challenge with offchain mock tx
+        let verify_context = build_verify_context(Arc::clone(&self.generator), &db, &target)?;
+        let cancel_attempts = match self.mock_ctx {
+            Some(ref mock_ctx) => {
+                let global_state = {
+                    let state = db.get_block_post_global_state(&block_hash)?;
+                    let state = state
+                        .ok_or_else(|| anyhow!("block #{} global state not found", block_number))?;
+                    let to_builder = state.as_builder().status((Status::Halting as u8).into());
+                    to_builder.build()
+                };
+
+                let strategies = vec![LoadDataStrategy::Witness, LoadDataStrategy::CellDep];
+                let attempts = strategies.into_iter().map(|load_data_strategy| {
+                    let mock_output = mock_cancel_challenge_tx(
+                        &mock_ctx.mock_rollup,
+                        &mock_ctx.mock_poa,
+                        global_state.clone(),
+                        target.clone(),
+                        verify_context.clone(),
+                        Some(load_data_strategy),
+                    );
+                    let mock_output = match mock_output {
+                        Ok(output) => output,
+                        Err(err) => {
+                            return CancelAttempt {
+                                load_data_strategy,
+                                tx_size: 0,
+                                cycles: Err(err.context("mock tx")),
+                                ok: false,
+                            }
+                        }
+                    };
+
+                    let tx_size = mock_output.tx.as_slice().len() as u64;
+                    let tx_with_context = TxWithContext::from(mock_output);
+                    let cycles = verify_tx(&mock_ctx.rollup_cell_deps, tx_with_context, u64::MAX);
+                    let ok = match cycles {
+                        Ok(cycles) => {
+                            cycles <= self.verify_max_cycles && tx_size <= MAX_BLOCK_BYTES
+                        }
+                        Err(_) => false,
+                    };
+                    CancelAttempt {
+                        load_data_strategy,
+                        tx_size,
+                        cycles,
+                        ok,
+                    }
+                });
+                Some(attempts.collect::<Vec<_>>())
+            }
+            None => None,
+        };
+
+        let mut report = SimulationReport {
+            block_hash,
+            tip_block_number,
+            enter_challenge_witness_size: challenge_output.rollup_witness.as_slice().len(),
+            challenge_capacity,
+            cancel_attempts,
+            revert: None,
+        };
+        if report.cancelable() == Some(true) {
+            db.rollback()?;
+            return Ok(report);
+        }
+
+        // Revert from challenge block to tip, stake cells are unknown offline so rewards
+        // only include challenge cell.
+        let reverted_blocks = (block_number..=tip_block_number)
+            .map(to_block)
+            .collect::<Result<Vec<_>>>()?;
+        let revert_context = build_revert_context(&db, &reverted_blocks)?;
+        // build_revert_context updates reverted_block_smt, must rollback
+        db.rollback()?;
+
+        let revert = Revert::new(
+            &self.rollup_context,
+            challenge_output.post_global_state,
+            &challenge_cell,
+            &[],
+            self.burn_lock.clone(),
+            revert_context,
+        );
+        let revert_output = revert.build_output()?;
+        report.revert = Some(RevertReport {
+            reverted_blocks: reverted_blocks.len(),
+            rollup_witness_size: revert_output.rollup_witness.as_slice().len(),
+        });
+
+        Ok(report)
+    }
+}
+
+pub fn simulate(
+    config: Config,
+    block_number: u64,
+    target_index: u32,
+    target_type: ChallengeTargetType,
+    verify_cancel: bool,
+) -> Result<()> {
+    if config.store.path.as_os_str().is_empty() {
+        bail!("empty store path, no db block to simulate");
+    }
+    let store = {
+        let db_config = DBConfig {
+            path: config.store.path.to_owned(),
+            options: Default::default(),
+            options_file: Default::default(),
+        };
+        let db = RocksDB::open_read_only(&db_config, COLUMNS)
+            .with_context(|| format!("open db {}", config.store.path.to_string_lossy()))?;
+        Store::new(db)
+    };
+
+    let rollup_context = RollupContext {
+        rollup_config: config.genesis.rollup_config.clone().into(),
+        rollup_script_hash: {
+            let rollup_script_hash: [u8; 32] = config.genesis.rollup_type_hash.clone().into();
+            rollup_script_hash.into()
+        },
+    };
+    let generator = build_generator(&config, &rollup_context)?;
+    let sudt_bond = (config.genesis.sudt_bond.as_ref())
+        .map(|sudt_bond| SUDTBond::from_config(&rollup_context, sudt_bond));
+    let validator_config = config.db_block_validator.clone().unwrap_or_default();
+    let burn_lock = match config.block_producer {
+        Some(ref block_producer_config) => block_producer_config
+            .watchtower
+            .challenger_config
+            .burn_lock
+            .clone()
+            .into(),
+        None => Script::default(),
+    };
+
+    let mock_ctx = if verify_cancel {
+        let mut mock_ctx = build_offchain_mock_context(&config, &rollup_context, &sudt_bond)?;
+        if let Some(ref scripts) = validator_config.replace_scripts {
+            mock_ctx = mock_ctx.replace_scripts(scripts)?;
+        }
+        Some(mock_ctx)
+    } else {
+        None
+    };
+
+    let simulator = ChallengeSimulator {
+        rollup_context,
+        generator,
+        sudt_bond,
+        burn_lock,
+        verify_max_cycles: validator_config.verify_max_cycles,
+        mock_ctx,
+    };
+    let report = simulator.simulate(&store, block_number, target_index, target_type)?;
+    report.print(block_number, target_index, target_type);
+
+    Ok(())
+}
+
+/// Mock context to verify cancel challenge txs, rollup cell and its deps are
+/// resolved from layer1. The challenger is an ephemeral secp256k1 sighash
+/// wallet and the rollup cell is unlocked without PoA, same as a watchtower.
+fn build_offchain_mock_context(
+    config: &Config,
+    rollup_context: &RollupContext,
+    sudt_bond: &Option<SUDTBond>,
+) -> Result<OffChainMockContext> {
+    let watchtower_config = match config.block_producer {
+        Some(ref block_producer_config) => block_producer_config.watchtower.clone(),
+        None => config
+            .watchtower
+            .clone()
+            .ok_or_else(|| anyhow!("verify cancel require block producer or watchtower config"))?,
+    };
+    let rpc_client = build_rpc_client(config, rollup_context)?;
+    let secp_data = query_secp_data(config, &rpc_client)?;
+    let ckb_genesis_info = query_ckb_genesis_info(&rpc_client)?;
+
+    let wallet = {
+        let privkey = KeyGenerator::random_privkey();
+        let pubkey = privkey.pubkey().map_err(|err| anyhow!("{}", err))?;
+        let mut hasher = new_blake2b();
+        hasher.update(&pubkey.serialize());
+        let mut pubkey_hash = [0u8; 32];
+        hasher.finalize(&mut pubkey_hash);
+        let lock = Script::new_builder()
+            .code_hash(ckb_genesis_info.sighash_type_hash().pack())
+            .hash_type(ScriptHashType::Type.into())
+            .args(Bytes::copy_from_slice(&pubkey_hash[..20]).pack())
+            .build();
+        Wallet::new(privkey, lock)
+    };
+
+    let ckb_genesis_info = gw_challenge::offchain::CKBGenesisInfo {
+        sighash_dep: ckb_genesis_info.sighash_dep(),
+    };
+    smol::block_on(OffChainMockContext::build(
+        &rpc_client,
+        None,
+        rollup_context.clone(),
+        wallet,
+        watchtower_config,
+        ckb_genesis_info,
+        build_builtin_load_data(config, &secp_data),
+        sudt_bond.clone(),
+    ))
+}
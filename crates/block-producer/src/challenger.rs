@@ -9,7 +9,7 @@ use ckb_types::prelude::{Builder, Entity, Reader};
 use gw_chain::chain::{Chain, ChallengeCell, SyncEvent};
 use gw_challenge::cancel_challenge::{
     CancelChallengeOutput, LoadData, LoadDataContext, LoadDataStrategy, RecoverAccounts,
-    RecoverAccountsContext,
+    RecoverAccountsContext, MAX_CANCEL_CYCLES, MAX_CANCEL_TX_BYTES,
 };
 use gw_challenge::enter_challenge::EnterChallenge;
 use gw_challenge::offchain::verify_tx::{verify_tx, TxWithContext};
@@ -17,7 +17,7 @@ use gw_challenge::offchain::{mock_cancel_challenge_tx, OffChainMockContext};
 use gw_challenge::revert::Revert;
 use gw_challenge::types::{RevertContext, VerifyContext};
 use gw_common::H256;
-use gw_config::{BlockProducerConfig, DebugConfig};
+use gw_config::{DebugConfig, WatchtowerConfig};
 use gw_generator::ChallengeContext;
 use gw_jsonrpc_types::test_mode::TestModePayload;
 use gw_poa::{PoA, ShouldIssueBlock};
@@ -34,6 +34,7 @@ use gw_types::packed::{
 use gw_types::prelude::{Pack, Unpack};
 use gw_utils::fee::fill_tx_fee;
 use gw_utils::genesis_info::CKBGenesisInfo;
+use gw_utils::sudt::collect_sudt;
 use gw_utils::transaction_skeleton::TransactionSkeleton;
 use gw_utils::wallet::Wallet;
 use smol::lock::Mutex;
@@ -43,18 +44,16 @@ use std::convert::TryFrom;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-const MAX_CANCEL_CYCLES: u64 = 7000_0000;
-const MAX_CANCEL_TX_BYTES: u64 = ckb_chain_spec::consensus::MAX_BLOCK_BYTES;
-
 pub struct Challenger {
     rollup_context: RollupContext,
     rpc_client: RPCClient,
     wallet: Wallet,
-    config: BlockProducerConfig,
+    config: WatchtowerConfig,
     ckb_genesis_info: CKBGenesisInfo,
     builtin_load_data: HashMap<H256, CellDep>,
     chain: Arc<Mutex<Chain>>,
-    poa: Arc<Mutex<PoA>>,
+    // None for a standalone watchtower, which isn't a PoA identity
+    poa: Option<Arc<Mutex<PoA>>>,
     tests_control: Option<TestModeControl>,
     cleaner: Arc<Cleaner>,
     debug_config: DebugConfig,
@@ -67,12 +66,12 @@ impl Challenger {
         rollup_context: RollupContext,
         rpc_client: RPCClient,
         wallet: Wallet,
-        config: BlockProducerConfig,
+        config: WatchtowerConfig,
         debug_config: DebugConfig,
         builtin_load_data: HashMap<H256, CellDep>,
         ckb_genesis_info: CKBGenesisInfo,
         chain: Arc<Mutex<Chain>>,
-        poa: Arc<Mutex<PoA>>,
+        poa: Option<Arc<Mutex<PoA>>>,
         tests_control: Option<TestModeControl>,
         cleaner: Arc<Cleaner>,
         offchain_mock_context: OffChainMockContext,
@@ -108,8 +107,10 @@ impl Challenger {
         let median_time = self.rpc_client.get_block_median_time(tip_hash).await?;
         let rollup = RollupState::query(&self.rpc_client).await?;
 
-        {
-            let mut poa = self.poa.lock().await;
+        // Block producer challenges in its PoA round. A standalone watchtower isn't a PoA
+        // identity, it challenges on any bad block.
+        if let Some(ref poa) = self.poa {
+            let mut poa = poa.lock().await;
             let rollup_input = rollup.rollup_input();
             let check_lock = poa.should_issue_next_block(median_time, &rollup_input);
             if ShouldIssueBlock::Yes != check_lock.await? {
@@ -149,6 +150,23 @@ impl Challenger {
                         .map_err(|_| anyhow!("invalid challenge type"))?;
                     log::info!("challenge block 0x{} target {} type {:?}", hash, idx, type_);
                 }
+                // Make sure a cancel challenge tx can't pass, otherwise we lose our stake
+                {
+                    let chain = self.chain.lock().await;
+                    let target = context.target.as_slice();
+                    let confirmation = chain.bad_block_confirmation();
+                    match confirmation.filter(|c| c.target.as_slice() == target) {
+                        Some(c) if !c.confirmed => {
+                            log::error!("skip challenge, bad block not confirmed {:?}", c);
+                            return Ok(());
+                        }
+                        Some(_) => (),
+                        None => {
+                            log::error!("skip challenge, bad block without offchain confirmation");
+                            return Ok(());
+                        }
+                    }
+                }
                 self.challenge_block(rollup, context, median_time).await
             }
             SyncEvent::BadChallenge { cell, context } => {
@@ -198,8 +216,14 @@ impl Challenger {
             challenger_config.rewards_receiver_lock.clone().into()
         };
         let prev_state = rollup_state.get_state().to_owned();
-        let enter_challenge =
-            EnterChallenge::new(prev_state, &self.rollup_context, context, rewards_lock);
+        let sudt_bond = self.offchain_mock_context.mock_rollup.sudt_bond.clone();
+        let enter_challenge = EnterChallenge::new(
+            prev_state,
+            &self.rollup_context,
+            context,
+            rewards_lock,
+            sudt_bond.clone(),
+        );
         let challenge_output = enter_challenge.build_output();
 
         // Build challenge transaction
@@ -222,13 +246,7 @@ impl Challenger {
         tx_skeleton.witnesses_mut().push(rollup_witness);
 
         // Poa
-        {
-            let poa = self.poa.lock().await;
-            let generated_poa = poa
-                .generate(&tx_skeleton.inputs()[0], tx_skeleton.inputs(), median_time)
-                .await?;
-            tx_skeleton.fill_poa(generated_poa, 0)?;
-        }
+        self.fill_poa(&mut tx_skeleton, median_time).await?;
 
         // Challenge
         let challenge_cell = challenge_output.challenge_cell;
@@ -237,9 +255,27 @@ impl Challenger {
         let challenger_lock_dep = self.ckb_genesis_info.sighash_dep();
         let challenger_lock = self.wallet.lock_script().to_owned();
         tx_skeleton.cell_deps_mut().push(challenger_lock_dep);
+
+        // Challenge bond in sudt
+        if let Some(sudt_bond) = sudt_bond {
+            let collected = collect_sudt(
+                &self.rpc_client,
+                challenger_lock.clone(),
+                sudt_bond.sudt_type,
+                sudt_bond.challenge_amount,
+                &HashSet::new(),
+            )
+            .await?;
+
+            let sudt_type_dep = self.config.l1_sudt_type_dep.clone().into();
+            tx_skeleton.cell_deps_mut().push(sudt_type_dep);
+            tx_skeleton.inputs_mut().extend(collected.inputs);
+            tx_skeleton.outputs_mut().extend(collected.change);
+        }
+
         fill_tx_fee(&mut tx_skeleton, &self.rpc_client.indexer, challenger_lock).await?;
 
-        let tx = self.wallet.sign_tx_skeleton(tx_skeleton)?;
+        let tx = self.wallet.sign_tx_skeleton(tx_skeleton).await?;
 
         utils::dry_run_transaction(
             &self.debug_config,
@@ -368,11 +404,11 @@ impl Challenger {
         );
         match self.rpc_client.send_transaction(tx).await {
             Ok(tx_hash) => {
-                self.cleaner.watch_verifier(verifier, Some(tx_hash)).await;
+                self.cleaner.watch_verifier(verifier, Some(tx_hash)).await?;
                 log::info!("Cancel challenge in tx {}", to_hex(&tx_hash));
             }
             Err(err) => {
-                self.cleaner.watch_verifier(verifier, None).await;
+                self.cleaner.watch_verifier(verifier, None).await?;
                 log::warn!("Cancel challenge failed {}", err);
             }
         }
@@ -469,13 +505,13 @@ impl Challenger {
         tx_skeleton.witnesses_mut().push(rollup_witness);
 
         // Poa
-        {
-            let poa = self.poa.lock().await;
-            let generated_poa = poa
-                .generate(&tx_skeleton.inputs()[0], tx_skeleton.inputs(), median_time)
-                .await?;
-            tx_skeleton.fill_poa(generated_poa, 0)?;
-        }
+        self.fill_poa(&mut tx_skeleton, median_time).await?;
+
+        // Stake and challenge cells bonded in sudt
+        let bonded_in_sudt = {
+            let mut cells = stake_cells.iter().chain(std::iter::once(&challenge_cell));
+            cells.any(|cell| cell.output.type_().is_some())
+        };
 
         // Challenge
         let challenge_input = to_input_cell_info_with_since(challenge_cell, since);
@@ -489,6 +525,11 @@ impl Challenger {
         tx_skeleton.cell_deps_mut().push(stake_dep);
         tx_skeleton.inputs_mut().extend(stake_inputs);
 
+        if bonded_in_sudt {
+            let sudt_type_dep = self.config.l1_sudt_type_dep.clone().into();
+            tx_skeleton.cell_deps_mut().push(sudt_type_dep);
+        }
+
         // Rewards
         tx_skeleton.outputs_mut().extend(revert_output.reward_cells);
 
@@ -500,7 +541,7 @@ impl Challenger {
         tx_skeleton.cell_deps_mut().push(challenger_lock_dep);
         fill_tx_fee(&mut tx_skeleton, &self.rpc_client.indexer, challenger_lock).await?;
 
-        let tx = self.wallet.sign_tx_skeleton(tx_skeleton)?;
+        let tx = self.wallet.sign_tx_skeleton(tx_skeleton).await?;
 
         utils::dry_run_transaction(
             &self.debug_config,
@@ -522,6 +563,22 @@ impl Challenger {
         Ok(())
     }
 
+    // Unlock PoA locked rollup cell, skipped by a standalone watchtower
+    async fn fill_poa(
+        &self,
+        tx_skeleton: &mut TransactionSkeleton,
+        median_time: Duration,
+    ) -> Result<()> {
+        let poa = match self.poa {
+            Some(ref poa) => poa.lock().await,
+            None => return Ok(()),
+        };
+        let generated_poa = poa
+            .generate(&tx_skeleton.inputs()[0], tx_skeleton.inputs(), median_time)
+            .await?;
+        tx_skeleton.fill_poa(generated_poa, 0)
+    }
+
     async fn build_verifier_tx(
         &self,
         verifier: (CellOutput, Bytes),
@@ -544,7 +601,7 @@ impl Challenger {
         tx_skeleton.cell_deps_mut().push(challenger_lock_dep);
         fill_tx_fee(&mut tx_skeleton, &self.rpc_client.indexer, challenger_lock).await?;
 
-        self.wallet.sign_tx_skeleton(tx_skeleton)
+        self.wallet.sign_tx_skeleton(tx_skeleton).await
     }
 
     async fn build_cancel_tx(
@@ -579,6 +636,11 @@ impl Challenger {
         tx_skeleton.cell_deps_mut().push(challenge_dep);
         tx_skeleton.inputs_mut().push(challenge_input);
         tx_skeleton.witnesses_mut().push(challenge_witness);
+        if let Some(sudt_return_cell) = cancel_output.sudt_return_cell {
+            let sudt_type_dep = self.config.l1_sudt_type_dep.clone().into();
+            tx_skeleton.cell_deps_mut().push(sudt_type_dep);
+            tx_skeleton.outputs_mut().push(sudt_return_cell);
+        }
 
         // Verifier
         let verifier_tx_hash = verifier_context.tx_hash();
@@ -637,13 +699,7 @@ impl Challenger {
         }
 
         // Poa
-        {
-            let poa = self.poa.lock().await;
-            let generated_poa = poa
-                .generate(&tx_skeleton.inputs()[0], tx_skeleton.inputs(), median_time)
-                .await?;
-            tx_skeleton.fill_poa(generated_poa, 0)?;
-        }
+        self.fill_poa(&mut tx_skeleton, median_time).await?;
 
         // ensure no cell dep duplicate
         {
@@ -653,7 +709,7 @@ impl Challenger {
 
         let owner_lock = self.wallet.lock_script().to_owned();
         fill_tx_fee(&mut tx_skeleton, &self.rpc_client.indexer, owner_lock).await?;
-        self.wallet.sign_tx_skeleton(tx_skeleton)
+        self.wallet.sign_tx_skeleton(tx_skeleton).await
     }
 
     async fn query_owner_cell_for_verifier(
@@ -1,5 +1,6 @@
 use crate::types::ChainEvent;
 use gw_utils::genesis_info::CKBGenesisInfo;
+use gw_utils::sudt::{build_sudt_cell, parse_sudt};
 use gw_utils::transaction_skeleton::TransactionSkeleton;
 use gw_utils::{fee::fill_tx_fee, wallet::Wallet};
 
@@ -8,10 +9,11 @@ use ckb_types::prelude::{Builder, Entity};
 use gw_challenge::cancel_challenge::RecoverAccountsContext;
 use gw_common::H256;
 use gw_rpc_client::rpc_client::RPCClient;
+use gw_store::Store;
 use gw_types::core::Status;
 use gw_types::offchain::{global_state_from_slice, CellInfo, InputCellInfo, TxStatus};
-use gw_types::packed::{CellDep, CellInput, Transaction, WitnessArgs};
-use gw_types::prelude::Unpack;
+use gw_types::packed::{self, CellDep, CellInput, CellOutput, OutPoint, Transaction, WitnessArgs};
+use gw_types::prelude::{Pack, PackVec, Reader, Unpack};
 
 use smol::lock::Mutex;
 use std::collections::HashSet;
@@ -49,27 +51,87 @@ impl Verifier {
     pub fn tx_hash(&self) -> H256 {
         self.input.input.previous_output().tx_hash().unpack()
     }
+
+    pub fn out_point(&self) -> OutPoint {
+        self.input.input.previous_output()
+    }
+}
+
+impl Pack<packed::Verifier> for Verifier {
+    fn pack(&self) -> packed::Verifier {
+        let recover_accounts_context = self.recover_accounts_context.as_ref().map(|ctx| {
+            packed::RecoverAccountsContext::new_builder()
+                .cell_deps(ctx.cell_deps.clone().pack())
+                .inputs(ctx.inputs.pack())
+                .witnesses(ctx.witnesses.clone().pack())
+                .build()
+        });
+
+        packed::Verifier::new_builder()
+            .load_data_inputs(self.load_data_inputs.pack())
+            .recover_accounts_context(recover_accounts_context.pack())
+            .cell_dep(self.cell_dep.clone())
+            .input(self.input.pack())
+            .witness(self.witness.clone().pack())
+            .build()
+    }
+}
+
+impl<'r> Unpack<Verifier> for packed::VerifierReader<'r> {
+    fn unpack(&self) -> Verifier {
+        let recover_accounts_context =
+            self.recover_accounts_context()
+                .to_opt()
+                .map(|ctx| RecoverAccountsContext {
+                    cell_deps: ctx.cell_deps().iter().map(|d| d.to_entity()).collect(),
+                    inputs: ctx.inputs().unpack(),
+                    witnesses: ctx.witnesses().iter().map(|w| w.to_entity()).collect(),
+                });
+
+        Verifier {
+            load_data_inputs: self.load_data_inputs().unpack(),
+            recover_accounts_context,
+            cell_dep: self.cell_dep().to_entity(),
+            input: self.input().unpack(),
+            witness: self.witness().to_opt().map(|w| w.to_entity()),
+        }
+    }
 }
 
 type ConsumedVerifiers = Arc<Mutex<Vec<(Verifier, Option<H256>)>>>;
 
-// TODO: verifier persistent, signature verifier needs witness to unlock, but verifier itself
-// doesn't provides context to restore this witness.
+// Verifiers are persisted with their reclaim context (recover accounts cells and witnesses),
+// so cleaner can resume reclaiming after restart.
 pub struct Cleaner {
     rpc_client: RPCClient,
     ckb_genesis_info: CKBGenesisInfo,
     wallet: Wallet,
+    store: Store,
+    sudt_type_dep: CellDep,
     consumed_verifiers: ConsumedVerifiers,
 }
 
 impl Cleaner {
-    pub fn new(rpc_client: RPCClient, ckb_genesis_info: CKBGenesisInfo, wallet: Wallet) -> Self {
-        Cleaner {
+    pub fn new(
+        rpc_client: RPCClient,
+        ckb_genesis_info: CKBGenesisInfo,
+        wallet: Wallet,
+        store: Store,
+        sudt_type_dep: CellDep,
+    ) -> Result<Self> {
+        let consumed_verifiers = load_verifiers(&store)?;
+        if !consumed_verifiers.is_empty() {
+            log::info!("resume {} verifiers from store", consumed_verifiers.len());
+        }
+
+        Ok(Cleaner {
             rpc_client,
             ckb_genesis_info,
             wallet,
-            consumed_verifiers: Arc::new(Mutex::new(Vec::new())),
-        }
+            store,
+            sudt_type_dep,
+            consumed_verifiers: Arc::new(Mutex::new(consumed_verifiers)),
+        })
     }
 
     pub async fn handle_event(&self, _event: ChainEvent) -> Result<()> {
@@ -83,11 +145,17 @@ impl Cleaner {
         Ok(())
     }
 
-    pub async fn watch_verifier(&self, verifier: Verifier, consumed_tx: Option<H256>) {
+    pub async fn watch_verifier(
+        &self,
+        verifier: Verifier,
+        consumed_tx: Option<H256>,
+    ) -> Result<()> {
         self.consumed_verifiers
             .lock()
             .await
-            .push((verifier, consumed_tx));
+            .push((verifier.clone(), consumed_tx));
+
+        persist_verifier(&self.store, &verifier, consumed_tx)
     }
 
     pub async fn prune(&self) -> Result<()> {
@@ -115,14 +183,20 @@ impl Cleaner {
             }
         }
 
-        {
-            self.consumed_verifiers
-                .lock()
-                .await
-                .retain(|(_, consumed_tx_hash)| match consumed_tx_hash {
-                    None => true,
-                    Some(consumed_tx_hash) => !confirmed.contains(consumed_tx_hash),
-                });
+        let pruned: Vec<OutPoint> = {
+            let mut verifiers = self.consumed_verifiers.lock().await;
+            let is_confirmed = |(_, consumed_tx_hash): &(Verifier, Option<H256>)| {
+                consumed_tx_hash.map_or(false, |tx_hash| confirmed.contains(&tx_hash))
+            };
+            let (pruned, remained): (Vec<_>, Vec<_>) = verifiers.drain(..).partition(is_confirmed);
+            *verifiers = remained;
+
+            let to_out_point = pruned.into_iter().map(|(verifier, _)| verifier.out_point());
+            to_out_point.collect()
+        };
+
+        if !pruned.is_empty() {
+            delete_verifiers(&self.store, &pruned)?;
         }
 
         Ok(())
@@ -159,13 +233,14 @@ impl Cleaner {
             }
 
             let verifier_tx = verifier.tx_hash();
-            let tx = self.build_reclaim_verifier_tx(verifier).await?;
+            let tx = self.build_reclaim_verifier_tx(verifier.clone()).await?;
             let tx_hash = rpc_client.send_transaction(tx).await?;
 
             {
                 let mut verifiers = self.consumed_verifiers.lock().await;
                 verifiers.get_mut(idx).expect("exists").1 = Some(tx_hash);
             }
+            persist_verifier(&self.store, &verifier, Some(tx_hash))?;
 
             log::info!(
                 "reclaim verifier {} in tx {}",
@@ -208,8 +283,23 @@ impl Cleaner {
             tx_skeleton.witnesses_mut().push(verifier_witness);
         }
 
-        // Verifier cell need an owner cell to unlock
+        // Return sudt in reclaimed cells to owner, otherwise it's burnt
         let owner_lock = self.wallet.lock_script().to_owned();
+        let sudt_outputs = {
+            let inputs = tx_skeleton.inputs().iter();
+            let sudt_cells = inputs.filter_map(|input| parse_sudt(&input.cell));
+            let to_output = sudt_cells.map(|(sudt_type, amount)| {
+                let output = CellOutput::new_builder().lock(owner_lock.clone()).build();
+                build_sudt_cell(output, sudt_type, amount)
+            });
+            to_output.collect::<Result<Vec<_>>>()?
+        };
+        if !sudt_outputs.is_empty() {
+            tx_skeleton.cell_deps_mut().push(self.sudt_type_dep.clone());
+            tx_skeleton.outputs_mut().extend(sudt_outputs);
+        }
+
+        // Verifier cell need an owner cell to unlock
         let rpc_client = &self.rpc_client;
         let owner_input = {
             let query = rpc_client.query_owner_cell(owner_lock, None).await?;
@@ -224,10 +314,51 @@ impl Cleaner {
 
         let owner_lock = self.wallet.lock_script().to_owned();
         fill_tx_fee(&mut tx_skeleton, &self.rpc_client.indexer, owner_lock).await?;
-        self.wallet.sign_tx_skeleton(tx_skeleton)
+        self.wallet.sign_tx_skeleton(tx_skeleton).await
     }
 }
 
+/// Load persisted verifiers and their consumed tx hashes
+pub fn load_verifiers(store: &Store) -> Result<Vec<(Verifier, Option<H256>)>> {
+    let db = store.begin_transaction();
+    let verifiers = db.get_cleaner_verifiers()?.into_iter();
+    let to_unpack = verifiers.map(|consumed| {
+        let verifier = consumed.verifier().as_reader().unpack();
+        let consumed_tx_hash = consumed.consumed_tx_hash().to_opt();
+        (verifier, consumed_tx_hash.map(|h| h.unpack()))
+    });
+
+    Ok(to_unpack.collect())
+}
+
+/// Insert or update a verifier, keyed by its out point
+pub fn persist_verifier(
+    store: &Store,
+    verifier: &Verifier,
+    consumed_tx: Option<H256>,
+) -> Result<()> {
+    let consumed_verifier = packed::ConsumedVerifier::new_builder()
+        .verifier(verifier.pack())
+        .consumed_tx_hash(consumed_tx.pack())
+        .build();
+
+    let db = store.begin_transaction();
+    db.set_cleaner_verifier(&verifier.out_point(), &consumed_verifier)?;
+    db.commit()?;
+
+    Ok(())
+}
+
+pub fn delete_verifiers(store: &Store, out_points: &[OutPoint]) -> Result<()> {
+    let db = store.begin_transaction();
+    for out_point in out_points {
+        db.delete_cleaner_verifier(out_point)?;
+    }
+    db.commit()?;
+
+    Ok(())
+}
+
 fn to_input_cell_info(cell_info: CellInfo) -> InputCellInfo {
     InputCellInfo {
         input: CellInput::new_builder()
@@ -47,12 +47,13 @@ fn build_validator(config: Config) -> Result<DBBlockCancelChallengeValidator> {
     let base = BaseInitComponents::init(&config, true)?;
     let block_producer_config = config.block_producer.expect("block producer config");
 
-    let wallet =
-        Wallet::from_config(&block_producer_config.wallet_config).with_context(|| "init wallet")?;
+    let wallet = Wallet::from_config(&block_producer_config.watchtower.wallet_config)
+        .with_context(|| "init wallet")?;
     let poa = base.init_poa(&wallet, &block_producer_config);
     let mut offchain_mock_context = smol::block_on(async {
         let poa = poa.lock().await;
-        base.init_offchain_mock_context(&poa, &block_producer_config)
+        let watchtower_config = block_producer_config.watchtower.clone();
+        base.init_offchain_mock_context(Some(&poa), &wallet, &watchtower_config)
             .await
     })?;
 
@@ -367,7 +368,7 @@ impl DumpContext {
     }
 }
 
-fn build_challenge_target(
+pub(crate) fn build_challenge_target(
     block_hash: H256,
     target_index: u32,
     target_type: ChallengeTargetType,
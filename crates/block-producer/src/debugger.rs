@@ -5,6 +5,7 @@ use std::{
 
 use anyhow::{anyhow, Result};
 use ckb_types::prelude::Entity;
+use gw_challenge::offchain::verify_tx::TxWithContext;
 use gw_common::H256;
 use gw_jsonrpc_types::{
     ckb_jsonrpc_types,
@@ -13,8 +14,8 @@ use gw_jsonrpc_types::{
 use gw_rpc_client::rpc_client::RPCClient;
 use gw_types::{
     core::DepType,
-    offchain::TxStatus,
-    packed::{CellDep, OutPointVec, Transaction},
+    offchain::{CellInfo, InputCellInfo, TxStatus},
+    packed::{CellDep, CellInput, OutPoint, OutPointVec, Transaction},
     prelude::*,
 };
 
@@ -59,31 +60,6 @@ pub async fn build_mock_transaction(
     rpc_client: &RPCClient,
     tx: Transaction,
 ) -> Result<ReprMockTransaction> {
-    async fn resolve_dep_group(rpc_client: &RPCClient, dep: &CellDep) -> Result<Vec<CellDep>> {
-        // return dep
-        if dep.dep_type() == DepType::Code.into() {
-            return Ok(vec![]);
-        }
-        // parse dep group
-        let cell = match rpc_client.get_cell(dep.out_point()).await? {
-            Some(cell) => Some(cell),
-            None => rpc_client.get_cell_from_mempool(dep.out_point()).await?,
-        }
-        .ok_or_else(|| anyhow!("can't find dep group cell"))?;
-        let out_points =
-            OutPointVec::from_slice(&cell.data).map_err(|_| anyhow!("invalid dep group"))?;
-        let cell_deps = out_points
-            .into_iter()
-            .map(|out_point| {
-                CellDep::new_builder()
-                    .out_point(out_point)
-                    .dep_type(DepType::Code.into())
-                    .build()
-            })
-            .collect();
-        Ok(cell_deps)
-    }
-
     // header deps hashes
     let mut header_deps_hashes: Vec<H256> = Vec::with_capacity(
         tx.raw().header_deps().len() + tx.raw().inputs().len() + tx.raw().cell_deps().len(),
@@ -209,3 +185,74 @@ pub async fn build_mock_transaction(
     };
     Ok(mock_tx)
 }
+
+async fn resolve_dep_group(rpc_client: &RPCClient, dep: &CellDep) -> Result<Vec<CellDep>> {
+    // return dep
+    if dep.dep_type() == DepType::Code.into() {
+        return Ok(vec![]);
+    }
+    // parse dep group
+    let cell = match rpc_client.get_cell(dep.out_point()).await? {
+        Some(cell) => Some(cell),
+        None => rpc_client.get_cell_from_mempool(dep.out_point()).await?,
+    }
+    .ok_or_else(|| anyhow!("can't find dep group cell"))?;
+    let out_points =
+        OutPointVec::from_slice(&cell.data).map_err(|_| anyhow!("invalid dep group"))?;
+    let cell_deps = out_points
+        .into_iter()
+        .map(|out_point| {
+            CellDep::new_builder()
+                .out_point(out_point)
+                .dep_type(DepType::Code.into())
+                .build()
+        })
+        .collect();
+    Ok(cell_deps)
+}
+
+async fn get_cell(rpc_client: &RPCClient, out_point: OutPoint) -> Result<Option<CellInfo>> {
+    match rpc_client.get_cell(out_point.clone()).await? {
+        Some(cell) => Ok(Some(cell)),
+        None => rpc_client.get_cell_from_mempool(out_point).await,
+    }
+}
+
+/// Resolve inputs and cell deps of a transaction, so it can be verified offchain
+pub async fn build_tx_with_context(
+    rpc_client: &RPCClient,
+    tx: Transaction,
+) -> Result<TxWithContext> {
+    let to_input_cell_info = |cell: CellInfo| InputCellInfo {
+        input: CellInput::new_builder()
+            .previous_output(cell.out_point.clone())
+            .build(),
+        cell,
+    };
+
+    let mut inputs = Vec::with_capacity(tx.raw().inputs().len());
+    for input in tx.raw().inputs() {
+        let cell = get_cell(rpc_client, input.previous_output())
+            .await?
+            .ok_or_else(|| anyhow!("can't find input cell"))?;
+        inputs.push(InputCellInfo { input, cell });
+    }
+
+    let mut cell_deps = Vec::with_capacity(tx.raw().cell_deps().len());
+    for cell_dep in tx.raw().cell_deps() {
+        let mut resolved_cell_deps = resolve_dep_group(rpc_client, &cell_dep).await?;
+        resolved_cell_deps.push(cell_dep);
+        for dep in resolved_cell_deps {
+            let dep_cell = get_cell(rpc_client, dep.out_point())
+                .await?
+                .ok_or_else(|| anyhow!("can't find dep cell"))?;
+            cell_deps.push(to_input_cell_info(dep_cell));
+        }
+    }
+
+    Ok(TxWithContext {
+        cell_deps,
+        inputs,
+        tx,
+    })
+}
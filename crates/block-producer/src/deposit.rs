@@ -81,7 +81,7 @@ pub fn revert(
     }
 
     let custodian_lock_dep = block_producer_config.custodian_cell_lock_dep.clone();
-    let sudt_type_dep = block_producer_config.l1_sudt_type_dep.clone();
+    let sudt_type_dep = block_producer_config.watchtower.l1_sudt_type_dep.clone();
     let mut cell_deps = vec![custodian_lock_dep.into()];
     if custodian_inputs
         .iter()
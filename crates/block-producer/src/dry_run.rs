@@ -0,0 +1,78 @@
+use crate::{debugger, utils};
+
+use anyhow::Result;
+use ckb_chain_spec::consensus::MAX_BLOCK_CYCLES;
+use gw_challenge::offchain::verify_tx::{verify_tx, RollupCellDeps};
+use gw_rpc_client::rpc_client::RPCClient;
+use gw_types::{packed::Transaction, prelude::*};
+
+use std::path::Path;
+
+/// A block tx verified in dry run mode, it is never sent to layer1
+pub struct DryRunReport {
+    pub block_number: u64,
+    pub tx: Transaction,
+    /// Cycles returned by layer1 `dry_run_transaction`
+    pub dry_run_cycles: Result<u64>,
+    /// Cycles of the offchain validator
+    pub verify_cycles: Result<u64>,
+}
+
+impl DryRunReport {
+    pub fn log(&self) {
+        let tx_hash = hex::encode(self.tx.hash());
+        match self.dry_run_cycles {
+            Ok(cycles) => log::info!(
+                "[dry run] l2 block {} tx {} execution cycles: {}",
+                self.block_number,
+                tx_hash,
+                cycles
+            ),
+            Err(ref err) => log::error!(
+                "[dry run] l2 block {} tx {} dry run error: {}",
+                self.block_number,
+                tx_hash,
+                err
+            ),
+        }
+        match self.verify_cycles {
+            Ok(cycles) => log::info!(
+                "[dry run] l2 block {} tx {} offchain verify cycles: {}",
+                self.block_number,
+                tx_hash,
+                cycles
+            ),
+            Err(ref err) => log::error!(
+                "[dry run] l2 block {} tx {} offchain verify error: {}",
+                self.block_number,
+                tx_hash,
+                err
+            ),
+        }
+    }
+}
+
+/// Verify a block tx by layer1 dry run and the offchain validator, then dump
+/// it to `dump_dir`. Never calls `send_transaction`.
+pub async fn dry_run_block_tx<P: AsRef<Path>>(
+    rpc_client: &RPCClient,
+    rollup_cell_deps: &RollupCellDeps,
+    dump_dir: P,
+    block_number: u64,
+    tx: Transaction,
+) -> DryRunReport {
+    let dry_run_cycles = rpc_client.dry_run_transaction(tx.clone()).await;
+
+    let tx_with_context = debugger::build_tx_with_context(rpc_client, tx.clone()).await;
+    let verify_cycles = tx_with_context
+        .and_then(|tx_with_context| verify_tx(rollup_cell_deps, tx_with_context, MAX_BLOCK_CYCLES));
+
+    utils::dump_transaction(dump_dir, rpc_client, tx.clone()).await;
+
+    DryRunReport {
+        block_number,
+        tx,
+        dry_run_cycles,
+        verify_cycles,
+    }
+}
@@ -1,9 +1,11 @@
 pub mod block_producer;
+pub mod challenge_simulator;
 pub mod challenger;
 pub mod cleaner;
 pub mod db_block_validator;
 pub mod debugger;
 pub mod deposit;
+pub mod dry_run;
 pub mod poller;
 pub mod produce_block;
 pub mod runner;
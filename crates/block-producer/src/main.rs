@@ -1,18 +1,24 @@
 use anyhow::{Context, Result};
 use clap::{App, Arg, SubCommand};
-use gw_block_producer::{db_block_validator, runner};
+use gw_block_producer::{challenge_simulator, db_block_validator, runner};
 use gw_config::Config;
+use gw_types::core::ChallengeTargetType;
 use gw_version::Version;
 use std::{fs, path::Path};
 
 const COMMAND_RUN: &str = "run";
 const COMMAND_EXAMPLE_CONFIG: &str = "generate-example-config";
 const COMMAND_VERIFY_DB_BLOCK: &str = "verify-db-block";
+const COMMAND_SIMULATE_CHALLENGE: &str = "simulate-challenge";
 const ARG_OUTPUT_PATH: &str = "output-path";
 const ARG_CONFIG: &str = "config";
 const ARG_SKIP_CONFIG_CHECK: &str = "skip-config-check";
 const ARG_FROM_BLOCK: &str = "from-block";
 const ARG_TO_BLOCK: &str = "to-block";
+const ARG_BLOCK: &str = "block";
+const ARG_TARGET_INDEX: &str = "target-index";
+const ARG_TARGET_TYPE: &str = "target-type";
+const ARG_VERIFY_CANCEL: &str = "verify-cancel";
 
 fn read_config<P: AsRef<Path>>(path: P) -> Result<Config> {
     let content = fs::read(&path)
@@ -90,6 +96,46 @@ fn run_cli() -> Result<()> {
                         .help("To block number"),
                 )
                 .display_order(2),
+        )
+        .subcommand(
+            SubCommand::with_name(COMMAND_SIMULATE_CHALLENGE)
+                .about("Simulate a challenge dispute against a block in db")
+                .arg(
+                    Arg::with_name(ARG_CONFIG)
+                        .short("c")
+                        .takes_value(true)
+                        .required(true)
+                        .default_value("./config.toml")
+                        .help("The config file path"),
+                )
+                .arg(
+                    Arg::with_name(ARG_BLOCK)
+                        .short("b")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Challenge block number"),
+                )
+                .arg(
+                    Arg::with_name(ARG_TARGET_INDEX)
+                        .short("i")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Challenge target index"),
+                )
+                .arg(
+                    Arg::with_name(ARG_TARGET_TYPE)
+                        .short("t")
+                        .takes_value(true)
+                        .required(true)
+                        .possible_values(&["tx_execution", "tx_signature", "withdrawal"])
+                        .help("Challenge target type"),
+                )
+                .arg(
+                    Arg::with_name(ARG_VERIFY_CANCEL)
+                        .long(ARG_VERIFY_CANCEL)
+                        .help("Verify cancel challenge tx, resolve rollup cell deps from CKB rpc"),
+                )
+                .display_order(3),
         );
 
     // handle subcommands
@@ -111,6 +157,26 @@ fn run_cli() -> Result<()> {
             let to_block: Option<u64> = m.value_of(ARG_TO_BLOCK).map(str::parse).transpose()?;
             db_block_validator::verify(config, from_block, to_block)?;
         }
+        (COMMAND_SIMULATE_CHALLENGE, Some(m)) => {
+            let config_path = m.value_of(ARG_CONFIG).unwrap();
+            let config = read_config(&config_path)?;
+            let block_number: u64 = m.value_of(ARG_BLOCK).unwrap().parse()?;
+            let target_index: u32 = m.value_of(ARG_TARGET_INDEX).unwrap().parse()?;
+            let target_type = match m.value_of(ARG_TARGET_TYPE).unwrap() {
+                "tx_execution" => ChallengeTargetType::TxExecution,
+                "tx_signature" => ChallengeTargetType::TxSignature,
+                "withdrawal" => ChallengeTargetType::Withdrawal,
+                _ => unreachable!("invalid challenge target type"),
+            };
+            let verify_cancel = m.is_present(ARG_VERIFY_CANCEL);
+            challenge_simulator::simulate(
+                config,
+                block_number,
+                target_index,
+                target_type,
+                verify_cancel,
+            )?;
+        }
         _ => {
             // default command: start a Godwoken node
             let config_path = "./config.toml";
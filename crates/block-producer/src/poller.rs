@@ -9,6 +9,7 @@ use gw_chain::chain::{
     SyncParam,
 };
 use gw_jsonrpc_types::ckb_jsonrpc_types::{BlockNumber, HeaderView, TransactionWithStatus, Uint32};
+use gw_metrics::GLOBAL_METRICS;
 use gw_rpc_client::{
     indexer_types::{Order, Pagination, ScriptType, SearchKey, SearchKeyFilter, Tx},
     rpc_client::RPCClient,
@@ -62,7 +63,7 @@ impl ChainUpdater {
     }
 
     // Start syncing
-    pub async fn handle_event(&mut self, _event: ChainEvent) -> Result<()> {
+    pub async fn handle_event(&mut self, event: ChainEvent) -> Result<()> {
         let initial_syncing = !self.initialized;
         // Always start from last valid tip on l1
         if !self.initialized {
@@ -137,9 +138,34 @@ impl ChainUpdater {
             self.chain.lock().await.complete_initial_syncing()?;
         }
 
+        self.record_sync_metrics(&event).await;
+
         Ok(())
     }
 
+    async fn record_sync_metrics(&self, event: &ChainEvent) {
+        let l1_tip_number: u64 = match event {
+            ChainEvent::NewBlock { block } => block.header().raw().number().unpack(),
+            ChainEvent::Reverted { new_block, .. } => new_block.header().raw().number().unpack(),
+        };
+        let (l1_synced_number, l2_tip_number): (u64, u64) = {
+            let chain = self.chain.lock().await;
+            let local_state = chain.local_state();
+            (
+                local_state.last_synced().number().unpack(),
+                local_state.tip().raw().number().unpack(),
+            )
+        };
+
+        let metrics = &GLOBAL_METRICS;
+        metrics.l1_tip_number.set(l1_tip_number as i64);
+        metrics.l1_synced_number.set(l1_synced_number as i64);
+        metrics
+            .l1_sync_lag
+            .set(l1_tip_number.saturating_sub(l1_synced_number) as i64);
+        metrics.l2_tip_number.set(l2_tip_number as i64);
+    }
+
     pub async fn update(&mut self, txs: &[Tx]) -> anyhow::Result<()> {
         for tx in txs.iter() {
             self.update_single(&tx.tx_hash).await?;
@@ -19,10 +19,13 @@ use gw_types::{
     },
     prelude::*,
 };
+use std::collections::BTreeMap;
 
 pub struct ProduceBlockResult {
     pub block: L2Block,
     pub global_state: GlobalState,
+    /// Fees paid to block producer, sudt id -> amount
+    pub collected_fees: BTreeMap<u32, u128>,
 }
 
 pub struct ProduceBlockParam {
@@ -60,6 +63,7 @@ pub fn produce_block(
                 kv_state,
                 kv_state_proof,
                 post_merkle_state,
+                collected_fees,
             },
     } = param;
 
@@ -157,5 +161,6 @@ pub fn produce_block(
     Ok(ProduceBlockResult {
         block,
         global_state,
+        collected_fees,
     })
 }
@@ -2,14 +2,16 @@ use crate::{
     block_producer::BlockProducer, challenger::Challenger, cleaner::Cleaner, poller::ChainUpdater,
     test_mode_control::TestModeControl, types::ChainEvent,
 };
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use async_jsonrpc_client::HttpClient;
 use ckb_types::core::hardfork::HardForkSwitch;
 use gw_chain::chain::Chain;
 use gw_challenge::offchain::{OffChainMockContext, OffChainValidatorContext};
 use gw_ckb_hardfork::{GLOBAL_CURRENT_EPOCH_NUMBER, GLOBAL_HARDFORK_SWITCH, GLOBAL_VM_VERSION};
 use gw_common::{blake2b::new_blake2b, H256};
-use gw_config::{BlockProducerConfig, Config, NodeMode};
+use gw_config::{
+    BlockProducerConfig, Config, ExecutionLimitsConfig, MemPoolConfig, NodeMode, WatchtowerConfig,
+};
 use gw_db::{config::Config as DBConfig, schema::COLUMNS, RocksDB};
 use gw_generator::{
     account_lock_manage::{
@@ -20,10 +22,15 @@ use gw_generator::{
     genesis::init_genesis,
     Generator,
 };
-use gw_mem_pool::{default_provider::DefaultMemPoolProvider, pool::MemPool};
+use gw_mem_pool::{
+    default_provider::DefaultMemPoolProvider, deposit_policy::DepositPolicy, pool::MemPool,
+};
 use gw_poa::PoA;
 use gw_rpc_client::rpc_client::RPCClient;
-use gw_rpc_server::{registry::Registry, server::start_jsonrpc_server};
+use gw_rpc_server::{
+    registry::{AdminRegistry, Registry},
+    server::{start_admin_jsonrpc_server, start_jsonrpc_server, start_metrics_server},
+};
 use gw_store::Store;
 use gw_types::{
     bytes::Bytes,
@@ -31,7 +38,7 @@ use gw_types::{
     packed::{CellDep, NumberHash, RollupConfig, Script},
     prelude::*,
 };
-use gw_utils::{genesis_info::CKBGenesisInfo, wallet::Wallet};
+use gw_utils::{genesis_info::CKBGenesisInfo, sudt::SUDTBond, wallet::Wallet};
 use gw_web3_indexer::Web3Indexer;
 use semver::Version;
 use smol::lock::Mutex;
@@ -208,6 +215,113 @@ pub struct BaseInitComponents {
     pub rpc_client: RPCClient,
     pub store: Store,
     pub generator: Arc<Generator>,
+    pub sudt_bond: Option<SUDTBond>,
+}
+
+/// Build the layer1 rpc client, no request is sent until it's used.
+pub fn build_rpc_client(config: &Config, rollup_context: &RollupContext) -> Result<RPCClient> {
+    let rollup_type_script: Script = config.chain.rollup_type_script.clone().into();
+    let indexer_client = HttpClient::new(config.rpc_client.indexer_url.to_owned())?;
+    let ckb_client = HttpClient::new(config.rpc_client.ckb_url.to_owned())?;
+    let rollup_type_script =
+        ckb_types::packed::Script::new_unchecked(rollup_type_script.as_bytes());
+    Ok(RPCClient::new(
+        rollup_type_script,
+        rollup_context.clone(),
+        ckb_client,
+        indexer_client,
+    ))
+}
+
+pub fn query_secp_data(config: &Config, rpc_client: &RPCClient) -> Result<Bytes> {
+    let out_point = config.genesis.secp_data_dep.out_point.clone();
+    let secp_data = smol::block_on(rpc_client.get_transaction(out_point.tx_hash.0.into()))?
+        .ok_or_else(|| anyhow!("can not found transaction: {:?}", out_point.tx_hash))?
+        .raw()
+        .outputs_data()
+        .get(out_point.index.value() as usize)
+        .expect("get secp output data")
+        .raw_data();
+    Ok(secp_data)
+}
+
+pub fn query_ckb_genesis_info(rpc_client: &RPCClient) -> Result<CKBGenesisInfo> {
+    let ckb_genesis = smol::block_on(async { rpc_client.get_block_by_number(0).await })?
+        .ok_or_else(|| anyhow!("can't found CKB genesis block"))?;
+    CKBGenesisInfo::from_block(&ckb_genesis)
+}
+
+/// Cell deps of data loaded by builtin scripts, keyed by data hash
+pub fn build_builtin_load_data(config: &Config, secp_data: &Bytes) -> HashMap<H256, CellDep> {
+    let to_hash = |data| -> [u8; 32] {
+        let mut hasher = new_blake2b();
+        hasher.update(data);
+        let mut hash = [0u8; 32];
+        hasher.finalize(&mut hash);
+        hash
+    };
+    let mut builtin_load_data = HashMap::new();
+    builtin_load_data.insert(
+        to_hash(secp_data.as_ref()).into(),
+        config.genesis.secp_data_dep.clone().into(),
+    );
+    builtin_load_data
+}
+
+/// Build the generator from the backends and account locks in config, the
+/// generator doesn't touch the store or layer1.
+pub fn build_generator(config: &Config, rollup_context: &RollupContext) -> Result<Arc<Generator>> {
+    let rollup_config = &rollup_context.rollup_config;
+    let backend_manage =
+        BackendManage::from_config(config.backends.clone()).with_context(|| "config backends")?;
+    let mut account_lock_manage = AccountLockManage::default();
+    let eth_lock_script_type_hash = rollup_config
+        .allowed_eoa_type_hashes()
+        .get(0)
+        .ok_or_else(|| anyhow!("Eth: No allowed EoA type hashes in the rollup config"))?;
+    account_lock_manage.register_lock_algorithm(
+        eth_lock_script_type_hash.unpack(),
+        Box::new(Secp256k1Eth::default()),
+    );
+    let tron_lock_script_type_hash = rollup_config.allowed_eoa_type_hashes().get(1);
+    if let Some(code_hash) = tron_lock_script_type_hash {
+        account_lock_manage
+            .register_lock_algorithm(code_hash.unpack(), Box::new(Secp256k1Tron::default()))
+    }
+    // verifier cells of cancel challenges are owned by the wallet
+    let owner_lock: Script = match (&config.block_producer, &config.watchtower) {
+        (Some(block_producer_config), _) => block_producer_config
+            .watchtower
+            .wallet_config
+            .lock
+            .clone()
+            .into(),
+        (None, Some(watchtower_config)) => watchtower_config.wallet_config.lock.clone().into(),
+        (None, None) => Script::default(),
+    };
+    for account_lock_config in config.account_locks.iter() {
+        let code_hash = account_lock_config.code_hash.clone();
+        let is_allowed = rollup_config
+            .allowed_eoa_type_hashes()
+            .into_iter()
+            .any(|hash| hash.as_slice() == code_hash.as_bytes());
+        if !is_allowed {
+            bail!(
+                "account lock {:#x} isn't in the allowed eoa type hashes of rollup config",
+                code_hash
+            );
+        }
+        account_lock_manage
+            .register_lock_config(account_lock_config.clone(), &owner_lock)
+            .with_context(|| format!("config account lock {:#x}", code_hash))?;
+    }
+    Ok(Arc::new(Generator::new(
+        backend_manage,
+        account_lock_manage,
+        rollup_context.clone(),
+        config.rpc.clone(),
+        config.execution_limits.clone(),
+    )))
 }
 
 impl BaseInitComponents {
@@ -221,29 +335,36 @@ impl BaseInitComponents {
             },
         };
         let rollup_type_script: Script = config.chain.rollup_type_script.clone().into();
-        let rpc_client = {
-            let indexer_client = HttpClient::new(config.rpc_client.indexer_url.to_owned())?;
-            let ckb_client = HttpClient::new(config.rpc_client.ckb_url.to_owned())?;
-            let rollup_type_script =
-                ckb_types::packed::Script::new_unchecked(rollup_type_script.as_bytes());
-            RPCClient::new(
-                rollup_type_script,
-                rollup_context.clone(),
-                ckb_client,
-                indexer_client,
-            )
-        };
+        let rpc_client = build_rpc_client(config, &rollup_context)?;
 
+        check_limits(&config.execution_limits, &config.mem_pool)?;
         if !skip_config_check {
             check_ckb_version(&rpc_client)?;
             // TODO: check ckb indexer version
-            if NodeMode::ReadOnly != config.node_mode {
-                let block_producer_config = config
-                    .block_producer
-                    .clone()
-                    .ok_or_else(|| anyhow!("not set block producer"))?;
-                check_rollup_config_cell(&block_producer_config, &rollup_config, &rpc_client)?;
-                check_locks(&block_producer_config, &rollup_config)?;
+            let watchtower_config = match config.node_mode {
+                NodeMode::ReadOnly => None,
+                NodeMode::Watchtower => Some(
+                    config
+                        .watchtower
+                        .clone()
+                        .ok_or_else(|| anyhow!("not set watchtower"))?,
+                ),
+                _ => Some(
+                    config
+                        .block_producer
+                        .as_ref()
+                        .ok_or_else(|| anyhow!("not set block producer"))?
+                        .watchtower
+                        .clone(),
+                ),
+            };
+            if let Some(watchtower_config) = watchtower_config {
+                check_rollup_config_cell(&watchtower_config, &rollup_config, &rpc_client)?;
+                let section = match config.node_mode {
+                    NodeMode::Watchtower => "watchtower",
+                    _ => "block_producer",
+                };
+                check_locks(&watchtower_config, &rollup_config, section)?;
             }
         }
 
@@ -260,16 +381,7 @@ impl BaseInitComponents {
             Store::new(RocksDB::open(&db_config, COLUMNS))
         };
 
-        let secp_data: Bytes = {
-            let out_point = config.genesis.secp_data_dep.out_point.clone();
-            smol::block_on(rpc_client.get_transaction(out_point.tx_hash.0.into()))?
-                .ok_or_else(|| anyhow!("can not found transaction: {:?}", out_point.tx_hash))?
-                .raw()
-                .outputs_data()
-                .get(out_point.index.value() as usize)
-                .expect("get secp output data")
-                .raw_data()
-        };
+        let secp_data = query_secp_data(config, &rpc_client)?;
 
         init_genesis(
             &store,
@@ -279,50 +391,24 @@ impl BaseInitComponents {
         )
         .with_context(|| "init genesis")?;
 
-        let rollup_config_hash: H256 = rollup_config.hash().into();
-        let generator = {
-            let backend_manage = BackendManage::from_config(config.backends.clone())
-                .with_context(|| "config backends")?;
-            let mut account_lock_manage = AccountLockManage::default();
-            let eth_lock_script_type_hash = rollup_config
-                .allowed_eoa_type_hashes()
-                .get(0)
-                .ok_or_else(|| anyhow!("Eth: No allowed EoA type hashes in the rollup config"))?;
-            account_lock_manage.register_lock_algorithm(
-                eth_lock_script_type_hash.unpack(),
-                Box::new(Secp256k1Eth::default()),
-            );
-            let tron_lock_script_type_hash = rollup_config.allowed_eoa_type_hashes().get(1);
-            if let Some(code_hash) = tron_lock_script_type_hash {
-                account_lock_manage
-                    .register_lock_algorithm(code_hash.unpack(), Box::new(Secp256k1Tron::default()))
+        // index withdrawals of blocks attached by an older version
+        {
+            let migrated = store
+                .migrate_withdrawal_index(&rollup_config)
+                .with_context(|| "migrate withdrawal index")?;
+            if migrated > 0 {
+                log::info!("backfilled withdrawal index of {} blocks", migrated);
             }
-            Arc::new(Generator::new(
-                backend_manage,
-                account_lock_manage,
-                rollup_context.clone(),
-                config.rpc.clone(),
-            ))
-        };
+        }
 
-        let ckb_genesis_info = {
-            let ckb_genesis = smol::block_on(async { rpc_client.get_block_by_number(0).await })?
-                .ok_or_else(|| anyhow!("can't found CKB genesis block"))?;
-            CKBGenesisInfo::from_block(&ckb_genesis)?
-        };
+        let rollup_config_hash: H256 = rollup_config.hash().into();
+        let generator = build_generator(config, &rollup_context)?;
 
-        let to_hash = |data| -> [u8; 32] {
-            let mut hasher = new_blake2b();
-            hasher.update(data);
-            let mut hash = [0u8; 32];
-            hasher.finalize(&mut hash);
-            hash
-        };
-        let mut builtin_load_data = HashMap::new();
-        builtin_load_data.insert(
-            to_hash(secp_data.as_ref()).into(),
-            config.genesis.secp_data_dep.clone().into(),
-        );
+        let ckb_genesis_info = query_ckb_genesis_info(&rpc_client)?;
+        let builtin_load_data = build_builtin_load_data(config, &secp_data);
+
+        let sudt_bond = (config.genesis.sudt_bond.as_ref())
+            .map(|sudt_bond| SUDTBond::from_config(&rollup_context, sudt_bond));
 
         let base = BaseInitComponents {
             rollup_config,
@@ -334,6 +420,7 @@ impl BaseInitComponents {
             rpc_client,
             store,
             generator,
+            sudt_bond,
         };
 
         Ok(base)
@@ -355,25 +442,23 @@ impl BaseInitComponents {
 
     pub async fn init_offchain_mock_context(
         &self,
-        poa: &PoA,
-        block_producer_config: &BlockProducerConfig,
+        poa: Option<&PoA>,
+        wallet: &Wallet,
+        watchtower_config: &WatchtowerConfig,
     ) -> Result<OffChainMockContext> {
         let ckb_genesis_info = gw_challenge::offchain::CKBGenesisInfo {
             sighash_dep: self.ckb_genesis_info.sighash_dep(),
         };
-        let wallet = {
-            let config = &block_producer_config.wallet_config;
-            Wallet::from_config(config).with_context(|| "init wallet")?
-        };
 
         OffChainMockContext::build(
             &self.rpc_client,
             poa,
             self.rollup_context.clone(),
-            wallet,
-            block_producer_config.clone(),
+            wallet.clone(),
+            watchtower_config.clone(),
             ckb_genesis_info,
             self.builtin_load_data.clone(),
+            self.sudt_bond.clone(),
         )
         .await
     }
@@ -396,14 +481,31 @@ pub fn run(config: Config, skip_config_check: bool) -> Result<()> {
     );
 
     let base = BaseInitComponents::init(&config, skip_config_check)?;
-    let (mem_pool, wallet, poa, offchain_mock_context) = match config.block_producer.clone() {
+    let block_producer_config = match config.node_mode {
+        // Standalone watchtower doesn't require block producer credentials
+        NodeMode::Watchtower => None,
+        _ => config.block_producer.clone(),
+    };
+    let (mem_pool, wallet, poa, offchain_mock_context) = match block_producer_config {
+        None if NodeMode::Watchtower == config.node_mode => {
+            let watchtower_config = config
+                .watchtower
+                .as_ref()
+                .ok_or_else(|| anyhow!("must provide watchtower config in mode: Watchtower"))?;
+            let wallet = Wallet::from_config(&watchtower_config.wallet_config)
+                .with_context(|| "init wallet")?;
+            let offchain_mock_context =
+                smol::block_on(base.init_offchain_mock_context(None, &wallet, watchtower_config))?;
+            (None, Some(wallet), None, Some(offchain_mock_context))
+        }
         Some(block_producer_config) => {
-            let wallet = Wallet::from_config(&block_producer_config.wallet_config)
+            let wallet = Wallet::from_config(&block_producer_config.watchtower.wallet_config)
                 .with_context(|| "init wallet")?;
             let poa = base.init_poa(&wallet, &block_producer_config);
             let offchain_mock_context = smol::block_on(async {
                 let poa = poa.lock().await;
-                base.init_offchain_mock_context(&poa, &block_producer_config)
+                let watchtower_config = block_producer_config.watchtower.clone();
+                base.init_offchain_mock_context(Some(&poa), &wallet, &watchtower_config)
                     .await
             })?;
 
@@ -424,6 +526,7 @@ pub fn run(config: Config, skip_config_check: bool) -> Result<()> {
                 base.rpc_client.clone(),
                 Arc::clone(&poa),
                 base.store.clone(),
+                DepositPolicy::new(config.mem_pool.deposit_policy.clone()),
             );
             let mem_pool = Arc::new(Mutex::new(
                 MemPool::create(
@@ -455,10 +558,11 @@ pub fn run(config: Config, skip_config_check: bool) -> Result<()> {
         rpc_client,
         store,
         generator,
+        sudt_bond: _,
     } = base;
 
-    let chain = Arc::new(Mutex::new(
-        Chain::create(
+    let chain = {
+        let mut chain = Chain::create(
             &rollup_config,
             &config.chain.rollup_type_script.clone().into(),
             &config.chain,
@@ -466,8 +570,13 @@ pub fn run(config: Config, skip_config_check: bool) -> Result<()> {
             generator.clone(),
             mem_pool.clone(),
         )
-        .with_context(|| "create chain")?,
-    ));
+        .with_context(|| "create chain")?;
+        if let Some(ref offchain_mock_context) = offchain_mock_context {
+            let ctx = offchain_mock_context.clone();
+            chain.enable_bad_block_confirmation(ctx, config.debug.clone());
+        }
+        Arc::new(Mutex::new(chain))
+    };
 
     // create web3 indexer
     let web3_indexer = match config.web3_indexer {
@@ -513,6 +622,39 @@ pub fn run(config: Config, skip_config_check: bool) -> Result<()> {
 
     let (block_producer, challenger, test_mode_control, cleaner) = match config.node_mode {
         NodeMode::ReadOnly => (None, None, None, None),
+        NodeMode::Watchtower => {
+            let watchtower_config = config
+                .watchtower
+                .clone()
+                .ok_or_else(|| anyhow!("must provide watchtower config in mode: Watchtower"))?;
+            let wallet = wallet.ok_or_else(|| anyhow!("wallet must be enabled in watchtower"))?;
+            let offchain_mock_context = offchain_mock_context
+                .clone()
+                .ok_or_else(|| anyhow!("offchain mock must be enabled in watchtower"))?;
+
+            let cleaner = Arc::new(Cleaner::new(
+                rpc_client.clone(),
+                ckb_genesis_info.clone(),
+                wallet.clone(),
+                store.clone(),
+                watchtower_config.l1_sudt_type_dep.clone().into(),
+            )?);
+            let challenger = Challenger::new(
+                rollup_context,
+                rpc_client.clone(),
+                wallet,
+                watchtower_config,
+                config.debug.clone(),
+                builtin_load_data,
+                ckb_genesis_info,
+                Arc::clone(&chain),
+                None,
+                None,
+                Arc::clone(&cleaner),
+                offchain_mock_context,
+            );
+            (None, Some(challenger), None, Some(cleaner))
+        }
         mode => {
             let block_producer_config = config
                 .block_producer
@@ -542,26 +684,29 @@ pub fn run(config: Config, skip_config_check: bool) -> Result<()> {
             let cleaner = Arc::new(Cleaner::new(
                 rpc_client.clone(),
                 ckb_genesis_info.clone(),
-                wallet,
-            ));
-
-            let wallet = Wallet::from_config(&block_producer_config.wallet_config)
-                .with_context(|| "init wallet")?;
+                wallet.clone(),
+                store.clone(),
+                block_producer_config
+                    .watchtower
+                    .l1_sudt_type_dep
+                    .clone()
+                    .into(),
+            )?);
 
             // Challenger
             let challenger = Challenger::new(
                 rollup_context,
                 rpc_client.clone(),
-                wallet,
-                block_producer_config.clone(),
+                wallet.clone(),
+                block_producer_config.watchtower.clone(),
                 config.debug.clone(),
                 builtin_load_data,
                 ckb_genesis_info.clone(),
                 Arc::clone(&chain),
-                Arc::clone(&poa),
+                Some(Arc::clone(&poa)),
                 tests_control.clone(),
                 Arc::clone(&cleaner),
-                offchain_mock_context,
+                offchain_mock_context.clone(),
             );
 
             // Block Producer
@@ -574,20 +719,39 @@ pub fn run(config: Config, skip_config_check: bool) -> Result<()> {
                 rpc_client.clone(),
                 ckb_genesis_info,
                 block_producer_config,
+                wallet,
                 config.debug.clone(),
                 tests_control.clone(),
+                config.node_mode,
+                offchain_mock_context,
             )
             .with_context(|| "init block producer")?;
 
-            (
-                Some(block_producer),
-                Some(challenger),
-                tests_control,
-                Some(cleaner),
-            )
+            match mode {
+                // Challenger and cleaner send transactions to L1, disable them in dry run mode
+                NodeMode::DryRun => (Some(block_producer), None, tests_control, None),
+                _ => (
+                    Some(block_producer),
+                    Some(challenger),
+                    tests_control,
+                    Some(cleaner),
+                ),
+            }
         }
     };
 
+    // Admin RPC registry, only served on the admin listen address
+    let admin_rpc_registry = config.rpc_server.admin.as_ref().map(|admin_config| {
+        (
+            admin_config.listen.clone(),
+            AdminRegistry::new(
+                store.clone(),
+                Arc::clone(&generator),
+                admin_config.backends_dir.clone(),
+            ),
+        )
+    });
+
     // RPC registry
     let rpc_registry = Registry::new(
         store,
@@ -599,6 +763,7 @@ pub fn run(config: Config, skip_config_check: bool) -> Result<()> {
         Arc::clone(&chain),
         offchain_mock_context,
         config.mem_pool.clone(),
+        config.rpc.clone(),
         config.node_mode,
     );
 
@@ -622,6 +787,31 @@ pub fn run(config: Config, skip_config_check: bool) -> Result<()> {
         addrs.remove(0)
     };
 
+    let admin_rpc = match admin_rpc_registry {
+        Some((listen, registry)) => {
+            let mut addrs: Vec<_> = listen.to_socket_addrs()?.collect();
+            if addrs.len() != 1 {
+                return Err(anyhow!("Invalid admin RPC listen address `{}`", listen));
+            }
+            Some((addrs.remove(0), registry))
+        }
+        None => None,
+    };
+
+    let metrics_address: Option<SocketAddr> = match config.metrics {
+        Some(ref metrics_config) => {
+            let mut addrs: Vec<_> = metrics_config.listen.to_socket_addrs()?.collect();
+            if addrs.len() != 1 {
+                return Err(anyhow!(
+                    "Invalid metrics listen address `{}`",
+                    &metrics_config.listen
+                ));
+            }
+            Some(addrs.remove(0))
+        }
+        None => None,
+    };
+
     {
         let rollup_type_script_hash = {
             let hash = rollup_type_script.hash();
@@ -664,17 +854,89 @@ pub fn run(config: Config, skip_config_check: bool) -> Result<()> {
         }
     });
 
+    let admin_rpc_task = admin_rpc.map(|(admin_rpc_address, admin_rpc_registry)| {
+        smol::spawn(async move {
+            if let Err(err) =
+                start_admin_jsonrpc_server(admin_rpc_address, admin_rpc_registry).await
+            {
+                log::error!("Error running admin JSONRPC server: {:?}", err);
+            }
+        })
+    });
+
+    let metrics_task = metrics_address.map(|metrics_address| {
+        smol::spawn(async move {
+            if let Err(err) = start_metrics_server(metrics_address).await {
+                log::error!("Error running metrics server: {:?}", err);
+            }
+        })
+    });
+
     smol::block_on(async {
         let _ = exit_recv.recv().await;
         log::info!("Exiting...");
 
         rpc_task.cancel().await;
         chain_task.cancel().await;
+        if let Some(admin_rpc_task) = admin_rpc_task {
+            admin_rpc_task.cancel().await;
+        }
+        if let Some(metrics_task) = metrics_task {
+            metrics_task.cancel().await;
+        }
     });
 
     Ok(())
 }
 
+fn check_limits(
+    execution_limits: &ExecutionLimitsConfig,
+    mem_pool_config: &MemPoolConfig,
+) -> Result<()> {
+    if 0 == execution_limits.l2tx_max_cycles {
+        return Err(anyhow!(
+            "[execution_limits.l2tx_max_cycles] must be positive"
+        ));
+    }
+    if 0 == execution_limits.max_write_data_bytes || 0 == execution_limits.max_read_data_bytes {
+        return Err(anyhow!(
+            "[execution_limits.max_write_data_bytes] and \
+            [execution_limits.max_read_data_bytes] must be positive"
+        ));
+    }
+    if 0 == mem_pool_config.max_mem_block_txs {
+        return Err(anyhow!("[mem_pool.max_mem_block_txs] must be positive"));
+    }
+    if 0 == mem_pool_config.max_tx_size {
+        return Err(anyhow!("[mem_pool.max_tx_size] must be positive"));
+    }
+    // a tx must fit in an empty mem block
+    if mem_pool_config.max_block_cycles < execution_limits.l2tx_max_cycles {
+        return Err(anyhow!(
+            "[mem_pool.max_block_cycles] {} is less than [execution_limits.l2tx_max_cycles] {}",
+            mem_pool_config.max_block_cycles,
+            execution_limits.l2tx_max_cycles
+        ));
+    }
+    if mem_pool_config.max_block_write_data_bytes < execution_limits.max_write_data_bytes {
+        return Err(anyhow!(
+            "[mem_pool.max_block_write_data_bytes] {} is less than \
+            [execution_limits.max_write_data_bytes] {}",
+            mem_pool_config.max_block_write_data_bytes,
+            execution_limits.max_write_data_bytes
+        ));
+    }
+    if mem_pool_config.execute_l2tx_max_cycles < execution_limits.l2tx_max_cycles {
+        return Err(anyhow!(
+            "[mem_pool.execute_l2tx_max_cycles] {} is less than \
+            [execution_limits.l2tx_max_cycles] {}",
+            mem_pool_config.execute_l2tx_max_cycles,
+            execution_limits.l2tx_max_cycles
+        ));
+    }
+    Ok(())
+}
+
 fn check_ckb_version(rpc_client: &RPCClient) -> Result<()> {
     let ckb_version = smol::block_on(rpc_client.get_ckb_version())?;
     let ckb_version = ckb_version.split('(').collect::<Vec<&str>>()[0].trim_end();
@@ -689,13 +951,13 @@ fn check_ckb_version(rpc_client: &RPCClient) -> Result<()> {
 }
 
 fn check_rollup_config_cell(
-    block_producer_config: &BlockProducerConfig,
+    watchtower_config: &WatchtowerConfig,
     rollup_config: &RollupConfig,
     rpc_client: &RPCClient,
 ) -> Result<()> {
     let rollup_config_cell = smol::block_on(
         rpc_client.get_cell(
-            block_producer_config
+            watchtower_config
                 .rollup_config_cell_dep
                 .out_point
                 .clone()
@@ -733,56 +995,63 @@ fn check_rollup_config_cell(
     Ok(())
 }
 
+/// Check locks of the watchtower config, `section` is the config section of
+/// it in error messages
 fn check_locks(
-    block_producer_config: &BlockProducerConfig,
+    watchtower_config: &WatchtowerConfig,
     rollup_config: &RollupConfig,
+    section: &str,
 ) -> Result<()> {
     let zeros = ckb_fixed_hash::H256([0u8; 32]);
 
     // check burn lock
-    if zeros != block_producer_config.challenger_config.burn_lock.code_hash {
+    if zeros != watchtower_config.challenger_config.burn_lock.code_hash {
         return Err(anyhow!(
-            "[block_producer.challenger.burn_lock.code_hash] is expected to be zero"
+            "[{}.challenger.burn_lock.code_hash] is expected to be zero",
+            section
         ));
     }
 
     let burn_lock_hash = {
-        let script: gw_types::packed::Script = block_producer_config
-            .challenger_config
-            .burn_lock
-            .clone()
-            .into();
+        let script: gw_types::packed::Script =
+            watchtower_config.challenger_config.burn_lock.clone().into();
         script.hash().pack()
     };
     if burn_lock_hash != rollup_config.burn_lock_hash() {
-        return Err(anyhow!("[block_producer.challenge.burn_lock] ({}) isn't match rollup config's burn_lock_hash ({})", burn_lock_hash, rollup_config.burn_lock_hash()));
+        return Err(anyhow!(
+            "[{}.challenger.burn_lock] ({}) isn't match rollup config's burn_lock_hash ({})",
+            section,
+            burn_lock_hash,
+            rollup_config.burn_lock_hash()
+        ));
     }
 
     // check challenge lock
     if zeros
-        == block_producer_config
+        == watchtower_config
             .challenger_config
             .rewards_receiver_lock
             .code_hash
     {
         return Err(anyhow!(
-            "[block_producer.challenger.rewards_receiver_lock.code_hash] shouldn't be zero"
+            "[{}.challenger.rewards_receiver_lock.code_hash] shouldn't be zero",
+            section
         ));
     }
 
     // check wallet lock
-    if zeros == block_producer_config.wallet_config.lock.code_hash {
+    if zeros == watchtower_config.wallet_config.lock.code_hash {
         return Err(anyhow!(
-            "[block_producer.wallet.lock.code_hash] shouldn't be zero"
+            "[{}.wallet.lock.code_hash] shouldn't be zero",
+            section
         ));
     }
-    if block_producer_config.wallet_config.lock
-        == block_producer_config
-            .challenger_config
-            .rewards_receiver_lock
+    if watchtower_config.wallet_config.lock
+        == watchtower_config.challenger_config.rewards_receiver_lock
     {
         return Err(anyhow!(
-            "[block_producer.challenger.rewards_receiver_lock] and [block_producer.wallet.lock] have the same address, which is not recommended"
+            "[{0}.challenger.rewards_receiver_lock] and [{0}.wallet.lock] have the same address, which is not recommended",
+            section
         ));
     }
     Ok(())
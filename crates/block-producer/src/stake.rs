@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use ckb_types::{
     bytes::Bytes,
     prelude::{Builder, Entity},
@@ -11,12 +11,15 @@ use gw_types::{
     packed::{CellDep, CellInput, CellOutput, L2Block, Script, StakeLockArgs},
     prelude::{Pack, Unpack},
 };
+use gw_utils::sudt::{build_sudt_cell, collect_sudt, parse_sudt, SUDTBond};
+use std::collections::HashSet;
 
 pub struct GeneratedStake {
     pub deps: Vec<CellDep>,
     pub inputs: Vec<InputCellInfo>,
     pub output: CellOutput,
     pub output_data: Bytes,
+    pub change_outputs: Vec<(CellOutput, Bytes)>,
 }
 
 pub async fn generate(
@@ -24,6 +27,7 @@ pub async fn generate(
     rollup_context: &RollupContext,
     block: &L2Block,
     block_producer_config: &BlockProducerConfig,
+    sudt_bond: Option<&SUDTBond>,
     rpc_client: &RPCClient,
     lock_script: Script,
 ) -> Result<GeneratedStake> {
@@ -51,7 +55,7 @@ pub async fn generate(
         .query_stake(rollup_context, owner_lock_hash, None)
         .await?
     {
-        let stake_lock_dep = block_producer_config.stake_cell_lock_dep.clone();
+        let stake_lock_dep = block_producer_config.watchtower.stake_cell_lock_dep.clone();
         let rollup_cell_dep = CellDep::new_builder()
             .out_point(rollup_cell.out_point.to_owned())
             .dep_type(DepType::Code.into())
@@ -62,18 +66,65 @@ pub async fn generate(
             .lock(lock)
             .build();
 
+        let mut deps = vec![stake_lock_dep.into(), rollup_cell_dep];
+        let mut inputs = vec![];
+        let mut change_outputs = vec![];
+        let (output, output_data) = match sudt_bond {
+            Some(sudt_bond) => {
+                // Reuse bonded sudt, top up if it isn't enough
+                let bonded_amount = match parse_sudt(&unlocked_stake) {
+                    Some((sudt_type, amount))
+                        if sudt_type.as_slice() == sudt_bond.sudt_type.as_slice() =>
+                    {
+                        amount
+                    }
+                    Some(_) => bail!("unlocked stake cell is bonded in another sudt"),
+                    None => 0,
+                };
+                let top_up_amount = sudt_bond.stake_amount.saturating_sub(bonded_amount);
+                let collected = collect_sudt(
+                    rpc_client,
+                    lock_script,
+                    sudt_bond.sudt_type.clone(),
+                    top_up_amount,
+                    &HashSet::new(),
+                )
+                .await?;
+
+                deps.push(
+                    block_producer_config
+                        .watchtower
+                        .l1_sudt_type_dep
+                        .clone()
+                        .into(),
+                );
+                inputs.extend(collected.inputs);
+                change_outputs.extend(collected.change);
+
+                let amount = bonded_amount.max(sudt_bond.stake_amount);
+                build_sudt_cell(stake_cell, sudt_bond.sudt_type.clone(), amount)?
+            }
+            None if unlocked_stake.output.type_().is_some() => {
+                bail!("unlocked stake cell is bonded in sudt, but sudt bond isn't configured")
+            }
+            None => (stake_cell, Bytes::new()),
+        };
+
         let input_unlocked_stake = InputCellInfo {
             input: CellInput::new_builder()
                 .previous_output(unlocked_stake.out_point.clone())
                 .build(),
             cell: unlocked_stake,
         };
+        inputs.insert(0, input_unlocked_stake);
+        verify_stake_cell(rollup_context, sudt_bond, &output, &output_data)?;
 
         let generated_stake = GeneratedStake {
-            deps: vec![stake_lock_dep.into(), rollup_cell_dep],
-            inputs: vec![input_unlocked_stake],
-            output: stake_cell,
-            output_data: Bytes::new(),
+            deps,
+            inputs,
+            output,
+            output_data,
+            change_outputs,
         };
 
         return Ok(generated_stake);
@@ -101,12 +152,97 @@ pub async fn generate(
         .lock(lock)
         .build();
 
-    let generated_stake = GeneratedStake {
-        deps: vec![],
-        inputs: vec![],
-        output: stake_cell,
-        output_data: Bytes::new(),
+    let generated_stake = match sudt_bond {
+        Some(sudt_bond) => {
+            let collected = collect_sudt(
+                rpc_client,
+                lock_script,
+                sudt_bond.sudt_type.clone(),
+                sudt_bond.stake_amount,
+                &HashSet::new(),
+            )
+            .await?;
+            let (output, output_data) = build_sudt_cell(
+                stake_cell,
+                sudt_bond.sudt_type.clone(),
+                sudt_bond.stake_amount,
+            )?;
+
+            GeneratedStake {
+                deps: vec![block_producer_config
+                    .watchtower
+                    .l1_sudt_type_dep
+                    .clone()
+                    .into()],
+                inputs: collected.inputs,
+                output,
+                output_data,
+                change_outputs: collected.change.into_iter().collect(),
+            }
+        }
+        None => GeneratedStake {
+            deps: vec![],
+            inputs: vec![],
+            output: stake_cell,
+            output_data: Bytes::new(),
+            change_outputs: vec![],
+        },
     };
+    verify_stake_cell(
+        rollup_context,
+        sudt_bond,
+        &generated_stake.output,
+        &generated_stake.output_data,
+    )?;
 
     Ok(generated_stake)
 }
+
+/// Check that a stake cell holds the required staking capacity, and bonds
+/// at least `stake_amount` of the rollup's bond sudt if it's configured.
+pub fn verify_stake_cell(
+    rollup_context: &RollupContext,
+    sudt_bond: Option<&SUDTBond>,
+    output: &CellOutput,
+    output_data: &Bytes,
+) -> Result<()> {
+    let capacity: u64 = output.capacity().unpack();
+    let required_staking_capacity: u64 = rollup_context
+        .rollup_config
+        .required_staking_capacity()
+        .unpack();
+    if capacity < required_staking_capacity {
+        bail!(
+            "stake capacity {} is less than required {}",
+            capacity,
+            required_staking_capacity
+        );
+    }
+
+    match (sudt_bond, output.type_().to_opt()) {
+        (None, None) => Ok(()),
+        (None, Some(_)) => bail!("stake cell is bonded in sudt, but sudt bond isn't configured"),
+        (Some(_), None) => bail!("stake cell isn't bonded in sudt"),
+        (Some(sudt_bond), Some(sudt_type)) => {
+            if sudt_type.as_slice() != sudt_bond.sudt_type.as_slice() {
+                bail!("stake cell is bonded in another sudt");
+            }
+            if output_data.len() < 16 {
+                bail!("invalid stake cell sudt data");
+            }
+
+            let mut buf = [0u8; 16];
+            buf.copy_from_slice(&output_data[..16]);
+            let amount = u128::from_le_bytes(buf);
+            if amount < sudt_bond.stake_amount {
+                bail!(
+                    "stake cell bonds {} sudt, less than required {}",
+                    amount,
+                    sudt_bond.stake_amount
+                );
+            }
+
+            Ok(())
+        }
+    }
+}
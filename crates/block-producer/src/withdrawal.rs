@@ -1,6 +1,9 @@
 use anyhow::{anyhow, Result};
 use gw_config::BlockProducerConfig;
-use gw_mem_pool::{custodian::sum_withdrawals, withdrawal::Generator};
+use gw_mem_pool::{
+    custodian::{generate_merged_custodians, sum_withdrawals},
+    withdrawal::Generator,
+};
 use gw_types::{
     bytes::Bytes,
     core::ScriptHashType,
@@ -50,7 +53,7 @@ pub fn generate(
     log::debug!("included withdrawals {}", generator.withdrawals().len());
 
     let custodian_lock_dep = block_producer_config.custodian_cell_lock_dep.clone();
-    let sudt_type_dep = block_producer_config.l1_sudt_type_dep.clone();
+    let sudt_type_dep = block_producer_config.watchtower.l1_sudt_type_dep.clone();
     let mut cell_deps = vec![custodian_lock_dep.into()];
     if !total_withdrawal_amount.sudt.is_empty() {
         cell_deps.push(sudt_type_dep.into());
@@ -72,6 +75,49 @@ pub fn generate(
     Ok(Some(generated_withdrawals))
 }
 
+// Note: custodian lock search rollup cell in inputs, so merged custodians are
+// packed into submit block tx.
+pub fn merge_custodians(
+    rollup_context: &RollupContext,
+    mergeable_custodians: CollectedCustodianCells,
+    block_producer_config: &BlockProducerConfig,
+) -> Result<Option<GeneratedWithdrawals>> {
+    if mergeable_custodians.cells_info.len() < 2 {
+        return Ok(None);
+    }
+    log::debug!("mergeable custodians {:?}", mergeable_custodians);
+
+    let outputs = match generate_merged_custodians(rollup_context, &mergeable_custodians) {
+        Some(outputs) => outputs,
+        None => {
+            log::warn!("skip merging custodians, merged custodian below minimal capacity");
+            return Ok(None);
+        }
+    };
+
+    let custodian_lock_dep = block_producer_config.custodian_cell_lock_dep.clone();
+    let sudt_type_dep = block_producer_config.watchtower.l1_sudt_type_dep.clone();
+    let mut cell_deps = vec![custodian_lock_dep.into()];
+    if !mergeable_custodians.sudt.is_empty() {
+        cell_deps.push(sudt_type_dep.into());
+    }
+
+    let custodian_inputs = mergeable_custodians.cells_info.into_iter().map(|cell| {
+        let input = CellInput::new_builder()
+            .previous_output(cell.out_point.clone())
+            .build();
+        InputCellInfo { input, cell }
+    });
+
+    let merged_custodians = GeneratedWithdrawals {
+        deps: cell_deps,
+        inputs: custodian_inputs.collect(),
+        outputs,
+    };
+
+    Ok(Some(merged_custodians))
+}
+
 pub struct RevertedWithdrawals {
     pub deps: Vec<CellDep>,
     pub inputs: Vec<InputCellInfo>,
@@ -164,7 +210,7 @@ pub fn revert(
     }
 
     let withdrawal_lock_dep = block_producer_config.withdrawal_cell_lock_dep.clone();
-    let sudt_type_dep = block_producer_config.l1_sudt_type_dep.clone();
+    let sudt_type_dep = block_producer_config.watchtower.l1_sudt_type_dep.clone();
     let mut cell_deps = vec![withdrawal_lock_dep.into()];
     if withdrawal_inputs
         .iter()
@@ -1,15 +1,22 @@
 #![allow(clippy::mutable_key_type)]
 
 use anyhow::{anyhow, Context, Result};
-use gw_challenge::offchain::{verify_tx::TxWithContext, OffChainMockContext};
+use gw_challenge::{
+    cancel_challenge::{LoadDataStrategy, MAX_CANCEL_CYCLES, MAX_CANCEL_TX_BYTES},
+    offchain::{
+        verify_tx::{verify_tx, TxWithContext},
+        OffChainMockContext,
+    },
+};
 use gw_common::{sparse_merkle_tree, state::State, H256};
-use gw_config::ChainConfig;
+use gw_config::{ChainConfig, DebugConfig};
 use gw_generator::{
     generator::{ApplyBlockArgs, ApplyBlockResult},
     ChallengeContext, Generator,
 };
 use gw_jsonrpc_types::debugger::ReprMockTransaction;
 use gw_mem_pool::pool::MemPool;
+use gw_metrics::GLOBAL_METRICS;
 use gw_store::{
     chain_view::ChainView,
     state_db::{CheckPoint, StateDBMode, StateDBTransaction, SubState},
@@ -18,7 +25,7 @@ use gw_store::{
 };
 use gw_types::{
     bytes::Bytes,
-    core::Status,
+    core::{ChallengeTargetType, Status},
     offchain::global_state_from_slice,
     packed::{
         BlockMerkleState, CellInput, CellOutput, ChallengeTarget, ChallengeWitness, DepositRequest,
@@ -27,7 +34,13 @@ use gw_types::{
     prelude::{Builder as GWBuilder, Entity as GWEntity, Pack as GWPack, Unpack as GWUnpack},
 };
 use smol::lock::Mutex;
-use std::{collections::HashSet, convert::TryFrom, sync::Arc};
+use std::{
+    collections::HashSet,
+    convert::TryFrom,
+    fs::{create_dir_all, write},
+    path::PathBuf,
+    sync::Arc,
+};
 
 #[derive(Debug, Clone)]
 pub struct ChallengeCell {
@@ -113,6 +126,62 @@ impl SyncEvent {
     pub fn is_success(&self) -> bool {
         matches!(self, SyncEvent::Success)
     }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SyncEvent::Success => "success",
+            SyncEvent::BadBlock { .. } => "bad_block",
+            SyncEvent::BadChallenge { .. } => "bad_challenge",
+            SyncEvent::WaitChallenge { .. } => "wait_challenge",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CancelChallengeResult {
+    /// cancel challenge tx passes verification, with used cycles
+    Verified(u64),
+    /// cancel challenge tx fails verification or exceeds the size limit
+    Rejected(String),
+    /// mock cancel challenge tx can't be built, nothing is verified
+    MockTxError(String),
+}
+
+/// offchain cancel challenge attempt against a local bad block
+#[derive(Debug, Clone)]
+pub struct CancelChallengeAttempt {
+    pub load_data_strategy: LoadDataStrategy,
+    pub result: CancelChallengeResult,
+    /// where the mock cancel challenge tx is dumped
+    pub dump_path: Option<PathBuf>,
+}
+
+/// Result of running offchain cancel challenge validation against a local bad block.
+/// A bad block is confirmed only if every cancel challenge tx is built and rejected,
+/// errors never confirm a bad block.
+#[derive(Debug, Clone)]
+pub struct BadBlockConfirmation {
+    pub target: ChallengeTarget,
+    pub confirmed: bool,
+    /// empty if the confirmation is loaded from db
+    pub attempts: Vec<CancelChallengeAttempt>,
+    /// error while building verify context
+    pub error: Option<String>,
+}
+
+impl BadBlockConfirmation {
+    pub fn from_attempts(target: ChallengeTarget, attempts: Vec<CancelChallengeAttempt>) -> Self {
+        let rejected = |attempt: &CancelChallengeAttempt| {
+            matches!(attempt.result, CancelChallengeResult::Rejected(_))
+        };
+        let confirmed = !attempts.is_empty() && attempts.iter().all(rejected);
+        BadBlockConfirmation {
+            target,
+            confirmed,
+            attempts,
+            error: None,
+        }
+    }
 }
 
 /// concrete type aliases
@@ -154,6 +223,8 @@ pub struct Chain {
     mem_pool: Option<Arc<Mutex<MemPool>>>,
     complete_initial_syncing: bool,
     skipped_invalid_block_list: HashSet<H256>,
+    offchain_validator: Option<(OffChainMockContext, DebugConfig)>,
+    bad_block_confirmation: Option<BadBlockConfirmation>,
 }
 
 impl Chain {
@@ -210,9 +281,21 @@ impl Chain {
             rollup_config_hash,
             complete_initial_syncing: false,
             skipped_invalid_block_list,
+            offchain_validator: None,
+            bad_block_confirmation: None,
         })
     }
 
+    /// Confirm bad blocks by offchain cancel challenge validation before reporting them,
+    /// mock cancel challenge txs are dumped to `debug_tx_dump_path`
+    pub fn enable_bad_block_confirmation(
+        &mut self,
+        offchain_mock_context: OffChainMockContext,
+        debug_config: DebugConfig,
+    ) {
+        self.offchain_validator = Some((offchain_mock_context, debug_config));
+    }
+
     /// return local state
     pub fn local_state(&self) -> &LocalState {
         &self.local_state
@@ -226,7 +309,7 @@ impl Chain {
         &self.mem_pool
     }
 
-    pub fn generator(&self) -> &Generator {
+    pub fn generator(&self) -> &Arc<Generator> {
         &self.generator
     }
 
@@ -242,6 +325,12 @@ impl Chain {
         &self.last_sync_event
     }
 
+    /// Offchain validation result of current bad block, none if validation isn't enabled
+    /// or no result is persisted for the bad block loaded from db
+    pub fn bad_block_confirmation(&self) -> Option<&BadBlockConfirmation> {
+        self.bad_block_confirmation.as_ref()
+    }
+
     pub fn bad_block_hash(&self) -> Option<H256> {
         self.challenge_target
             .as_ref()
@@ -345,6 +434,17 @@ impl Chain {
                     let challenge_target =
                         db.get_bad_block_challenge_target(&l2block.hash().into())?;
                     if self.challenge_target.is_none() && challenge_target.is_some() {
+                        let block_hash: H256 = l2block.hash().into();
+                        let confirmed = db.get_bad_block_confirmed(&block_hash)?;
+                        self.bad_block_confirmation =
+                            challenge_target.as_ref().and_then(|target| {
+                                confirmed.map(|confirmed| BadBlockConfirmation {
+                                    target: target.to_owned(),
+                                    confirmed,
+                                    attempts: vec![],
+                                    error: None,
+                                })
+                            });
                         self.challenge_target = challenge_target;
                     }
 
@@ -371,6 +471,15 @@ impl Chain {
                         deposit_requests,
                         deposit_asset_scripts,
                     )? {
+                        // Tx checkpoints are still available before rollback
+                        self.bad_block_confirmation = self.confirm_bad_block(
+                            db,
+                            &l2block,
+                            &l2block_committed_info,
+                            &global_state,
+                            &challenge_target,
+                        )?;
+
                         db.rollback()?;
                         log::warn!("bad block found, rollback db");
 
@@ -386,6 +495,12 @@ impl Chain {
                             &l2block.hash().into(),
                             &challenge_target,
                         )?;
+                        if let Some(ref confirmation) = self.bad_block_confirmation {
+                            db.set_bad_block_confirmed(
+                                &l2block.hash().into(),
+                                confirmation.confirmed,
+                            )?;
+                        }
                         self.challenge_target = Some(challenge_target.clone());
                         self.local_state.tip = l2block;
 
@@ -555,6 +670,7 @@ impl Chain {
                     // Check whether our bad block is reverted
                     if Some(H256::from(first_reverted_block.hash())) == self.bad_block_hash() {
                         self.challenge_target = None;
+                        self.bad_block_confirmation = None;
                         log::info!("clear local bad block");
                     }
 
@@ -587,6 +703,10 @@ impl Chain {
         };
 
         self.last_sync_event = update()?;
+        if !self.last_sync_event.is_success() {
+            let event = self.last_sync_event.name();
+            GLOBAL_METRICS.challenge_events.with_label(event).inc();
+        }
         self.local_state.last_global_state = global_state;
         self.local_state.last_synced = l2block_committed_info;
         log::debug!("last sync event {:?}", self.last_sync_event);
@@ -785,6 +905,7 @@ impl Chain {
         revert()?;
         self.last_sync_event = SyncEvent::Success;
         self.challenge_target = None;
+        self.bad_block_confirmation = None;
 
         self.local_state.last_global_state = prev_global_state;
         self.local_state.tip = db.get_tip_block()?;
@@ -901,7 +1022,6 @@ impl Chain {
         );
 
         // process transactions
-        // TODO: run offchain validator before send challenge, to make sure the block is bad
         let generator = &self.generator;
         let (withdrawal_receipts, prev_txs_state, tx_receipts) = match generator
             .verify_and_apply_block(db, &chain_view, args, &self.skipped_invalid_block_list)
@@ -958,6 +1078,147 @@ impl Chain {
         self.local_state.tip = l2block;
         Ok(None)
     }
+
+    /// Run offchain cancel challenge validation against bad block, db must contain tx
+    /// checkpoints of the bad block.
+    fn confirm_bad_block(
+        &self,
+        db: &StoreTransaction,
+        l2block: &L2Block,
+        l2block_committed_info: &L2BlockCommittedInfo,
+        global_state: &GlobalState,
+        target: &ChallengeTarget,
+    ) -> Result<Option<BadBlockConfirmation>> {
+        let (mock_ctx, debug_config) = match self.offchain_validator {
+            Some((ref mock_ctx, ref debug_config)) => (mock_ctx, debug_config),
+            None => return Ok(None),
+        };
+
+        // Verify context requires bad block in db
+        db.insert_bad_block(l2block, l2block_committed_info, global_state)?;
+
+        let block_number: u64 = l2block.raw().number().unpack();
+        let target_index: u32 = target.target_index().unpack();
+        let target_type = match ChallengeTargetType::try_from(target.target_type()) {
+            Ok(ChallengeTargetType::TxSignature) => "tx-signature",
+            Ok(ChallengeTargetType::TxExecution) => "tx-execution",
+            Ok(ChallengeTargetType::Withdrawal) => "withdrawal",
+            Err(_) => return Err(anyhow!("invalid challenge type")),
+        };
+
+        let generator = Arc::clone(&self.generator);
+        let verify_context =
+            match gw_challenge::context::build_verify_context(generator, db, target) {
+                Ok(context) => context,
+                Err(err) => {
+                    log::error!("bad block #{} build verify context {}", block_number, err);
+                    return Ok(Some(BadBlockConfirmation {
+                        target: target.to_owned(),
+                        confirmed: false,
+                        attempts: vec![],
+                        error: Some(err.to_string()),
+                    }));
+                }
+            };
+        let halting_global_state = {
+            let to_builder = global_state.clone().as_builder();
+            to_builder.status((Status::Halting as u8).into()).build()
+        };
+
+        let dump_mock_tx = |strategy: &str, tx_with_context: TxWithContext| -> Result<PathBuf> {
+            let dir = debug_config.debug_tx_dump_path.as_path();
+            create_dir_all(&dir)?;
+
+            let mut dump_path = PathBuf::new();
+            dump_path.push(dir);
+            let dump_filename = format!(
+                "bad-block-#{}-{}-{}-{}-offchain-cancel-tx.json",
+                block_number, target_type, target_index, strategy
+            );
+            dump_path.push(dump_filename);
+
+            let tx = gw_challenge::offchain::dump_tx(&mock_ctx.rollup_cell_deps, tx_with_context)?;
+            write(&dump_path, serde_json::to_string_pretty(&tx)?)?;
+
+            Ok(dump_path)
+        };
+
+        let mut attempts = Vec::with_capacity(2);
+        for load_data_strategy in vec![LoadDataStrategy::Witness, LoadDataStrategy::CellDep] {
+            let strategy = match load_data_strategy {
+                LoadDataStrategy::Witness => "with-witness-load-data",
+                LoadDataStrategy::CellDep => "with-celldep-load-data",
+            };
+
+            let mock_output = match gw_challenge::offchain::mock_cancel_challenge_tx(
+                &mock_ctx.mock_rollup,
+                &mock_ctx.mock_poa,
+                halting_global_state.clone(),
+                target.to_owned(),
+                verify_context.clone(),
+                Some(load_data_strategy),
+            ) {
+                Ok(output) => output,
+                Err(err) => {
+                    log::error!(
+                        "bad block #{} {} {} mock cancel challenge tx {}",
+                        block_number,
+                        target_type,
+                        target_index,
+                        err
+                    );
+                    attempts.push(CancelChallengeAttempt {
+                        load_data_strategy,
+                        result: CancelChallengeResult::MockTxError(err.to_string()),
+                        dump_path: None,
+                    });
+                    continue;
+                }
+            };
+
+            let tx_size = mock_output.tx.as_slice().len() as u64;
+            let result = if tx_size > MAX_CANCEL_TX_BYTES {
+                let err = format!("cancel tx max bytes exceeded {}", tx_size);
+                CancelChallengeResult::Rejected(err)
+            } else {
+                let tx_with_context = TxWithContext::from(mock_output.clone());
+                let verify = verify_tx(
+                    &mock_ctx.rollup_cell_deps,
+                    tx_with_context,
+                    MAX_CANCEL_CYCLES,
+                );
+                match verify {
+                    Ok(cycles) => CancelChallengeResult::Verified(cycles),
+                    Err(err) => CancelChallengeResult::Rejected(err.to_string()),
+                }
+            };
+
+            let dump_path = match dump_mock_tx(strategy, TxWithContext::from(mock_output)) {
+                Ok(path) => Some(path),
+                Err(err) => {
+                    log::error!("unable to dump offchain cancel challenge tx {}", err);
+                    None
+                }
+            };
+
+            log::info!(
+                "bad block #{} {} {} offchain cancel challenge {:?}, dump to {:?}",
+                block_number,
+                target_type,
+                target_index,
+                result,
+                dump_path
+            );
+            attempts.push(CancelChallengeAttempt {
+                load_data_strategy,
+                result,
+                dump_path,
+            });
+        }
+
+        let confirmation = BadBlockConfirmation::from_attempts(target.to_owned(), attempts);
+        Ok(Some(confirmation))
+    }
 }
 
 fn parse_global_state(tx: &Transaction, rollup_id: &[u8; 32]) -> Result<GlobalState> {
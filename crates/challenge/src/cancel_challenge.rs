@@ -3,7 +3,7 @@ use crate::types::{VerifyContext, VerifyWitness};
 use anyhow::{anyhow, Result};
 use ckb_types::prelude::{Builder, Entity};
 use gw_common::H256;
-use gw_config::BlockProducerConfig;
+use gw_config::WatchtowerConfig;
 use gw_types::core::{DepType, SigningType, Status};
 use gw_types::offchain::{CellInfo, InputCellInfo, RecoverAccount, RollupContext};
 use gw_types::packed::{
@@ -13,8 +13,14 @@ use gw_types::packed::{
 };
 use gw_types::prelude::Unpack;
 use gw_types::{bytes::Bytes, prelude::Pack as GWPack};
+use gw_utils::sudt::{build_sudt_cell, parse_sudt};
 use std::collections::{HashMap, HashSet};
 
+/// Cycles limit of a cancel challenge tx, a tx exceeding it can't cancel a challenge
+pub const MAX_CANCEL_CYCLES: u64 = 7000_0000;
+/// Size limit of a cancel challenge tx
+pub const MAX_CANCEL_TX_BYTES: u64 = ckb_chain_spec::consensus::MAX_BLOCK_BYTES;
+
 pub struct CancelChallenge<'a, W: Entity> {
     rollup_type_hash: H256,
     reward_burn_rate: u8,
@@ -58,7 +64,8 @@ pub struct CancelChallengeOutput {
     pub recover_accounts: Option<RecoverAccounts>,
     pub verifier_cell: (CellOutput, Bytes),
     pub burn_cells: Vec<(CellOutput, Bytes)>,
-    pub verifier_witness: Option<WitnessArgs>, // Some for signature verification
+    pub sudt_return_cell: Option<(CellOutput, Bytes)>, // Some for challenge cell bonded in sudt
+    pub verifier_witness: Option<WitnessArgs>,         // Some for signature verification
     pub challenge_witness: WitnessArgs,
     pub rollup_witness: WitnessArgs,
 }
@@ -86,11 +93,11 @@ impl CancelChallengeOutput {
         InputCellInfo { input, cell }
     }
 
-    pub fn verifier_dep(&self, block_producer_config: &BlockProducerConfig) -> Result<CellDep> {
+    pub fn verifier_dep(&self, config: &WatchtowerConfig) -> Result<CellDep> {
         let lock_code_hash: [u8; 32] = self.verifier_cell.0.lock().code_hash().unpack();
         let mut allowed_script_deps = {
-            let eoa = block_producer_config.allowed_eoa_deps.iter();
-            eoa.chain(block_producer_config.allowed_contract_deps.iter())
+            let eoa = config.allowed_eoa_deps.iter();
+            eoa.chain(config.allowed_contract_deps.iter())
         };
         let has_dep = allowed_script_deps.find(|(code_hash, _)| code_hash.0 == lock_code_hash);
         let to_dep = has_dep.map(|(_, dep)| dep.clone().into());
@@ -147,7 +154,7 @@ pub fn build_output(
             );
 
             let data = cancel.build_verifier_data();
-            Ok(cancel.build_output(data, Some(verifier_witness), None, None))
+            cancel.build_output(data, Some(verifier_witness), None, None)
         }
         VerifyWitness::TxSignature(witness) => {
             let verifier_lock = context.sender_script;
@@ -173,7 +180,7 @@ pub fn build_output(
             );
 
             let data = cancel.build_verifier_data(receiver_script.hash().into());
-            Ok(cancel.build_output(data, Some(verifier_witness), None, None))
+            cancel.build_output(data, Some(verifier_witness), None, None)
         }
         VerifyWitness::TxExecution {
             witness,
@@ -236,7 +243,7 @@ pub fn build_output(
                 RecoverAccounts { cells, witnesses }
             };
 
-            Ok(cancel.build_output(data, None, Some(load_data), Some(recover_accounts)))
+            cancel.build_output(data, None, Some(load_data), Some(recover_accounts))
         }
     }
 }
@@ -294,13 +301,13 @@ impl RecoverAccounts {
         self,
         verifier_tx_hash: H256,
         index_offset: usize,
-        block_producer_config: &BlockProducerConfig,
+        config: &WatchtowerConfig,
     ) -> Result<RecoverAccountsContext> {
         assert!(index_offset != 0, "verifier cell should be first one");
         let RecoverAccounts { cells, witnesses } = self;
 
         let cell_deps = {
-            let allowed_eoa_deps = &block_producer_config.allowed_eoa_deps;
+            let allowed_eoa_deps = &config.allowed_eoa_deps;
             let accounts = cells.iter();
             let to_code_hash: HashSet<_> = accounts
                 .map(|(output, _)| output.lock().code_hash().unpack())
@@ -379,27 +386,28 @@ impl<'a, W: Entity> CancelChallenge<'a, W> {
         verifier_witness: Option<WitnessArgs>,
         load_data: Option<LoadData>,
         recover_accounts: Option<RecoverAccounts>,
-    ) -> CancelChallengeOutput {
+    ) -> Result<CancelChallengeOutput> {
         let verifier_cell = build_cell(verifier_data, self.verifier_lock);
 
         let burn = Burn::new(self.challenge_cell, self.reward_burn_rate);
-        let burn_output = burn.build_output(self.burn_lock);
+        let burn_output = burn.build_output(self.burn_lock, self.owner_lock)?;
 
         let post_global_state = build_post_global_state(self.prev_global_state);
         let challenge_witness = WitnessArgs::new_builder()
             .lock(Some(self.verify_witness.as_bytes()).pack())
             .build();
 
-        CancelChallengeOutput {
+        Ok(CancelChallengeOutput {
             post_global_state,
             verifier_cell,
             load_data,
             recover_accounts,
             burn_cells: burn_output.burn_cells,
+            sudt_return_cell: burn_output.sudt_return_cell,
             verifier_witness,
             challenge_witness,
             rollup_witness: build_rollup_witness(),
-        }
+        })
     }
 }
 
@@ -455,10 +463,12 @@ impl<'a> CancelChallenge<'a, VerifyWithdrawalWitness> {
 
 struct Burn {
     burn_capacity: u128,
+    sudt: Option<(Script, u128, u128)>, // (sudt type, burn amount, return amount)
 }
 
 struct BurnOutput {
     burn_cells: Vec<(CellOutput, Bytes)>,
+    sudt_return_cell: Option<(CellOutput, Bytes)>,
 }
 
 impl Burn {
@@ -468,10 +478,18 @@ impl Burn {
 
         let burn_capacity = challenge_capacity.saturating_mul(reward_burn_rate.into()) / 100;
 
-        Self { burn_capacity }
+        let sudt = parse_sudt(challenge_cell).map(|(sudt_type, amount)| {
+            let burn_amount = amount.saturating_mul(reward_burn_rate.into()) / 100;
+            (sudt_type, burn_amount, amount.saturating_sub(burn_amount))
+        });
+
+        Self {
+            burn_capacity,
+            sudt,
+        }
     }
 
-    fn build_output(self, burn_lock: Script) -> BurnOutput {
+    fn build_output(self, burn_lock: Script, owner_lock: Script) -> Result<BurnOutput> {
         let build_outputs = |total_capacity: u128, lock: Script| -> Vec<(CellOutput, Bytes)> {
             let build = |capacity: u64, lock: Script| -> (CellOutput, Bytes) {
                 let output = CellOutput::new_builder()
@@ -513,9 +531,25 @@ impl Burn {
             outputs
         };
 
-        BurnOutput {
-            burn_cells: build_outputs(self.burn_capacity, burn_lock),
+        let mut burn_cells = build_outputs(self.burn_capacity, burn_lock);
+        let mut sudt_return_cell = None;
+
+        // Burn sudt in first burn cell, rest is returned to owner
+        if let Some((sudt_type, burn_amount, return_amount)) = self.sudt {
+            if let Some(cell) = burn_cells.first_mut().filter(|_| burn_amount > 0) {
+                let output = cell.0.clone();
+                *cell = build_sudt_cell(output, sudt_type.clone(), burn_amount)?;
+            }
+            if return_amount > 0 {
+                let output = CellOutput::new_builder().lock(owner_lock).build();
+                sudt_return_cell = Some(build_sudt_cell(output, sudt_type, return_amount)?);
+            }
         }
+
+        Ok(BurnOutput {
+            burn_cells,
+            sudt_return_cell,
+        })
     }
 }
 
@@ -571,3 +605,56 @@ fn build_recover_account_cell(
 
     ((output, data), witness)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Burn;
+    use gw_types::{
+        offchain::CellInfo,
+        packed::{CellOutput, Script},
+        prelude::*,
+    };
+    use gw_utils::sudt::{build_sudt_cell, parse_sudt};
+
+    #[test]
+    fn test_burn_challenge_sudt_bond() {
+        let sudt_type = Script::new_builder().args(vec![2u8; 32].pack()).build();
+        let burn_lock = Script::new_builder().args(vec![5u8; 20].pack()).build();
+        let owner_lock = Script::new_builder().args(vec![7u8; 20].pack()).build();
+
+        let challenge_cell = {
+            let output = CellOutput::new_builder()
+                .capacity(50_000_000_000u64.pack())
+                .build();
+            let (output, data) = build_sudt_cell(output, sudt_type.clone(), 100).unwrap();
+            CellInfo {
+                output,
+                data,
+                ..Default::default()
+            }
+        };
+
+        let burn = Burn::new(&challenge_cell, 30);
+        let output = burn.build_output(burn_lock, owner_lock.clone()).unwrap();
+
+        let (burn_output, burn_data) = output.burn_cells.first().unwrap().to_owned();
+        let burn_cell = CellInfo {
+            output: burn_output,
+            data: burn_data,
+            ..Default::default()
+        };
+        let (burn_type, burn_amount) = parse_sudt(&burn_cell).unwrap();
+        assert_eq!(burn_type.as_slice(), sudt_type.as_slice());
+        assert_eq!(burn_amount, 30);
+
+        let (return_output, return_data) = output.sudt_return_cell.unwrap();
+        assert_eq!(return_output.lock().as_slice(), owner_lock.as_slice());
+        let return_cell = CellInfo {
+            output: return_output,
+            data: return_data,
+            ..Default::default()
+        };
+        let (_, return_amount) = parse_sudt(&return_cell).unwrap();
+        assert_eq!(return_amount, 70);
+    }
+}
@@ -7,7 +7,6 @@ use gw_common::smt::Blake2bHasher;
 use gw_common::sparse_merkle_tree::CompiledMerkleProof;
 use gw_common::state::State;
 use gw_common::{blake2b::new_blake2b, H256};
-use gw_generator::constants::L2TX_MAX_CYCLES;
 use gw_generator::traits::StateExt;
 use gw_generator::{ChallengeContext, Generator};
 use gw_store::chain_view::ChainView;
@@ -397,7 +396,7 @@ fn build_tx_kv_witness(
                 &tree,
                 &block_info,
                 raw_tx,
-                L2TX_MAX_CYCLES,
+                generator.execution_limits().l2tx_max_cycles,
             )?;
             tree.apply_run_result(&run_result)?;
 
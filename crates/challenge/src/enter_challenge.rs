@@ -8,6 +8,7 @@ use gw_types::packed::{
     RollupAction, RollupActionUnion, RollupEnterChallenge, Script, WitnessArgs,
 };
 use gw_types::{bytes::Bytes, prelude::Pack};
+use gw_utils::sudt::SUDTBond;
 
 pub struct EnterChallenge {
     rollup_type_hash: H256,
@@ -16,6 +17,7 @@ pub struct EnterChallenge {
     target: ChallengeTarget,
     witness: ChallengeWitness,
     rewards_lock: Script,
+    sudt_bond: Option<SUDTBond>,
 }
 
 pub struct EnterChallengeOutput {
@@ -30,6 +32,7 @@ impl EnterChallenge {
         rollup_context: &RollupContext,
         challenge_context: ChallengeContext,
         rewards_lock: Script,
+        sudt_bond: Option<SUDTBond>,
     ) -> Self {
         let rollup_type_hash = rollup_context.rollup_script_hash;
         let challenge_script_type_hash = rollup_context.rollup_config.challenge_script_type_hash();
@@ -41,6 +44,7 @@ impl EnterChallenge {
             target: challenge_context.target,
             witness: challenge_context.witness,
             rewards_lock,
+            sudt_bond,
         }
     }
 
@@ -64,18 +68,25 @@ impl EnterChallenge {
             .args(lock_args.pack())
             .build();
 
-        let challenge = {
+        let challenge_cell = {
+            let (type_, data) = match self.sudt_bond {
+                Some(sudt_bond) => {
+                    let amount = sudt_bond.challenge_amount.to_le_bytes();
+                    (Some(sudt_bond.sudt_type), Bytes::from(amount.to_vec()))
+                }
+                None => (None, Bytes::default()),
+            };
             let dummy = CellOutput::new_builder()
                 .capacity(u64::MAX.pack())
                 .lock(challenge_lock)
+                .type_(type_.pack())
                 .build();
 
             let capacity = dummy
-                .occupied_capacity(0)
+                .occupied_capacity(data.len())
                 .expect("challenge cell capacity overflow");
-            dummy.as_builder().capacity(capacity.pack()).build()
+            (dummy.as_builder().capacity(capacity.pack()).build(), data)
         };
-        let challenge_cell = (challenge, Bytes::default());
 
         let halting_status: u8 = Status::Halting.into();
         let post_global_state = {
@@ -103,3 +114,54 @@ impl EnterChallenge {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::EnterChallenge;
+    use gw_common::H256;
+    use gw_generator::ChallengeContext;
+    use gw_types::{
+        offchain::{CellInfo, RollupContext},
+        packed::{GlobalState, RollupConfig, Script},
+        prelude::*,
+    };
+    use gw_utils::sudt::{parse_sudt, SUDTBond};
+
+    #[test]
+    fn test_challenge_cell_with_sudt_bond() {
+        let rollup_context = RollupContext {
+            rollup_script_hash: H256::zero(),
+            rollup_config: RollupConfig::default(),
+        };
+        let sudt_bond = SUDTBond {
+            sudt_type: Script::new_builder().args(vec![2u8; 32].pack()).build(),
+            stake_amount: 500,
+            challenge_amount: 100,
+        };
+        let challenge_context = ChallengeContext {
+            target: Default::default(),
+            witness: Default::default(),
+        };
+
+        let enter_challenge = EnterChallenge::new(
+            GlobalState::default(),
+            &rollup_context,
+            challenge_context,
+            Script::default(),
+            Some(sudt_bond.clone()),
+        );
+        let (output, data) = enter_challenge.build_output().challenge_cell;
+
+        let capacity: u64 = output.capacity().unpack();
+        assert_eq!(capacity, output.occupied_capacity(data.len()).unwrap());
+
+        let challenge_cell = CellInfo {
+            output,
+            data,
+            ..Default::default()
+        };
+        let (sudt_type, amount) = parse_sudt(&challenge_cell).unwrap();
+        assert_eq!(sudt_type.as_slice(), sudt_bond.sudt_type.as_slice());
+        assert_eq!(amount, 100);
+    }
+}
@@ -5,7 +5,7 @@ use crate::cancel_challenge::LoadDataStrategy;
 use anyhow::{anyhow, bail, Result};
 use ckb_chain_spec::consensus::MAX_BLOCK_BYTES;
 use gw_common::H256;
-use gw_config::{BlockProducerConfig, DebugConfig, OffChainValidatorConfig};
+use gw_config::{DebugConfig, OffChainValidatorConfig, WatchtowerConfig};
 use gw_poa::PoA;
 use gw_rpc_client::rpc_client::RPCClient;
 use gw_store::{state_db::StateDBTransaction, transaction::StoreTransaction};
@@ -15,7 +15,7 @@ use gw_types::packed::{
     CellDep, CellInput, L2Block, L2Transaction, OutPoint, OutPointVec, Uint32, WithdrawalRequest,
 };
 use gw_types::prelude::{Builder, Entity};
-use gw_utils::wallet::Wallet;
+use gw_utils::{sudt::SUDTBond, wallet::Wallet};
 
 use std::{
     collections::{HashMap, HashSet},
@@ -61,18 +61,23 @@ pub struct OffChainMockContext {
 impl OffChainMockContext {
     pub async fn build(
         rpc_client: &RPCClient,
-        poa: &PoA,
+        poa: Option<&PoA>,
         rollup_context: RollupContext,
         wallet: Wallet,
-        config: BlockProducerConfig,
+        config: WatchtowerConfig,
         ckb_genesis_info: CKBGenesisInfo,
         builtin_load_data: HashMap<H256, CellDep>,
+        sudt_bond: Option<SUDTBond>,
     ) -> Result<Self> {
         let rollup_cell = {
             let query = rpc_client.query_rollup_cell().await?;
             into_input_cell_info(query.ok_or_else(|| anyhow!("can't found rollup cell"))?)
         };
-        let mock_poa = Arc::new(MockPoA::build(rpc_client, poa, &rollup_cell).await?);
+        let mock_poa = match poa {
+            Some(poa) => MockPoA::build(rpc_client, poa, &rollup_cell).await?,
+            None => MockPoA::without_poa(&rollup_cell),
+        };
+        let mock_poa = Arc::new(mock_poa);
 
         let rollup_type_script = rollup_cell.cell.output.type_();
         let mock_rollup = {
@@ -83,6 +88,7 @@ impl OffChainMockContext {
                 config,
                 ckb_genesis_info,
                 builtin_load_data,
+                sudt_bond,
             };
             Arc::new(mock)
         };
@@ -104,6 +110,9 @@ impl OffChainMockContext {
             });
             deps.extend(mock_rollup.builtin_load_data.values().cloned());
             deps.extend(mock_poa.cell_deps.clone());
+            if mock_rollup.sudt_bond.is_some() {
+                deps.push(mock_rollup.config.l1_sudt_type_dep.clone().into());
+            }
 
             deps
         };
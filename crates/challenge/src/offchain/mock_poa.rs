@@ -44,6 +44,17 @@ impl MockPoA {
         Ok(mock_poa)
     }
 
+    /// Rollup cell isn't locked by PoA, e.g. challenged by a watchtower
+    pub fn without_poa(rollup_cell: &InputCellInfo) -> Self {
+        MockPoA {
+            cell_deps: vec![],
+            input_since: 0,
+            inputs: vec![],
+            outputs: vec![],
+            lock: rollup_cell.cell.output.lock(),
+        }
+    }
+
     fn ensure_unlockable(mut context: PoAContext, poa: &PoA, median_time: Duration) -> PoAContext {
         let next_round_start_time = poa.estimate_next_round_start_time(context.clone());
         // Already unlocked
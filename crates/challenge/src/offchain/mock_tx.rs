@@ -5,13 +5,14 @@ use crate::cancel_challenge::{
 use crate::enter_challenge::EnterChallenge;
 use crate::offchain::{mock_poa::MockPoA, CKBGenesisInfo};
 use crate::types::VerifyContext;
+use gw_utils::sudt::SUDTBond;
 use gw_utils::transaction_skeleton::TransactionSkeleton;
 use gw_utils::wallet::Wallet;
 
 use anyhow::Result;
 use gw_common::blake2b::new_blake2b;
 use gw_common::H256;
-use gw_config::BlockProducerConfig;
+use gw_config::WatchtowerConfig;
 use gw_generator::ChallengeContext;
 use gw_types::bytes::Bytes;
 use gw_types::offchain::{CellInfo, InputCellInfo, RollupContext};
@@ -27,9 +28,10 @@ pub struct MockRollup {
     pub rollup_type_script: ScriptOpt,
     pub rollup_context: RollupContext,
     pub wallet: Wallet,
-    pub config: BlockProducerConfig,
+    pub config: WatchtowerConfig,
     pub ckb_genesis_info: CKBGenesisInfo,
     pub builtin_load_data: HashMap<H256, CellDep>,
+    pub sudt_bond: Option<SUDTBond>,
 }
 
 #[derive(Clone)]
@@ -103,6 +105,11 @@ pub fn mock_cancel_challenge_tx(
     tx_skeleton.cell_deps_mut().push(challenge_dep);
     tx_skeleton.inputs_mut().push(challenge_input);
     tx_skeleton.witnesses_mut().push(challenge_witness);
+    if let Some(sudt_return_cell) = cancel_output.sudt_return_cell {
+        let sudt_type_dep = mock_rollup.config.l1_sudt_type_dep.clone().into();
+        tx_skeleton.cell_deps_mut().push(sudt_type_dep);
+        tx_skeleton.outputs_mut().push(sudt_return_cell);
+    }
 
     // Verifier
     inputs.push(verifier_context.input.clone());
@@ -176,7 +183,7 @@ pub fn mock_cancel_challenge_tx(
 
     let owner_lock = mock_rollup.wallet.lock_script().to_owned();
     mock_rollup.fill_tx_fee(&mut tx_skeleton, owner_lock)?;
-    let tx = mock_rollup.wallet.sign_tx_skeleton(tx_skeleton)?;
+    let tx = mock_rollup.wallet.sign_tx_skeleton_blocking(tx_skeleton)?;
 
     Ok(MockOutput {
         cell_deps,
@@ -191,8 +198,9 @@ impl MockRollup {
         rollup_context: RollupContext,
         wallet: Wallet,
         ckb_genesis_info: CKBGenesisInfo,
-        config: BlockProducerConfig,
+        config: WatchtowerConfig,
         builtin_load_data: HashMap<H256, CellDep>,
+        sudt_bond: Option<SUDTBond>,
     ) -> Self {
         MockRollup {
             rollup_type_script,
@@ -201,6 +209,7 @@ impl MockRollup {
             config,
             ckb_genesis_info,
             builtin_load_data,
+            sudt_bond,
         }
     }
 
@@ -276,6 +285,7 @@ impl MockRollup {
             &self.rollup_context,
             challenge_context,
             rewards_lock,
+            self.sudt_bond.clone(),
         );
         let challenge_output = enter_challenge.build_output();
 
@@ -395,7 +405,7 @@ struct VerifierContext {
 impl VerifierContext {
     fn mock_from(
         cancel_output: &mut CancelChallengeOutput,
-        config: &BlockProducerConfig,
+        config: &WatchtowerConfig,
     ) -> Result<VerifierContext> {
         let verifier_tx_hash = random_hash().unpack();
         let load_data_len = {
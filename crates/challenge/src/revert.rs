@@ -17,6 +17,7 @@ use gw_types::packed::{
 };
 use gw_types::prelude::Unpack;
 use gw_types::{bytes::Bytes, prelude::Pack};
+use gw_utils::sudt::{build_sudt_cell, parse_sudt};
 
 pub struct Revert<'a> {
     finality_blocks: u64,
@@ -71,8 +72,8 @@ impl<'a> Revert<'a> {
         };
         let reward_lock = challenge_lock_args.rewards_receiver_lock();
 
-        let rewards = Rewards::new(self.stake_cells, self.challenge_cell, self.reward_burn_rate);
-        let rewards_output = rewards.build_output(reward_lock, self.burn_lock);
+        let rewards = Rewards::new(self.stake_cells, self.challenge_cell, self.reward_burn_rate)?;
+        let rewards_output = rewards.build_output(reward_lock, self.burn_lock)?;
 
         // Post global state
         let first_reverted_block = {
@@ -142,6 +143,14 @@ impl<'a> Revert<'a> {
 struct Rewards {
     receive_capacity: u128,
     burn_capacity: u128,
+    sudt: Option<RewardsSUDT>,
+}
+
+// sUDT bonded in stake and challenge cells
+struct RewardsSUDT {
+    sudt_type: Script,
+    receive_amount: u128,
+    burn_amount: u128,
 }
 
 struct RewardsOutput {
@@ -150,7 +159,11 @@ struct RewardsOutput {
 }
 
 impl Rewards {
-    fn new(stake_cells: &[CellInfo], challenge_cell: &CellInfo, reward_burn_rate: u8) -> Self {
+    fn new(
+        stake_cells: &[CellInfo],
+        challenge_cell: &CellInfo,
+        reward_burn_rate: u8,
+    ) -> Result<Self> {
         let to_capacity = |c: &CellInfo| c.output.capacity().unpack() as u128;
 
         let total_stake_capacity: u128 = stake_cells.iter().map(to_capacity).sum();
@@ -160,13 +173,16 @@ impl Rewards {
         let challenge_capacity = to_capacity(challenge_cell);
         let receive_capacity = reward_capacity.saturating_add(challenge_capacity);
 
-        Self {
+        let sudt = RewardsSUDT::new(stake_cells, challenge_cell, reward_burn_rate)?;
+
+        Ok(Self {
             receive_capacity,
             burn_capacity,
-        }
+            sudt,
+        })
     }
 
-    fn build_output(self, reward_lock: Script, burn_lock: Script) -> RewardsOutput {
+    fn build_output(self, reward_lock: Script, burn_lock: Script) -> Result<RewardsOutput> {
         let build_outputs = |total_capacity: u128, lock: Script| -> Vec<(CellOutput, Bytes)> {
             let build = |capacity: u64, lock: Script| -> (CellOutput, Bytes) {
                 let output = CellOutput::new_builder()
@@ -208,9 +224,133 @@ impl Rewards {
             outputs
         };
 
-        RewardsOutput {
-            reward_cells: build_outputs(self.receive_capacity, reward_lock),
-            burn_cells: build_outputs(self.burn_capacity, burn_lock),
+        let mut reward_cells = build_outputs(self.receive_capacity, reward_lock);
+        let mut burn_cells = build_outputs(self.burn_capacity, burn_lock);
+
+        // Attach sudt to first reward and burn cell
+        if let Some(sudt) = self.sudt {
+            let attach = |cells: &mut Vec<(CellOutput, Bytes)>, amount: u128| -> Result<()> {
+                match cells.first_mut() {
+                    Some(cell) if amount > 0 => {
+                        let output = cell.0.clone();
+                        *cell = build_sudt_cell(output, sudt.sudt_type.clone(), amount)?;
+                        Ok(())
+                    }
+                    _ => Ok(()),
+                }
+            };
+            attach(&mut reward_cells, sudt.receive_amount)?;
+            attach(&mut burn_cells, sudt.burn_amount)?;
         }
+
+        Ok(RewardsOutput {
+            reward_cells,
+            burn_cells,
+        })
+    }
+}
+
+impl RewardsSUDT {
+    fn new(
+        stake_cells: &[CellInfo],
+        challenge_cell: &CellInfo,
+        reward_burn_rate: u8,
+    ) -> Result<Option<Self>> {
+        let mut sudt_type: Option<Script> = None;
+        let mut sum_sudt = |cells: &[&CellInfo]| -> Result<u128> {
+            let mut total_amount = 0u128;
+            for (cell_type, amount) in cells.iter().filter_map(|c| parse_sudt(c)) {
+                match sudt_type {
+                    Some(ref sudt_type) if sudt_type.as_slice() != cell_type.as_slice() => {
+                        return Err(anyhow!("stake and challenge cells bond different sudt"));
+                    }
+                    Some(_) => (),
+                    None => sudt_type = Some(cell_type),
+                }
+                total_amount = total_amount.saturating_add(amount);
+            }
+            Ok(total_amount)
+        };
+
+        let total_stake_amount = sum_sudt(&stake_cells.iter().collect::<Vec<_>>())?;
+        let challenge_amount = sum_sudt(&[challenge_cell])?;
+
+        let reward_amount = total_stake_amount.saturating_mul(reward_burn_rate.into()) / 100;
+        let burn_amount = total_stake_amount.saturating_sub(reward_amount);
+        let receive_amount = reward_amount.saturating_add(challenge_amount);
+
+        Ok(sudt_type.map(|sudt_type| RewardsSUDT {
+            sudt_type,
+            receive_amount,
+            burn_amount,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rewards;
+    use gw_types::{
+        bytes::Bytes,
+        offchain::CellInfo,
+        packed::{CellOutput, Script},
+        prelude::*,
+    };
+    use gw_utils::sudt::{build_sudt_cell, parse_sudt};
+
+    fn sudt_cell(sudt_type: &Script, capacity: u64, amount: u128) -> CellInfo {
+        let output = CellOutput::new_builder()
+            .capacity(capacity.pack())
+            .lock(Script::new_builder().args(vec![3u8; 20].pack()).build())
+            .build();
+        let (output, data) = build_sudt_cell(output, sudt_type.clone(), amount).unwrap();
+
+        CellInfo {
+            output,
+            data,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_rewards_with_sudt_bond() {
+        let sudt_type = Script::new_builder().args(vec![2u8; 32].pack()).build();
+        let reward_lock = Script::new_builder().args(vec![4u8; 20].pack()).build();
+        let burn_lock = Script::new_builder().args(vec![5u8; 20].pack()).build();
+
+        let stake_cells = vec![
+            sudt_cell(&sudt_type, 100_000_000_000, 500),
+            sudt_cell(&sudt_type, 100_000_000_000, 500),
+        ];
+        let challenge_cell = sudt_cell(&sudt_type, 50_000_000_000, 100);
+
+        let rewards = Rewards::new(&stake_cells, &challenge_cell, 50).unwrap();
+        let output = rewards.build_output(reward_lock, burn_lock).unwrap();
+
+        let to_cell = |(output, data): &(CellOutput, Bytes)| CellInfo {
+            output: output.to_owned(),
+            data: data.to_owned(),
+            ..Default::default()
+        };
+        let reward_cell = to_cell(output.reward_cells.first().unwrap());
+        let (reward_type, reward_amount) = parse_sudt(&reward_cell).unwrap();
+        assert_eq!(reward_type.as_slice(), sudt_type.as_slice());
+        assert_eq!(reward_amount, 600);
+
+        let burn_cell = to_cell(output.burn_cells.first().unwrap());
+        let (burn_type, burn_amount) = parse_sudt(&burn_cell).unwrap();
+        assert_eq!(burn_type.as_slice(), sudt_type.as_slice());
+        assert_eq!(burn_amount, 500);
+    }
+
+    #[test]
+    fn test_rewards_reject_mixed_sudt() {
+        let sudt_type = Script::new_builder().args(vec![2u8; 32].pack()).build();
+        let other_type = Script::new_builder().args(vec![6u8; 32].pack()).build();
+
+        let stake_cells = vec![sudt_cell(&sudt_type, 100_000_000_000, 500)];
+        let challenge_cell = sudt_cell(&other_type, 50_000_000_000, 100);
+
+        assert!(Rewards::new(&stake_cells, &challenge_cell, 50).is_err());
     }
 }
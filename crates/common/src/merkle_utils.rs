@@ -4,7 +4,7 @@ use crate::vec::Vec;
 use crate::{
     blake2b::new_blake2b,
     h256_ext::H256Ext,
-    smt::{default_store::DefaultStore, Error, H256, SMT},
+    smt::{default_store::DefaultStore, Blake2bHasher, CompiledMerkleProof, Error, H256, SMT},
 };
 
 // Calculate compacted account root
@@ -62,6 +62,36 @@ pub fn ckb_merkle_leaf_hash(index: u32, item_hash: &H256) -> H256 {
     buf.into()
 }
 
+/// Verify a compiled SMT proof of `leaves` (key, value) against `root`
+///
+/// Works for both the account SMT and the block SMT, a zero value proves
+/// non-existence of the key.
+pub fn verify_smt_proof(
+    root: &H256,
+    proof: Vec<u8>,
+    leaves: Vec<(H256, H256)>,
+) -> Result<bool, Error> {
+    CompiledMerkleProof(proof).verify::<Blake2bHasher>(root, leaves)
+}
+
+/// Verify a CKB merkle proof of `leaves` (index, item_hash) against `root`,
+/// the root is calculated by `calculate_ckb_merkle_root` from
+/// `ckb_merkle_leaf_hash` leaves, e.g. the tx_witness_root of a block.
+///
+/// Leaves must be in the same order of the indices used to build the proof.
+pub fn verify_ckb_merkle_proof(
+    root: &H256,
+    indices: Vec<u32>,
+    lemmas: Vec<H256>,
+    leaves: &[(u32, H256)],
+) -> bool {
+    let leaves: Vec<H256> = leaves
+        .iter()
+        .map(|(index, item_hash)| ckb_merkle_leaf_hash(*index, item_hash))
+        .collect();
+    CBMTMerkleProof::new(indices, lemmas).verify(root, &leaves)
+}
+
 mod tests {
 
     #[test]
@@ -104,4 +134,65 @@ mod tests {
         let proof_leaves = vec![[1u8; 32].into(), [3u8; 32].into()];
         assert!(!proof.verify(&root, &proof_leaves));
     }
+
+    #[test]
+    fn verify_smt_proof_test() {
+        use crate::h256_ext::H256Ext;
+        use crate::smt::{default_store::DefaultStore, H256, SMT};
+
+        let mut tree = SMT::<DefaultStore<H256>>::default();
+        for i in 1..=5u32 {
+            tree.update(H256::from_u32(i), [i as u8; 32].into())
+                .unwrap();
+        }
+        let root = *tree.root();
+
+        // existence and non-existence
+        let leaves = vec![
+            (H256::from_u32(2), [2u8; 32].into()),
+            (H256::from_u32(42), H256::zero()),
+        ];
+        let keys = leaves.iter().map(|(k, _)| *k).collect();
+        let proof = tree
+            .merkle_proof(keys)
+            .unwrap()
+            .compile(leaves.clone())
+            .unwrap();
+
+        assert!(crate::merkle_utils::verify_smt_proof(&root, proof.0.clone(), leaves).unwrap());
+
+        let wrong_leaves = vec![
+            (H256::from_u32(2), [3u8; 32].into()),
+            (H256::from_u32(42), H256::zero()),
+        ];
+        assert!(!crate::merkle_utils::verify_smt_proof(&root, proof.0, wrong_leaves).unwrap());
+    }
+
+    #[test]
+    fn verify_ckb_merkle_proof_test() {
+        let items: Vec<crate::smt::H256> = (0..5u8).map(|i| [i; 32].into()).collect();
+        let leaves = items
+            .iter()
+            .enumerate()
+            .map(|(index, hash)| crate::merkle_utils::ckb_merkle_leaf_hash(index as u32, hash))
+            .collect::<Vec<_>>();
+        let root = crate::merkle_utils::calculate_ckb_merkle_root(leaves.clone()).unwrap();
+
+        let proof = crate::merkle_utils::CBMT::build_merkle_proof(&leaves, &[3]).unwrap();
+        let indices = proof.indices().to_vec();
+        let lemmas = proof.lemmas().to_vec();
+
+        assert!(crate::merkle_utils::verify_ckb_merkle_proof(
+            &root,
+            indices.clone(),
+            lemmas.clone(),
+            &[(3, items[3])]
+        ));
+        assert!(!crate::merkle_utils::verify_ckb_merkle_proof(
+            &root,
+            indices,
+            lemmas,
+            &[(3, items[2])]
+        ));
+    }
 }
@@ -1,6 +1,7 @@
 use ckb_fixed_hash::H256;
 use gw_jsonrpc_types::{
     blockchain::{CellDep, Script},
+    ckb_jsonrpc_types::{JsonBytes, Uint128},
     godwoken::{ChallengeTargetType, L2BlockCommittedInfo, RollupConfig},
 };
 use serde::{Deserialize, Serialize};
@@ -29,12 +30,37 @@ pub struct Config {
     #[serde(default)]
     pub mem_pool: MemPoolConfig,
     #[serde(default)]
+    pub execution_limits: ExecutionLimitsConfig,
+    #[serde(default)]
+    pub account_locks: Vec<AccountLockConfig>,
+    #[serde(default)]
     pub db_block_validator: Option<DBBlockValidatorConfig>,
+    #[serde(default)]
+    pub metrics: Option<MetricsConfig>,
+    #[serde(default)]
+    pub watchtower: Option<WatchtowerConfig>,
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RPCServerConfig {
     pub listen: String,
+    /// Admin RPCs, e.g. registering new backend versions, disabled if not set
+    #[serde(default)]
+    pub admin: Option<AdminRPCServerConfig>,
+}
+
+/// Admin RPC server, it should only listen on a local or private address
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AdminRPCServerConfig {
+    pub listen: String,
+    /// Backends registered by admin RPCs must be placed in this directory
+    pub backends_dir: PathBuf,
+}
+
+/// Prometheus metrics endpoint, disabled if not set
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MetricsConfig {
+    pub listen: String,
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
@@ -43,10 +69,31 @@ pub struct RPCClientConfig {
     pub ckb_url: String,
 }
 
-#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RPCConfig {
     pub allowed_sudt_proxy_creator_account_id: Vec<u32>,
     pub sudt_proxy_code_hashes: Vec<H256>,
+    /// Cycles paid by 1 unit of fee, used to suggest fee in `gw_estimate_cycles`
+    #[serde(default)]
+    pub cycles_per_fee: Option<u64>,
+    /// Max keys proved by one `gw_get_account_proof` or `gw_get_storage_proof` request
+    #[serde(default = "default_max_proof_keys")]
+    pub max_proof_keys: usize,
+}
+
+fn default_max_proof_keys() -> usize {
+    100
+}
+
+impl Default for RPCConfig {
+    fn default() -> Self {
+        Self {
+            allowed_sudt_proxy_creator_account_id: Vec::new(),
+            sudt_proxy_code_hashes: Vec::new(),
+            cycles_per_fee: None,
+            max_proof_keys: default_max_proof_keys(),
+        }
+    }
 }
 
 /// Onchain rollup cell config
@@ -68,18 +115,57 @@ pub struct GenesisConfig {
     pub rollup_config: RollupConfig,
     // For load secp data and use in challenge transaction
     pub secp_data_dep: CellDep,
+    /// sUDT bonded in stake and challenge cells, all nodes of a rollup must agree on it
+    #[serde(default)]
+    pub sudt_bond: Option<SUDTBondConfig>,
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct WalletConfig {
+    /// Hex encoded privkey file, ignored if signer is set
+    #[serde(default)]
     pub privkey_path: PathBuf,
     pub lock: Script,
+    #[serde(default)]
+    pub signer: Option<SignerConfig>,
+}
+
+/// Sign with a key outside of the node
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SignerConfig {
+    /// Encrypted keystore file (Web3 Secret Storage), the passphrase is read from
+    /// the environment variable
+    Keystore {
+        path: PathBuf,
+        #[serde(default = "default_keystore_passphrase_env")]
+        passphrase_env: String,
+    },
+    /// External signer process listening on a Unix domain socket
+    Unix {
+        path: PathBuf,
+        #[serde(default = "default_signer_timeout_secs")]
+        timeout_secs: u64,
+    },
+    /// External signer process serving JSONRPC over HTTP
+    Http {
+        url: String,
+        #[serde(default = "default_signer_timeout_secs")]
+        timeout_secs: u64,
+    },
+}
+
+fn default_signer_timeout_secs() -> u64 {
+    10
+}
+
+fn default_keystore_passphrase_env() -> String {
+    "GODWOKEN_KEYSTORE_PASSPHRASE".to_string()
 }
 
 // NOTE: Rewards receiver lock must be different than lock in WalletConfig,
 // since stake_capacity(minus burnt) + challenge_capacity - tx_fee will never
 // bigger or equal than stake_capacity(minus burnt) + challenge_capacity.
-// TODO: Support sudt stake ?
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChallengerConfig {
     pub rewards_receiver_lock: Script,
@@ -90,14 +176,30 @@ pub struct ChallengerConfig {
 pub struct BlockProducerConfig {
     pub account_id: u32,
     // cell deps
-    pub rollup_cell_type_dep: CellDep,
-    pub rollup_config_cell_dep: CellDep,
     pub deposit_cell_lock_dep: CellDep,
-    pub stake_cell_lock_dep: CellDep,
     pub poa_lock_dep: CellDep,
     pub poa_state_dep: CellDep,
     pub custodian_cell_lock_dep: CellDep,
     pub withdrawal_cell_lock_dep: CellDep,
+    /// Block producer challenges bad blocks with its own wallet, the shared
+    /// cell deps, challenger and wallet are flattened into `[block_producer]`
+    #[serde(flatten)]
+    pub watchtower: WatchtowerConfig,
+    #[serde(default)]
+    pub fee_config: FeeConfig,
+    #[serde(default)]
+    pub custodian_merge: CustodianMergeConfig,
+}
+
+/// Challenge bad blocks, cancel bad challenges and revert blocks. Used by the
+/// standalone watchtower mode, which doesn't require a block producer config,
+/// a mem-pool or a PoA identity.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WatchtowerConfig {
+    // cell deps
+    pub rollup_cell_type_dep: CellDep,
+    pub rollup_config_cell_dep: CellDep,
+    pub stake_cell_lock_dep: CellDep,
     pub challenge_cell_lock_dep: CellDep,
     pub l1_sudt_type_dep: CellDep,
     pub allowed_eoa_deps: HashMap<H256, CellDep>,
@@ -106,6 +208,67 @@ pub struct BlockProducerConfig {
     pub wallet_config: WalletConfig,
 }
 
+/// Merge fragmented finalized custodian cells in submit block tx. Custodian
+/// lock requires rollup cell in inputs, so cells are only merged in blocks
+/// without withdrawals.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CustodianMergeConfig {
+    /// Try to merge custodian cells every `interval_blocks` blocks, 0 to disable
+    pub interval_blocks: u64,
+    /// Skip merging if there are less mergeable custodian cells
+    pub min_cells: usize,
+    /// Max custodian cells merged in a block
+    pub max_cells: usize,
+}
+
+impl Default for CustodianMergeConfig {
+    fn default() -> Self {
+        Self {
+            interval_blocks: 10,
+            min_cells: 10,
+            max_cells: 50,
+        }
+    }
+}
+
+/// Bond stake and challenge cells in a sUDT of the rollup's l1 sudt script,
+/// cells are still required to hold `required_staking_capacity` CKB for stake.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SUDTBondConfig {
+    /// Args of the l1 sudt type script, the owner lock hash of sudt issuer
+    pub sudt_args: JsonBytes,
+    pub stake_amount: Uint128,
+    pub challenge_amount: Uint128,
+}
+
+/// L1 fee rate of submitted block transactions, all rates are in shannons per KB
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FeeConfig {
+    /// Lower bound of estimated fee rate, also used if estimation is unavailable
+    pub fee_rate: u64,
+    /// Upper bound of estimated and bumped fee rate
+    pub max_fee_rate: u64,
+    /// Number of recent L1 blocks used to estimate fee rate, 0 to disable estimation
+    pub estimate_blocks: u64,
+    /// Re-submit block tx with bumped fee rate if it's still pending after these seconds,
+    /// 0 to disable fee bumping
+    pub bump_timeout_secs: u64,
+    /// Fee rate increment on each bump, should not be less than the min RBF fee rate of CKB
+    pub bump_fee_rate: u64,
+}
+
+impl Default for FeeConfig {
+    fn default() -> Self {
+        FeeConfig {
+            fee_rate: 1000,
+            max_fee_rate: 100_000,
+            estimate_blocks: 20,
+            bump_timeout_secs: 180,
+            bump_fee_rate: 1500,
+        }
+    }
+}
+
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct StoreConfig {
     pub path: PathBuf,
@@ -116,6 +279,48 @@ pub struct BackendConfig {
     pub validator_path: PathBuf,
     pub generator_path: PathBuf,
     pub validator_script_type_hash: H256,
+    /// Blocks since this number are executed by this backend, multiple
+    /// versions of a backend can be configured to replay old blocks
+    #[serde(default)]
+    pub activation_block_number: u64,
+    /// Execute transactions with a builtin native implementation of the
+    /// generator instead of running the generator binary in CKB-VM
+    #[serde(default)]
+    pub native_generator: Option<NativeGenerator>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NativeGenerator {
+    Sudt,
+}
+
+/// Lock algorithm of layer2 accounts with the code hash
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AccountLockConfig {
+    pub code_hash: H256,
+    pub lock_algorithm: LockAlgorithmConfig,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LockAlgorithmConfig {
+    Secp256k1,
+    Secp256k1Eth,
+    Secp256k1Tron,
+    /// Verify signatures by running the lock binary in CKB-VM
+    CkbVm {
+        lock_path: PathBuf,
+        /// Data of the cell deps the lock loads, e.g. secp256k1_data
+        #[serde(default)]
+        dep_paths: Vec<PathBuf>,
+        #[serde(default = "default_ckb_vm_lock_max_cycles")]
+        max_cycles: u64,
+    },
+}
+
+fn default_ckb_vm_lock_max_cycles() -> u64 {
+    70_000_000
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -179,12 +384,111 @@ impl Default for OffChainValidatorConfig {
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct MemPoolConfig {
     pub execute_l2tx_max_cycles: u64,
+    #[serde(default)]
+    pub deposit_policy: DepositPolicyConfig,
+    /// Max txs in the mem block
+    #[serde(default = "default_max_mem_block_txs")]
+    pub max_mem_block_txs: usize,
+    /// Max tx size in bytes
+    #[serde(default = "default_max_tx_size")]
+    pub max_tx_size: usize,
+    /// Max sum of txs cycles in the mem block, txs exceed the budget are
+    /// deferred to the next block
+    #[serde(default = "default_max_block_cycles")]
+    pub max_block_cycles: u64,
+    /// Max sum of txs write data bytes in the mem block, txs exceed the budget
+    /// are deferred to the next block
+    #[serde(default = "default_max_block_write_data_bytes")]
+    pub max_block_write_data_bytes: usize,
+    /// Max txs deferred to the next mem block, new txs are rejected if exceeded
+    #[serde(default = "default_max_deferred_txs")]
+    pub max_deferred_txs: usize,
+}
+
+fn default_max_mem_block_txs() -> usize {
+    500
+}
+
+fn default_max_tx_size() -> usize {
+    // 50 KB
+    50_000
+}
+
+fn default_max_block_cycles() -> u64 {
+    // max block cycles of CKB
+    3_500_000_000
+}
+
+fn default_max_block_write_data_bytes() -> usize {
+    // 500 KB
+    500_000
+}
+
+fn default_max_deferred_txs() -> usize {
+    500
 }
 
 impl Default for MemPoolConfig {
     fn default() -> Self {
         Self {
             execute_l2tx_max_cycles: 100_000_000,
+            deposit_policy: DepositPolicyConfig::default(),
+            max_mem_block_txs: default_max_mem_block_txs(),
+            max_tx_size: default_max_tx_size(),
+            max_block_cycles: default_max_block_cycles(),
+            max_block_write_data_bytes: default_max_block_write_data_bytes(),
+            max_deferred_txs: default_max_deferred_txs(),
+        }
+    }
+}
+
+/// Limits of layer2 transaction execution, all nodes of a rollup must use the
+/// same values, otherwise they may disagree on the execution results
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExecutionLimitsConfig {
+    /// Max bytes of data written by a tx
+    pub max_write_data_bytes: usize,
+    /// Max bytes of data read by a tx
+    pub max_read_data_bytes: usize,
+    /// Max cycles of a tx
+    pub l2tx_max_cycles: u64,
+    /// Min capacity (in shannons) of a withdrawal request, only the occupied
+    /// capacity of the withdrawal cell is required if not set
+    pub min_withdrawal_capacity: Option<u64>,
+}
+
+impl Default for ExecutionLimitsConfig {
+    fn default() -> Self {
+        Self {
+            // 25 KB
+            max_write_data_bytes: 25_000,
+            // 2MB
+            max_read_data_bytes: 1024 * 1024 * 2,
+            l2tx_max_cycles: 7000_0000,
+            min_withdrawal_capacity: None,
+        }
+    }
+}
+
+/// Deposit selection policy of mem block, deposits are collected in ascending
+/// order of L1 block number
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DepositPolicyConfig {
+    /// Deposit cells with less capacity (in shannons) are ignored
+    pub min_capacity: u64,
+    /// Max deposits in a mem block
+    pub max_deposits: usize,
+    /// Max deposits of the same owner lock in a mem block
+    pub max_deposits_per_owner: usize,
+}
+
+impl Default for DepositPolicyConfig {
+    fn default() -> Self {
+        Self {
+            min_capacity: 0,
+            max_deposits: 50,
+            max_deposits_per_owner: 10,
         }
     }
 }
@@ -195,6 +499,10 @@ pub enum NodeMode {
     FullNode,
     Test,
     ReadOnly,
+    /// Produce and verify blocks as a full node, but never submit them to L1
+    DryRun,
+    /// Verify synced blocks and challenge bad blocks without producing blocks
+    Watchtower,
 }
 
 impl Default for NodeMode {
@@ -20,6 +20,7 @@ use std::sync::Arc;
 #[derive(Clone)]
 pub struct RocksDB {
     pub(crate) inner: Arc<OptimisticTransactionDB>,
+    pub(crate) read_only: bool,
 }
 
 impl RocksDB {
@@ -110,9 +111,37 @@ impl RocksDB {
 
         Ok(RocksDB {
             inner: Arc::new(db),
+            read_only: false,
         })
     }
 
+    /// Open an existing database without creating, repairing or migrating it.
+    ///
+    /// OptimisticTransactionDB has no read-only mode, so writes are rejected
+    /// by the wrapper instead: transactions can't be committed and write
+    /// batches can't be written. The database is still locked, stop the node
+    /// or open a copy of it.
+    pub fn open_read_only(config: &DBConfig, columns: u32) -> Result<Self> {
+        let cf_descriptors: Vec<_> = (0..columns)
+            .map(|c| ColumnFamilyDescriptor::new(c.to_string(), Options::default()))
+            .collect();
+        let mut opts = Options::default();
+        opts.create_if_missing(false);
+        opts.create_missing_column_families(false);
+
+        let db = OptimisticTransactionDB::open_cf_descriptors(&opts, &config.path, cf_descriptors)
+            .map_err(|err| internal_error(format!("failed to open the database: {}", err)))?;
+
+        Ok(RocksDB {
+            inner: Arc::new(db),
+            read_only: true,
+        })
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
     pub fn open(config: &DBConfig, columns: u32) -> Self {
         Self::open_with_check(config, columns).unwrap_or_else(|err| panic!("{}", err))
     }
@@ -140,6 +169,7 @@ impl RocksDB {
         K: AsRef<[u8]>,
         V: AsRef<[u8]>,
     {
+        self.check_writable()?;
         self.inner.put(key, value).map_err(internal_error)
     }
 
@@ -167,6 +197,7 @@ impl RocksDB {
         RocksDBTransaction {
             db: Arc::clone(&self.inner),
             inner: self.inner.transaction(&write_options, &transaction_options),
+            read_only: self.read_only,
         }
     }
 
@@ -178,6 +209,7 @@ impl RocksDB {
     }
 
     pub fn write(&self, batch: &RocksDBWriteBatch) -> Result<()> {
+        self.check_writable()?;
         self.inner.write(&batch.inner).map_err(internal_error)
     }
 
@@ -193,6 +225,7 @@ impl RocksDB {
     }
 
     pub fn create_cf(&mut self, col: Col) -> Result<()> {
+        self.check_writable()?;
         let inner = Arc::get_mut(&mut self.inner)
             .ok_or_else(|| internal_error("create_cf get_mut failed"))?;
         let opts = Options::default();
@@ -202,10 +235,18 @@ impl RocksDB {
     }
 
     pub fn drop_cf(&mut self, col: Col) -> Result<()> {
+        self.check_writable()?;
         let inner = Arc::get_mut(&mut self.inner)
             .ok_or_else(|| internal_error("drop_cf get_mut failed"))?;
         inner.drop_cf(&col.to_string()).map_err(internal_error)
     }
+
+    fn check_writable(&self) -> Result<()> {
+        if self.read_only {
+            return Err(internal_error("write to a read-only database"));
+        }
+        Ok(())
+    }
 }
 
 #[inline]
@@ -284,6 +325,51 @@ mod tests {
         RocksDB::open(&config, 2); // panic
     }
 
+    #[test]
+    fn read_only_rejects_writes() {
+        let tmp_dir = tempfile::Builder::new()
+            .prefix("read_only_rejects_writes")
+            .tempdir()
+            .unwrap();
+        let config = DBConfig {
+            path: tmp_dir.as_ref().to_path_buf(),
+            ..Default::default()
+        };
+        {
+            let db = RocksDB::open(&config, 2);
+            let txn = db.transaction();
+            txn.put(0, &[0], &[1]).unwrap();
+            txn.commit().unwrap();
+        }
+
+        let db = RocksDB::open_read_only(&config, 2).unwrap();
+        assert!(db.is_read_only());
+        assert_eq!(db.get_pinned(0, &[0]).unwrap().unwrap().as_ref(), &[1]);
+
+        let txn = db.transaction();
+        txn.put(0, &[0], &[2]).unwrap();
+        assert_eq!(txn.get(0, &[0]).unwrap().unwrap().as_ref(), &[2]);
+        assert!(txn.commit().is_err());
+        assert!(db.put_default([0], [2]).is_err());
+        assert!(db.write(&db.new_write_batch()).is_err());
+        drop(txn);
+        assert_eq!(db.get_pinned(0, &[0]).unwrap().unwrap().as_ref(), &[1]);
+    }
+
+    #[test]
+    fn read_only_requires_existing_db() {
+        let tmp_dir = tempfile::Builder::new()
+            .prefix("read_only_requires_existing_db")
+            .tempdir()
+            .unwrap();
+        let config = DBConfig {
+            path: tmp_dir.as_ref().join("missing"),
+            ..Default::default()
+        };
+        assert!(RocksDB::open_read_only(&config, 2).is_err());
+        assert!(!config.path.exists());
+    }
+
     #[test]
     fn write_and_read() {
         let db = setup_db("write_and_read", 2);
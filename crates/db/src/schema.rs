@@ -3,7 +3,7 @@
 /// Column families alias type
 pub type Col = u8;
 /// Total column number
-pub const COLUMNS: u32 = 28;
+pub const COLUMNS: u32 = 33;
 /// Column store meta data
 pub const COLUMN_META: Col = 0;
 /// Column store chain index
@@ -60,6 +60,16 @@ pub const COLUMN_ASSET_SCRIPT: Col = 25;
 pub const COLUMN_MEM_POOL_TRANSACTION: Col = 26;
 /// Column mem pool withdrawal
 pub const COLUMN_MEM_POOL_WITHDRAWAL: Col = 27;
+/// Column cleaner verifier out point => consumed verifier
+pub const COLUMN_CLEANER_VERIFIER: Col = 28;
+/// Column withdrawal hash => withdrawal info (block number and block hash | index key)
+pub const COLUMN_WITHDRAWAL_INFO: Col = 29;
+/// Column unfinalized withdrawal offers: block number (BE) | withdrawal index (BE) => withdrawal request
+pub const COLUMN_WITHDRAWAL_OFFER: Col = 30;
+/// Column bad block hash => offchain confirmation result (1 confirmed, 0 not confirmed)
+pub const COLUMN_BAD_BLOCK_CONFIRMATION: Col = 31;
+/// Column block hash => fees paid by block txs, entries of sudt id (LE u32) | amount (LE u128)
+pub const COLUMN_BLOCK_COLLECTED_FEES: Col = 32;
 
 /// chain id
 pub const META_CHAIN_ID_KEY: &[u8] = b"CHAIN_ID";
@@ -77,6 +87,10 @@ pub const META_MEM_BLOCK_ACCOUNT_SMT_ROOT_KEY: &[u8] = b"MEM_BLOCK_ACCOUNT_SMT_R
 pub const META_MEM_BLOCK_ACCOUNT_SMT_COUNT_KEY: &[u8] = b"MEM_BLOCK_ACCOUNT_SMT_COUNT_KEY";
 /// mem pool block info
 pub const META_MEM_POOL_BLOCK_INFO: &[u8] = b"MEM_POOL_BLOCK_INFO";
+/// hashes of txs deferred to the next mem block, in order
+pub const META_MEM_POOL_DEFERRED_TXS: &[u8] = b"MEM_POOL_DEFERRED_TXS";
+/// next block number (BE) to backfill by the unfinished withdrawal index migration
+pub const META_WITHDRAWAL_INDEX_MIGRATION_KEY: &[u8] = b"WITHDRAWAL_INDEX_MIGRATION";
 
 /// CHAIN_SPEC_HASH_KEY tracks the hash of chain spec which created current database
 pub const CHAIN_SPEC_HASH_KEY: &[u8] = b"chain-spec-hash";
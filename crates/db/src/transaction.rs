@@ -11,6 +11,7 @@ use std::sync::Arc;
 pub struct RocksDBTransaction {
     pub(crate) db: Arc<OptimisticTransactionDB>,
     pub(crate) inner: OptimisticTransaction,
+    pub(crate) read_only: bool,
 }
 
 impl RocksDBTransaction {
@@ -44,6 +45,9 @@ impl RocksDBTransaction {
     }
 
     pub fn commit(&self) -> Result<()> {
+        if self.read_only {
+            return Err(internal_error("commit to a read-only database"));
+        }
         self.inner.commit().map_err(internal_error)
     }
 
@@ -0,0 +1,324 @@
+use ckb_vm::{
+    machine::asm::{AsmCoreMachine, AsmMachine},
+    memory::Memory,
+    registers::{A0, A3, A4, A5, A7},
+    DefaultMachineBuilder, Error as VMError, Register, SupportMachine, Syscalls,
+};
+use gw_ckb_hardfork::GLOBAL_VM_VERSION;
+use gw_common::{blake2b::new_blake2b, H256};
+use gw_types::{
+    bytes::Bytes,
+    core::ScriptHashType,
+    offchain::RollupContext,
+    packed::{L2Transaction, Script, WitnessArgs},
+    prelude::*,
+};
+
+use super::{secp256k1::calc_godwoken_signing_message, LockAlgorithm};
+use crate::{
+    error::LockAlgorithmError,
+    generator::AsmCoreMachineParams,
+    syscalls::{error_codes::SUCCESS, store_data},
+    vm_cost_model::instruction_cycles,
+};
+
+/* CKB syscalls */
+const LOAD_SCRIPT_HASH_SYSCALL_NUMBER: u64 = 2062;
+const LOAD_SCRIPT_SYSCALL_NUMBER: u64 = 2052;
+const LOAD_WITNESS_SYSCALL_NUMBER: u64 = 2074;
+const LOAD_CELL_BY_FIELD_SYSCALL_NUMBER: u64 = 2081;
+const LOAD_CELL_DATA_SYSCALL_NUMBER: u64 = 2092;
+const DEBUG_PRINT_SYSCALL_NUMBER: u64 = 2177;
+const SOURCE_INPUT: u64 = 1;
+const SOURCE_CELL_DEP: u64 = 3;
+const SOURCE_GROUP_INPUT: u64 = 0x0100000000000001;
+const CELL_FIELD_DATA_HASH: u64 = 1;
+const CELL_FIELD_LOCK: u64 = 2;
+const CELL_FIELD_LOCK_HASH: u64 = 3;
+const CELL_FIELD_TYPE_HASH: u64 = 5;
+const INDEX_OUT_OF_BOUND: u8 = 1;
+const ITEM_MISSING: u8 = 2;
+
+/// Verify signatures by running the on-chain account lock in CKB-VM, so new
+/// account types can be supported without a builtin lock algorithm.
+///
+/// The lock is executed as the lock of the verifier cell in the cancel challenge
+/// transaction of a tx or withdrawal signature challenge, which is emulated as:
+///
+/// * inputs: the verifier cell, which is the only group input, and the owner cell
+/// * cell deps: the lock cell, whose type hash is `code_hash`, and the configured deps
+///
+/// The lock can load:
+///
+/// * `load_script` and `load_script_hash`: the account script
+/// * `load_cell_data`: the verifier cell data `owner_lock_hash (32 bytes) |
+///   message (32 bytes)`, the lock binary and the data of deps
+/// * `load_cell_by_field`: the data hash, lock, lock hash and type hash of the
+///   cells above, the verifier cell has no type
+/// * `load_witness` of the verifier cell: `WitnessArgs` with the signature as `lock`
+///
+/// These are the same as on-chain. Other syscalls, cells and fields unknown
+/// off-chain, e.g. the data of the owner cell, fail the verification instead,
+/// so a signature accepted here can't lose the challenge.
+#[derive(Debug)]
+pub struct CkbVmLock {
+    code_hash: H256,
+    program: Bytes,
+    deps: Vec<Bytes>,
+    owner_lock: Script,
+    max_cycles: u64,
+}
+
+impl CkbVmLock {
+    /// `owner_lock` is the lock of the verifier cell owner, which is the block
+    /// producer or watchtower wallet lock.
+    pub fn new(
+        code_hash: H256,
+        program: Bytes,
+        deps: Vec<Bytes>,
+        owner_lock: Script,
+        max_cycles: u64,
+    ) -> Self {
+        CkbVmLock {
+            code_hash,
+            program,
+            deps,
+            owner_lock,
+            max_cycles,
+        }
+    }
+
+    fn run(&self, syscalls: LockSyscalls) -> Result<i8, VMError> {
+        let global_vm_version = smol::block_on(async { *GLOBAL_VM_VERSION.lock().await });
+        let params = AsmCoreMachineParams::with_version(global_vm_version)?;
+        let core_machine = AsmCoreMachine::new(params.vm_isa, params.vm_version, self.max_cycles);
+        let machine_builder = DefaultMachineBuilder::new(core_machine)
+            .syscall(Box::new(syscalls))
+            .instruction_cycle_func(Box::new(instruction_cycles));
+        let mut machine = AsmMachine::new(machine_builder.build(), None);
+        machine.load_program(&self.program, &[])?;
+        machine.run()
+    }
+
+    fn verify_script(
+        &self,
+        script: Script,
+        signature: Bytes,
+        message: H256,
+    ) -> Result<bool, LockAlgorithmError> {
+        // owner_lock_hash(32 bytes) | message(32 bytes)
+        let mut verifier_data = self.owner_lock.hash().to_vec();
+        verifier_data.extend_from_slice(message.as_slice());
+        let witness = WitnessArgs::new_builder()
+            .lock(Some(signature).pack())
+            .build();
+
+        let inputs = vec![
+            MockCell {
+                lock: Some(script.clone()),
+                type_hash: Some(None),
+                data: Some(verifier_data.into()),
+            },
+            MockCell {
+                lock: Some(self.owner_lock.clone()),
+                type_hash: None,
+                data: None,
+            },
+        ];
+        let lock_cell = MockCell {
+            lock: None,
+            type_hash: Some(Some(self.code_hash.into())),
+            data: Some(self.program.clone()),
+        };
+        let dep_cells = self.deps.iter().map(|data| MockCell {
+            lock: None,
+            type_hash: None,
+            data: Some(data.clone()),
+        });
+        let cell_deps = vec![lock_cell].into_iter().chain(dep_cells).collect();
+
+        let syscalls = LockSyscalls {
+            script,
+            inputs,
+            cell_deps,
+            witness: witness.as_bytes(),
+        };
+        let exit_code = self
+            .run(syscalls)
+            .map_err(|err| LockAlgorithmError::VM(err.to_string()))?;
+        Ok(0 == exit_code)
+    }
+}
+
+/// Usage
+/// register CkbVmLock to AccountLockManage
+///
+/// manage.register_lock_algorithm(code_hash, Box::new(CkbVmLock::new(code_hash, program, deps, owner_lock, max_cycles)));
+impl LockAlgorithm for CkbVmLock {
+    fn recover(&self, _message: H256, _signature: &[u8]) -> Result<Bytes, LockAlgorithmError> {
+        Err(LockAlgorithmError::UnsupportedRecover)
+    }
+
+    fn verify_message(
+        &self,
+        lock_args: Bytes,
+        signature: Bytes,
+        message: H256,
+    ) -> Result<bool, LockAlgorithmError> {
+        let script = Script::new_builder()
+            .code_hash(self.code_hash.pack())
+            .hash_type(ScriptHashType::Type.into())
+            .args(lock_args.pack())
+            .build();
+        self.verify_script(script, signature, message)
+    }
+
+    fn verify_tx(
+        &self,
+        ctx: &RollupContext,
+        sender_script: Script,
+        receiver_script: Script,
+        tx: &L2Transaction,
+    ) -> Result<bool, LockAlgorithmError> {
+        let message = calc_godwoken_signing_message(
+            &ctx.rollup_script_hash,
+            &sender_script,
+            &receiver_script,
+            tx,
+        );
+
+        self.verify_script(sender_script, tx.signature().unpack(), message)
+    }
+}
+
+/// A cell of the emulated verifier transaction, `None` if the field is unknown off-chain
+struct MockCell {
+    lock: Option<Script>,
+    type_hash: Option<Option<[u8; 32]>>,
+    data: Option<Bytes>,
+}
+
+/// CKB syscalls of the verifier cell
+struct LockSyscalls {
+    script: Script,
+    inputs: Vec<MockCell>,
+    cell_deps: Vec<MockCell>,
+    witness: Bytes,
+}
+
+enum CellLookup<'a> {
+    Found(&'a MockCell),
+    OutOfBound,
+    Unknown,
+}
+
+impl LockSyscalls {
+    fn lookup_cell(&self, index: u64, source: u64) -> CellLookup {
+        let cells = match source {
+            SOURCE_GROUP_INPUT if index > 0 => return CellLookup::OutOfBound,
+            SOURCE_INPUT | SOURCE_GROUP_INPUT => &self.inputs,
+            SOURCE_CELL_DEP => &self.cell_deps,
+            _ => return CellLookup::Unknown,
+        };
+        // there are other cells in the cancel challenge transaction
+        match cells.get(index as usize) {
+            Some(cell) => CellLookup::Found(cell),
+            None => CellLookup::Unknown,
+        }
+    }
+}
+
+impl<Mac: SupportMachine> Syscalls<Mac> for LockSyscalls {
+    fn initialize(&mut self, _machine: &mut Mac) -> Result<(), VMError> {
+        Ok(())
+    }
+
+    fn ecall(&mut self, machine: &mut Mac) -> Result<bool, VMError> {
+        let code = machine.registers()[A7].to_u64();
+        let index = machine.registers()[A3].to_u64();
+        let source = machine.registers()[A4].to_u64();
+        let data = match code {
+            LOAD_SCRIPT_HASH_SYSCALL_NUMBER => Bytes::from(self.script.hash().to_vec()),
+            LOAD_SCRIPT_SYSCALL_NUMBER => self.script.as_bytes(),
+            LOAD_WITNESS_SYSCALL_NUMBER => match (source, index) {
+                (SOURCE_INPUT, 0) | (SOURCE_GROUP_INPUT, 0) => self.witness.clone(),
+                (SOURCE_GROUP_INPUT, _) => return return_code(machine, INDEX_OUT_OF_BOUND),
+                _ => return Ok(false),
+            },
+            LOAD_CELL_DATA_SYSCALL_NUMBER => match self.lookup_cell(index, source) {
+                CellLookup::Found(MockCell {
+                    data: Some(data), ..
+                }) => data.clone(),
+                CellLookup::OutOfBound => return return_code(machine, INDEX_OUT_OF_BOUND),
+                _ => return Ok(false),
+            },
+            LOAD_CELL_BY_FIELD_SYSCALL_NUMBER => {
+                let cell = match self.lookup_cell(index, source) {
+                    CellLookup::Found(cell) => cell,
+                    CellLookup::OutOfBound => return return_code(machine, INDEX_OUT_OF_BOUND),
+                    CellLookup::Unknown => return Ok(false),
+                };
+                let field = machine.registers()[A5].to_u64();
+                let item = match field {
+                    CELL_FIELD_DATA_HASH => cell.data.as_ref().map(|data| Some(data_hash(data))),
+                    CELL_FIELD_LOCK => cell.lock.as_ref().map(|lock| Some(lock.as_bytes())),
+                    CELL_FIELD_LOCK_HASH => cell
+                        .lock
+                        .as_ref()
+                        .map(|lock| Some(Bytes::from(lock.hash().to_vec()))),
+                    CELL_FIELD_TYPE_HASH => cell
+                        .type_hash
+                        .map(|type_hash| type_hash.map(|hash| Bytes::from(hash.to_vec()))),
+                    _ => None,
+                };
+                match item {
+                    Some(Some(data)) => data,
+                    Some(None) => return return_code(machine, ITEM_MISSING),
+                    None => return Ok(false),
+                }
+            }
+            DEBUG_PRINT_SYSCALL_NUMBER => {
+                output_debug(machine)?;
+                return Ok(true);
+            }
+            _ => return Ok(false),
+        };
+        store_data(machine, &data)?;
+        return_code(machine, SUCCESS)
+    }
+}
+
+fn return_code<Mac: SupportMachine>(machine: &mut Mac, code: u8) -> Result<bool, VMError> {
+    machine.set_register(A0, Mac::REG::from_u8(code));
+    Ok(true)
+}
+
+fn data_hash(data: &Bytes) -> Bytes {
+    if data.is_empty() {
+        return Bytes::from(vec![0u8; 32]);
+    }
+    let mut hash = [0u8; 32];
+    let mut hasher = new_blake2b();
+    hasher.update(data);
+    hasher.finalize(&mut hash);
+    Bytes::from(hash.to_vec())
+}
+
+fn output_debug<Mac: SupportMachine>(machine: &mut Mac) -> Result<(), VMError> {
+    let mut addr = machine.registers()[A0].to_u64();
+    let mut buffer = Vec::new();
+    loop {
+        let byte = machine
+            .memory_mut()
+            .load8(&Mac::REG::from_u64(addr))?
+            .to_u8();
+        if byte == 0 {
+            break;
+        }
+        buffer.push(byte);
+        addr += 1;
+    }
+    let s = String::from_utf8(buffer).map_err(|_| VMError::ParseError)?;
+    log::debug!("[account lock debug]: {}", s);
+    Ok(())
+}
@@ -1,6 +1,8 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fs};
 
+use anyhow::{anyhow, Result};
 use gw_common::H256;
+use gw_config::{AccountLockConfig, LockAlgorithmConfig};
 use gw_types::{
     bytes::Bytes,
     offchain::RollupContext,
@@ -9,9 +11,12 @@ use gw_types::{
 
 #[cfg(debug_assertions)]
 pub mod always_success;
+pub mod ckb_vm_lock;
 pub mod secp256k1;
 
 use crate::error::LockAlgorithmError;
+use ckb_vm_lock::CkbVmLock;
+use secp256k1::{Secp256k1, Secp256k1Eth, Secp256k1Tron};
 
 pub trait LockAlgorithm {
     fn recover(&self, message: H256, signature: &[u8]) -> Result<Bytes, LockAlgorithmError>;
@@ -53,6 +58,54 @@ impl AccountLockManage {
         self.locks.insert(code_hash, lock_algo);
     }
 
+    /// Register the lock algorithm of config, replace the registered one with the same code hash.
+    ///
+    /// `owner_lock` is the lock of the cancel challenge verifier cells created by this node.
+    pub fn register_lock_config(
+        &mut self,
+        config: AccountLockConfig,
+        owner_lock: &Script,
+    ) -> Result<()> {
+        let AccountLockConfig {
+            code_hash,
+            lock_algorithm,
+        } = config;
+        let code_hash: H256 = {
+            let hash: [u8; 32] = code_hash.into();
+            hash.into()
+        };
+        let lock_algo: Box<dyn LockAlgorithm + Send + Sync> = match lock_algorithm {
+            LockAlgorithmConfig::Secp256k1 => Box::new(Secp256k1::default()),
+            LockAlgorithmConfig::Secp256k1Eth => Box::new(Secp256k1Eth::default()),
+            LockAlgorithmConfig::Secp256k1Tron => Box::new(Secp256k1Tron::default()),
+            LockAlgorithmConfig::CkbVm {
+                lock_path,
+                dep_paths,
+                max_cycles,
+            } => {
+                let program = fs::read(&lock_path)
+                    .map_err(|err| anyhow!("read lock {:?}: {}", lock_path, err))?;
+                let deps = dep_paths
+                    .iter()
+                    .map(|path| {
+                        let data = fs::read(path)
+                            .map_err(|err| anyhow!("read dep {:?}: {}", path, err))?;
+                        Ok(data.into())
+                    })
+                    .collect::<Result<_>>()?;
+                Box::new(CkbVmLock::new(
+                    code_hash,
+                    program.into(),
+                    deps,
+                    owner_lock.clone(),
+                    max_cycles,
+                ))
+            }
+        };
+        self.register_lock_algorithm(code_hash, lock_algo);
+        Ok(())
+    }
+
     #[allow(clippy::borrowed_box)]
     pub fn get_lock_algorithm(
         &self,
@@ -273,7 +273,7 @@ impl LockAlgorithm for Secp256k1Tron {
     }
 }
 
-fn calc_godwoken_signing_message(
+pub(super) fn calc_godwoken_signing_message(
     rollup_type_hash: &H256,
     sender_script: &Script,
     receiver_script: &Script,
@@ -1,6 +1,6 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use gw_common::H256;
-use gw_config::BackendConfig;
+use gw_config::{BackendConfig, NativeGenerator};
 use gw_types::bytes::Bytes;
 use std::{collections::HashMap, fs};
 
@@ -9,18 +9,22 @@ pub struct Backend {
     pub validator: Bytes,
     pub generator: Bytes,
     pub validator_script_type_hash: H256,
+    /// The backend is used to execute blocks since this block number
+    pub activation_block_number: u64,
+    /// Execute with the native generator instead of `generator`
+    pub native_generator: Option<NativeGenerator>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct BackendManage {
-    backends: HashMap<H256, Backend>,
+    /// Backend versions of a validator script type hash,
+    /// sorted by activation block number
+    backends: HashMap<H256, Vec<Backend>>,
 }
 
 impl BackendManage {
     pub fn from_config(configs: Vec<BackendConfig>) -> Result<Self> {
-        let mut backend_manage = BackendManage {
-            backends: Default::default(),
-        };
+        let mut backend_manage = BackendManage::default();
 
         for config in configs {
             backend_manage.register_backend_config(config)?;
@@ -34,9 +38,15 @@ impl BackendManage {
             validator_path,
             generator_path,
             validator_script_type_hash,
+            activation_block_number,
+            native_generator,
         } = config;
-        let validator = fs::read(validator_path)?.into();
-        let generator = fs::read(generator_path)?.into();
+        let validator = fs::read(&validator_path)
+            .map_err(|err| anyhow!("read validator {:?}: {}", validator_path, err))?
+            .into();
+        let generator = fs::read(&generator_path)
+            .map_err(|err| anyhow!("read generator {:?}: {}", generator_path, err))?
+            .into();
         let validator_script_type_hash = {
             let hash: [u8; 32] = validator_script_type_hash.into();
             hash.into()
@@ -45,21 +55,38 @@ impl BackendManage {
             validator,
             generator,
             validator_script_type_hash,
+            activation_block_number,
+            native_generator,
         };
         self.register_backend(backend);
         Ok(())
     }
 
+    /// Register a backend version, replace the version with the same activation block number
     pub fn register_backend(&mut self, backend: Backend) {
-        self.backends
-            .insert(backend.validator_script_type_hash, backend);
+        let versions = self
+            .backends
+            .entry(backend.validator_script_type_hash)
+            .or_default();
+        match versions.binary_search_by_key(&backend.activation_block_number, |b| {
+            b.activation_block_number
+        }) {
+            Ok(idx) => versions[idx] = backend,
+            Err(idx) => versions.insert(idx, backend),
+        }
     }
 
-    pub fn get_backend(&self, code_hash: &H256) -> Option<&Backend> {
-        self.backends.get(code_hash)
+    /// Get the backend version activated at the block number
+    pub fn get_backend(&self, code_hash: &H256, block_number: u64) -> Option<&Backend> {
+        self.backends.get(code_hash).and_then(|versions| {
+            versions
+                .iter()
+                .rev()
+                .find(|b| b.activation_block_number <= block_number)
+        })
     }
 
-    pub fn get_backends(&self) -> &HashMap<H256, Backend> {
+    pub fn get_backends(&self) -> &HashMap<H256, Vec<Backend>> {
         &self.backends
     }
 }
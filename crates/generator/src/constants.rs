@@ -1,10 +1,2 @@
 // TODO ensure this value
-pub const MIN_WITHDRAWAL_CAPACITY: u64 = 1000_00000000;
-// TODO ensure this value
 pub const MIN_DEPOSIT_CAPACITY: u64 = 1000_00000000;
-// 25 KB
-pub const MAX_WRITE_DATA_BYTES_LIMIT: usize = 25_000;
-// 2MB
-pub const MAX_READ_DATA_BYTES_LIMIT: usize = 1024 * 1024 * 2;
-// max cycles
-pub const L2TX_MAX_CYCLES: u64 = 7000_0000;
@@ -37,6 +37,10 @@ pub enum LockAlgorithmError {
     UnknownAccountLock,
     #[error("Invalid transaction args")]
     InvalidTransactionArgs,
+    #[error("Lock algorithm doesn't support recovering account")]
+    UnsupportedRecover,
+    #[error("Lock VM error {0}")]
+    VM(String),
 }
 
 impl From<LockAlgorithmError> for Error {
@@ -1,11 +1,11 @@
-use std::collections::{HashMap, HashSet};
+use std::{collections::HashSet, sync::RwLock};
 
 use crate::{
     account_lock_manage::AccountLockManage,
     backend_manage::BackendManage,
-    constants::{L2TX_MAX_CYCLES, MAX_READ_DATA_BYTES_LIMIT, MAX_WRITE_DATA_BYTES_LIMIT},
     erc20_creator_whitelist::SUDTProxyAccountWhitelist,
     error::{BlockError, TransactionValidateError, WithdrawalError},
+    native_generator,
     vm_cost_model::instruction_cycles,
 };
 use crate::{
@@ -24,7 +24,7 @@ use gw_common::{
     state::{build_account_field_key, to_short_address, State, GW_ACCOUNT_NONCE_TYPE},
     H256,
 };
-use gw_config::RPCConfig;
+use gw_config::{BackendConfig, ExecutionLimitsConfig, RPCConfig};
 use gw_store::{
     state_db::{CheckPoint, StateDBMode, StateDBTransaction, SubState, WriteContext},
     transaction::StoreTransaction,
@@ -47,7 +47,7 @@ use ckb_vm::{
     DefaultMachineBuilder, Error as VMError, SupportMachine,
 };
 
-struct AsmCoreMachineParams {
+pub(crate) struct AsmCoreMachineParams {
     pub vm_isa: u8,
     pub vm_version: u32,
 }
@@ -90,10 +90,11 @@ pub enum ApplyBlockResult {
 }
 
 pub struct Generator {
-    backend_manage: BackendManage,
+    backend_manage: RwLock<BackendManage>,
     account_lock_manage: AccountLockManage,
     rollup_context: RollupContext,
     sudt_proxy_account_whitelist: SUDTProxyAccountWhitelist,
+    execution_limits: ExecutionLimitsConfig,
 }
 
 impl Generator {
@@ -102,6 +103,7 @@ impl Generator {
         account_lock_manage: AccountLockManage,
         rollup_context: RollupContext,
         rpc_config: RPCConfig,
+        execution_limits: ExecutionLimitsConfig,
     ) -> Self {
         let sudt_proxy_account_whitelist = SUDTProxyAccountWhitelist::new(
             rpc_config.allowed_sudt_proxy_creator_account_id,
@@ -112,10 +114,11 @@ impl Generator {
                 .collect(),
         );
         Generator {
-            backend_manage,
+            backend_manage: RwLock::new(backend_manage),
             account_lock_manage,
             rollup_context,
             sudt_proxy_account_whitelist,
+            execution_limits,
         }
     }
 
@@ -123,6 +126,10 @@ impl Generator {
         &self.rollup_context
     }
 
+    pub fn execution_limits(&self) -> &ExecutionLimitsConfig {
+        &self.execution_limits
+    }
+
     pub fn account_lock_manage(&self) -> &AccountLockManage {
         &self.account_lock_manage
     }
@@ -145,7 +152,17 @@ impl Generator {
         let fee_amount: u128 = fee.amount().unpack();
         let account_short_address = to_short_address(&account_script_hash);
 
-        // check capacity (use dummy block hash and number)
+        // check capacity
+        if let Some(min_withdrawal_capacity) = self.execution_limits.min_withdrawal_capacity {
+            if capacity < min_withdrawal_capacity {
+                return Err(AccountError::InsufficientCapacity {
+                    expected: min_withdrawal_capacity as u128,
+                    actual: capacity,
+                }
+                .into());
+            }
+        }
+        // check occupied capacity (use dummy block hash and number)
         let rollup_context = self.rollup_context();
         if let Err(min_capacity) = Self::build_withdrawal_cell_output(
             rollup_context,
@@ -506,23 +523,27 @@ impl Generator {
 
             // build call context
             // NOTICE users only allowed to send HandleMessage CallType txs
-            let run_result =
-                match self.execute_transaction(chain, state, &block_info, &raw_tx, L2TX_MAX_CYCLES)
-                {
-                    Ok(run_result) => run_result,
-                    Err(err) => {
-                        let target = build_challenge_target(
-                            block_hash.into(),
-                            ChallengeTargetType::TxExecution,
-                            tx_index as u32,
-                        );
+            let run_result = match self.execute_transaction(
+                chain,
+                state,
+                &block_info,
+                &raw_tx,
+                self.execution_limits.l2tx_max_cycles,
+            ) {
+                Ok(run_result) => run_result,
+                Err(err) => {
+                    let target = build_challenge_target(
+                        block_hash.into(),
+                        ChallengeTargetType::TxExecution,
+                        tx_index as u32,
+                    );
 
-                        return ApplyBlockResult::Challenge {
-                            target,
-                            error: Error::Transaction(err),
-                        };
-                    }
-                };
+                    return ApplyBlockResult::Challenge {
+                        target,
+                        error: Error::Transaction(err),
+                    };
+                }
+            };
 
             {
                 if let Err(err) = state.apply_run_result(&run_result) {
@@ -584,28 +605,35 @@ impl Generator {
         }
     }
 
-    fn load_backend<S: State + CodeStore>(&self, state: &S, script_hash: &H256) -> Option<Backend> {
+    fn load_backend<S: State + CodeStore>(
+        &self,
+        state: &S,
+        script_hash: &H256,
+        block_number: u64,
+    ) -> Option<Backend> {
         log::debug!(
-            "load_backend for script_hash: {}",
-            hex::encode(script_hash.as_slice())
+            "load_backend for script_hash: {} at block: {}",
+            hex::encode(script_hash.as_slice()),
+            block_number
         );
-        state
-            .get_script(script_hash)
-            .and_then(|script| {
-                // only accept type script hash type for now
-                if script.hash_type() == ScriptHashType::Type.into() {
-                    let code_hash: [u8; 32] = script.code_hash().unpack();
-                    log::debug!("load_backend by code_hash: {}", hex::encode(code_hash));
-                    self.backend_manage.get_backend(&code_hash.into())
-                } else {
-                    log::error!(
-                        "Found a invalid account script which hash_type is data: {:?}",
-                        script
-                    );
-                    None
-                }
-            })
-            .cloned()
+        state.get_script(script_hash).and_then(|script| {
+            // only accept type script hash type for now
+            if script.hash_type() == ScriptHashType::Type.into() {
+                let code_hash: [u8; 32] = script.code_hash().unpack();
+                log::debug!("load_backend by code_hash: {}", hex::encode(code_hash));
+                self.backend_manage
+                    .read()
+                    .expect("backend manage lock")
+                    .get_backend(&code_hash.into(), block_number)
+                    .cloned()
+            } else {
+                log::error!(
+                    "Found a invalid account script which hash_type is data: {:?}",
+                    script
+                );
+                None
+            }
+        })
     }
 
     /// execute a layer2 tx
@@ -642,31 +670,42 @@ impl Generator {
         let used_cycles;
         let exit_code;
         {
-            // let core_machine = AsmCoreMachine::new_with_max_cycles(L2TX_MAX_CYCLES);
-            let global_vm_version = smol::block_on(async { *GLOBAL_VM_VERSION.lock().await });
-            let params = AsmCoreMachineParams::with_version(global_vm_version)?;
-            let core_machine = AsmCoreMachine::new(params.vm_isa, params.vm_version, max_cycles);
-            let machine_builder = DefaultMachineBuilder::new(core_machine)
-                .syscall(Box::new(L2Syscalls {
-                    chain,
-                    state,
-                    block_info,
-                    raw_tx,
-                    rollup_context: &self.rollup_context,
-                    account_lock_manage: &self.account_lock_manage,
-                    result: &mut run_result,
-                    code_store: state,
-                }))
-                .instruction_cycle_func(Box::new(instruction_cycles));
-            let mut machine = AsmMachine::new(machine_builder.build(), None);
             let account_id = raw_tx.to_id().unpack();
             let script_hash = state.get_script_hash(account_id)?;
             let backend = self
-                .load_backend(state, &script_hash)
+                .load_backend(state, &script_hash, block_info.number().unpack())
                 .ok_or(TransactionError::BackendNotFound { script_hash })?;
-            machine.load_program(&backend.generator, &[])?;
-            exit_code = machine.run()?;
-            used_cycles = machine.machine.cycles();
+            let mut syscalls = L2Syscalls {
+                chain,
+                state,
+                block_info,
+                raw_tx,
+                rollup_context: &self.rollup_context,
+                account_lock_manage: &self.account_lock_manage,
+                result: &mut run_result,
+                code_store: state,
+            };
+            match backend.native_generator {
+                Some(native_generator) => {
+                    exit_code = native_generator::execute(native_generator, &mut syscalls)?;
+                    // no measured cycles, see `native_generator`
+                    used_cycles = 0;
+                }
+                None => {
+                    let global_vm_version =
+                        smol::block_on(async { *GLOBAL_VM_VERSION.lock().await });
+                    let params = AsmCoreMachineParams::with_version(global_vm_version)?;
+                    let core_machine =
+                        AsmCoreMachine::new(params.vm_isa, params.vm_version, max_cycles);
+                    let machine_builder = DefaultMachineBuilder::new(core_machine)
+                        .syscall(Box::new(syscalls))
+                        .instruction_cycle_func(Box::new(instruction_cycles));
+                    let mut machine = AsmMachine::new(machine_builder.build(), None);
+                    machine.load_program(&backend.generator, &[])?;
+                    exit_code = machine.run()?;
+                    used_cycles = machine.machine.cycles();
+                }
+            }
         }
         // record used cycles
         run_result.used_cycles = used_cycles;
@@ -690,17 +729,19 @@ impl Generator {
 
         // check write data bytes
         let write_data_bytes: usize = run_result.write_data.values().map(|data| data.len()).sum();
-        if write_data_bytes > MAX_WRITE_DATA_BYTES_LIMIT {
+        let max_write_data_bytes = self.execution_limits.max_write_data_bytes;
+        if write_data_bytes > max_write_data_bytes {
             return Err(TransactionError::ExceededMaxWriteData {
-                max_bytes: MAX_WRITE_DATA_BYTES_LIMIT,
+                max_bytes: max_write_data_bytes,
                 used_bytes: write_data_bytes,
             });
         }
         // check read data bytes
         let read_data_bytes: usize = run_result.read_data.values().map(Vec::len).sum();
-        if read_data_bytes > MAX_READ_DATA_BYTES_LIMIT {
+        let max_read_data_bytes = self.execution_limits.max_read_data_bytes;
+        if read_data_bytes > max_read_data_bytes {
             return Err(TransactionError::ExceededMaxReadData {
-                max_bytes: MAX_READ_DATA_BYTES_LIMIT,
+                max_bytes: max_read_data_bytes,
                 used_bytes: read_data_bytes,
             });
         }
@@ -776,8 +817,21 @@ impl Generator {
         Ok((output, data))
     }
 
-    pub fn get_backends(&self) -> &HashMap<H256, Backend> {
-        self.backend_manage.get_backends()
+    /// All backend versions
+    pub fn get_backends(&self) -> Vec<Backend> {
+        let backend_manage = self.backend_manage.read().expect("backend manage lock");
+        backend_manage
+            .get_backends()
+            .values()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    /// Register a new backend version without restart
+    pub fn register_backend_config(&self, config: BackendConfig) -> Result<(), anyhow::Error> {
+        let mut backend_manage = self.backend_manage.write().expect("backend manage lock");
+        backend_manage.register_backend_config(config)
     }
 }
 
@@ -9,6 +9,7 @@ pub mod erc20_creator_whitelist;
 pub mod error;
 pub mod generator;
 pub mod genesis;
+mod native_generator;
 pub mod sudt;
 pub mod syscalls;
 pub mod traits;
@@ -0,0 +1,40 @@
+//! Native implementations of builtin backend generators
+//!
+//! A native generator must produce the same run result as the generator binary,
+//! all state accesses go through `L2Syscalls` like the syscalls of CKB-VM.
+//!
+//! Native generators don't run in CKB-VM, so there are no measured cycles. They
+//! report zero `used_cycles` and never reject by the max cycles limit, cycles of
+//! native transactions don't count in mem block budgets or cycle estimates.
+
+mod sudt;
+
+use crate::syscalls::L2Syscalls;
+use ckb_vm::Error as VMError;
+use gw_common::{
+    h256_ext::H256Ext,
+    state::{build_account_field_key, State, GW_ACCOUNT_NONCE_TYPE},
+    H256,
+};
+use gw_config::NativeGenerator;
+use gw_traits::ChainStore;
+use gw_types::prelude::*;
+
+/// Execute the transaction, return the exit code
+pub(crate) fn execute<S: State, C: ChainStore>(
+    native_generator: NativeGenerator,
+    syscalls: &mut L2Syscalls<S, C>,
+) -> Result<i8, VMError> {
+    match native_generator {
+        NativeGenerator::Sudt => sudt::execute(syscalls),
+    }
+}
+
+/// Same as `gw_finalize` of generator utils, increase nonce of the sender
+fn finalize<S: State, C: ChainStore>(syscalls: &mut L2Syscalls<S, C>) -> Result<i8, VMError> {
+    let from_id: u32 = syscalls.raw_tx.from_id().unpack();
+    let nonce_key = build_account_field_key(from_id, GW_ACCOUNT_NONCE_TYPE);
+    let nonce = syscalls.get_raw(&nonce_key)?.to_u32();
+    syscalls.store_raw(nonce_key, H256::from_u32(nonce.wrapping_add(1)));
+    Ok(0)
+}
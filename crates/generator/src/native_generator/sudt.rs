@@ -0,0 +1,211 @@
+//! Native simple UDT generator, follows `sudt.c` of godwoken-scripts
+
+use super::finalize;
+use crate::syscalls::{
+    error_codes::{
+        GW_ERROR_ACCOUNT_NOT_FOUND, GW_FATAL_INVALID_DATA, GW_FATAL_INVALID_SUDT_SCRIPT,
+        GW_SUDT_ERROR_AMOUNT_OVERFLOW, GW_SUDT_ERROR_INSUFFICIENT_BALANCE,
+    },
+    L2Syscalls,
+};
+use ckb_vm::Error as VMError;
+use gw_common::{
+    h256_ext::H256Ext,
+    state::{
+        build_account_key, build_sudt_key, State, DEFAULT_SHORT_SCRIPT_HASH_LEN,
+        SUDT_KEY_FLAG_BALANCE,
+    },
+    H256,
+};
+use gw_traits::ChainStore;
+use gw_types::{
+    bytes::Bytes,
+    core::ScriptHashType,
+    packed::{SUDTArgs, SUDTArgsUnion},
+    prelude::*,
+};
+
+const GW_LOG_SUDT_TRANSFER: u8 = 0x0;
+const GW_LOG_SUDT_PAY_FEE: u8 = 0x1;
+const MAX_SHORT_ADDRESS_LEN: usize = 32;
+
+/// Inner result is the non-zero exit code of the generator
+type ExitResult<T> = Result<Result<T, i8>, VMError>;
+
+// return the non-zero exit code to caller
+macro_rules! try_exit {
+    ($ret:expr) => {
+        match $ret? {
+            Ok(value) => value,
+            Err(exit_code) => return Ok(Err(exit_code)),
+        }
+    };
+}
+
+pub(super) fn execute<S: State, C: ChainStore>(
+    syscalls: &mut L2Syscalls<S, C>,
+) -> Result<i8, VMError> {
+    match handle_message(syscalls)? {
+        Ok(()) => finalize(syscalls),
+        Err(exit_code) => Ok(exit_code),
+    }
+}
+
+fn handle_message<S: State, C: ChainStore>(syscalls: &mut L2Syscalls<S, C>) -> ExitResult<()> {
+    let args: Bytes = syscalls.raw_tx.args().unpack();
+    let msg = match SUDTArgs::from_slice(&args) {
+        Ok(msg) => msg,
+        Err(_) => return Ok(Err(GW_FATAL_INVALID_DATA)),
+    };
+    let sudt_id: u32 = syscalls.raw_tx.to_id().unpack();
+    try_exit!(verify_sudt_account(syscalls, sudt_id));
+
+    match msg.to_enum() {
+        SUDTArgsUnion::SUDTQuery(query) => {
+            let short_address: Bytes = query.short_address().unpack();
+            let balance = try_exit!(get_balance(syscalls, sudt_id, &short_address));
+            syscalls.result.return_data = balance.to_le_bytes().to_vec();
+        }
+        SUDTArgsUnion::SUDTTransfer(transfer) => {
+            let to: Bytes = transfer.to().unpack();
+            let amount: u128 = transfer.amount().unpack();
+            let fee: u128 = transfer.fee().unpack();
+            if to.len() > MAX_SHORT_ADDRESS_LEN {
+                return Ok(Err(GW_FATAL_INVALID_DATA));
+            }
+            let from_id: u32 = syscalls.raw_tx.from_id().unpack();
+            let from_script_hash = syscalls.get_script_hash(from_id)?;
+            let from = &from_script_hash.as_slice()[..to.len()];
+
+            try_exit!(pay_fee(syscalls, sudt_id, from, fee));
+            try_exit!(transfer_sudt(
+                syscalls,
+                sudt_id,
+                from,
+                &to,
+                amount,
+                GW_LOG_SUDT_TRANSFER
+            ));
+        }
+    }
+
+    Ok(Ok(()))
+}
+
+/// Same as `gw_verify_sudt_account`
+fn verify_sudt_account<S: State, C: ChainStore>(
+    syscalls: &mut L2Syscalls<S, C>,
+    sudt_id: u32,
+) -> ExitResult<()> {
+    let script_hash = syscalls.get_script_hash(sudt_id)?;
+    if script_hash.is_zero() {
+        return Ok(Err(GW_ERROR_ACCOUNT_NOT_FOUND));
+    }
+    let script = syscalls.get_script(&script_hash).ok_or_else(|| {
+        log::error!(
+            "native sudt error: script not found by script hash: {:?}",
+            script_hash
+        );
+        VMError::Unexpected
+    })?;
+    let l2_sudt_validator_script_type_hash = syscalls
+        .rollup_context
+        .rollup_config
+        .l2_sudt_validator_script_type_hash();
+    if script.code_hash() != l2_sudt_validator_script_type_hash
+        || script.hash_type() != ScriptHashType::Type.into()
+    {
+        return Ok(Err(GW_FATAL_INVALID_SUDT_SCRIPT));
+    }
+    Ok(Ok(()))
+}
+
+fn balance_key(sudt_id: u32, short_address: &[u8]) -> H256 {
+    let sudt_key = build_sudt_key(SUDT_KEY_FLAG_BALANCE, short_address);
+    build_account_key(sudt_id, &sudt_key)
+}
+
+fn get_balance<S: State, C: ChainStore>(
+    syscalls: &mut L2Syscalls<S, C>,
+    sudt_id: u32,
+    short_address: &[u8],
+) -> ExitResult<u128> {
+    if short_address.len() > MAX_SHORT_ADDRESS_LEN {
+        return Ok(Err(GW_FATAL_INVALID_DATA));
+    }
+    let balance = syscalls.get_raw(&balance_key(sudt_id, short_address))?;
+    Ok(Ok(balance.to_u128()))
+}
+
+fn set_balance<S: State, C: ChainStore>(
+    syscalls: &mut L2Syscalls<S, C>,
+    sudt_id: u32,
+    short_address: &[u8],
+    balance: u128,
+) {
+    syscalls.store_raw(
+        balance_key(sudt_id, short_address),
+        H256::from_u128(balance),
+    );
+}
+
+/// Same as `_sudt_transfer`, balances are read before written,
+/// so a transfer to self is handled as the generator binary does
+fn transfer_sudt<S: State, C: ChainStore>(
+    syscalls: &mut L2Syscalls<S, C>,
+    sudt_id: u32,
+    from: &[u8],
+    to: &[u8],
+    amount: u128,
+    service_flag: u8,
+) -> ExitResult<()> {
+    let from_balance = try_exit!(get_balance(syscalls, sudt_id, from));
+    let new_from_balance = match from_balance.checked_sub(amount) {
+        Some(balance) => balance,
+        None => return Ok(Err(GW_SUDT_ERROR_INSUFFICIENT_BALANCE)),
+    };
+    let to_balance = try_exit!(get_balance(syscalls, sudt_id, to));
+    let new_to_balance = match to_balance.checked_add(amount) {
+        Some(balance) => balance,
+        None => return Ok(Err(GW_SUDT_ERROR_AMOUNT_OVERFLOW)),
+    };
+
+    set_balance(syscalls, sudt_id, from, new_from_balance);
+    set_balance(syscalls, sudt_id, to, new_to_balance);
+
+    // log data: short address len | from | to | amount
+    let mut data = Vec::with_capacity(1 + from.len() * 2 + 16);
+    data.push(from.len() as u8);
+    data.extend_from_slice(from);
+    data.extend_from_slice(to);
+    data.extend_from_slice(&amount.to_le_bytes());
+    syscalls.log(sudt_id, service_flag, data);
+    Ok(Ok(()))
+}
+
+/// Same as `sudt_pay_fee`, transfer fee to block producer then record it
+fn pay_fee<S: State, C: ChainStore>(
+    syscalls: &mut L2Syscalls<S, C>,
+    sudt_id: u32,
+    from: &[u8],
+    amount: u128,
+) -> ExitResult<()> {
+    let block_producer_id: u32 = syscalls.block_info.block_producer_id().unpack();
+    let block_producer_script_hash = syscalls.get_script_hash(block_producer_id)?;
+    let to = &block_producer_script_hash.as_slice()[..from.len()];
+    try_exit!(transfer_sudt(
+        syscalls,
+        sudt_id,
+        from,
+        to,
+        amount,
+        GW_LOG_SUDT_PAY_FEE
+    ));
+    // same as the check of SYS_PAY_FEE
+    if from.len() != DEFAULT_SHORT_SCRIPT_HASH_LEN {
+        log::error!("unexpected script hash short length: {}", from.len());
+        return Err(VMError::Unexpected);
+    }
+    syscalls.pay_fee(from, sudt_id, amount)?;
+    Ok(Ok(()))
+}
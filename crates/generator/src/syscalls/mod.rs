@@ -19,7 +19,7 @@ use gw_types::{
     bytes::Bytes,
     core::ScriptHashType,
     offchain::{RecoverAccount, RollupContext, RunResult},
-    packed::{BlockInfo, LogItem, RawL2Transaction, Script},
+    packed::{BlockInfo, FeePayment, LogItem, RawL2Transaction, Script},
     prelude::*,
 };
 use std::cmp;
@@ -137,7 +137,7 @@ impl<'a, S: State, C: ChainStore, Mac: SupportMachine> Syscalls<Mac> for L2Sysca
                 let key = load_data_h256(machine, key_addr)?;
                 let value_addr = machine.registers()[A1].to_u64();
                 let value = load_data_h256(machine, value_addr)?;
-                self.result.write_values.insert(key, value);
+                self.store_raw(key, value);
                 machine.set_register(A0, Mac::REG::from_u8(SUCCESS));
                 Ok(true)
             }
@@ -429,13 +429,7 @@ impl<'a, S: State, C: ChainStore, Mac: SupportMachine> Syscalls<Mac> for L2Sysca
                 let data_addr = machine.registers()[A3].to_u64();
 
                 let data = load_bytes(machine, data_addr, data_len as usize)?;
-                self.result.logs.push(
-                    LogItem::new_builder()
-                        .account_id(account_id.pack())
-                        .service_flag(service_flag.into())
-                        .data(Bytes::from(data).pack())
-                        .build(),
-                );
+                self.log(account_id, service_flag, data);
                 machine.set_register(A0, Mac::REG::from_u8(SUCCESS));
                 Ok(true)
             }
@@ -462,19 +456,13 @@ impl<'a, S: State, C: ChainStore, Mac: SupportMachine> Syscalls<Mac> for L2Sysca
                     }
                     load_bytes(machine, short_address_addr, short_address_len as usize)?
                 };
-                let sudt_id = machine.registers()[A2].to_u8();
+                let sudt_id = machine.registers()[A2].to_u32();
                 let amount = {
                     let amount_addr = machine.registers()[A3].to_u64();
                     load_data_u128(machine, amount_addr)?
                 };
 
-                // TODO record fee payment in the generator context
-                log::debug!(
-                    "[contract syscall: SYS_PAY_FEE] payer: {}, sudt_id: {}, amount: {}",
-                    hex::encode(&short_address),
-                    sudt_id,
-                    amount
-                );
+                self.pay_fee(&short_address, sudt_id, amount)?;
                 machine.set_register(A0, Mac::REG::from_u8(SUCCESS));
                 Ok(true)
             }
@@ -487,8 +475,9 @@ impl<'a, S: State, C: ChainStore, Mac: SupportMachine> Syscalls<Mac> for L2Sysca
     }
 }
 
+// State accessors shared by syscalls and native generators, so both produce the same run result
 impl<'a, S: State, C: ChainStore> L2Syscalls<'a, S, C> {
-    fn get_raw(&mut self, key: &H256) -> Result<H256, VMError> {
+    pub(crate) fn get_raw(&mut self, key: &H256) -> Result<H256, VMError> {
         let value = match self.result.write_values.get(key) {
             Some(value) => *value,
             None => {
@@ -499,6 +488,46 @@ impl<'a, S: State, C: ChainStore> L2Syscalls<'a, S, C> {
         };
         Ok(value)
     }
+    pub(crate) fn store_raw(&mut self, key: H256, value: H256) {
+        self.result.write_values.insert(key, value);
+    }
+    pub(crate) fn log(&mut self, account_id: u32, service_flag: u8, data: Vec<u8>) {
+        self.result.logs.push(
+            LogItem::new_builder()
+                .account_id(account_id.pack())
+                .service_flag(service_flag.into())
+                .data(Bytes::from(data).pack())
+                .build(),
+        );
+    }
+    /// Record the fee paid to block producer, the transfer of fee must be done before
+    pub(crate) fn pay_fee(
+        &mut self,
+        short_address: &[u8],
+        sudt_id: u32,
+        amount: u128,
+    ) -> Result<(), VMError> {
+        log::debug!(
+            "[contract syscall: SYS_PAY_FEE] payer: {}, sudt_id: {}, amount: {}",
+            hex::encode(short_address),
+            sudt_id,
+            amount
+        );
+        match self.get_account_id_by_short_address(short_address)? {
+            Some(payer_id) => self.result.fee_payments.push(
+                FeePayment::new_builder()
+                    .payer_id(payer_id.pack())
+                    .sudt_id(sudt_id.pack())
+                    .amount(amount.pack())
+                    .build(),
+            ),
+            None => log::warn!(
+                "[contract syscall: SYS_PAY_FEE] payer {} not found, fee isn't recorded",
+                hex::encode(short_address)
+            ),
+        }
+        Ok(())
+    }
     fn get_account_count(&self) -> Result<u32, VMError> {
         if let Some(id) = self.result.account_count {
             Ok(id)
@@ -512,7 +541,7 @@ impl<'a, S: State, C: ChainStore> L2Syscalls<'a, S, C> {
     fn set_account_count(&mut self, count: u32) {
         self.result.account_count = Some(count);
     }
-    fn get_script(&mut self, script_hash: &H256) -> Option<Script> {
+    pub(crate) fn get_script(&mut self, script_hash: &H256) -> Option<Script> {
         let opt_script = self
             .result
             .new_scripts
@@ -533,7 +562,7 @@ impl<'a, S: State, C: ChainStore> L2Syscalls<'a, S, C> {
             .map(|data| Bytes::from(data.clone()))
             .or_else(|| self.code_store.get_data(data_hash))
     }
-    fn get_script_hash(&mut self, id: u32) -> Result<H256, VMError> {
+    pub(crate) fn get_script_hash(&mut self, id: u32) -> Result<H256, VMError> {
         let value = self
             .get_raw(&build_account_field_key(id, GW_ACCOUNT_SCRIPT_HASH_TYPE))
             .map_err(|err| {
@@ -559,6 +588,31 @@ impl<'a, S: State, C: ChainStore> L2Syscalls<'a, S, C> {
         Ok(Some(id))
     }
 
+    /// Fee payments are offchain bookkeeping, so lookups are not recorded in read values
+    fn get_account_id_by_short_address(
+        &self,
+        short_address: &[u8],
+    ) -> Result<Option<u32>, VMError> {
+        let get_raw = |key: &H256| -> Result<H256, VMError> {
+            match self.result.write_values.get(key) {
+                Some(value) => Ok(*value),
+                None => self.state.get_raw(key).map_err(|err| {
+                    log::error!("syscall error: get account id by short address : {:?}", err);
+                    VMError::Unexpected
+                }),
+            }
+        };
+        let script_hash = get_raw(&build_short_script_hash_to_script_hash_key(short_address))?;
+        if script_hash.is_zero() {
+            return Ok(None);
+        }
+        let id = get_raw(&build_script_hash_to_account_id_key(script_hash.as_slice()))?;
+        if id.is_zero() {
+            return Ok(None);
+        }
+        Ok(Some(id.to_u32()))
+    }
+
     fn output_debug<Mac: SupportMachine>(&self, machine: &mut Mac) -> Result<(), VMError> {
         let mut addr = machine.registers()[A0].to_u64();
         let mut buffer = Vec::new();
@@ -0,0 +1,38 @@
+use crate::backend_manage::{Backend, BackendManage};
+use gw_common::H256;
+use gw_types::bytes::Bytes;
+
+fn backend(activation_block_number: u64, generator: &'static [u8]) -> Backend {
+    Backend {
+        validator: Bytes::new(),
+        generator: Bytes::from_static(generator),
+        validator_script_type_hash: [1u8; 32].into(),
+        activation_block_number,
+        native_generator: None,
+    }
+}
+
+#[test]
+fn test_get_backend_by_block_number() {
+    let mut backend_manage = BackendManage::default();
+    backend_manage.register_backend(backend(100, b"v2"));
+    backend_manage.register_backend(backend(0, b"v1"));
+    backend_manage.register_backend(backend(200, b"v3"));
+
+    let code_hash: H256 = [1u8; 32].into();
+    let generator_at = |number| {
+        backend_manage
+            .get_backend(&code_hash, number)
+            .map(|b| b.generator.clone())
+    };
+    assert_eq!(generator_at(0), Some(Bytes::from_static(b"v1")));
+    assert_eq!(generator_at(99), Some(Bytes::from_static(b"v1")));
+    assert_eq!(generator_at(100), Some(Bytes::from_static(b"v2")));
+    assert_eq!(generator_at(1000), Some(Bytes::from_static(b"v3")));
+    assert!(backend_manage.get_backend(&[2u8; 32].into(), 0).is_none());
+
+    // replace version with the same activation block number
+    backend_manage.register_backend(backend(100, b"v2.1"));
+    assert_eq!(generator_at(150), Some(Bytes::from_static(b"v2.1")));
+    assert_eq!(backend_manage.get_backends()[&code_hash].len(), 3);
+}
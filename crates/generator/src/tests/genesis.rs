@@ -29,6 +29,7 @@ fn test_init_genesis() {
         rollup_config: RollupConfig::default().into(),
         rollup_type_hash: rollup_script_hash.into(),
         secp_data_dep: Default::default(),
+        sudt_bond: None,
     };
     let genesis = build_genesis(&config, Bytes::default()).unwrap();
     let genesis_block_hash: [u8; 32] = genesis.genesis.hash();
@@ -1 +1,2 @@
+mod backend_manage;
 mod genesis;
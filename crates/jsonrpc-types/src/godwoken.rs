@@ -1,4 +1,4 @@
-use crate::blockchain::Script;
+use crate::blockchain::{OutPoint, Script};
 use anyhow::{anyhow, Error as JsonError};
 use ckb_fixed_hash::H256;
 use ckb_jsonrpc_types::{JsonBytes, Uint128, Uint32, Uint64};
@@ -127,6 +127,42 @@ impl From<packed::LogItem> for LogItem {
     }
 }
 
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct FeePayment {
+    pub payer_id: Uint32,
+    pub sudt_id: Uint32,
+    pub amount: Uint128,
+}
+
+impl From<FeePayment> for packed::FeePayment {
+    fn from(json: FeePayment) -> packed::FeePayment {
+        let FeePayment {
+            payer_id,
+            sudt_id,
+            amount,
+        } = json;
+        packed::FeePayment::new_builder()
+            .payer_id(payer_id.value().pack())
+            .sudt_id(sudt_id.value().pack())
+            .amount(amount.value().pack())
+            .build()
+    }
+}
+
+impl From<packed::FeePayment> for FeePayment {
+    fn from(data: packed::FeePayment) -> FeePayment {
+        let payer_id: u32 = data.payer_id().unpack();
+        let sudt_id: u32 = data.sudt_id().unpack();
+        let amount: u128 = data.amount().unpack();
+        FeePayment {
+            payer_id: payer_id.into(),
+            sudt_id: sudt_id.into(),
+            amount: amount.into(),
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct TxReceipt {
@@ -134,6 +170,8 @@ pub struct TxReceipt {
     pub post_state: AccountMerkleState,
     pub read_data_hashes: Vec<H256>,
     pub logs: Vec<LogItem>,
+    #[serde(default)]
+    pub fee_payments: Vec<FeePayment>,
 }
 
 impl From<TxReceipt> for packed::TxReceipt {
@@ -143,6 +181,7 @@ impl From<TxReceipt> for packed::TxReceipt {
             post_state,
             read_data_hashes,
             logs,
+            fee_payments,
         } = json;
         let tx_witness_hash: [u8; 32] = tx_witness_hash.into();
         let read_data_hashes: Vec<_> = read_data_hashes
@@ -153,11 +192,14 @@ impl From<TxReceipt> for packed::TxReceipt {
             })
             .collect();
         let logs: Vec<packed::LogItem> = logs.into_iter().map(|item| item.into()).collect();
+        let fee_payments: Vec<packed::FeePayment> =
+            fee_payments.into_iter().map(|item| item.into()).collect();
         packed::TxReceipt::new_builder()
             .tx_witness_hash(tx_witness_hash.pack())
             .post_state(post_state.into())
             .read_data_hashes(read_data_hashes.pack())
             .logs(logs.pack())
+            .fee_payments(fee_payments.pack())
             .build()
     }
 }
@@ -175,11 +217,17 @@ impl From<packed::TxReceipt> for TxReceipt {
             })
             .collect();
         let logs: Vec<LogItem> = data.logs().into_iter().map(|item| item.into()).collect();
+        let fee_payments: Vec<FeePayment> = data
+            .fee_payments()
+            .into_iter()
+            .map(|item| item.into())
+            .collect();
         TxReceipt {
             tx_witness_hash: tx_witness_hash.into(),
             post_state,
             read_data_hashes,
             logs,
+            fee_payments,
         }
     }
 }
@@ -491,6 +539,16 @@ impl Default for L2BlockStatus {
 pub struct L2BlockWithStatus {
     pub block: L2BlockView,
     pub status: L2BlockStatus,
+    /// Fees paid by block txs, aggregated by sudt id
+    #[serde(default)]
+    pub collected_fees: Vec<CollectedFee>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct CollectedFee {
+    pub sudt_id: Uint32,
+    pub amount: Uint128,
 }
 
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
@@ -748,6 +806,33 @@ pub struct WithdrawalRequest {
     pub signature: JsonBytes,
 }
 
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum WithdrawalStatus {
+    Pending,
+    Committed,
+    Finalized,
+}
+
+impl Default for WithdrawalStatus {
+    fn default() -> Self {
+        Self::Pending
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct WithdrawalWithStatus {
+    pub withdrawal: WithdrawalRequest,
+    pub status: WithdrawalStatus,
+    // layer2 block contains the withdrawal, none if it's pending
+    pub l2_block_number: Option<Uint64>,
+    pub l2_block_hash: Option<H256>,
+    pub l2_withdrawal_index: Option<Uint32>,
+    // layer1 tx which submits the layer2 block and creates the withdrawal cell
+    pub l1_committed_info: Option<L2BlockCommittedInfo>,
+}
+
 impl From<WithdrawalRequest> for packed::WithdrawalRequest {
     fn from(json: WithdrawalRequest) -> packed::WithdrawalRequest {
         let WithdrawalRequest { raw, signature } = json;
@@ -887,6 +972,17 @@ impl From<L2BlockCommittedInfo> for packed::L2BlockCommittedInfo {
     }
 }
 
+impl From<packed::L2BlockCommittedInfo> for L2BlockCommittedInfo {
+    fn from(data: packed::L2BlockCommittedInfo) -> L2BlockCommittedInfo {
+        let number: u64 = data.number().unpack();
+        Self {
+            number: number.into(),
+            block_hash: data.block_hash().unpack(),
+            transaction_hash: data.transaction_hash().unpack(),
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct RollupConfig {
@@ -998,20 +1094,36 @@ pub struct RunResult {
     pub return_data: JsonBytes,
     // log data
     pub logs: Vec<LogItem>,
+    // fee payments
+    #[serde(default)]
+    pub fee_payments: Vec<FeePayment>,
 }
 
 impl From<offchain::RunResult> for RunResult {
     fn from(data: offchain::RunResult) -> RunResult {
         let offchain::RunResult {
-            return_data, logs, ..
+            return_data,
+            logs,
+            fee_payments,
+            ..
         } = data;
         RunResult {
             return_data: JsonBytes::from_vec(return_data),
             logs: logs.into_iter().map(Into::into).collect(),
+            fee_payments: fee_payments.into_iter().map(Into::into).collect(),
         }
     }
 }
 
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct CyclesEstimation {
+    // min max cycles to execute the tx successfully
+    pub cycles: Uint64,
+    // suggested fee, null if the node doesn't set a cycles per fee rate
+    pub fee: Option<Uint128>,
+}
+
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct NodeInfo {
@@ -1026,4 +1138,114 @@ pub struct BackendInfo {
     pub validator_code_hash: H256,
     pub generator_code_hash: H256,
     pub validator_script_type_hash: H256,
+    pub activation_block_number: Uint64,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum DepositRefundReason {
+    Invalid,
+    CancelTimeout,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct RefundableDeposit {
+    pub out_point: OutPoint,
+    pub owner_lock_hash: H256,
+    pub capacity: Uint64,
+    pub sudt_script_hash: H256,
+    pub amount: Uint128,
+    pub reason: DepositRefundReason,
+    // error message of invalid deposit
+    pub error: Option<String>,
+}
+
+/// Withdrawal in an unfinalized block which can be bought by paying its
+/// `sell_capacity` and `sell_amount` to `payment_lock_hash` on layer1
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct WithdrawalOffer {
+    pub withdrawal_hash: H256,
+    pub block_hash: H256,
+    pub block_number: Uint64,
+    pub raw: RawWithdrawalRequest,
+}
+
+/// Compiled account SMT proof of `leaves` at a block, `merkle_root` equals to
+/// the block's `post_account.merkle_root`
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct AccountProof {
+    pub block_number: Uint64,
+    pub block_hash: H256,
+    pub merkle_root: H256,
+    // raw smt keys and values, zero value proves non-existence
+    pub leaves: Vec<KVPair>,
+    pub proof: JsonBytes,
+}
+
+/// Compiled block SMT proof of a block, `merkle_root` equals to the block
+/// merkle root in the post global state of `tip_block_hash`
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct BlockProof {
+    pub block_number: Uint64,
+    pub block_hash: H256,
+    pub tip_block_hash: H256,
+    pub merkle_root: H256,
+    pub proof: JsonBytes,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct CKBMerkleProof {
+    pub indices: Vec<Uint32>,
+    pub lemmas: Vec<H256>,
+}
+
+impl From<packed::CKBMerkleProof> for CKBMerkleProof {
+    fn from(proof: packed::CKBMerkleProof) -> CKBMerkleProof {
+        let indices: Vec<u32> = proof.indices().unpack();
+        Self {
+            indices: indices.into_iter().map(Into::into).collect(),
+            lemmas: proof.lemmas().into_iter().map(|h| h.unpack()).collect(),
+        }
+    }
+}
+
+/// Proof of a transaction's witness hash in `raw_block.submit_transactions.tx_witness_root`,
+/// along with the block proof of `raw_block`
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct TransactionProof {
+    pub tx_hash: H256,
+    pub witness_hash: H256,
+    pub tx_index: Uint32,
+    pub raw_block: RawL2Block,
+    pub tx_proof: CKBMerkleProof,
+    pub block_proof: BlockProof,
+}
+
+/// Sub state of a block, `withdrawal` and `tx` are the post state of the
+/// withdrawal or tx at the index, `prev_txs` is the state before txs
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(tag = "type", content = "index", rename_all = "snake_case")]
+pub enum SubState {
+    Withdrawal(Uint32),
+    PrevTxs,
+    Tx(Uint32),
+    Block,
+}
+
+/// State to query, either the post state of a block number, or a sub state
+/// checkpoint inside the block
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+#[serde(untagged)]
+pub enum StateCheckPoint {
+    Block(Uint64),
+    SubState {
+        block_number: Uint64,
+        sub_state: SubState,
+    },
 }
@@ -1,10 +1,6 @@
-/// MAX deposits in the mem block
-pub const MAX_MEM_BLOCK_DEPOSITS: usize = 50;
 /// MAX withdrawals in the mem block
 pub const MAX_MEM_BLOCK_WITHDRAWALS: usize = 50;
-/// MAX withdrawals in the mem block
-pub const MAX_MEM_BLOCK_TXS: usize = 500;
-/// MAX tx size 50 KB
-pub const MAX_TX_SIZE: usize = 50_000;
 /// MAX withdrawal size 50 KB
 pub const MAX_WITHDRAWAL_SIZE: usize = 50_000;
+/// MAX entry lists exported as per account metrics
+pub const MAX_ENTRY_LIST_METRICS: usize = 100;
@@ -12,6 +12,8 @@ use gw_types::{
     prelude::*,
 };
 
+use crate::withdrawal::Generator;
+
 pub fn to_custodian_cell(
     rollup_context: &RollupContext,
     block_hash: &H256,
@@ -135,6 +137,27 @@ pub async fn query_finalized_custodians<WithdrawalIter: Iterator<Item = Withdraw
         .await
 }
 
+/// Merge finalized custodian cells into one ckb custodian and one custodian per sudt,
+/// return None if a merged custodian can't hold its occupied capacity.
+pub fn generate_merged_custodians(
+    rollup_context: &RollupContext,
+    custodians: &CollectedCustodianCells,
+) -> Option<Vec<(CellOutput, Bytes)>> {
+    let outputs = Generator::new(rollup_context, custodians.into()).finish();
+
+    let is_valid = |(output, data): &(CellOutput, Bytes)| -> bool {
+        match output.occupied_capacity(data.len()) {
+            Ok(capacity) => capacity <= output.capacity().unpack(),
+            Err(_) => false,
+        }
+    };
+    if !outputs.iter().all(is_valid) {
+        return None;
+    }
+
+    Some(outputs)
+}
+
 pub fn calc_ckb_custodian_min_capacity(rollup_context: &RollupContext) -> u64 {
     let lock = build_finalized_custodian_lock(rollup_context);
     let dummy = CellOutput::new_builder()
@@ -206,3 +229,77 @@ fn sum_change_capacity(
 
     ckb_change_capacity + sudt_change_capacity
 }
+
+#[cfg(test)]
+mod tests {
+    use gw_common::H256;
+    use gw_types::{
+        offchain::{CellInfo, CollectedCustodianCells, RollupContext},
+        packed::{CellOutput, RollupConfig, Script},
+        prelude::*,
+    };
+
+    use super::{
+        build_finalized_custodian_lock, calc_ckb_custodian_min_capacity,
+        generate_finalized_custodian, generate_merged_custodians,
+    };
+
+    const CKB: u64 = 100_000_000;
+
+    fn rollup_context() -> RollupContext {
+        RollupContext {
+            rollup_script_hash: H256::from([1u8; 32]),
+            rollup_config: RollupConfig::default(),
+        }
+    }
+
+    fn ckb_custodian(rollup_context: &RollupContext, capacity: u64) -> CellInfo {
+        let output = CellOutput::new_builder()
+            .capacity(capacity.pack())
+            .lock(build_finalized_custodian_lock(rollup_context))
+            .build();
+
+        CellInfo {
+            output,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_merge_ckb_custodians() {
+        let rollup_context = rollup_context();
+        let mut custodians = CollectedCustodianCells::default();
+        for _ in 0..3 {
+            let cell = ckb_custodian(&rollup_context, 1000 * CKB);
+            custodians.capacity += 1000 * CKB as u128;
+            custodians.cells_info.push(cell);
+        }
+
+        let outputs = generate_merged_custodians(&rollup_context, &custodians).unwrap();
+        assert_eq!(outputs.len(), 1);
+        let capacity: u64 = outputs[0].0.capacity().unpack();
+        assert_eq!(capacity, 3000 * CKB);
+    }
+
+    #[test]
+    fn test_merge_below_ckb_custodian_min_capacity() {
+        let rollup_context = rollup_context();
+        let sudt_script = Script::new_builder().args([2u8; 32].pack()).build();
+        let (sudt_output, _) =
+            generate_finalized_custodian(&rollup_context, 1, sudt_script.clone());
+        let sudt_capacity: u64 = sudt_output.capacity().unpack();
+        let ckb_min_capacity = calc_ckb_custodian_min_capacity(&rollup_context);
+
+        // Merged ckb custodian only holds capacity left by sudt custodian
+        let mut custodians = CollectedCustodianCells::default();
+        custodians.capacity = (sudt_capacity + ckb_min_capacity - 1) as u128;
+        custodians
+            .sudt
+            .insert(sudt_script.hash(), (200, sudt_script));
+        assert!(generate_merged_custodians(&rollup_context, &custodians).is_none());
+
+        custodians.capacity = (sudt_capacity + ckb_min_capacity) as u128;
+        let outputs = generate_merged_custodians(&rollup_context, &custodians).unwrap();
+        assert_eq!(outputs.len(), 2);
+    }
+}
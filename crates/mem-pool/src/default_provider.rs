@@ -1,18 +1,25 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::{anyhow, Result};
+use gw_common::H256;
 use gw_poa::PoA;
 use gw_rpc_client::rpc_client::RPCClient;
 use gw_store::Store;
 use gw_types::{
     offchain::{CollectedCustodianCells, DepositInfo, InputCellInfo, RollupContext},
-    packed::{CellInput, WithdrawalRequest},
+    packed::{CellInput, OutPoint, WithdrawalRequest},
     prelude::*,
 };
 use smol::{lock::Mutex, Task};
 
 use crate::{
-    constants::MAX_MEM_BLOCK_DEPOSITS, custodian::query_finalized_custodians,
+    custodian::query_finalized_custodians,
+    deposit::{deposit_cancel_timeout, is_since_relative, is_since_satisfied, SinceBlock},
+    deposit_policy::DepositPolicy,
     traits::MemPoolProvider,
 };
 
@@ -22,14 +29,21 @@ pub struct DefaultMemPoolProvider {
     /// POA Context
     poa: Arc<Mutex<PoA>>,
     store: Store,
+    deposit_policy: DepositPolicy,
 }
 
 impl DefaultMemPoolProvider {
-    pub fn new(rpc_client: RPCClient, poa: Arc<Mutex<PoA>>, store: Store) -> Self {
+    pub fn new(
+        rpc_client: RPCClient,
+        poa: Arc<Mutex<PoA>>,
+        store: Store,
+        deposit_policy: DepositPolicy,
+    ) -> Self {
         DefaultMemPoolProvider {
             rpc_client,
             poa,
             store,
+            deposit_policy,
         }
     }
 }
@@ -60,7 +74,75 @@ impl MemPoolProvider for DefaultMemPoolProvider {
 
     fn collect_deposit_cells(&self) -> Task<Result<Vec<DepositInfo>>> {
         let rpc_client = self.rpc_client.clone();
-        smol::spawn(async move { rpc_client.query_deposit_cells(MAX_MEM_BLOCK_DEPOSITS).await })
+        let policy = self.deposit_policy.clone();
+        smol::spawn(async move {
+            let deposits = rpc_client
+                .query_deposit_cells(policy.query_limit(), policy.min_capacity())
+                .await?;
+            Ok(policy.select(deposits))
+        })
+    }
+
+    fn query_cancel_timeout_deposits(
+        &self,
+        deposits: Vec<DepositInfo>,
+    ) -> Task<Result<Vec<OutPoint>>> {
+        let rpc_client = self.rpc_client.clone();
+        smol::spawn(async move {
+            if deposits.is_empty() {
+                return Ok(vec![]);
+            }
+
+            let tip_hash: H256 = rpc_client.get_tip().await?.block_hash().unpack();
+            let tip = query_since_block(&rpc_client, tip_hash).await?;
+
+            let to_cancel_timeout = |deposit: DepositInfo| {
+                let since = deposit_cancel_timeout(&deposit)?;
+                Some((deposit, since))
+            };
+            let cancel_timeouts: Vec<(DepositInfo, u64)> =
+                deposits.into_iter().filter_map(to_cancel_timeout).collect();
+
+            // Committed block is only used in relative since, query them concurrently
+            let committed_blocks: HashMap<H256, Option<SinceBlock>> = {
+                let tx_hashes: HashSet<H256> = (cancel_timeouts.iter())
+                    .filter(|(_, since)| is_since_relative(*since))
+                    .map(|(deposit, _)| deposit.cell.out_point.tx_hash().unpack())
+                    .collect();
+
+                let to_task = tx_hashes.into_iter().map(|tx_hash| {
+                    let rpc_client = rpc_client.clone();
+                    let query = async move { query_committed_block(&rpc_client, tx_hash).await };
+                    (tx_hash, smol::spawn(query))
+                });
+                let tasks: Vec<_> = to_task.collect();
+
+                let mut committed_blocks = HashMap::with_capacity(tasks.len());
+                for (tx_hash, task) in tasks {
+                    committed_blocks.insert(tx_hash, task.await?);
+                }
+                committed_blocks
+            };
+
+            let mut timeout_deposits = Vec::new();
+            for (deposit, cancel_timeout) in cancel_timeouts {
+                let committed = if is_since_relative(cancel_timeout) {
+                    let tx_hash: H256 = deposit.cell.out_point.tx_hash().unpack();
+                    match committed_blocks.get(&tx_hash) {
+                        Some(Some(committed)) => committed,
+                        _ => continue,
+                    }
+                } else {
+                    &tip
+                };
+
+                if is_since_satisfied(cancel_timeout, committed, &tip) {
+                    timeout_deposits.push(deposit.cell.out_point);
+                }
+            }
+
+            Ok(timeout_deposits)
+        })
     }
 
     fn query_available_custodians(
@@ -84,3 +166,28 @@ impl MemPoolProvider for DefaultMemPoolProvider {
         })
     }
 }
+
+async fn query_committed_block(
+    rpc_client: &RPCClient,
+    tx_hash: H256,
+) -> Result<Option<SinceBlock>> {
+    match rpc_client.get_transaction_block_hash(tx_hash).await? {
+        Some(block_hash) => Ok(Some(
+            query_since_block(rpc_client, block_hash.into()).await?,
+        )),
+        None => Ok(None),
+    }
+}
+
+async fn query_since_block(rpc_client: &RPCClient, block_hash: H256) -> Result<SinceBlock> {
+    let header = rpc_client.get_header(block_hash).await?;
+    let header =
+        header.ok_or_else(|| anyhow!("block {} not found", hex::encode(block_hash.as_slice())))?;
+    let median_time = rpc_client.get_block_median_time(block_hash).await?;
+
+    Ok(SinceBlock {
+        number: header.inner.number.value(),
+        epoch: header.inner.epoch.value(),
+        median_time,
+    })
+}
@@ -4,31 +4,140 @@ use gw_types::{
     bytes::Bytes,
     core::ScriptHashType,
     offchain::{DepositInfo, RollupContext},
+    packed::{DepositLockArgs, DepositLockArgsReader},
     prelude::*,
 };
+use std::time::Duration;
 
 use crate::custodian::to_custodian_cell;
 
+const SINCE_RELATIVE_FLAG: u64 = 1 << 63;
+const SINCE_METRIC_MASK: u64 = 0b0110_0000 << 56;
+const SINCE_METRIC_BLOCK_NUMBER: u64 = 0;
+const SINCE_METRIC_EPOCH: u64 = 0b0010_0000 << 56;
+const SINCE_METRIC_TIMESTAMP: u64 = 0b0100_0000 << 56;
+const SINCE_VALUE_MASK: u64 = 0x00ff_ffff_ffff_ffff;
+const SINCE_RESERVED_MASK: u64 = 0b0001_1111 << 56;
+
+/// Deposit which won't be packaged into block, its owner can cancel it to get a refund
+#[derive(Debug, Clone)]
+pub struct RefundableDeposit {
+    pub info: DepositInfo,
+    pub reason: DepositRefundReason,
+}
+
+#[derive(Debug, Clone)]
+pub enum DepositRefundReason {
+    /// Deposit request is invalid, may caused by malicious user
+    Invalid(String),
+    /// Deposit cancel timeout is passed, its owner can cancel it at any moment
+    CancelTimeout,
+}
+
+/// L1 block fields used to check since
+#[derive(Debug, Clone)]
+pub struct SinceBlock {
+    pub number: u64,
+    pub epoch: u64,
+    pub median_time: Duration,
+}
+
 /// check deposit cells again to prevent upstream components errors.
+/// return valid deposits and deposits which have invalid requests
 pub fn sanitize_deposit_cells(
     ctx: &RollupContext,
     unsanitize_deposits: Vec<DepositInfo>,
-) -> Vec<DepositInfo> {
+) -> (Vec<DepositInfo>, Vec<RefundableDeposit>) {
     let mut deposit_cells = Vec::with_capacity(unsanitize_deposits.len());
+    let mut invalid_deposits = Vec::new();
     for cell in unsanitize_deposits {
         // check deposit lock
         // the lock should be correct unless the upstream ckb-indexer has bugs
-        if let Err(err) = check_deposit_cell(ctx, &cell) {
+        if let Err(err) = check_deposit_lock(ctx, &cell) {
             log::debug!("[sanitize deposit cell] {}", err);
             continue;
         }
+        if let Err(err) = check_deposit_request(ctx, &cell) {
+            log::debug!("[sanitize deposit cell] {}", err);
+            invalid_deposits.push(RefundableDeposit {
+                info: cell,
+                reason: DepositRefundReason::Invalid(err.to_string()),
+            });
+            continue;
+        }
         deposit_cells.push(cell);
     }
-    deposit_cells
+    (deposit_cells, invalid_deposits)
+}
+
+/// deposit lock args: rollup_type_hash (32 bytes) | DepositLockArgs
+pub fn deposit_lock_args(deposit: &DepositInfo) -> Option<DepositLockArgs> {
+    let args: Bytes = deposit.cell.output.lock().args().unpack();
+    if args.len() < 32 || DepositLockArgsReader::verify(&args[32..], false).is_err() {
+        return None;
+    }
+
+    Some(DepositLockArgs::new_unchecked(args.slice(32..)))
+}
+
+/// return since of deposit lock's cancel path
+pub fn deposit_cancel_timeout(deposit: &DepositInfo) -> Option<u64> {
+    let lock_args = deposit_lock_args(deposit)?;
+    Some(lock_args.cancel_timeout().unpack())
+}
+
+pub fn is_since_relative(since: u64) -> bool {
+    since & SINCE_RELATIVE_FLAG != 0
+}
+
+/// check whether since is satisfied in a transaction committed after tip block,
+/// committed block is the block contains the input cell, only used in relative since.
+pub fn is_since_satisfied(since: u64, committed: &SinceBlock, tip: &SinceBlock) -> bool {
+    if since & SINCE_RESERVED_MASK != 0 {
+        return false;
+    }
+
+    let relative = is_since_relative(since);
+    let value = since & SINCE_VALUE_MASK;
+
+    match since & SINCE_METRIC_MASK {
+        SINCE_METRIC_BLOCK_NUMBER if relative => {
+            tip.number >= committed.number.saturating_add(value)
+        }
+        SINCE_METRIC_BLOCK_NUMBER => tip.number >= value,
+        SINCE_METRIC_EPOCH => {
+            let (tip_epoch, since_epoch) = (to_fraction(tip.epoch), to_fraction(value));
+            let start_epoch = if relative {
+                to_fraction(committed.epoch)
+            } else {
+                (0, 1)
+            };
+
+            // tip_epoch >= start_epoch + since_epoch
+            let lhs = tip_epoch.0 * start_epoch.1 * since_epoch.1;
+            let rhs = (start_epoch.0 * since_epoch.1 + since_epoch.0 * start_epoch.1) * tip_epoch.1;
+            lhs >= rhs
+        }
+        SINCE_METRIC_TIMESTAMP if relative => {
+            let elapsed = tip.median_time.saturating_sub(committed.median_time);
+            elapsed >= Duration::from_secs(value)
+        }
+        SINCE_METRIC_TIMESTAMP => tip.median_time >= Duration::from_secs(value),
+        _ => false,
+    }
+}
+
+// epoch number with fraction: number(24 bits) | index(16 bits) | length(16 bits)
+fn to_fraction(epoch: u64) -> (u128, u128) {
+    let number = (epoch & 0xff_ffff) as u128;
+    let index = ((epoch >> 24) & 0xffff) as u128;
+    let length = ((epoch >> 40) & 0xffff).max(1) as u128;
+
+    (number * length + index, length)
 }
 
-// check deposit cell
-fn check_deposit_cell(ctx: &RollupContext, cell: &DepositInfo) -> Result<()> {
+// check deposit lock
+fn check_deposit_lock(ctx: &RollupContext, cell: &DepositInfo) -> Result<()> {
     let hash_type = ScriptHashType::Type.into();
 
     // check deposit lock
@@ -61,6 +170,13 @@ fn check_deposit_cell(ctx: &RollupContext, cell: &DepositInfo) -> Result<()> {
         }
     }
 
+    Ok(())
+}
+
+// check deposit sUDT, request and capacity
+fn check_deposit_request(ctx: &RollupContext, cell: &DepositInfo) -> Result<()> {
+    let hash_type = ScriptHashType::Type.into();
+
     // check sUDT
     // sUDT may be invalid, this may caused by malicious user
     if let Some(type_) = cell.cell.output.type_().to_opt() {
@@ -124,3 +240,118 @@ fn check_deposit_cell(ctx: &RollupContext, cell: &DepositInfo) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        is_since_satisfied, SinceBlock, SINCE_METRIC_EPOCH, SINCE_METRIC_MASK,
+        SINCE_METRIC_TIMESTAMP, SINCE_RELATIVE_FLAG,
+    };
+
+    fn block(number: u64, epoch: u64, median_time_secs: u64) -> SinceBlock {
+        SinceBlock {
+            number,
+            epoch,
+            median_time: Duration::from_secs(median_time_secs),
+        }
+    }
+
+    fn epoch(number: u64, index: u64, length: u64) -> u64 {
+        number | (index << 24) | (length << 40)
+    }
+
+    #[test]
+    fn test_absolute_block_number() {
+        let since = 100;
+        let committed = block(0, 0, 0);
+        assert!(!is_since_satisfied(since, &committed, &block(99, 0, 0)));
+        assert!(is_since_satisfied(since, &committed, &block(100, 0, 0)));
+    }
+
+    #[test]
+    fn test_relative_block_number() {
+        let since = SINCE_RELATIVE_FLAG | 10;
+        let committed = block(100, 0, 0);
+        assert!(!is_since_satisfied(since, &committed, &block(109, 0, 0)));
+        assert!(is_since_satisfied(since, &committed, &block(110, 0, 0)));
+    }
+
+    #[test]
+    fn test_absolute_timestamp() {
+        let since = SINCE_METRIC_TIMESTAMP | 1000;
+        let committed = block(0, 0, 0);
+        assert!(!is_since_satisfied(since, &committed, &block(0, 0, 999)));
+        assert!(is_since_satisfied(since, &committed, &block(0, 0, 1000)));
+    }
+
+    #[test]
+    fn test_relative_timestamp() {
+        let since = SINCE_RELATIVE_FLAG | SINCE_METRIC_TIMESTAMP | 60;
+        let committed = block(0, 0, 1000);
+        assert!(!is_since_satisfied(since, &committed, &block(0, 0, 1059)));
+        assert!(is_since_satisfied(since, &committed, &block(0, 0, 1060)));
+    }
+
+    #[test]
+    fn test_absolute_epoch() {
+        let since = SINCE_METRIC_EPOCH | epoch(10, 1, 2);
+        let committed = block(0, 0, 0);
+        assert!(!is_since_satisfied(
+            since,
+            &committed,
+            &block(0, epoch(10, 0, 1), 0)
+        ));
+        assert!(!is_since_satisfied(
+            since,
+            &committed,
+            &block(0, epoch(10, 1, 3), 0)
+        ));
+        assert!(is_since_satisfied(
+            since,
+            &committed,
+            &block(0, epoch(10, 2, 4), 0)
+        ));
+        assert!(is_since_satisfied(
+            since,
+            &committed,
+            &block(0, epoch(11, 0, 1), 0)
+        ));
+    }
+
+    #[test]
+    fn test_relative_epoch() {
+        let since = SINCE_RELATIVE_FLAG | SINCE_METRIC_EPOCH | epoch(1, 1, 2);
+        let committed = block(0, epoch(5, 0, 1), 0);
+        assert!(!is_since_satisfied(
+            since,
+            &committed,
+            &block(0, epoch(6, 0, 1), 0)
+        ));
+        assert!(is_since_satisfied(
+            since,
+            &committed,
+            &block(0, epoch(6, 1, 2), 0)
+        ));
+    }
+
+    #[test]
+    fn test_malformed_since() {
+        let committed = block(0, 0, 0);
+        let tip = block(u64::max_value(), epoch(0xff_ffff, 0, 1), u64::max_value());
+
+        // Unknown metric flag
+        let since = SINCE_METRIC_MASK;
+        assert!(!is_since_satisfied(since, &committed, &tip));
+        assert!(!is_since_satisfied(
+            SINCE_RELATIVE_FLAG | since,
+            &committed,
+            &tip
+        ));
+
+        // Reserved flags are set
+        let since = (1 << 56) | 100;
+        assert!(!is_since_satisfied(since, &committed, &tip));
+    }
+}
@@ -0,0 +1,168 @@
+//! Deposit selection policy
+//!
+//! Collected deposits are ordered by L1 block number (FIFO), deposits with
+//! too little capacity are ignored, and the deposits of a same owner lock
+//! in a mem block are limited, so a spammer can't starve other users.
+
+use std::collections::HashMap;
+
+use gw_config::DepositPolicyConfig;
+use gw_types::{offchain::DepositInfo, prelude::*};
+
+use crate::deposit::deposit_lock_args;
+
+/// Query more deposit cells than the policy selects, leave rooms for the filtered ones
+const QUERY_DEPOSIT_CELLS_FACTOR: usize = 4;
+
+#[derive(Debug, Clone)]
+pub struct DepositPolicy {
+    config: DepositPolicyConfig,
+}
+
+impl DepositPolicy {
+    pub fn new(config: DepositPolicyConfig) -> Self {
+        DepositPolicy { config }
+    }
+
+    pub fn min_capacity(&self) -> u64 {
+        self.config.min_capacity
+    }
+
+    /// Number of deposit cells to query from L1
+    pub fn query_limit(&self) -> usize {
+        self.config
+            .max_deposits
+            .saturating_mul(QUERY_DEPOSIT_CELLS_FACTOR)
+    }
+
+    /// Select deposits for the next mem block
+    pub fn select(&self, mut deposits: Vec<DepositInfo>) -> Vec<DepositInfo> {
+        // stable sort, keep the order of deposits in the same L1 block
+        deposits.sort_by_key(|deposit| deposit.block_number);
+
+        let mut owner_deposits: HashMap<[u8; 32], usize> = HashMap::new();
+        let mut selected = Vec::with_capacity(self.config.max_deposits);
+        for deposit in deposits {
+            if selected.len() >= self.config.max_deposits {
+                break;
+            }
+
+            let capacity: u64 = deposit.cell.output.capacity().unpack();
+            if capacity < self.config.min_capacity {
+                log::debug!(
+                    "[deposit policy] skip deposit {:?}, capacity {} is less than {}",
+                    deposit.cell.out_point,
+                    capacity,
+                    self.config.min_capacity
+                );
+                continue;
+            }
+
+            let owner_lock_hash: [u8; 32] = match deposit_lock_args(&deposit) {
+                Some(args) => args.owner_lock_hash().unpack(),
+                None => {
+                    log::debug!(
+                        "[deposit policy] skip deposit {:?}, invalid deposit lock args",
+                        deposit.cell.out_point
+                    );
+                    continue;
+                }
+            };
+            let count = owner_deposits.entry(owner_lock_hash).or_insert(0);
+            if *count >= self.config.max_deposits_per_owner {
+                log::debug!(
+                    "[deposit policy] skip deposit {:?}, owner 0x{} reaches limit {}",
+                    deposit.cell.out_point,
+                    hex::encode(owner_lock_hash),
+                    self.config.max_deposits_per_owner
+                );
+                continue;
+            }
+            *count += 1;
+
+            selected.push(deposit);
+        }
+
+        selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gw_config::DepositPolicyConfig;
+    use gw_types::{
+        offchain::{CellInfo, DepositInfo},
+        packed::{CellOutput, DepositLockArgs, OutPoint, Script},
+        prelude::*,
+    };
+
+    use super::DepositPolicy;
+
+    fn deposit(owner: u8, capacity: u64, block_number: u64, index: u32) -> DepositInfo {
+        let lock_args = {
+            let args = DepositLockArgs::new_builder()
+                .owner_lock_hash([owner; 32].pack())
+                .build();
+            let mut buf = vec![0u8; 32];
+            buf.extend_from_slice(args.as_slice());
+            buf
+        };
+        let output = CellOutput::new_builder()
+            .capacity(capacity.pack())
+            .lock(Script::new_builder().args(lock_args.pack()).build())
+            .build();
+
+        DepositInfo {
+            request: Default::default(),
+            cell: CellInfo {
+                out_point: OutPoint::new_builder().index(index.pack()).build(),
+                output,
+                data: Default::default(),
+            },
+            block_number,
+        }
+    }
+
+    fn indexes(deposits: &[DepositInfo]) -> Vec<u32> {
+        let to_index = |d: &DepositInfo| -> u32 { d.cell.out_point.index().unpack() };
+        deposits.iter().map(to_index).collect()
+    }
+
+    #[test]
+    fn test_select_in_block_order() {
+        let policy = DepositPolicy::new(DepositPolicyConfig {
+            min_capacity: 100,
+            max_deposits: 3,
+            max_deposits_per_owner: 10,
+        });
+        let deposits = vec![
+            deposit(1, 200, 3, 0),
+            deposit(2, 200, 1, 1),
+            deposit(3, 99, 0, 2),
+            deposit(4, 200, 1, 3),
+            deposit(5, 200, 2, 4),
+        ];
+
+        let selected = policy.select(deposits);
+        assert_eq!(indexes(&selected), vec![1, 3, 4]);
+    }
+
+    #[test]
+    fn test_limit_deposits_per_owner() {
+        let policy = DepositPolicy::new(DepositPolicyConfig {
+            min_capacity: 0,
+            max_deposits: 10,
+            max_deposits_per_owner: 2,
+        });
+        let deposits = vec![
+            deposit(1, 200, 0, 0),
+            deposit(1, 200, 0, 1),
+            deposit(1, 200, 0, 2),
+            deposit(2, 200, 1, 3),
+            deposit(1, 200, 1, 4),
+        ];
+
+        let selected = policy.select(deposits);
+        assert_eq!(indexes(&selected), vec![0, 1, 3]);
+    }
+}
@@ -6,7 +6,8 @@
 mod constants;
 pub mod custodian;
 pub mod default_provider;
-mod deposit;
+pub mod deposit;
+pub mod deposit_policy;
 mod mem_block;
 pub mod pool;
 pub mod traits;
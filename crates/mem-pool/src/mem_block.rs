@@ -2,7 +2,7 @@ use std::{collections::HashSet, time::Duration};
 
 use gw_common::{merkle_utils::calculate_state_checkpoint, H256};
 use gw_types::{
-    offchain::{CollectedCustodianCells, DepositInfo},
+    offchain::{CollectedCustodianCells, DepositInfo, RunResult},
     packed::{AccountMerkleState, BlockInfo, L2Block, TxReceipt},
     prelude::*,
 };
@@ -12,6 +12,22 @@ pub struct MemBlockContent {
     pub txs: Vec<H256>,
 }
 
+/// Resources used by a tx, counted into the budget of the mem block
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TxCost {
+    pub cycles: u64,
+    pub write_data_bytes: usize,
+}
+
+impl TxCost {
+    pub fn from_run_result(run_result: &RunResult) -> Self {
+        TxCost {
+            cycles: run_result.used_cycles,
+            write_data_bytes: run_result.write_data.values().map(Vec::len).sum(),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct MemBlock {
     block_producer_id: u32,
@@ -19,6 +35,12 @@ pub struct MemBlock {
     txs: Vec<H256>,
     /// Txs set
     txs_set: HashSet<H256>,
+    /// Cost of finalized txs
+    txs_cost: Vec<TxCost>,
+    /// Sum of txs cycles
+    used_cycles: u64,
+    /// Sum of txs write data bytes
+    used_write_data_bytes: usize,
     /// Finalized withdrawals
     withdrawals: Vec<H256>,
     /// Finalized custodians to produce finalized withdrawals
@@ -77,6 +99,9 @@ impl MemBlock {
     pub fn clear(&mut self) {
         self.txs.clear();
         self.txs_set.clear();
+        self.txs_cost.clear();
+        self.used_cycles = 0;
+        self.used_write_data_bytes = 0;
         self.withdrawals.clear();
         self.withdrawals_set.clear();
         self.finalized_custodians = None;
@@ -105,7 +130,7 @@ impl MemBlock {
         self.txs_prev_state_checkpoint = Some(prev_state_checkpoint);
     }
 
-    pub fn push_tx(&mut self, tx_hash: H256, receipt: &TxReceipt) {
+    pub fn push_tx(&mut self, tx_hash: H256, receipt: &TxReceipt, cost: TxCost) {
         let post_state = receipt.post_state();
         let state_checkpoint = calculate_state_checkpoint(
             &post_state.merkle_root().unpack(),
@@ -118,6 +143,11 @@ impl MemBlock {
         );
         self.txs.push(tx_hash);
         self.txs_set.insert(tx_hash);
+        self.txs_cost.push(cost);
+        self.used_cycles = self.used_cycles.saturating_add(cost.cycles);
+        self.used_write_data_bytes = self
+            .used_write_data_bytes
+            .saturating_add(cost.write_data_bytes);
         self.state_checkpoints.push(state_checkpoint);
     }
 
@@ -149,6 +179,18 @@ impl MemBlock {
         &self.txs_set
     }
 
+    pub fn txs_cost(&self) -> &[TxCost] {
+        &self.txs_cost
+    }
+
+    pub fn used_cycles(&self) -> u64 {
+        self.used_cycles
+    }
+
+    pub fn used_write_data_bytes(&self) -> usize {
+        self.used_write_data_bytes
+    }
+
     pub fn state_checkpoints(&self) -> &[H256] {
         &self.state_checkpoints
     }
@@ -169,3 +211,39 @@ impl MemBlock {
         &self.prev_merkle_state
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{MemBlock, TxCost};
+
+    use gw_common::{h256_ext::H256Ext, H256};
+    use gw_types::packed::TxReceipt;
+
+    #[test]
+    fn test_push_tx_cost() {
+        let mut mem_block = MemBlock::default();
+        let costs = [
+            TxCost {
+                cycles: 1000,
+                write_data_bytes: 10,
+            },
+            TxCost {
+                cycles: 2000,
+                write_data_bytes: 0,
+            },
+        ];
+        for (i, cost) in costs.iter().enumerate() {
+            let tx_hash = H256::from_u32(i as u32 + 1);
+            mem_block.push_tx(tx_hash, &TxReceipt::default(), *cost);
+        }
+        assert_eq!(mem_block.txs().len(), 2);
+        assert_eq!(mem_block.txs_cost(), &costs);
+        assert_eq!(mem_block.used_cycles(), 3000);
+        assert_eq!(mem_block.used_write_data_bytes(), 10);
+
+        mem_block.clear();
+        assert!(mem_block.txs_cost().is_empty());
+        assert_eq!(mem_block.used_cycles(), 0);
+        assert_eq!(mem_block.used_write_data_bytes(), 0);
+    }
+}
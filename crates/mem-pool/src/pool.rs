@@ -18,9 +18,8 @@ use gw_common::{
     H256,
 };
 use gw_config::MemPoolConfig;
-use gw_generator::{
-    constants::L2TX_MAX_CYCLES, error::TransactionError, traits::StateExt, Generator,
-};
+use gw_generator::{error::TransactionError, traits::StateExt, Generator};
+use gw_metrics::GLOBAL_METRICS;
 use gw_store::{
     chain_view::ChainView,
     state_db::{CheckPoint, StateDBMode, StateDBTransaction, SubState, WriteContext},
@@ -28,23 +27,25 @@ use gw_store::{
     Store,
 };
 use gw_types::{
-    offchain::{BlockParam, CollectedCustodianCells, DepositInfo, RunResult},
+    offchain::{collect_fees, BlockParam, CollectedCustodianCells, DepositInfo, RunResult},
     packed::{
         AccountMerkleState, BlockInfo, L2Block, L2Transaction, RawL2Transaction, Script, TxReceipt,
         WithdrawalRequest,
     },
     prelude::{Entity, Pack, Unpack},
 };
+use smol::Task;
 use std::{
     cmp::{max, min},
-    collections::{HashMap, HashSet, VecDeque},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     sync::Arc,
 };
 
 use crate::{
-    constants::{MAX_MEM_BLOCK_TXS, MAX_MEM_BLOCK_WITHDRAWALS, MAX_TX_SIZE, MAX_WITHDRAWAL_SIZE},
+    constants::{MAX_ENTRY_LIST_METRICS, MAX_MEM_BLOCK_WITHDRAWALS, MAX_WITHDRAWAL_SIZE},
     custodian::AvailableCustodians,
-    mem_block::MemBlock,
+    deposit::{sanitize_deposit_cells, DepositRefundReason, RefundableDeposit},
+    mem_block::{MemBlock, TxCost},
     traits::MemPoolProvider,
     types::EntryList,
 };
@@ -89,6 +90,12 @@ pub struct MemPool {
     offchain_validator: Option<OffChainCancelChallengeValidator>,
     /// Mem pool config
     config: MemPoolConfig,
+    /// Deposits which won't be packaged, their owners can cancel them to get refund
+    invalid_deposits: Vec<RefundableDeposit>,
+    /// Deposits collected in the last reset, their owners may cancel them after cancel timeout
+    pending_deposits: Vec<DepositInfo>,
+    /// Txs exceed the budget of mem block, re-inject them to the next mem block
+    deferred_txs: VecDeque<L2Transaction>,
 }
 
 impl MemPool {
@@ -110,6 +117,17 @@ impl MemPool {
             let smt = db.reverted_block_smt()?;
             smt.root().to_owned()
         };
+        // deferred txs of the last run are re-injected on reset
+        let deferred_txs = {
+            let db = store.begin_transaction();
+            let mut txs = VecDeque::new();
+            for tx_hash in db.get_mem_pool_deferred_txs()? {
+                if let Some(tx) = db.get_mem_pool_transaction(&tx_hash)? {
+                    txs.push_back(tx);
+                }
+            }
+            txs
+        };
         let offchain_validator = offchain_validator_context.map(|offchain_validator_context| {
             OffChainCancelChallengeValidator::new(
                 offchain_validator_context,
@@ -129,6 +147,9 @@ impl MemPool {
             provider,
             offchain_validator,
             config,
+            invalid_deposits: Vec::new(),
+            pending_deposits: Vec::new(),
+            deferred_txs,
         };
 
         // set tip
@@ -140,6 +161,31 @@ impl MemPool {
         &self.mem_block
     }
 
+    /// Invalid deposits and deposits whose cancel timeout is passed. Cancel timeout is
+    /// checked on L1, await the returned task without holding the mem pool lock.
+    pub fn query_refundable_deposits(&self) -> Task<Result<Vec<RefundableDeposit>>> {
+        let invalid_deposits = self.invalid_deposits.clone();
+        let pending_deposits = self.pending_deposits.clone();
+        let query = (self.provider).query_cancel_timeout_deposits(pending_deposits.clone());
+        smol::spawn(async move {
+            let timeout_out_points = query.await?;
+            let timeout_deposits = pending_deposits.into_iter().filter_map(|info| {
+                if !timeout_out_points.contains(&info.cell.out_point) {
+                    return None;
+                }
+                Some(RefundableDeposit {
+                    info,
+                    reason: DepositRefundReason::CancelTimeout,
+                })
+            });
+
+            Ok(invalid_deposits
+                .into_iter()
+                .chain(timeout_deposits)
+                .collect())
+        })
+    }
+
     pub fn set_provider(&mut self, provider: Box<dyn MemPoolProvider + Send>) {
         self.provider = provider;
     }
@@ -171,6 +217,7 @@ impl MemPool {
         let db = self.store.begin_transaction();
         self.push_transaction_with_db(&db, tx)?;
         db.commit()?;
+        self.record_metrics();
         Ok(())
     }
 
@@ -178,27 +225,35 @@ impl MemPool {
     fn push_transaction_with_db(&mut self, db: &StoreTransaction, tx: L2Transaction) -> Result<()> {
         // check duplication
         let tx_hash: H256 = tx.raw().hash().into();
-        if self.mem_block.txs_set().contains(&tx_hash) {
+        if self.mem_block.txs_set().contains(&tx_hash)
+            || self
+                .deferred_txs
+                .iter()
+                .any(|d| d.raw().hash() == tx.raw().hash())
+        {
             return Err(anyhow!("duplicated tx"));
         }
 
-        // reject if mem block is full
-        // TODO: we can use the pool as a buffer
-        if self.mem_block.txs().len() >= MAX_MEM_BLOCK_TXS {
-            return Err(anyhow!(
-                "Mem block is full, MAX_MEM_BLOCK_TXS: {}",
-                MAX_MEM_BLOCK_TXS
-            ));
-        }
-
-        // verification
+        // verification, only valid txs are deferred
         self.verify_tx(db, &tx)?;
 
+        // defer if mem block is full, or sender has deferred txs to keep the nonce order
+        let sender_id: u32 = tx.raw().from_id().unpack();
+        if self.mem_block.txs().len() >= self.config.max_mem_block_txs
+            || self.deferred_txs_count(sender_id) > 0
+        {
+            self.check_tx_executable(db, &tx)?;
+            return self.defer_tx(db, tx);
+        }
+
         // instantly run tx in background & update local state
-        let tx_receipt = self.finalize_tx(db, tx.clone())?;
+        let (tx_receipt, tx_cost) = match self.finalize_tx(db, tx.clone())? {
+            Some(finalized) => finalized,
+            None => return self.defer_tx(db, tx),
+        };
 
         // save tx receipt in mem pool
-        self.mem_block.push_tx(tx_hash, &tx_receipt);
+        self.mem_block.push_tx(tx_hash, &tx_receipt, tx_cost);
         db.insert_mem_pool_transaction_receipt(&tx_hash, tx_receipt)?;
 
         // Add to pool
@@ -210,10 +265,56 @@ impl MemPool {
         Ok(())
     }
 
+    /// Defer tx to the next mem block, the tx must be verified
+    fn defer_tx(&mut self, db: &StoreTransaction, tx: L2Transaction) -> Result<()> {
+        if self.deferred_txs.len() >= self.config.max_deferred_txs {
+            return Err(anyhow!(
+                "Mem pool is full, deferred txs: {}",
+                self.deferred_txs.len()
+            ));
+        }
+        log::debug!(
+            "[mem-pool] defer tx {} to the next mem block",
+            hex::encode(tx.hash())
+        );
+        // persist as pending txs, so deferred txs are queryable and survive restarts
+        db.insert_mem_pool_transaction(&tx.hash().into(), tx.clone())?;
+        self.deferred_txs.push_back(tx);
+        self.persist_deferred_txs(db)
+    }
+
+    fn persist_deferred_txs(&self, db: &StoreTransaction) -> Result<()> {
+        let tx_hashes: Vec<H256> = self
+            .deferred_txs
+            .iter()
+            .map(|tx| tx.hash().into())
+            .collect();
+        db.update_mem_pool_deferred_txs(&tx_hashes)?;
+        Ok(())
+    }
+
+    fn deferred_txs_count(&self, sender_id: u32) -> usize {
+        self.deferred_txs
+            .iter()
+            .filter(|d| d.raw().from_id().unpack() == sender_id)
+            .count()
+    }
+
+    /// Return true if the mem block has no budget for the tx
+    fn exceeds_mem_block_budget(&self, cost: &TxCost) -> bool {
+        let cycles = self.mem_block.used_cycles().saturating_add(cost.cycles);
+        let write_data_bytes = self
+            .mem_block
+            .used_write_data_bytes()
+            .saturating_add(cost.write_data_bytes);
+        cycles > self.config.max_block_cycles
+            || write_data_bytes > self.config.max_block_write_data_bytes
+    }
+
     /// verify tx
     fn verify_tx(&self, db: &StoreTransaction, tx: &L2Transaction) -> Result<()> {
         // check tx size
-        if tx.as_slice().len() > MAX_TX_SIZE {
+        if tx.as_slice().len() > self.config.max_tx_size {
             return Err(anyhow!("tx over size"));
         }
 
@@ -221,8 +322,60 @@ impl MemPool {
         let state = state_db.state_tree()?;
         // verify signature
         self.generator.check_transaction_signature(&state, tx)?;
-        self.generator.verify_transaction(&state, tx)?;
+        // verify nonce, deferred txs of the sender are executed before the tx
+        let sender_id: u32 = tx.raw().from_id().unpack();
+        let deferred_count = self.deferred_txs_count(sender_id);
+        if 0 == deferred_count {
+            self.generator.verify_transaction(&state, tx)?;
+        } else {
+            let expected = state.get_nonce(sender_id)? + deferred_count as u32;
+            let nonce: u32 = tx.raw().nonce().unpack();
+            if nonce != expected {
+                return Err(TransactionError::Nonce {
+                    expected,
+                    actual: nonce,
+                    account_id: sender_id,
+                }
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Execute tx on the mem block state after the deferred txs of the sender,
+    /// a tx which can't be executed, e.g. the sender can't afford the amount or
+    /// fee, isn't deferred. State changes are rolled back.
+    fn check_tx_executable(&self, db: &StoreTransaction, tx: &L2Transaction) -> Result<()> {
+        db.set_save_point();
+        let ret = self.execute_after_deferred_txs(db, tx);
+        db.rollback_to_save_point()?;
+        ret
+    }
 
+    fn execute_after_deferred_txs(&self, db: &StoreTransaction, tx: &L2Transaction) -> Result<()> {
+        let state_db = self.fetch_state_db(db)?;
+        let mut state = state_db.state_tree()?;
+        let tip_block_hash = db.get_tip_block_hash()?;
+        let chain_view = ChainView::new(db, tip_block_hash);
+        let sender_id: u32 = tx.raw().from_id().unpack();
+        let deferred_txs = self
+            .deferred_txs
+            .iter()
+            .filter(|d| d.raw().from_id().unpack() == sender_id);
+        for tx in deferred_txs.chain(std::iter::once(tx)) {
+            let run_result = self.generator.unchecked_execute_transaction(
+                &chain_view,
+                &state,
+                self.mem_block.block_info(),
+                &tx.raw(),
+                self.generator.execution_limits().l2tx_max_cycles,
+            )?;
+            if run_result.exit_code != 0 {
+                return Err(TransactionError::InvalidExitCode(run_result.exit_code).into());
+            }
+            state.apply_run_result(&run_result)?;
+        }
         Ok(())
     }
 
@@ -313,6 +466,7 @@ impl MemPool {
         // Add to pool
         db.insert_mem_pool_withdrawal(&withdrawal_hash, withdrawal)?;
         db.commit()?;
+        self.record_metrics();
         Ok(())
     }
 
@@ -371,7 +525,10 @@ impl MemPool {
         self.mem_block.clear();
         log::warn!("[mem-pool] drop pending: {}", self.pending.len());
         self.pending.clear();
+        // deferred txs aren't applied to the mem block state, re-inject them on reset
+        log::warn!("[mem-pool] keep deferred txs: {}", self.deferred_txs.len());
         log::warn!("[mem-pool] try_to_recovery - done");
+        self.record_metrics();
         Ok(())
     }
 
@@ -435,6 +592,7 @@ impl MemPool {
                 })
             })
             .collect::<Result<_>>()?;
+        let collected_fees = self.collect_mem_block_fees(&db, &mem_block)?;
         let state_checkpoint_list = mem_block.state_checkpoints().to_vec();
         let txs_prev_state_checkpoint = mem_block
             .txs_prev_state_checkpoint()
@@ -459,6 +617,7 @@ impl MemPool {
             post_merkle_state,
             kv_state,
             kv_state_proof,
+            collected_fees,
         };
         let finalized_custodians = mem_block.finalized_custodians().cloned();
 
@@ -472,6 +631,22 @@ impl MemPool {
         Ok((finalized_custodians, param))
     }
 
+    /// Sum up fees paid by mem block txs, sudt id -> amount
+    fn collect_mem_block_fees(
+        &self,
+        db: &StoreTransaction,
+        mem_block: &MemBlock,
+    ) -> Result<BTreeMap<u32, u128>> {
+        let mut receipts = Vec::with_capacity(mem_block.txs().len());
+        for tx_hash in mem_block.txs() {
+            let receipt = db
+                .get_mem_pool_transaction_receipt(tx_hash)?
+                .ok_or_else(|| anyhow!("can't find tx receipt from mem pool"))?;
+            receipts.push(receipt);
+        }
+        Ok(collect_fees(receipts.iter()))
+    }
+
     fn package_mem_block(
         &self,
         output_param: &OutputParam,
@@ -592,12 +767,13 @@ impl MemPool {
         // Repackage txs
         let mut post_tx_merkle_state = None;
         let tx_len = tx_hashes.len();
-        for (idx, tx_hash) in tx_hashes.into_iter().enumerate() {
+        let txs_cost = mem_block.txs_cost().iter();
+        for (idx, (tx_hash, tx_cost)) in tx_hashes.zip(txs_cost).enumerate() {
             let tx_receipt = db
                 .get_mem_pool_transaction_receipt(tx_hash)?
                 .ok_or_else(|| anyhow!("tx {:?} receipt not found", tx_hash))?;
 
-            repackage_block.push_tx(*tx_hash, &tx_receipt);
+            repackage_block.push_tx(*tx_hash, &tx_receipt, *tx_cost);
 
             if idx + 1 == tx_len {
                 post_tx_merkle_state = Some(tx_receipt.post_state())
@@ -738,19 +914,71 @@ impl MemPool {
         // remove from pending
         self.remove_unexecutables(&db)?;
 
-        log::info!("[mem-pool] reset reinject txs: {} mem-block txs: {} reinject withdrawals: {} mem-block withdrawals: {}", reinject_txs.len(), mem_block_txs.len(), reinject_withdrawals.len(), mem_block_withdrawals.len());
+        // deferred txs
+        let deferred_txs = std::mem::take(&mut self.deferred_txs);
+
+        log::info!("[mem-pool] reset reinject txs: {} mem-block txs: {} deferred txs: {} reinject withdrawals: {} mem-block withdrawals: {}", reinject_txs.len(), mem_block_txs.len(), deferred_txs.len(), reinject_withdrawals.len(), mem_block_withdrawals.len());
         // re-inject withdrawals
         let withdrawals_iter = reinject_withdrawals
             .into_iter()
             .chain(mem_block_withdrawals);
         // re-inject txs
-        let txs_iter = reinject_txs.into_iter().chain(mem_block_txs);
+        let txs_iter = reinject_txs
+            .into_iter()
+            .chain(mem_block_txs)
+            .chain(deferred_txs);
         self.prepare_next_mem_block(&db, withdrawals_iter, txs_iter)?;
+        self.persist_deferred_txs(&db)?;
         db.commit()?;
+        self.record_metrics();
 
         Ok(())
     }
 
+    /// Update mem-pool & mem block gauges
+    fn record_metrics(&self) {
+        let (pending_txs, pending_withdrawals) = self
+            .pending
+            .values()
+            .fold((0, 0), |(txs, withdrawals), list| {
+                (txs + list.txs.len(), withdrawals + list.withdrawals.len())
+            });
+        let metrics = &GLOBAL_METRICS;
+        metrics
+            .mem_pool_pending_accounts
+            .set(self.pending.len() as i64);
+        metrics.mem_pool_pending_txs.set(pending_txs as i64);
+        metrics
+            .mem_pool_pending_withdrawals
+            .set(pending_withdrawals as i64);
+        // only export the largest entry lists to bound label cardinality
+        let mut entry_lists: Vec<_> = self.pending.iter().collect();
+        entry_lists.sort_unstable_by_key(|(id, list)| {
+            (
+                std::cmp::Reverse(list.txs.len() + list.withdrawals.len()),
+                **id,
+            )
+        });
+        entry_lists.truncate(MAX_ENTRY_LIST_METRICS);
+        metrics.mem_pool_entry_list_txs.replace(
+            entry_lists
+                .iter()
+                .map(|(id, list)| (id.to_string(), list.txs.len() as i64)),
+        );
+        metrics.mem_pool_entry_list_withdrawals.replace(
+            entry_lists
+                .iter()
+                .map(|(id, list)| (id.to_string(), list.withdrawals.len() as i64)),
+        );
+        metrics.mem_block_txs.set(self.mem_block.txs().len() as i64);
+        metrics
+            .mem_block_withdrawals
+            .set(self.mem_block.withdrawals().len() as i64);
+        metrics
+            .mem_block_deposits
+            .set(self.mem_block.deposits().len() as i64);
+    }
+
     /// Discard unexecutables from pending.
     fn remove_unexecutables(&mut self, db: &StoreTransaction) -> Result<()> {
         let state_db = self.fetch_state_db(db)?;
@@ -783,7 +1011,19 @@ impl MemPool {
         for account_id in remove_list {
             self.pending.remove(&account_id);
         }
-        Ok(())
+
+        // drop deferred txs if tx.nonce lower than nonce
+        let deferred_txs = std::mem::take(&mut self.deferred_txs);
+        for tx in deferred_txs {
+            let sender_id: u32 = tx.raw().from_id().unpack();
+            let tx_nonce: u32 = tx.raw().nonce().unpack();
+            if tx_nonce < state.get_nonce(sender_id)? {
+                db.remove_mem_pool_transaction(&tx.hash().into())?;
+            } else {
+                self.deferred_txs.push_back(tx);
+            }
+        }
+        self.persist_deferred_txs(db)
     }
 
     fn reset_mem_block_state_db(
@@ -833,7 +1073,12 @@ impl MemPool {
         // deposits
         let deposit_cells = {
             let cells = smol::block_on(task)?;
-            crate::deposit::sanitize_deposit_cells(self.generator.rollup_context(), cells)
+            let (cells, invalid_deposits) =
+                sanitize_deposit_cells(self.generator.rollup_context(), cells);
+            self.invalid_deposits = invalid_deposits;
+            self.pending_deposits = cells.clone();
+
+            cells
         };
         self.finalize_deposits(db, deposit_cells)?;
         // re-inject txs
@@ -845,6 +1090,12 @@ impl MemPool {
                     hex::encode(&tx_hash),
                     err
                 );
+                // drop the stored tx, unless it's a duplicate of a pooled one
+                let tx_hash: H256 = tx_hash.into();
+                let is_deferred = self.deferred_txs.iter().any(|d| d.hash() == tx.hash());
+                if !self.mem_block.txs_set().contains(&tx_hash) && !is_deferred {
+                    db.remove_mem_pool_transaction(&tx_hash)?;
+                }
             }
         }
 
@@ -1035,7 +1286,12 @@ impl MemPool {
     }
 
     /// Execute tx & update local state
-    fn finalize_tx(&mut self, db: &StoreTransaction, tx: L2Transaction) -> Result<TxReceipt> {
+    /// Return None if the mem block has no budget for the tx
+    fn finalize_tx(
+        &mut self,
+        db: &StoreTransaction,
+        tx: L2Transaction,
+    ) -> Result<Option<(TxReceipt, TxCost)>> {
         let state_db = self.fetch_state_db(db)?;
         let mut state = state_db.state_tree()?;
         let tip_block_hash = db.get_tip_block_hash()?;
@@ -1050,9 +1306,22 @@ impl MemPool {
             &state,
             block_info,
             &raw_tx,
-            L2TX_MAX_CYCLES,
+            self.generator.execution_limits().l2tx_max_cycles,
         )?;
 
+        // check mem block budget
+        let tx_cost = TxCost::from_run_result(&run_result);
+        if 0 == run_result.exit_code && self.exceeds_mem_block_budget(&tx_cost) {
+            if self.mem_block.txs().is_empty() {
+                return Err(anyhow!(
+                    "tx exceeds mem block budget, cycles: {}, write data bytes: {}",
+                    tx_cost.cycles,
+                    tx_cost.write_data_bytes
+                ));
+            }
+            return Ok(None);
+        }
+
         if let Some(ref mut offchain_validator) = self.offchain_validator {
             let maybe_cycles =
                 offchain_validator.verify_transaction(db, &state_db, tx.clone(), &run_result);
@@ -1076,6 +1345,6 @@ impl MemPool {
         let tx_receipt =
             TxReceipt::build_receipt(tx.witness_hash().into(), run_result, merkle_state);
 
-        Ok(tx_receipt)
+        Ok(Some((tx_receipt, tx_cost)))
     }
 }
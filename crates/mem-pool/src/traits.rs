@@ -3,13 +3,18 @@ use std::time::Duration;
 use anyhow::Result;
 use gw_types::{
     offchain::{CollectedCustodianCells, DepositInfo, RollupContext},
-    packed::WithdrawalRequest,
+    packed::{OutPoint, WithdrawalRequest},
 };
 use smol::Task;
 
 pub trait MemPoolProvider {
     fn estimate_next_blocktime(&self) -> Task<Result<Duration>>;
     fn collect_deposit_cells(&self) -> Task<Result<Vec<DepositInfo>>>;
+    /// return out points of deposits which cancel timeout is passed
+    fn query_cancel_timeout_deposits(
+        &self,
+        deposits: Vec<DepositInfo>,
+    ) -> Task<Result<Vec<OutPoint>>>;
     fn query_available_custodians(
         &self,
         withdrawals: Vec<WithdrawalRequest>,
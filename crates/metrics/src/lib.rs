@@ -0,0 +1,13 @@
+//! Godwoken node metrics
+//!
+//! Metrics are kept in process-wide atomics and rendered in the Prometheus
+//! text exposition format, so every crate can record values without threading
+//! a registry through constructors.
+
+mod metrics;
+
+pub use metrics::*;
+
+lazy_static::lazy_static! {
+    pub static ref GLOBAL_METRICS: NodeMetrics = NodeMetrics::default();
+}
@@ -0,0 +1,514 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Latency buckets in seconds, used by duration histograms
+pub const DURATION_BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+
+/// CKB cycles buckets, used by L1 transaction cycles histograms
+pub const CYCLES_BUCKETS: &[f64] = &[
+    1_000_000.0,
+    2_500_000.0,
+    5_000_000.0,
+    10_000_000.0,
+    15_000_000.0,
+    25_000_000.0,
+    50_000_000.0,
+    70_000_000.0,
+    100_000_000.0,
+];
+
+/// Monotonically increasing counter
+#[derive(Debug, Default)]
+pub struct Counter(AtomicU64);
+
+impl Counter {
+    pub fn inc(&self) {
+        self.inc_by(1);
+    }
+
+    pub fn inc_by(&self, n: u64) {
+        self.0.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Gauge, a value can go up and down
+#[derive(Debug, Default)]
+pub struct Gauge(AtomicI64);
+
+impl Gauge {
+    pub fn set(&self, n: i64) {
+        self.0.store(n, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> i64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Histogram with fixed upper bounds
+#[derive(Debug)]
+pub struct Histogram {
+    bounds: &'static [f64],
+    // non-cumulative bucket counts, the last one is +Inf
+    buckets: Vec<AtomicU64>,
+    count: AtomicU64,
+    // f64 bits
+    sum: AtomicU64,
+}
+
+impl Histogram {
+    pub fn new(bounds: &'static [f64]) -> Self {
+        let buckets = (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect();
+        Histogram {
+            bounds,
+            buckets,
+            count: AtomicU64::new(0),
+            sum: AtomicU64::new(0f64.to_bits()),
+        }
+    }
+
+    pub fn observe(&self, value: f64) {
+        let idx = self
+            .bounds
+            .iter()
+            .position(|bound| value <= *bound)
+            .unwrap_or(self.bounds.len());
+        self.buckets[idx].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        let mut old = self.sum.load(Ordering::Relaxed);
+        loop {
+            let new = (f64::from_bits(old) + value).to_bits();
+            match self
+                .sum
+                .compare_exchange_weak(old, new, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => break,
+                Err(current) => old = current,
+            }
+        }
+    }
+
+    pub fn observe_duration(&self, duration: Duration) {
+        self.observe(duration.as_secs_f64());
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    pub fn sum(&self) -> f64 {
+        f64::from_bits(self.sum.load(Ordering::Relaxed))
+    }
+
+    fn encode(&self, out: &mut String, name: &str, label: Option<(&str, &str)>) {
+        let labels = |le: Option<String>| -> String {
+            let mut pairs = Vec::new();
+            if let Some((k, v)) = label {
+                pairs.push(format!("{}=\"{}\"", k, escape_label_value(v)));
+            }
+            if let Some(le) = le {
+                pairs.push(format!("le=\"{}\"", le));
+            }
+            if pairs.is_empty() {
+                String::new()
+            } else {
+                format!("{{{}}}", pairs.join(","))
+            }
+        };
+
+        let mut cumulative = 0;
+        for (idx, bucket) in self.buckets.iter().enumerate() {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let le = match self.bounds.get(idx) {
+                Some(bound) => bound.to_string(),
+                None => "+Inf".to_string(),
+            };
+            let _ = writeln!(out, "{}_bucket{} {}", name, labels(Some(le)), cumulative);
+        }
+        let _ = writeln!(out, "{}_sum{} {}", name, labels(None), self.sum());
+        let _ = writeln!(out, "{}_count{} {}", name, labels(None), self.count());
+    }
+}
+
+/// Counters partitioned by one label
+#[derive(Debug)]
+pub struct CounterVec {
+    label: &'static str,
+    inner: Mutex<BTreeMap<String, Arc<Counter>>>,
+}
+
+impl CounterVec {
+    pub fn new(label: &'static str) -> Self {
+        CounterVec {
+            label,
+            inner: Default::default(),
+        }
+    }
+
+    pub fn with_label(&self, value: &str) -> Arc<Counter> {
+        let mut inner = self.inner.lock().expect("metrics lock");
+        Arc::clone(inner.entry(value.to_owned()).or_default())
+    }
+}
+
+/// Gauges partitioned by one label, the whole set is replaced on each update
+#[derive(Debug)]
+pub struct GaugeVec {
+    label: &'static str,
+    inner: Mutex<BTreeMap<String, i64>>,
+}
+
+impl GaugeVec {
+    pub fn new(label: &'static str) -> Self {
+        GaugeVec {
+            label,
+            inner: Default::default(),
+        }
+    }
+
+    /// Replace all label values, stale labels are dropped
+    pub fn replace<I: IntoIterator<Item = (String, i64)>>(&self, values: I) {
+        let mut inner = self.inner.lock().expect("metrics lock");
+        *inner = values.into_iter().collect();
+    }
+
+    pub fn get(&self, value: &str) -> Option<i64> {
+        let inner = self.inner.lock().expect("metrics lock");
+        inner.get(value).cloned()
+    }
+}
+
+/// Histograms partitioned by one label
+#[derive(Debug)]
+pub struct HistogramVec {
+    label: &'static str,
+    bounds: &'static [f64],
+    inner: Mutex<BTreeMap<String, Arc<Histogram>>>,
+}
+
+impl HistogramVec {
+    pub fn new(label: &'static str, bounds: &'static [f64]) -> Self {
+        HistogramVec {
+            label,
+            bounds,
+            inner: Default::default(),
+        }
+    }
+
+    pub fn with_label(&self, value: &str) -> Arc<Histogram> {
+        let mut inner = self.inner.lock().expect("metrics lock");
+        let bounds = self.bounds;
+        let histogram = inner
+            .entry(value.to_owned())
+            .or_insert_with(|| Arc::new(Histogram::new(bounds)));
+        Arc::clone(histogram)
+    }
+}
+
+/// All metrics exported by a Godwoken node
+#[derive(Debug)]
+pub struct NodeMetrics {
+    // mem-pool
+    pub mem_pool_pending_accounts: Gauge,
+    pub mem_pool_pending_txs: Gauge,
+    pub mem_pool_pending_withdrawals: Gauge,
+    pub mem_pool_entry_list_txs: GaugeVec,
+    pub mem_pool_entry_list_withdrawals: GaugeVec,
+    pub mem_block_txs: Gauge,
+    pub mem_block_withdrawals: Gauge,
+    pub mem_block_deposits: Gauge,
+    // block producer
+    pub produce_block_duration: Histogram,
+    pub produce_block_retries: Counter,
+    pub produced_blocks: Counter,
+    pub submit_block_cycles: Histogram,
+    pub submit_block_failures: Counter,
+    // sync
+    pub l1_tip_number: Gauge,
+    pub l1_synced_number: Gauge,
+    pub l1_sync_lag: Gauge,
+    pub l2_tip_number: Gauge,
+    // rpc
+    pub rpc_requests: CounterVec,
+    pub rpc_duration: HistogramVec,
+    // challenge
+    pub challenge_events: CounterVec,
+}
+
+impl Default for NodeMetrics {
+    fn default() -> Self {
+        NodeMetrics {
+            mem_pool_pending_accounts: Gauge::default(),
+            mem_pool_pending_txs: Gauge::default(),
+            mem_pool_pending_withdrawals: Gauge::default(),
+            mem_pool_entry_list_txs: GaugeVec::new("account_id"),
+            mem_pool_entry_list_withdrawals: GaugeVec::new("account_id"),
+            mem_block_txs: Gauge::default(),
+            mem_block_withdrawals: Gauge::default(),
+            mem_block_deposits: Gauge::default(),
+            produce_block_duration: Histogram::new(DURATION_BUCKETS),
+            produce_block_retries: Counter::default(),
+            produced_blocks: Counter::default(),
+            submit_block_cycles: Histogram::new(CYCLES_BUCKETS),
+            submit_block_failures: Counter::default(),
+            l1_tip_number: Gauge::default(),
+            l1_synced_number: Gauge::default(),
+            l1_sync_lag: Gauge::default(),
+            l2_tip_number: Gauge::default(),
+            rpc_requests: CounterVec::new("method"),
+            rpc_duration: HistogramVec::new("method", DURATION_BUCKETS),
+            challenge_events: CounterVec::new("event"),
+        }
+    }
+}
+
+impl NodeMetrics {
+    /// Render all metrics in the Prometheus text exposition format
+    pub fn encode(&self) -> String {
+        let mut out = String::new();
+
+        let gauges: &[(&str, &str, &Gauge)] = &[
+            (
+                "gw_mem_pool_pending_accounts",
+                "Accounts which have pending entries in the mem-pool",
+                &self.mem_pool_pending_accounts,
+            ),
+            (
+                "gw_mem_pool_pending_txs",
+                "Pending txs of all mem-pool entry lists",
+                &self.mem_pool_pending_txs,
+            ),
+            (
+                "gw_mem_pool_pending_withdrawals",
+                "Pending withdrawals of all mem-pool entry lists",
+                &self.mem_pool_pending_withdrawals,
+            ),
+            (
+                "gw_mem_block_txs",
+                "Txs in the mem block",
+                &self.mem_block_txs,
+            ),
+            (
+                "gw_mem_block_withdrawals",
+                "Withdrawals in the mem block",
+                &self.mem_block_withdrawals,
+            ),
+            (
+                "gw_mem_block_deposits",
+                "Deposits in the mem block",
+                &self.mem_block_deposits,
+            ),
+            (
+                "gw_l1_tip_number",
+                "Layer1 tip block number seen by the node",
+                &self.l1_tip_number,
+            ),
+            (
+                "gw_l1_synced_number",
+                "Layer1 block number of the last synced rollup action",
+                &self.l1_synced_number,
+            ),
+            (
+                "gw_l1_sync_lag",
+                "Layer1 blocks between the tip and the last synced rollup action",
+                &self.l1_sync_lag,
+            ),
+            (
+                "gw_l2_tip_number",
+                "Local layer2 tip block number",
+                &self.l2_tip_number,
+            ),
+        ];
+        for (name, help, gauge) in gauges {
+            encode_header(&mut out, name, help, "gauge");
+            let _ = writeln!(out, "{} {}", name, gauge.get());
+        }
+
+        let gauge_vecs: &[(&str, &str, &GaugeVec)] = &[
+            (
+                "gw_mem_pool_entry_list_txs",
+                "Pending txs of the largest mem-pool entry lists by account",
+                &self.mem_pool_entry_list_txs,
+            ),
+            (
+                "gw_mem_pool_entry_list_withdrawals",
+                "Pending withdrawals of the largest mem-pool entry lists by account",
+                &self.mem_pool_entry_list_withdrawals,
+            ),
+        ];
+        for (name, help, gauge_vec) in gauge_vecs {
+            encode_header(&mut out, name, help, "gauge");
+            let inner = gauge_vec.inner.lock().expect("metrics lock");
+            for (value, gauge) in inner.iter() {
+                let _ = writeln!(
+                    out,
+                    "{}{{{}=\"{}\"}} {}",
+                    name,
+                    gauge_vec.label,
+                    escape_label_value(value),
+                    gauge
+                );
+            }
+        }
+
+        let counters: &[(&str, &str, &Counter)] = &[
+            (
+                "gw_produce_block_retries_total",
+                "Retries of packaging a block within the max block bytes",
+                &self.produce_block_retries,
+            ),
+            (
+                "gw_produced_blocks_total",
+                "Blocks produced by this node",
+                &self.produced_blocks,
+            ),
+            (
+                "gw_submit_block_failures_total",
+                "Failed submissions of block txs to layer1",
+                &self.submit_block_failures,
+            ),
+        ];
+        for (name, help, counter) in counters {
+            encode_header(&mut out, name, help, "counter");
+            let _ = writeln!(out, "{} {}", name, counter.get());
+        }
+
+        let histograms: &[(&str, &str, &Histogram)] = &[
+            (
+                "gw_produce_block_duration_seconds",
+                "Time spent in produce_next_block",
+                &self.produce_block_duration,
+            ),
+            (
+                "gw_submit_block_cycles",
+                "Dry run cycles of submitting block txs",
+                &self.submit_block_cycles,
+            ),
+        ];
+        for (name, help, histogram) in histograms {
+            encode_header(&mut out, name, help, "histogram");
+            histogram.encode(&mut out, name, None);
+        }
+
+        let counter_vecs: &[(&str, &str, &CounterVec)] = &[
+            (
+                "gw_rpc_requests_total",
+                "RPC requests by method",
+                &self.rpc_requests,
+            ),
+            (
+                "gw_challenge_events_total",
+                "Challenge related events by kind",
+                &self.challenge_events,
+            ),
+        ];
+        for (name, help, counter_vec) in counter_vecs {
+            encode_header(&mut out, name, help, "counter");
+            let inner = counter_vec.inner.lock().expect("metrics lock");
+            for (value, counter) in inner.iter() {
+                let _ = writeln!(
+                    out,
+                    "{}{{{}=\"{}\"}} {}",
+                    name,
+                    counter_vec.label,
+                    escape_label_value(value),
+                    counter.get()
+                );
+            }
+        }
+
+        let name = "gw_rpc_duration_seconds";
+        encode_header(&mut out, name, "RPC latencies by method", "histogram");
+        let inner = self.rpc_duration.inner.lock().expect("metrics lock");
+        for (value, histogram) in inner.iter() {
+            histogram.encode(&mut out, name, Some((self.rpc_duration.label, value)));
+        }
+
+        out
+    }
+}
+
+// Escape a label value, see https://prometheus.io/docs/instrumenting/exposition_formats/
+fn escape_label_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn encode_header(out: &mut String, name: &str, help: &str, type_: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, type_);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let metrics = NodeMetrics::default();
+        metrics.mem_block_txs.set(3);
+        metrics.produce_block_duration.observe(0.2);
+        metrics.rpc_requests.with_label("gw_ping").inc();
+        metrics
+            .rpc_duration
+            .with_label("gw_ping")
+            .observe_duration(Duration::from_millis(2));
+
+        let text = metrics.encode();
+        assert!(text.contains("gw_mem_block_txs 3\n"));
+        assert!(text.contains("gw_produce_block_duration_seconds_bucket{le=\"0.1\"} 0\n"));
+        assert!(text.contains("gw_produce_block_duration_seconds_bucket{le=\"0.25\"} 1\n"));
+        assert!(text.contains("gw_produce_block_duration_seconds_bucket{le=\"+Inf\"} 1\n"));
+        assert!(text.contains("gw_produce_block_duration_seconds_count 1\n"));
+        assert!(text.contains("gw_rpc_requests_total{method=\"gw_ping\"} 1\n"));
+        assert!(
+            text.contains("gw_rpc_duration_seconds_bucket{method=\"gw_ping\",le=\"0.005\"} 1\n")
+        );
+    }
+
+    #[test]
+    fn test_encode_escape_label_value() {
+        let metrics = NodeMetrics::default();
+        metrics.challenge_events.with_label("a\"b\\c\nd").inc();
+        metrics
+            .rpc_duration
+            .with_label("x\"")
+            .observe_duration(Duration::from_millis(2));
+
+        let text = metrics.encode();
+        assert!(text.contains("gw_challenge_events_total{event=\"a\\\"b\\\\c\\nd\"} 1\n"));
+        assert!(text.contains("gw_rpc_duration_seconds_count{method=\"x\\\"\"} 1\n"));
+    }
+
+    #[test]
+    fn test_encode_gauge_vec() {
+        let metrics = NodeMetrics::default();
+        metrics
+            .mem_pool_entry_list_txs
+            .replace(vec![("1".to_string(), 3), ("2".to_string(), 1)]);
+        metrics
+            .mem_pool_entry_list_txs
+            .replace(vec![("2".to_string(), 2)]);
+
+        let text = metrics.encode();
+        assert!(!text.contains("gw_mem_pool_entry_list_txs{account_id=\"1\"}"));
+        assert!(text.contains("gw_mem_pool_entry_list_txs{account_id=\"2\"} 2\n"));
+    }
+}
@@ -8,7 +8,9 @@ use async_jsonrpc_client::{HttpClient, Params as ClientParams, Transport};
 use ckb_types::core::hardfork::HardForkSwitch;
 use ckb_types::prelude::Entity;
 use gw_common::{CKB_SUDT_SCRIPT_ARGS, H256};
-use gw_jsonrpc_types::ckb_jsonrpc_types::{self, BlockNumber, Consensus, Uint32};
+use gw_jsonrpc_types::ckb_jsonrpc_types::{
+    self, BlockNumber, Consensus, JsonBytes, Uint32, Uint64,
+};
 use gw_types::offchain::{
     CollectedCustodianCells, DepositInfo, RollupContext, TxStatus, WithdrawalsAmount,
 };
@@ -25,7 +27,11 @@ use gw_types::{
 };
 use serde_json::json;
 
-use std::{collections::HashSet, time::Duration};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
 
 fn to_cell_info(cell: Cell) -> CellInfo {
     let out_point = {
@@ -386,9 +392,57 @@ impl RPCClient {
         }))
     }
 
-    /// return all lived deposit requests
-    /// NOTICE the returned cells may contains invalid cells.
-    pub async fn query_deposit_cells(&self, count: usize) -> Result<Vec<DepositInfo>> {
+    /// Return average fee rate (shannons per KB) of transactions committed in block,
+    /// none if the block has no transactions except cellbase or isn't finalized yet
+    pub async fn get_block_fee_rate(&self, number: u64) -> Result<Option<u64>> {
+        let block_number = BlockNumber::from(number);
+        let block_opt: Option<ckb_jsonrpc_types::BlockView> = to_result(
+            self.ckb
+                .request(
+                    "get_block_by_number",
+                    Some(ClientParams::Array(vec![json!(block_number)])),
+                )
+                .await?,
+        )?;
+        let block: ckb_types::core::BlockView = match block_opt {
+            Some(block) => block.into(),
+            None => return Ok(None),
+        };
+
+        // skip cellbase
+        let txs_size: u64 = block
+            .transactions()
+            .iter()
+            .skip(1)
+            .map(|tx| tx.data().serialized_size_in_block() as u64)
+            .sum();
+        if 0 == txs_size {
+            return Ok(None);
+        }
+
+        let block_hash: ckb_types::H256 = ckb_types::prelude::Unpack::unpack(&block.hash());
+        let economic_state: Option<ckb_jsonrpc_types::BlockEconomicState> = to_result(
+            self.ckb
+                .request(
+                    "get_block_economic_state",
+                    Some(ClientParams::Array(vec![json!(block_hash)])),
+                )
+                .await?,
+        )?;
+        Ok(economic_state.map(|state| {
+            let txs_fee: u64 = state.txs_fee.into();
+            txs_fee.saturating_mul(1000) / txs_size
+        }))
+    }
+
+    /// Query at most `count` live deposit cells committed in recent L1 blocks, in ascending
+    /// order of L1 block number. Cells with capacity less than `min_capacity` are skipped.
+    /// NOTICE the returned cells may contain invalid deposit requests.
+    pub async fn query_deposit_cells(
+        &self,
+        count: usize,
+        min_capacity: u64,
+    ) -> Result<Vec<DepositInfo>> {
         const BLOCKS_TO_SEARCH: u64 = 100;
 
         let tip_number = self.get_tip().await?.number().unpack();
@@ -418,7 +472,10 @@ impl RPCClient {
             filter: Some(SearchKeyFilter {
                 script: None,
                 output_data_len_range: None,
-                output_capacity_range: None,
+                output_capacity_range: Some([
+                    Uint64::from(min_capacity),
+                    Uint64::from(u64::max_value()),
+                ]),
                 block_range: Some([
                     BlockNumber::from(tip_number.saturating_sub(BLOCKS_TO_SEARCH)),
                     BlockNumber::from(u64::max_value()),
@@ -452,14 +509,15 @@ impl RPCClient {
                 CellOutput::new_unchecked(output.as_bytes())
             };
             let data = cell.output_data.into_bytes();
-            CellInfo {
+            let cell_info = CellInfo {
                 out_point,
                 output,
                 data,
-            }
+            };
+            (cell_info, cell.block_number.value())
         });
 
-        for cell in cells {
+        for (cell, block_number) in cells {
             let args: Bytes = cell.output.lock().args().unpack();
             let deposit_lock_args = match DepositLockArgsReader::verify(&args[32..], false) {
                 Ok(()) => DepositLockArgs::new_unchecked(args.slice(32..)),
@@ -477,7 +535,11 @@ impl RPCClient {
                 }
             };
 
-            let info = DepositInfo { cell, request };
+            let info = DepositInfo {
+                cell,
+                request,
+                block_number,
+            };
             deposit_infos.push(info);
         }
 
@@ -581,25 +643,7 @@ impl RPCClient {
         &self,
         owner_lock_hashes: impl Iterator<Item = [u8; 32]>,
     ) -> Result<Vec<CellInfo>> {
-        let lock = Script::new_builder()
-            .code_hash(self.rollup_context.rollup_config.stake_script_type_hash())
-            .hash_type(ScriptHashType::Type.into())
-            .args(self.rollup_context.rollup_script_hash.as_slice().pack())
-            .build();
-
-        let search_key = SearchKey {
-            script: {
-                let lock = ckb_types::packed::Script::new_unchecked(lock.as_bytes());
-                lock.into()
-            },
-            script_type: ScriptType::Lock,
-            filter: Some(SearchKeyFilter {
-                script: None,
-                output_data_len_range: None,
-                output_capacity_range: None,
-                block_range: None,
-            }),
-        };
+        let search_key = self.stake_cells_search_key();
         let order = Order::Desc;
         let limit = Uint32::from(DEFAULT_QUERY_LIMIT as u32);
 
@@ -649,31 +693,104 @@ impl RPCClient {
         Ok(collected_cells)
     }
 
-    pub async fn query_custodian_cells_by_block_hashes(
+    /// Query all stake cells of an owner, unlike `query_stake_cells_by_owner_lock_hashes`,
+    /// which only returns one cell for each owner.
+    pub async fn query_stake_cells_by_owner_lock_hash(
         &self,
-        block_hashes: &HashSet<H256>,
-    ) -> Result<(Vec<CellInfo>, HashSet<H256>)> {
-        let rollup_context = &self.rollup_context;
+        owner_lock_hash: [u8; 32],
+    ) -> Result<Vec<CellInfo>> {
+        let search_key = self.stake_cells_search_key();
+        let order = Order::Desc;
+        let limit = Uint32::from(DEFAULT_QUERY_LIMIT as u32);
 
-        let custodian_lock = Script::new_builder()
-            .code_hash(rollup_context.rollup_config.custodian_script_type_hash())
+        let mut collected_cells = Vec::new();
+        let mut cursor = None;
+        loop {
+            let cells: Pagination<Cell> = to_result(
+                self.indexer
+                    .client()
+                    .request(
+                        "get_cells",
+                        Some(ClientParams::Array(vec![
+                            json!(search_key),
+                            json!(order),
+                            json!(limit),
+                            json!(cursor),
+                        ])),
+                    )
+                    .await?,
+            )?;
+
+            if cells.last_cursor.is_empty() {
+                return Ok(collected_cells);
+            }
+            cursor = Some(cells.last_cursor);
+
+            for cell in cells.objects.into_iter() {
+                let args = cell.output.lock.args.clone().into_bytes();
+                if args.len() < 32 {
+                    continue;
+                }
+                let stake_lock_args = match StakeLockArgsReader::verify(&args[32..], false) {
+                    Ok(()) => StakeLockArgs::new_unchecked(args.slice(32..)),
+                    Err(_) => continue,
+                };
+
+                let cell_owner_lock_hash: [u8; 32] = stake_lock_args.owner_lock_hash().unpack();
+                if cell_owner_lock_hash == owner_lock_hash {
+                    collected_cells.push(to_cell_info(cell));
+                }
+            }
+        }
+    }
+
+    fn stake_cells_search_key(&self) -> SearchKey {
+        let lock = Script::new_builder()
+            .code_hash(self.rollup_context.rollup_config.stake_script_type_hash())
             .hash_type(ScriptHashType::Type.into())
-            .args(rollup_context.rollup_script_hash.as_slice().pack())
+            .args(self.rollup_context.rollup_script_hash.as_slice().pack())
             .build();
 
+        SearchKey {
+            script: {
+                let lock = ckb_types::packed::Script::new_unchecked(lock.as_bytes());
+                lock.into()
+            },
+            script_type: ScriptType::Lock,
+            filter: Some(SearchKeyFilter {
+                script: None,
+                output_data_len_range: None,
+                output_capacity_range: None,
+                block_range: None,
+            }),
+        }
+    }
+
+    /// query sudt cells of lock, the returned cells should provide at least required_amount
+    pub async fn query_sudt_cells(
+        &self,
+        lock: Script,
+        sudt_type: Script,
+        required_amount: u128,
+        taken_outpoints: &HashSet<OutPoint>,
+    ) -> Result<Vec<CellInfo>> {
         let search_key = SearchKey {
-            script: ckb_types::packed::Script::new_unchecked(custodian_lock.as_bytes()).into(),
+            script: ckb_types::packed::Script::new_unchecked(lock.as_bytes()).into(),
             script_type: ScriptType::Lock,
-            filter: None,
+            filter: Some(SearchKeyFilter {
+                script: Some(ckb_types::packed::Script::new_unchecked(sudt_type.as_bytes()).into()),
+                output_data_len_range: None,
+                output_capacity_range: None,
+                block_range: None,
+            }),
         };
         let order = Order::Desc;
         let limit = Uint32::from(DEFAULT_QUERY_LIMIT as u32);
 
-        let mut collected = vec![];
-        let mut collected_block_hashes = HashSet::new();
+        let mut collected_cells = Vec::new();
+        let mut collected_amount = 0u128;
         let mut cursor = None;
-
-        while collected.is_empty() {
+        while collected_amount < required_amount {
             let cells: Pagination<Cell> = to_result(
                 self.indexer
                     .client()
@@ -690,49 +807,34 @@ impl RPCClient {
             )?;
 
             if cells.last_cursor.is_empty() {
-                return Ok((collected, collected_block_hashes));
+                return Err(anyhow!("no enough sudt cells"));
             }
             cursor = Some(cells.last_cursor);
 
-            for cell in cells.objects.into_iter() {
-                let args = cell.output.lock.args.clone().into_bytes();
-                let custodian_lock_args = match CustodianLockArgsReader::verify(&args[32..], false)
-                {
-                    Ok(()) => CustodianLockArgs::new_unchecked(args.slice(32..)),
-                    Err(_) => continue,
-                };
-
-                let deposit_block_hash: H256 = custodian_lock_args.deposit_block_hash().unpack();
-                if !block_hashes.contains(&deposit_block_hash) {
+            for cell in cells.objects.into_iter().map(to_cell_info) {
+                if taken_outpoints.contains(&cell.out_point) || cell.data.len() < 16 {
                     continue;
                 }
 
-                collected.push(to_cell_info(cell));
-                collected_block_hashes.insert(deposit_block_hash);
+                let mut buf = [0u8; 16];
+                buf.copy_from_slice(&cell.data[..16]);
+                collected_amount = collected_amount.saturating_add(u128::from_le_bytes(buf));
+                collected_cells.push(cell);
+                if collected_amount >= required_amount {
+                    break;
+                }
             }
         }
 
-        Ok((collected, collected_block_hashes))
+        Ok(collected_cells)
     }
 
-    pub async fn query_finalized_custodian_cells(
+    pub async fn query_custodian_cells_by_block_hashes(
         &self,
-        withdrawals_amount: &WithdrawalsAmount,
-        custodian_change_capacity: u128,
-        last_finalized_block_number: u64,
-    ) -> Result<QueryResult<CollectedCustodianCells>> {
+        block_hashes: &HashSet<H256>,
+    ) -> Result<(Vec<CellInfo>, HashSet<H256>)> {
         let rollup_context = &self.rollup_context;
 
-        let parse_sudt_amount = |cell: &Cell| -> Result<u128> {
-            if cell.output.type_.is_none() {
-                return Err(anyhow!("no a sudt cell"));
-            }
-
-            gw_types::packed::Uint128::from_slice(cell.output_data.as_bytes())
-                .map(|a| a.unpack())
-                .map_err(|e| anyhow!("invalid sudt amount {}", e))
-        };
-
         let custodian_lock = Script::new_builder()
             .code_hash(rollup_context.rollup_config.custodian_script_type_hash())
             .hash_type(ScriptHashType::Type.into())
@@ -747,19 +849,11 @@ impl RPCClient {
         let order = Order::Desc;
         let limit = Uint32::from(DEFAULT_QUERY_LIMIT as u32);
 
-        let mut collected = CollectedCustodianCells::default();
-        let mut collected_fullfilled_sudt = HashSet::new();
+        let mut collected = vec![];
+        let mut collected_block_hashes = HashSet::new();
         let mut cursor = None;
 
-        // withdrawal ckb + change custodian capacity
-        let required_capacity = {
-            let withdrawal_capacity = withdrawals_amount.capacity;
-            withdrawal_capacity.saturating_add(custodian_change_capacity)
-        };
-
-        while collected.capacity < required_capacity
-            || collected_fullfilled_sudt.len() < withdrawals_amount.sudt.len()
-        {
+        while collected.is_empty() {
             let cells: Pagination<Cell> = to_result(
                 self.indexer
                     .client()
@@ -776,7 +870,7 @@ impl RPCClient {
             )?;
 
             if cells.last_cursor.is_empty() {
-                return Ok(QueryResult::NotEnough(collected));
+                return Ok((collected, collected_block_hashes));
             }
             cursor = Some(cells.last_cursor);
 
@@ -788,87 +882,182 @@ impl RPCClient {
                     Err(_) => continue,
                 };
 
-                if custodian_lock_args.deposit_block_number().unpack() > last_finalized_block_number
-                {
+                let deposit_block_hash: H256 = custodian_lock_args.deposit_block_hash().unpack();
+                if !block_hashes.contains(&deposit_block_hash) {
                     continue;
                 }
 
-                // Collect sudt
-                if let Some(json_script) = cell.output.type_.clone() {
-                    let sudt_type_script = {
-                        let script = ckb_types::packed::Script::from(json_script);
-                        Script::new_unchecked(script.as_bytes())
+                collected.push(to_cell_info(cell));
+                collected_block_hashes.insert(deposit_block_hash);
+            }
+        }
+
+        Ok((collected, collected_block_hashes))
+    }
+
+    pub async fn query_finalized_custodian_cells(
+        &self,
+        withdrawals_amount: &WithdrawalsAmount,
+        custodian_change_capacity: u128,
+        last_finalized_block_number: u64,
+    ) -> Result<QueryResult<CollectedCustodianCells>> {
+        // withdrawal ckb + change custodian capacity
+        let required_capacity = {
+            let withdrawal_capacity = withdrawals_amount.capacity;
+            withdrawal_capacity.saturating_add(custodian_change_capacity)
+        };
+
+        // Collect candidates until they can cover withdrawals, then select large cells
+        // from them, so that less custodian inputs are packed into submit block tx.
+        let mut candidates = FinalizedCustodianCandidates::default();
+        let mut cursor = None;
+        while !candidates.is_enough(withdrawals_amount, required_capacity) {
+            let cells = self.query_custodian_cells_page(cursor.take()).await?;
+            if cells.last_cursor.is_empty() {
+                let collected = candidates.select(withdrawals_amount, required_capacity);
+                return Ok(QueryResult::NotEnough(collected));
+            }
+            cursor = Some(cells.last_cursor);
+
+            for cell in cells.objects.into_iter() {
+                let custodian =
+                    match self.parse_finalized_custodian(cell, last_finalized_block_number) {
+                        Some(custodian) => custodian,
+                        None => continue,
                     };
 
-                    // Invalid custodian type script
-                    let l1_sudt_script_type_hash =
-                        rollup_context.rollup_config.l1_sudt_script_type_hash();
-                    if sudt_type_script.code_hash() != l1_sudt_script_type_hash
-                        || sudt_type_script.hash_type() != ScriptHashType::Type.into()
-                    {
+                // Not target withdrawal sudt
+                if let Some((ref sudt_type_hash, _, _)) = custodian.sudt {
+                    if !withdrawals_amount.sudt.contains_key(sudt_type_hash) {
                         continue;
                     }
+                }
 
-                    let sudt_type_hash = sudt_type_script.hash();
-                    if sudt_type_hash != CKB_SUDT_SCRIPT_ARGS {
-                        // Already collected enough sudt amount
-                        if collected_fullfilled_sudt.contains(&sudt_type_hash) {
-                            continue;
-                        }
+                candidates.push(custodian);
+            }
+        }
 
-                        // Not target withdrawal sudt
-                        let withdrawal_amount = match withdrawals_amount.sudt.get(&sudt_type_hash) {
-                            Some(amount) => amount,
-                            None => continue,
-                        };
-
-                        let sudt_amount = match parse_sudt_amount(&cell) {
-                            Ok(amount) => amount,
-                            Err(_) => {
-                                log::error!("invalid sudt amount, out_point: {:?}", cell.out_point);
-                                continue;
-                            }
-                        };
-
-                        let (collected_amount, type_script) = {
-                            let sudt = collected.sudt.entry(sudt_type_hash);
-                            sudt.or_insert((0, Script::default()))
-                        };
-                        *collected_amount = collected_amount.saturating_add(sudt_amount);
-                        *type_script = sudt_type_script;
-
-                        if *collected_amount >= *withdrawal_amount {
-                            collected_fullfilled_sudt.insert(sudt_type_hash);
-                        }
-                    }
+        let collected = candidates.select(withdrawals_amount, required_capacity);
+        Ok(QueryResult::Full(collected))
+    }
+
+    /// Query fragmented finalized custodian cells to merge, small cells are selected first.
+    pub async fn query_mergeable_custodian_cells(
+        &self,
+        max_cells: usize,
+        last_finalized_block_number: u64,
+    ) -> Result<CollectedCustodianCells> {
+        let mut candidates = FinalizedCustodianCandidates::default();
+        let mut cursor = None;
+        loop {
+            let cells = self.query_custodian_cells_page(cursor.take()).await?;
+            if cells.last_cursor.is_empty() {
+                return Ok(candidates.select_mergeable(max_cells));
+            }
+            cursor = Some(cells.last_cursor);
+
+            for cell in cells.objects.into_iter() {
+                if let Some(custodian) =
+                    self.parse_finalized_custodian(cell, last_finalized_block_number)
+                {
+                    candidates.push(custodian);
                 }
+            }
+        }
+    }
 
-                // Collect capacity
-                let out_point = {
-                    let out_point: ckb_types::packed::OutPoint = cell.out_point.into();
-                    OutPoint::new_unchecked(out_point.as_bytes())
-                };
+    async fn query_custodian_cells_page(
+        &self,
+        cursor: Option<JsonBytes>,
+    ) -> Result<Pagination<Cell>> {
+        let rollup_context = &self.rollup_context;
 
-                let output = {
-                    let output: ckb_types::packed::CellOutput = cell.output.into();
-                    CellOutput::new_unchecked(output.as_bytes())
-                };
+        let custodian_lock = Script::new_builder()
+            .code_hash(rollup_context.rollup_config.custodian_script_type_hash())
+            .hash_type(ScriptHashType::Type.into())
+            .args(rollup_context.rollup_script_hash.as_slice().pack())
+            .build();
+
+        let search_key = SearchKey {
+            script: ckb_types::packed::Script::new_unchecked(custodian_lock.as_bytes()).into(),
+            script_type: ScriptType::Lock,
+            filter: None,
+        };
+        let order = Order::Desc;
+        let limit = Uint32::from(DEFAULT_QUERY_LIMIT as u32);
 
-                collected.capacity = collected
-                    .capacity
-                    .saturating_add(output.capacity().unpack() as u128);
+        to_result(
+            self.indexer
+                .client()
+                .request(
+                    "get_cells",
+                    Some(ClientParams::Array(vec![
+                        json!(search_key),
+                        json!(order),
+                        json!(limit),
+                        json!(cursor),
+                    ])),
+                )
+                .await?,
+        )
+    }
 
-                let info = CellInfo {
-                    out_point,
-                    output,
-                    data: cell.output_data.into_bytes(),
-                };
+    fn parse_finalized_custodian(
+        &self,
+        cell: Cell,
+        last_finalized_block_number: u64,
+    ) -> Option<FinalizedCustodian> {
+        let args = cell.output.lock.args.clone().into_bytes();
+        let custodian_lock_args = match CustodianLockArgsReader::verify(&args[32..], false) {
+            Ok(()) => CustodianLockArgs::new_unchecked(args.slice(32..)),
+            Err(_) => return None,
+        };
+
+        if custodian_lock_args.deposit_block_number().unpack() > last_finalized_block_number {
+            return None;
+        }
+
+        let mut sudt = None;
+        if let Some(json_script) = cell.output.type_.clone() {
+            let sudt_type_script = {
+                let script = ckb_types::packed::Script::from(json_script);
+                Script::new_unchecked(script.as_bytes())
+            };
 
-                collected.cells_info.push(info);
+            // Invalid custodian type script
+            let l1_sudt_script_type_hash =
+                self.rollup_context.rollup_config.l1_sudt_script_type_hash();
+            if sudt_type_script.code_hash() != l1_sudt_script_type_hash
+                || sudt_type_script.hash_type() != ScriptHashType::Type.into()
+            {
+                return None;
+            }
+
+            let sudt_type_hash = sudt_type_script.hash();
+            if sudt_type_hash != CKB_SUDT_SCRIPT_ARGS {
+                let sudt_amount =
+                    match gw_types::packed::Uint128::from_slice(cell.output_data.as_bytes()) {
+                        Ok(amount) => amount.unpack(),
+                        Err(err) => {
+                            log::error!(
+                                "invalid sudt amount {}, out_point: {:?}",
+                                err,
+                                cell.out_point
+                            );
+                            return None;
+                        }
+                    };
+                sudt = Some((sudt_type_hash, sudt_amount, sudt_type_script));
             }
         }
 
-        Ok(QueryResult::Full(collected))
+        let info = to_cell_info(cell);
+        let capacity = info.output.capacity().unpack();
+        Some(FinalizedCustodian {
+            info,
+            capacity,
+            sudt,
+        })
     }
 
     pub async fn query_verified_custodian_type_script(
@@ -1261,3 +1450,300 @@ impl RPCClient {
         Ok(epoch_number)
     }
 }
+
+struct FinalizedCustodian {
+    info: CellInfo,
+    capacity: u64,
+    // (sudt type hash, amount, type script)
+    sudt: Option<([u8; 32], u128, Script)>,
+}
+
+impl FinalizedCustodian {
+    fn sudt_amount(&self) -> u128 {
+        self.sudt
+            .as_ref()
+            .map(|(_, amount, _)| *amount)
+            .unwrap_or(0)
+    }
+
+    fn collect_into(self, collected: &mut CollectedCustodianCells) {
+        if let Some((sudt_type_hash, amount, type_script)) = self.sudt {
+            let (collected_amount, script) = collected
+                .sudt
+                .entry(sudt_type_hash)
+                .or_insert((0, Script::default()));
+            *collected_amount = collected_amount.saturating_add(amount);
+            *script = type_script;
+        }
+
+        collected.capacity = collected.capacity.saturating_add(self.capacity as u128);
+        collected.cells_info.push(self.info);
+    }
+}
+
+#[derive(Default)]
+struct FinalizedCustodianCandidates {
+    capacity: u128,
+    sudt: HashMap<[u8; 32], u128>,
+    cells: Vec<FinalizedCustodian>,
+}
+
+impl FinalizedCustodianCandidates {
+    fn push(&mut self, custodian: FinalizedCustodian) {
+        self.capacity = self.capacity.saturating_add(custodian.capacity as u128);
+        if let Some((sudt_type_hash, amount, _)) = custodian.sudt {
+            let total_amount = self.sudt.entry(sudt_type_hash).or_insert(0);
+            *total_amount = total_amount.saturating_add(amount);
+        }
+        self.cells.push(custodian);
+    }
+
+    fn is_enough(&self, withdrawals_amount: &WithdrawalsAmount, required_capacity: u128) -> bool {
+        let is_sudt_enough = |(sudt_type_hash, amount): (&[u8; 32], &u128)| -> bool {
+            let total_amount = self.sudt.get(sudt_type_hash).cloned().unwrap_or(0);
+            total_amount >= *amount
+        };
+
+        self.capacity >= required_capacity && withdrawals_amount.sudt.iter().all(is_sudt_enough)
+    }
+
+    // Large cells first, sudt cells are selected by amount, ckb cells are selected by capacity
+    fn select(
+        self,
+        withdrawals_amount: &WithdrawalsAmount,
+        required_capacity: u128,
+    ) -> CollectedCustodianCells {
+        let mut collected = CollectedCustodianCells::default();
+        let (mut sudt_cells, mut ckb_cells): (Vec<_>, Vec<_>) =
+            self.cells.into_iter().partition(|c| c.sudt.is_some());
+
+        sudt_cells.sort_by_key(|c| Reverse(c.sudt_amount()));
+        let mut unselected_sudt_cells = vec![];
+        for custodian in sudt_cells {
+            let sudt_type_hash = match custodian.sudt {
+                Some((ref sudt_type_hash, _, _)) => sudt_type_hash,
+                None => continue,
+            };
+            let required_amount = withdrawals_amount.sudt.get(sudt_type_hash);
+            let required_amount = required_amount.cloned().unwrap_or(0);
+            let collected_amount = collected.sudt.get(sudt_type_hash);
+            let collected_amount = collected_amount.map(|(amount, _)| *amount).unwrap_or(0);
+            if collected_amount >= required_amount {
+                unselected_sudt_cells.push(custodian);
+                continue;
+            }
+            custodian.collect_into(&mut collected);
+        }
+
+        // Fallback to unselected sudt cells if there isn't enough ckb cells
+        ckb_cells.sort_by_key(|c| Reverse(c.capacity));
+        unselected_sudt_cells.sort_by_key(|c| Reverse(c.capacity));
+        for custodian in ckb_cells.into_iter().chain(unselected_sudt_cells) {
+            if collected.capacity >= required_capacity {
+                break;
+            }
+            custodian.collect_into(&mut collected);
+        }
+
+        collected
+    }
+
+    // Small cells first, sudt cells are only selected if there are at least two cells
+    // of the same sudt.
+    fn select_mergeable(self, max_cells: usize) -> CollectedCustodianCells {
+        let mut ckb_cells = vec![];
+        let mut sudt_cells: HashMap<[u8; 32], Vec<FinalizedCustodian>> = HashMap::new();
+        for custodian in self.cells {
+            match custodian.sudt {
+                Some((sudt_type_hash, _, _)) => sudt_cells
+                    .entry(sudt_type_hash)
+                    .or_default()
+                    .push(custodian),
+                None => ckb_cells.push(custodian),
+            }
+        }
+
+        let mut collected = CollectedCustodianCells::default();
+        for mut cells in sudt_cells.into_iter().map(|(_, cells)| cells) {
+            let remaind = max_cells.saturating_sub(collected.cells_info.len());
+            if cells.len() < 2 || remaind < 2 {
+                continue;
+            }
+
+            cells.sort_by_key(|c| c.sudt_amount());
+            for custodian in cells.into_iter().take(remaind) {
+                custodian.collect_into(&mut collected);
+            }
+        }
+
+        ckb_cells.sort_by_key(|c| c.capacity);
+        let remaind = max_cells.saturating_sub(collected.cells_info.len());
+        for custodian in ckb_cells.into_iter().take(remaind) {
+            custodian.collect_into(&mut collected);
+        }
+
+        collected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FinalizedCustodian, FinalizedCustodianCandidates};
+    use gw_types::{
+        offchain::{CellInfo, WithdrawalsAmount},
+        packed::{OutPoint, Script},
+        prelude::*,
+    };
+
+    const SUDT_A: [u8; 32] = [1u8; 32];
+    const SUDT_B: [u8; 32] = [2u8; 32];
+
+    fn custodian(index: u32, capacity: u64, sudt: Option<([u8; 32], u128)>) -> FinalizedCustodian {
+        let info = CellInfo {
+            out_point: OutPoint::new_builder().index(index.pack()).build(),
+            ..Default::default()
+        };
+        let sudt = sudt.map(|(sudt_type_hash, amount)| {
+            let type_script = Script::new_builder()
+                .args(sudt_type_hash.to_vec().pack())
+                .build();
+            (sudt_type_hash, amount, type_script)
+        });
+        FinalizedCustodian {
+            info,
+            capacity,
+            sudt,
+        }
+    }
+
+    fn build_candidates(custodians: Vec<FinalizedCustodian>) -> FinalizedCustodianCandidates {
+        let mut candidates = FinalizedCustodianCandidates::default();
+        for custodian in custodians {
+            candidates.push(custodian);
+        }
+        candidates
+    }
+
+    fn selected_indexes(cells_info: &[CellInfo]) -> Vec<u32> {
+        let mut indexes: Vec<u32> = cells_info
+            .iter()
+            .map(|cell| cell.out_point.index().unpack())
+            .collect();
+        indexes.sort_unstable();
+        indexes
+    }
+
+    #[test]
+    fn test_select_large_ckb_cells_first() {
+        let candidates = build_candidates(vec![
+            custodian(0, 100, None),
+            custodian(1, 500, None),
+            custodian(2, 300, None),
+            custodian(3, 200, None),
+        ]);
+        let withdrawals_amount = WithdrawalsAmount {
+            capacity: 600,
+            ..Default::default()
+        };
+        assert!(candidates.is_enough(&withdrawals_amount, 600));
+
+        let collected = candidates.select(&withdrawals_amount, 600);
+        assert_eq!(selected_indexes(&collected.cells_info), vec![1, 2]);
+        assert_eq!(collected.capacity, 800);
+        assert!(collected.sudt.is_empty());
+    }
+
+    #[test]
+    fn test_select_sudt_cells_by_amount() {
+        let candidates = build_candidates(vec![
+            custodian(0, 1000, None),
+            custodian(1, 100, Some((SUDT_A, 10))),
+            custodian(2, 100, Some((SUDT_A, 50))),
+            custodian(3, 100, Some((SUDT_A, 30))),
+            // not required by withdrawals, only used as capacity fallback
+            custodian(4, 100, Some((SUDT_B, 1000))),
+        ]);
+        let withdrawals_amount = WithdrawalsAmount {
+            capacity: 150,
+            sudt: vec![(SUDT_A, 60)].into_iter().collect(),
+        };
+        assert!(candidates.is_enough(&withdrawals_amount, 150));
+
+        let collected = candidates.select(&withdrawals_amount, 150);
+        assert_eq!(selected_indexes(&collected.cells_info), vec![2, 3]);
+        assert_eq!(collected.sudt.get(&SUDT_A).map(|(a, _)| *a), Some(80));
+        assert_eq!(collected.capacity, 200);
+    }
+
+    #[test]
+    fn test_select_fallback_to_unselected_sudt_cells() {
+        let candidates = build_candidates(vec![
+            custodian(0, 100, None),
+            custodian(1, 300, Some((SUDT_B, 1))),
+        ]);
+        let withdrawals_amount = WithdrawalsAmount {
+            capacity: 350,
+            ..Default::default()
+        };
+
+        let collected = candidates.select(&withdrawals_amount, 350);
+        assert_eq!(selected_indexes(&collected.cells_info), vec![0, 1]);
+        assert_eq!(collected.capacity, 400);
+        assert_eq!(collected.sudt.get(&SUDT_B).map(|(a, _)| *a), Some(1));
+    }
+
+    #[test]
+    fn test_not_enough_candidates() {
+        let candidates = build_candidates(vec![
+            custodian(0, 100, None),
+            custodian(1, 100, Some((SUDT_A, 10))),
+        ]);
+        let withdrawals_amount = WithdrawalsAmount {
+            capacity: 100,
+            sudt: vec![(SUDT_A, 20)].into_iter().collect(),
+        };
+        assert!(!candidates.is_enough(&withdrawals_amount, 100));
+    }
+
+    #[test]
+    fn test_select_mergeable_small_cells_first() {
+        let candidates = build_candidates(vec![
+            custodian(0, 500, None),
+            custodian(1, 100, None),
+            custodian(2, 300, None),
+            custodian(3, 200, None),
+        ]);
+
+        let collected = candidates.select_mergeable(3);
+        assert_eq!(selected_indexes(&collected.cells_info), vec![1, 2, 3]);
+        assert_eq!(collected.capacity, 600);
+    }
+
+    #[test]
+    fn test_select_mergeable_sudt_cells() {
+        let candidates = build_candidates(vec![
+            custodian(0, 100, None),
+            custodian(1, 100, Some((SUDT_A, 30))),
+            custodian(2, 100, Some((SUDT_A, 10))),
+            custodian(3, 100, Some((SUDT_A, 20))),
+            // a single cell of a sudt has nothing to merge with
+            custodian(4, 100, Some((SUDT_B, 10))),
+        ]);
+
+        let collected = candidates.select_mergeable(3);
+        assert_eq!(selected_indexes(&collected.cells_info), vec![1, 2, 3]);
+        assert_eq!(collected.sudt.get(&SUDT_A).map(|(a, _)| *a), Some(60));
+        assert!(collected.sudt.get(&SUDT_B).is_none());
+
+        // sudt cells are skipped if less than two cells can be merged
+        let candidates = build_candidates(vec![
+            custodian(0, 100, None),
+            custodian(1, 200, None),
+            custodian(2, 100, Some((SUDT_A, 30))),
+            custodian(3, 100, Some((SUDT_A, 10))),
+        ]);
+        let collected = candidates.select_mergeable(1);
+        assert_eq!(selected_indexes(&collected.cells_info), vec![0]);
+        assert!(collected.sudt.is_empty());
+    }
+}
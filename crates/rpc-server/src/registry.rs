@@ -3,16 +3,24 @@ use async_trait::async_trait;
 use ckb_types::prelude::{Builder, Entity};
 use gw_chain::chain::Chain;
 use gw_challenge::offchain::OffChainMockContext;
-use gw_common::{blake2b::new_blake2b, state::State, H256};
-use gw_config::{DebugConfig, MemPoolConfig, NodeMode};
+use gw_common::{
+    blake2b::new_blake2b,
+    merkle_utils::{ckb_merkle_leaf_hash, CBMT},
+    state::{build_account_key, State},
+    H256,
+};
+use gw_config::{BackendConfig, DebugConfig, MemPoolConfig, NodeMode, RPCConfig};
 use gw_generator::{error::TransactionError, sudt::build_l2_sudt_script, Generator};
 use gw_jsonrpc_types::{
     blockchain::Script,
     ckb_jsonrpc_types::{JsonBytes, Uint128, Uint32},
     debugger::{DumpChallengeTarget, ReprMockTransaction},
     godwoken::{
-        BackendInfo, GlobalState, L2BlockStatus, L2BlockView, L2BlockWithStatus,
-        L2TransactionStatus, L2TransactionWithStatus, NodeInfo, RunResult, TxReceipt,
+        AccountProof, BackendInfo, BlockProof, CollectedFee, CyclesEstimation, DepositRefundReason,
+        GlobalState, KVPair, L2BlockStatus, L2BlockView, L2BlockWithStatus, L2TransactionStatus,
+        L2TransactionWithStatus, NodeInfo, RefundableDeposit, RunResult, StateCheckPoint,
+        SubState as JsonSubState, TransactionProof, TxReceipt, WithdrawalOffer, WithdrawalStatus,
+        WithdrawalWithStatus,
     },
     test_mode::{ShouldProduceBlock, TestModePayload},
 };
@@ -30,16 +38,33 @@ use gw_types::{
 use gw_version::Version;
 use jsonrpc_v2::{Data, Error as RpcError, MapRouter, Params, Server, Server as JsonrpcServer};
 use smol::lock::Mutex;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 // type alias
-type RPCServer = Arc<Server<MapRouter>>;
 type MemPool = Option<Arc<Mutex<gw_mem_pool::pool::MemPool>>>;
 type AccountID = Uint32;
 type JsonH256 = ckb_fixed_hash::H256;
 type BoxedTestsRPCImpl = Box<dyn TestModeRPC + Send + Sync>;
 type GwUint64 = gw_jsonrpc_types::ckb_jsonrpc_types::Uint64;
 
+/// Register handlers and record their method names
+macro_rules! with_methods {
+    ($server:ident, $methods:ident, { $($name:literal => $handler:expr),* $(,)? }) => {
+        $(
+            $methods.insert($name);
+            $server = $server.with_method($name, $handler);
+        )*
+    };
+}
+
+/// JSONRPC server with the names of registered methods
+pub struct RPCServer {
+    pub server: Arc<Server<MapRouter>>,
+    pub methods: Arc<HashSet<&'static str>>,
+}
+
 const HEADER_NOT_FOUND_ERR_CODE: i64 = -32000;
 const INVALID_NONCE_ERR_CODE: i64 = -32001;
 const INTERNAL_ERROR_ERR_CODE: i64 = -32099;
@@ -109,6 +134,69 @@ fn get_state_db_at_block<'a>(
     }
 }
 
+// block info of an executed block
+fn get_block_info_at(db: &StoreTransaction, block_number: u64) -> Result<BlockInfo, RpcError> {
+    let block_hash = match db.get_block_hash_by_number(block_number)? {
+        Some(block_hash) => block_hash,
+        None => return Err(header_not_found_err()),
+    };
+    let raw_block = match db.get_block(&block_hash)? {
+        Some(block) => block.raw(),
+        None => return Err(header_not_found_err()),
+    };
+    Ok(BlockInfo::new_builder()
+        .block_producer_id(raw_block.block_producer_id())
+        .timestamp(raw_block.timestamp())
+        .number(raw_block.number())
+        .build())
+}
+
+fn get_mem_pool_block_info(db: &StoreTransaction) -> Result<BlockInfo, RpcError> {
+    db.get_mem_pool_block_info()?.ok_or(RpcError::Provided {
+        code: INTERNAL_ERROR_ERR_CODE,
+        message: "mem pool block info not found",
+    })
+}
+
+// read the post state of a block, or the sub state checkpoint inside a block
+fn get_state_db_at_checkpoint<'a>(
+    db: &'a StoreTransaction,
+    checkpoint: Option<StateCheckPoint>,
+    is_mem_pool_enabled: bool,
+) -> Result<StateDBTransaction<'a>, RpcError> {
+    let (block_number, sub_state) = match checkpoint {
+        None => return get_state_db_at_block(db, None, is_mem_pool_enabled),
+        Some(StateCheckPoint::Block(block_number)) => {
+            return get_state_db_at_block(db, Some(block_number), is_mem_pool_enabled)
+        }
+        Some(StateCheckPoint::SubState {
+            block_number,
+            sub_state,
+        }) => (block_number.value(), sub_state),
+    };
+
+    let sub_state = match sub_state {
+        JsonSubState::Withdrawal(index) => SubState::Withdrawal(index.value()),
+        JsonSubState::PrevTxs => SubState::PrevTxs,
+        JsonSubState::Tx(index) => SubState::Tx(index.value()),
+        JsonSubState::Block => SubState::Block,
+    };
+    let block_hash = db
+        .get_block_hash_by_number(block_number)?
+        .ok_or_else(header_not_found_err)?;
+    let block = db
+        .get_block(&block_hash)?
+        .ok_or_else(header_not_found_err)?;
+    if sub_state.validate_in_block(&block).is_err() {
+        return Err(RpcError::Provided {
+            code: INVALID_PARAM_ERR_CODE,
+            message: "invalid sub state index",
+        });
+    }
+    let checkpoint = CheckPoint::from_block_hash(db, block_hash, sub_state)?;
+    StateDBTransaction::from_checkpoint(db, checkpoint, StateDBMode::ReadOnly).map_err(Into::into)
+}
+
 pub struct Registry {
     generator: Arc<Generator>,
     mem_pool: MemPool,
@@ -119,7 +207,7 @@ pub struct Registry {
     rollup_config: RollupConfig,
     debug_config: DebugConfig,
     mem_pool_config: MemPoolConfig,
-    backend_info: Vec<BackendInfo>,
+    rpc_config: RPCConfig,
     node_mode: NodeMode,
 }
 
@@ -135,12 +223,12 @@ impl Registry {
         chain: Arc<Mutex<Chain>>,
         offchain_mock_context: Option<OffChainMockContext>,
         mem_pool_config: MemPoolConfig,
+        rpc_config: RPCConfig,
         node_mode: NodeMode,
     ) -> Self
     where
         T: TestModeRPC + Send + Sync + 'static,
     {
-        let backend_info = get_backend_info(generator.clone());
         Self {
             mem_pool,
             store,
@@ -152,13 +240,14 @@ impl Registry {
             chain,
             offchain_mock_context,
             mem_pool_config,
-            backend_info,
+            rpc_config,
             node_mode,
         }
     }
 
     pub fn build_rpc_server(self) -> Result<RPCServer> {
         let mut server = JsonrpcServer::new();
+        let mut methods = HashSet::new();
 
         server = server
             .with_data(Data::new(self.mem_pool))
@@ -166,69 +255,109 @@ impl Registry {
             .with_data(Data::new(self.store))
             .with_data(Data::new(self.rollup_config))
             .with_data(Data::new(self.mem_pool_config))
-            .with_data(Data::new(self.backend_info))
-            .with_method("gw_ping", ping)
-            .with_method("gw_get_tip_block_hash", get_tip_block_hash)
-            .with_method("gw_get_block_hash", get_block_hash)
-            .with_method("gw_get_block", get_block)
-            .with_method("gw_get_block_by_number", get_block_by_number)
-            .with_method("gw_get_balance", get_balance)
-            .with_method("gw_get_storage_at", get_storage_at)
-            .with_method(
-                "gw_get_account_id_by_script_hash",
-                get_account_id_by_script_hash,
-            )
-            .with_method("gw_get_nonce", get_nonce)
-            .with_method("gw_get_script", get_script)
-            .with_method("gw_get_script_hash", get_script_hash)
-            .with_method(
-                "gw_get_script_hash_by_short_address",
-                get_script_hash_by_short_address,
-            )
-            .with_method("gw_get_data", get_data)
-            .with_method("gw_get_transaction", get_transaction)
-            .with_method("gw_get_transaction_receipt", get_transaction_receipt)
-            .with_method("gw_execute_l2transaction", execute_l2transaction)
-            .with_method("gw_execute_raw_l2transaction", execute_raw_l2transaction)
-            .with_method(
-                "gw_compute_l2_sudt_script_hash",
-                compute_l2_sudt_script_hash,
-            )
-            .with_method("gw_get_node_info", get_node_info);
-        if self.node_mode != NodeMode::ReadOnly {
-            server = server
-                .with_method("gw_submit_l2transaction", submit_l2transaction)
-                .with_method("gw_submit_withdrawal_request", submit_withdrawal_request);
+            .with_data(Data::new(self.rpc_config.clone()));
+        with_methods!(server, methods, {
+            "gw_ping" => ping,
+            "gw_get_tip_block_hash" => get_tip_block_hash,
+            "gw_get_block_hash" => get_block_hash,
+            "gw_get_block" => get_block,
+            "gw_get_block_by_number" => get_block_by_number,
+            "gw_get_block_proof" => get_block_proof,
+            "gw_get_balance" => get_balance,
+            "gw_get_storage_at" => get_storage_at,
+            "gw_get_account_proof" => get_account_proof,
+            "gw_get_storage_proof" => get_storage_proof,
+            "gw_get_account_id_by_script_hash" => get_account_id_by_script_hash,
+            "gw_get_nonce" => get_nonce,
+            "gw_get_script" => get_script,
+            "gw_get_script_hash" => get_script_hash,
+            "gw_get_script_hash_by_short_address" => get_script_hash_by_short_address,
+            "gw_get_data" => get_data,
+            "gw_get_transaction" => get_transaction,
+            "gw_get_transaction_receipt" => get_transaction_receipt,
+            "gw_get_transaction_proof" => get_transaction_proof,
+            "gw_get_withdrawal" => get_withdrawal,
+            "gw_execute_l2transaction" => execute_l2transaction,
+            "gw_execute_raw_l2transaction" => execute_raw_l2transaction,
+            "gw_estimate_cycles" => estimate_cycles,
+            "gw_compute_l2_sudt_script_hash" => compute_l2_sudt_script_hash,
+            "gw_get_node_info" => get_node_info,
+            "gw_get_refundable_deposits" => get_refundable_deposits,
+            "gw_get_withdrawal_offers" => get_withdrawal_offers,
+        });
+        if self.node_mode != NodeMode::ReadOnly && self.node_mode != NodeMode::Watchtower {
+            with_methods!(server, methods, {
+                "gw_submit_l2transaction" => submit_l2transaction,
+                "gw_submit_withdrawal_request" => submit_withdrawal_request,
+            });
         }
 
         // Tests
         if let Some(tests_rpc_impl) = self.tests_rpc_impl {
-            server = server
-                .with_data(Data(Arc::clone(&tests_rpc_impl)))
-                .with_method("tests_produce_block", tests_produce_block)
-                .with_method("tests_should_produce_block", tests_should_produce_block)
-                .with_method("tests_get_global_state", tests_get_global_state);
+            server = server.with_data(Data(Arc::clone(&tests_rpc_impl)));
+            with_methods!(server, methods, {
+                "tests_produce_block" => tests_produce_block,
+                "tests_should_produce_block" => tests_should_produce_block,
+                "tests_get_global_state" => tests_get_global_state,
+            });
         }
 
         // Debug
         if self.debug_config.enable_debug_rpc {
             server = server
                 .with_data(Data::new(self.chain))
-                .with_data(Data::new(self.offchain_mock_context))
-                .with_method(
-                    "debug_dump_cancel_challenge_tx",
-                    debug_dump_cancel_challenge_tx,
-                );
+                .with_data(Data::new(self.offchain_mock_context));
+            with_methods!(server, methods, {
+                "debug_dump_cancel_challenge_tx" => debug_dump_cancel_challenge_tx,
+            });
+        }
+
+        Ok(RPCServer {
+            server: server.finish(),
+            methods: Arc::new(methods),
+        })
+    }
+}
+
+/// Admin RPCs, served on a separate listen address from the public RPCs
+pub struct AdminRegistry {
+    store: Store,
+    generator: Arc<Generator>,
+    backends_dir: PathBuf,
+}
+
+impl AdminRegistry {
+    pub fn new(store: Store, generator: Arc<Generator>, backends_dir: PathBuf) -> Self {
+        Self {
+            store,
+            generator,
+            backends_dir,
         }
+    }
 
-        Ok(server.finish())
+    pub fn build_rpc_server(self) -> Result<RPCServer> {
+        let mut server = JsonrpcServer::new();
+        let mut methods = HashSet::new();
+
+        server = server
+            .with_data(Data(self.generator))
+            .with_data(Data::new(self.store))
+            .with_data(Data::new(BackendsDir(self.backends_dir)));
+        with_methods!(server, methods, {
+            "admin_register_backend" => admin_register_backend,
+        });
+
+        Ok(RPCServer {
+            server: server.finish(),
+            methods: Arc::new(methods),
+        })
     }
 }
 
-fn get_backend_info(generator: Arc<Generator>) -> Vec<BackendInfo> {
+fn get_backend_info(generator: &Generator) -> Vec<BackendInfo> {
     generator
         .get_backends()
-        .values()
+        .iter()
         .map(|b| {
             let mut validator_code_hash = [0u8; 32];
             let mut hasher = new_blake2b();
@@ -244,6 +373,7 @@ fn get_backend_info(generator: Arc<Generator>) -> Vec<BackendInfo> {
                 validator_script_type_hash: ckb_fixed_hash::H256(
                     b.validator_script_type_hash.into(),
                 ),
+                activation_block_number: b.activation_block_number.into(),
             }
         })
         .collect()
@@ -304,6 +434,142 @@ async fn get_transaction(
     }))
 }
 
+async fn get_transaction_proof(
+    Params((tx_hash,)): Params<(JsonH256,)>,
+    store: Data<Store>,
+) -> Result<Option<TransactionProof>> {
+    let tx_hash = to_h256(tx_hash);
+    let db = store.begin_transaction();
+    let tx_info = match db.get_transaction_info(&tx_hash)? {
+        Some(tx_info) => tx_info,
+        None => return Ok(None),
+    };
+    let block_number: u64 = tx_info.block_number().unpack();
+    let key = tx_info.key();
+    let block_hash: H256 = {
+        let mut buf = [0u8; 32];
+        buf.copy_from_slice(&key.as_slice()[..32]);
+        buf.into()
+    };
+    let tx_index = {
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(&key.as_slice()[32..]);
+        u32::from_be_bytes(buf)
+    };
+
+    // return None if tx's block is not on the main chain
+    let block_proof = match build_block_proof(&db, block_number)? {
+        Some(proof) if to_h256(proof.block_hash.clone()) == block_hash => proof,
+        _ => return Ok(None),
+    };
+    let block = match db.get_block(&block_hash)? {
+        Some(block) => block,
+        None => return Ok(None),
+    };
+    let tx = block
+        .transactions()
+        .get(tx_index as usize)
+        .ok_or_else(|| anyhow!("tx not found in block"))?;
+
+    let leaves: Vec<H256> = block
+        .transactions()
+        .into_iter()
+        .enumerate()
+        .map(|(idx, tx)| ckb_merkle_leaf_hash(idx as u32, &tx.witness_hash().into()))
+        .collect();
+    let proof = CBMT::build_merkle_proof(&leaves, &[tx_index])
+        .ok_or_else(|| anyhow!("build tx proof failed"))?;
+    let tx_proof = packed::CKBMerkleProof::new_builder()
+        .lemmas(proof.lemmas().pack())
+        .indices(proof.indices().pack())
+        .build();
+
+    Ok(Some(TransactionProof {
+        tx_hash: to_jsonh256(tx_hash),
+        witness_hash: tx.witness_hash().into(),
+        tx_index: tx_index.into(),
+        raw_block: block.raw().into(),
+        tx_proof: tx_proof.into(),
+        block_proof,
+    }))
+}
+
+async fn get_withdrawal(
+    Params((withdrawal_hash,)): Params<(JsonH256,)>,
+    store: Data<Store>,
+    mem_pool: Data<MemPool>,
+    rollup_config: Data<RollupConfig>,
+) -> Result<Option<WithdrawalWithStatus>> {
+    let withdrawal_hash = to_h256(withdrawal_hash);
+    let db = store.begin_transaction();
+    if let Some(withdrawal_info) = db.get_withdrawal_info(&withdrawal_hash)? {
+        let block_number: u64 = withdrawal_info.block_number().unpack();
+
+        // return None if withdrawal's committed block is reverted
+        if !db
+            .reverted_block_smt()?
+            .get(&RawL2Block::compute_smt_key(block_number).into())?
+            .is_zero()
+        {
+            return Ok(None);
+        }
+
+        let key = withdrawal_info.key();
+        let withdrawal = match db.get_withdrawal_by_key(&key)? {
+            Some(withdrawal) => withdrawal,
+            None => return Ok(None),
+        };
+        let block_hash: H256 = {
+            let mut buf = [0u8; 32];
+            buf.copy_from_slice(&key.as_slice()[..32]);
+            buf.into()
+        };
+        let index = {
+            let mut buf = [0u8; 4];
+            buf.copy_from_slice(&key.as_slice()[32..]);
+            u32::from_be_bytes(buf)
+        };
+        let l1_committed_info = db.get_l2block_committed_info(&block_hash)?;
+
+        let tip_block_number = db.get_last_valid_tip_block()?.raw().number().unpack();
+        let status = if tip_block_number >= block_number + rollup_config.finality_blocks().unpack()
+        {
+            WithdrawalStatus::Finalized
+        } else {
+            WithdrawalStatus::Committed
+        };
+
+        return Ok(Some(WithdrawalWithStatus {
+            withdrawal: withdrawal.into(),
+            status,
+            l2_block_number: Some(block_number.into()),
+            l2_block_hash: Some(to_jsonh256(block_hash)),
+            l2_withdrawal_index: Some(index.into()),
+            l1_committed_info: l1_committed_info.map(Into::into),
+        }));
+    }
+
+    let withdrawal_opt = match db.get_mem_pool_withdrawal(&withdrawal_hash)? {
+        Some(withdrawal) => Some(withdrawal),
+        None => {
+            // the withdrawal maybe in the mem-pool but not finalized
+            // so we try to sync with mem-pool, then fetch from db again
+            if let Some(mem_pool) = mem_pool.as_ref() {
+                mem_pool.lock().await;
+                db.get_mem_pool_withdrawal(&withdrawal_hash)?
+            } else {
+                None
+            }
+        }
+    };
+
+    Ok(withdrawal_opt.map(|withdrawal| WithdrawalWithStatus {
+        withdrawal: withdrawal.into(),
+        status: WithdrawalStatus::Pending,
+        ..Default::default()
+    }))
+}
+
 async fn get_block(
     Params((block_hash,)): Params<(JsonH256,)>,
     store: Data<Store>,
@@ -335,9 +601,19 @@ async fn get_block(
         }
     }
 
+    let collected_fees = db.get_block_collected_fees(&block_hash)?;
+    let collected_fees = collected_fees
+        .into_iter()
+        .map(|(sudt_id, amount)| CollectedFee {
+            sudt_id: sudt_id.into(),
+            amount: amount.into(),
+        })
+        .collect();
+
     Ok(Some(L2BlockWithStatus {
         block: block.into(),
         status,
+        collected_fees,
     }))
 }
 
@@ -368,6 +644,35 @@ async fn get_block_hash(
     Ok(hash_opt)
 }
 
+fn build_block_proof(db: &StoreTransaction, block_number: u64) -> Result<Option<BlockProof>> {
+    let block_hash = match db.get_block_hash_by_number(block_number)? {
+        Some(hash) => hash,
+        None => return Ok(None),
+    };
+
+    let block_smt = db.block_smt()?;
+    let key: H256 = RawL2Block::compute_smt_key(block_number).into();
+    let proof = block_smt
+        .merkle_proof(vec![key])?
+        .compile(vec![(key, block_hash)])?;
+
+    Ok(Some(BlockProof {
+        block_number: block_number.into(),
+        block_hash: to_jsonh256(block_hash),
+        tip_block_hash: to_jsonh256(db.get_tip_block_hash()?),
+        merkle_root: to_jsonh256(*block_smt.root()),
+        proof: JsonBytes::from_vec(proof.0),
+    }))
+}
+
+async fn get_block_proof(
+    Params((block_number,)): Params<(gw_jsonrpc_types::ckb_jsonrpc_types::Uint64,)>,
+    store: Data<Store>,
+) -> Result<Option<BlockProof>> {
+    let db = store.begin_transaction();
+    build_block_proof(&db, block_number.value())
+}
+
 async fn get_tip_block_hash(store: Data<Store>) -> Result<JsonH256> {
     let tip_block_hash = store.get_tip_block_hash()?;
     Ok(to_jsonh256(tip_block_hash))
@@ -438,28 +743,8 @@ async fn execute_l2transaction(
     let db = store.begin_transaction();
 
     let block_info = match block_number_opt {
-        Some(block_number) => {
-            let block_hash = match db.get_block_hash_by_number(block_number)? {
-                Some(block_hash) => block_hash,
-                None => return Err(header_not_found_err()),
-            };
-            let raw_block = match store.get_block(&block_hash)? {
-                Some(block) => block.raw(),
-                None => return Err(header_not_found_err()),
-            };
-            let block_producer_id = raw_block.block_producer_id();
-            let timestamp = raw_block.timestamp();
-            let number: u64 = raw_block.number().unpack();
-
-            BlockInfo::new_builder()
-                .block_producer_id(block_producer_id)
-                .timestamp(timestamp)
-                .number(number.pack())
-                .build()
-        }
-        None => db
-            .get_mem_pool_block_info()?
-            .expect("get mem pool block info"),
+        Some(block_number) => get_block_info_at(&db, block_number)?,
+        None => get_mem_pool_block_info(&db)?,
     };
 
     let run_result: RunResult = mem_pool
@@ -496,28 +781,8 @@ async fn execute_raw_l2transaction(
     let db = store.begin_transaction();
 
     let block_info = match block_number_opt {
-        Some(block_number) => {
-            let block_hash = match db.get_block_hash_by_number(block_number)? {
-                Some(block_hash) => block_hash,
-                None => return Err(header_not_found_err()),
-            };
-            let raw_block = match store.get_block(&block_hash)? {
-                Some(block) => block.raw(),
-                None => return Err(header_not_found_err()),
-            };
-            let block_producer_id = raw_block.block_producer_id();
-            let timestamp = raw_block.timestamp();
-            let number: u64 = raw_block.number().unpack();
-
-            BlockInfo::new_builder()
-                .block_producer_id(block_producer_id)
-                .timestamp(timestamp)
-                .number(number.pack())
-                .build()
-        }
-        None => db
-            .get_mem_pool_block_info()?
-            .expect("get mem pool block info"),
+        Some(block_number) => get_block_info_at(&db, block_number)?,
+        None => get_mem_pool_block_info(&db)?,
     };
 
     let execute_l2tx_max_cycles = mem_pool_config.execute_l2tx_max_cycles;
@@ -544,6 +809,65 @@ async fn execute_raw_l2transaction(
     Ok(run_result)
 }
 
+async fn estimate_cycles(
+    Params((raw_l2tx,)): Params<(JsonBytes,)>,
+    mem_pool: Data<MemPool>,
+    rpc_config: Data<RPCConfig>,
+    store: Data<Store>,
+    generator: Data<Generator>,
+) -> Result<CyclesEstimation, RpcError> {
+    let raw_l2tx_bytes = raw_l2tx.into_bytes();
+    let raw_l2tx = packed::RawL2Transaction::from_slice(&raw_l2tx_bytes)?;
+
+    let db = store.begin_transaction();
+    // estimate on the mem block, or on the tip block if mem-pool is disabled
+    let is_mem_pool_enabled = mem_pool.is_some();
+    let block_info = if is_mem_pool_enabled {
+        get_mem_pool_block_info(&db)?
+    } else {
+        let tip_block_number: u64 = db.get_tip_block()?.raw().number().unpack();
+        get_block_info_at(&db, tip_block_number)?
+    };
+
+    // txs are executed with the l2tx max cycles of execution limits
+    let max_cycles = generator.execution_limits().l2tx_max_cycles;
+    // execute tx in task
+    let task: smol::Task<Result<u64>> = smol::spawn(async move {
+        let state_db = get_state_db_at_block(&db, None, is_mem_pool_enabled)
+            .map_err(|_err| anyhow!("get state db error"))?;
+        let state = state_db.state_tree()?;
+        let chain_view = {
+            let tip_block_hash = db.get_tip_block_hash()?;
+            ChainView::new(&db, tip_block_hash)
+        };
+        let run_result = generator
+            .unchecked_execute_transaction(&chain_view, &state, &block_info, &raw_l2tx, max_cycles)
+            .map_err(|err| anyhow!("execute with max cycles {}: {}", max_cycles, err))?;
+        if 0 != run_result.exit_code {
+            return Err(TransactionError::InvalidExitCode(run_result.exit_code).into());
+        }
+
+        // CKB-VM counts cycles deterministically, the used cycles of a
+        // successful run is the minimum max cycles to run it
+        Ok(run_result.used_cycles)
+    });
+    let cycles = task.await?;
+
+    let fee = rpc_config
+        .cycles_per_fee
+        .filter(|&cycles_per_fee| cycles_per_fee > 0)
+        .map(|cycles_per_fee| {
+            // round up
+            let cycles_per_fee = cycles_per_fee as u128;
+            (cycles as u128 + cycles_per_fee - 1) / cycles_per_fee
+        });
+
+    Ok(CyclesEstimation {
+        cycles: cycles.into(),
+        fee: fee.map(Into::into),
+    })
+}
+
 async fn submit_l2transaction(
     Params((l2tx,)): Params<(JsonBytes,)>,
     mem_pool: Data<MemPool>,
@@ -617,12 +941,86 @@ async fn submit_withdrawal_request(
     Ok(())
 }
 
+async fn get_refundable_deposits(
+    mem_pool: Data<MemPool>,
+) -> Result<Vec<RefundableDeposit>, RpcError> {
+    let mem_pool = match &*mem_pool {
+        Some(mem_pool) => mem_pool,
+        None => {
+            return Err(mem_pool_is_disabled_err());
+        }
+    };
+
+    // Cancel timeout is queried from L1, don't hold the mem pool lock
+    let query = mem_pool.lock().await.query_refundable_deposits();
+    let to_json = |deposit: &gw_mem_pool::deposit::RefundableDeposit| -> Option<RefundableDeposit> {
+        let lock_args = gw_mem_pool::deposit::deposit_lock_args(&deposit.info)?;
+        let request = &deposit.info.request;
+        let capacity: u64 = request.capacity().unpack();
+        let amount: u128 = request.amount().unpack();
+        let (reason, error) = match &deposit.reason {
+            gw_mem_pool::deposit::DepositRefundReason::Invalid(err) => {
+                (DepositRefundReason::Invalid, Some(err.to_owned()))
+            }
+            gw_mem_pool::deposit::DepositRefundReason::CancelTimeout => {
+                (DepositRefundReason::CancelTimeout, None)
+            }
+        };
+
+        Some(RefundableDeposit {
+            out_point: deposit.info.cell.out_point.clone().into(),
+            owner_lock_hash: lock_args.owner_lock_hash().unpack(),
+            capacity: capacity.into(),
+            sudt_script_hash: request.sudt_script_hash().unpack(),
+            amount: amount.into(),
+            reason,
+            error,
+        })
+    };
+
+    let deposits = query.await.map_err(|err| RpcError::Full {
+        code: INTERNAL_ERROR_ERR_CODE,
+        message: err.to_string(),
+        data: None,
+    })?;
+    Ok(deposits.iter().filter_map(to_json).collect())
+}
+
+// Withdrawals in unfinalized blocks with a payment lock are on sale
+async fn get_withdrawal_offers(
+    store: Data<Store>,
+    rollup_config: Data<RollupConfig>,
+) -> Result<Vec<WithdrawalOffer>> {
+    let db = store.begin_transaction();
+    let tip_block_number: u64 = db.get_last_valid_tip_block()?.raw().number().unpack();
+    let finality_blocks: u64 = rollup_config.finality_blocks().unpack();
+    // block is finalized if tip_block_number >= block_number + finality_blocks
+    let unfinalized_block_number = (tip_block_number + 1).saturating_sub(finality_blocks);
+
+    let mut offers = Vec::new();
+    for (block_number, withdrawal) in db.get_withdrawal_offers(unfinalized_block_number)? {
+        let block_hash = match db.get_block_hash_by_number(block_number)? {
+            Some(block_hash) => block_hash,
+            None => continue,
+        };
+
+        offers.push(WithdrawalOffer {
+            withdrawal_hash: to_jsonh256(withdrawal.hash().into()),
+            block_hash: to_jsonh256(block_hash),
+            block_number: block_number.into(),
+            raw: withdrawal.raw().into(),
+        });
+    }
+
+    Ok(offers)
+}
+
 // short_address, sudt_id, block_number
 #[derive(serde::Serialize, serde::Deserialize)]
 #[serde(untagged)]
 enum GetBalanceParams {
     Tip((JsonBytes, AccountID)),
-    Number((JsonBytes, AccountID, Option<GwUint64>)),
+    Number((JsonBytes, AccountID, Option<StateCheckPoint>)),
 }
 
 async fn get_balance(
@@ -630,13 +1028,13 @@ async fn get_balance(
     mem_pool: Data<MemPool>,
     store: Data<Store>,
 ) -> Result<Uint128, RpcError> {
-    let (short_address, sudt_id, block_number) = match params {
+    let (short_address, sudt_id, checkpoint) = match params {
         GetBalanceParams::Tip(p) => (p.0, p.1, None),
         GetBalanceParams::Number(p) => p,
     };
 
     let db = store.begin_transaction();
-    let state_db = get_state_db_at_block(&db, block_number, mem_pool.is_some())?;
+    let state_db = get_state_db_at_checkpoint(&db, checkpoint, mem_pool.is_some())?;
     let tree = state_db.state_tree()?;
     let balance = tree.get_sudt_balance(sudt_id.into(), short_address.as_bytes())?;
     Ok(balance.into())
@@ -647,7 +1045,7 @@ async fn get_balance(
 #[serde(untagged)]
 enum GetStorageAtParams {
     Tip((AccountID, JsonH256)),
-    Number((AccountID, JsonH256, Option<GwUint64>)),
+    Number((AccountID, JsonH256, Option<StateCheckPoint>)),
 }
 
 async fn get_storage_at(
@@ -655,13 +1053,13 @@ async fn get_storage_at(
     mem_pool: Data<MemPool>,
     store: Data<Store>,
 ) -> Result<JsonH256, RpcError> {
-    let (account_id, key, block_number) = match params {
+    let (account_id, key, checkpoint) = match params {
         GetStorageAtParams::Tip(p) => (p.0, p.1, None),
         GetStorageAtParams::Number(p) => p,
     };
 
     let db = store.begin_transaction();
-    let state_db = get_state_db_at_block(&db, block_number, mem_pool.is_some())?;
+    let state_db = get_state_db_at_checkpoint(&db, checkpoint, mem_pool.is_some())?;
 
     let tree = state_db.state_tree()?;
     let key: H256 = to_h256(key);
@@ -671,13 +1069,128 @@ async fn get_storage_at(
     Ok(json_value)
 }
 
+fn build_account_proof(
+    db: &StoreTransaction,
+    keys: Vec<H256>,
+    block_number: Option<GwUint64>,
+    max_proof_keys: usize,
+) -> Result<AccountProof, RpcError> {
+    if keys.is_empty() {
+        return Err(RpcError::Provided {
+            code: INVALID_PARAM_ERR_CODE,
+            message: "empty keys",
+        });
+    }
+    if keys.len() > max_proof_keys {
+        return Err(RpcError::Full {
+            code: INVALID_PARAM_ERR_CODE,
+            message: format!("too many keys, max {} keys", max_proof_keys),
+            data: None,
+        });
+    }
+
+    let block_number = match block_number {
+        Some(number) => number.value(),
+        None => db.get_tip_block()?.raw().number().unpack(),
+    };
+    let block_hash = db
+        .get_block_hash_by_number(block_number)?
+        .ok_or_else(header_not_found_err)?;
+
+    // always prove against a committed block, mem block state isn't in any block root
+    let state_db = get_state_db_at_block(db, Some(block_number.into()), false)?;
+    let smt = state_db.account_smt()?;
+    let mut leaves = Vec::with_capacity(keys.len());
+    for key in keys.iter() {
+        leaves.push((*key, smt.get(key)?));
+    }
+    let proof = smt.merkle_proof(keys)?.compile(leaves.clone())?;
+
+    Ok(AccountProof {
+        block_number: block_number.into(),
+        block_hash: to_jsonh256(block_hash),
+        merkle_root: to_jsonh256(*smt.root()),
+        leaves: leaves
+            .into_iter()
+            .map(|(k, v)| KVPair {
+                k: to_jsonh256(k),
+                v: to_jsonh256(v),
+            })
+            .collect(),
+        proof: JsonBytes::from_vec(proof.0),
+    })
+}
+
+// raw smt keys, block_number
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+enum GetAccountProofParams {
+    Tip((Vec<JsonH256>,)),
+    Number((Vec<JsonH256>, Option<GwUint64>)),
+}
+
+async fn get_account_proof(
+    Params(params): Params<GetAccountProofParams>,
+    store: Data<Store>,
+    rpc_config: Data<RPCConfig>,
+) -> Result<AccountProof, RpcError> {
+    let (keys, block_number) = match params {
+        GetAccountProofParams::Tip(p) => (p.0, None),
+        GetAccountProofParams::Number(p) => p,
+    };
+
+    let db = store.begin_transaction();
+    let keys = keys.into_iter().map(to_h256).collect();
+    build_account_proof(&db, keys, block_number, rpc_config.max_proof_keys)
+}
+
+// account_id, storage keys, block_number
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+enum GetStorageProofParams {
+    Tip((AccountID, Vec<JsonH256>)),
+    Number((AccountID, Vec<JsonH256>, Option<GwUint64>)),
+}
+
+async fn get_storage_proof(
+    Params(params): Params<GetStorageProofParams>,
+    store: Data<Store>,
+    rpc_config: Data<RPCConfig>,
+) -> Result<AccountProof, RpcError> {
+    let (account_id, keys, block_number) = match params {
+        GetStorageProofParams::Tip(p) => (p.0, p.1, None),
+        GetStorageProofParams::Number(p) => p,
+    };
+
+    let db = store.begin_transaction();
+    let account_id: u32 = account_id.into();
+    let keys = keys
+        .into_iter()
+        .map(|key| build_account_key(account_id, to_h256(key).as_slice()))
+        .collect();
+    build_account_proof(&db, keys, block_number, rpc_config.max_proof_keys)
+}
+
+// script_hash, block_number
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+enum GetAccountIdByScriptHashParams {
+    Tip((JsonH256,)),
+    Number((JsonH256, Option<StateCheckPoint>)),
+}
+
 async fn get_account_id_by_script_hash(
-    Params((script_hash,)): Params<(JsonH256,)>,
+    Params(params): Params<GetAccountIdByScriptHashParams>,
     mem_pool: Data<MemPool>,
     store: Data<Store>,
 ) -> Result<Option<AccountID>, RpcError> {
+    let (script_hash, checkpoint) = match params {
+        GetAccountIdByScriptHashParams::Tip(p) => (p.0, None),
+        GetAccountIdByScriptHashParams::Number(p) => p,
+    };
+
     let db = store.begin_transaction();
-    let state_db = get_state_db_at_block(&db, None, mem_pool.is_some())?;
+    let state_db = get_state_db_at_checkpoint(&db, checkpoint, mem_pool.is_some())?;
     let tree = state_db.state_tree()?;
 
     let script_hash = to_h256(script_hash);
@@ -694,7 +1207,7 @@ async fn get_account_id_by_script_hash(
 #[serde(untagged)]
 enum GetNonceParams {
     Tip((AccountID,)),
-    Number((AccountID, Option<GwUint64>)),
+    Number((AccountID, Option<StateCheckPoint>)),
 }
 
 async fn get_nonce(
@@ -702,13 +1215,13 @@ async fn get_nonce(
     mem_pool: Data<MemPool>,
     store: Data<Store>,
 ) -> Result<Uint32, RpcError> {
-    let (account_id, block_number) = match params {
+    let (account_id, checkpoint) = match params {
         GetNonceParams::Tip(p) => (p.0, None),
         GetNonceParams::Number(p) => p,
     };
 
     let db = store.begin_transaction();
-    let state_db = get_state_db_at_block(&db, block_number, mem_pool.is_some())?;
+    let state_db = get_state_db_at_checkpoint(&db, checkpoint, mem_pool.is_some())?;
     let tree = state_db.state_tree()?;
 
     let nonce = tree.get_nonce(account_id.into())?;
@@ -716,13 +1229,26 @@ async fn get_nonce(
     Ok(nonce.into())
 }
 
+// script_hash, block_number
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+enum GetScriptParams {
+    Tip((JsonH256,)),
+    Number((JsonH256, Option<StateCheckPoint>)),
+}
+
 async fn get_script(
-    Params((script_hash,)): Params<(JsonH256,)>,
+    Params(params): Params<GetScriptParams>,
     mem_pool: Data<MemPool>,
     store: Data<Store>,
 ) -> Result<Option<Script>, RpcError> {
+    let (script_hash, checkpoint) = match params {
+        GetScriptParams::Tip(p) => (p.0, None),
+        GetScriptParams::Number(p) => p,
+    };
+
     let db = store.begin_transaction();
-    let state_db = get_state_db_at_block(&db, None, mem_pool.is_some())?;
+    let state_db = get_state_db_at_checkpoint(&db, checkpoint, mem_pool.is_some())?;
     let tree = state_db.state_tree()?;
 
     let script_hash = to_h256(script_hash);
@@ -731,26 +1257,52 @@ async fn get_script(
     Ok(script_opt)
 }
 
+// account_id, block_number
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+enum GetScriptHashParams {
+    Tip((AccountID,)),
+    Number((AccountID, Option<StateCheckPoint>)),
+}
+
 async fn get_script_hash(
-    Params((account_id,)): Params<(AccountID,)>,
+    Params(params): Params<GetScriptHashParams>,
     mem_pool: Data<MemPool>,
     store: Data<Store>,
 ) -> Result<JsonH256, RpcError> {
+    let (account_id, checkpoint) = match params {
+        GetScriptHashParams::Tip(p) => (p.0, None),
+        GetScriptHashParams::Number(p) => p,
+    };
+
     let db = store.begin_transaction();
-    let state_db = get_state_db_at_block(&db, None, mem_pool.is_some())?;
+    let state_db = get_state_db_at_checkpoint(&db, checkpoint, mem_pool.is_some())?;
     let tree = state_db.state_tree()?;
 
     let script_hash = tree.get_script_hash(account_id.into())?;
     Ok(to_jsonh256(script_hash))
 }
 
+// short_address, block_number
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+enum GetScriptHashByShortAddressParams {
+    Tip((JsonBytes,)),
+    Number((JsonBytes, Option<StateCheckPoint>)),
+}
+
 async fn get_script_hash_by_short_address(
-    Params((short_address,)): Params<(JsonBytes,)>,
+    Params(params): Params<GetScriptHashByShortAddressParams>,
     mem_pool: Data<MemPool>,
     store: Data<Store>,
 ) -> Result<Option<JsonH256>, RpcError> {
+    let (short_address, checkpoint) = match params {
+        GetScriptHashByShortAddressParams::Tip(p) => (p.0, None),
+        GetScriptHashByShortAddressParams::Number(p) => p,
+    };
+
     let db = store.begin_transaction();
-    let state_db = get_state_db_at_block(&db, None, mem_pool.is_some())?;
+    let state_db = get_state_db_at_checkpoint(&db, checkpoint, mem_pool.is_some())?;
     let tree = state_db.state_tree()?;
     let script_hash_opt = tree.get_script_hash_by_short_address(&short_address.into_bytes());
     Ok(script_hash_opt.map(to_jsonh256))
@@ -761,7 +1313,7 @@ async fn get_script_hash_by_short_address(
 #[serde(untagged)]
 enum GetDataParams {
     Tip((JsonH256,)),
-    Number((JsonH256, Option<GwUint64>)),
+    Number((JsonH256, Option<StateCheckPoint>)),
 }
 
 async fn get_data(
@@ -769,13 +1321,13 @@ async fn get_data(
     mem_pool: Data<MemPool>,
     store: Data<Store>,
 ) -> Result<Option<JsonBytes>, RpcError> {
-    let (data_hash, block_number) = match params {
+    let (data_hash, checkpoint) = match params {
         GetDataParams::Tip(p) => (p.0, None),
         GetDataParams::Number(p) => p,
     };
 
     let db = store.begin_transaction();
-    let state_db = get_state_db_at_block(&db, block_number, mem_pool.is_some())?;
+    let state_db = get_state_db_at_checkpoint(&db, checkpoint, mem_pool.is_some())?;
     let tree = state_db.state_tree()?;
 
     let data_opt = tree
@@ -794,13 +1346,84 @@ async fn compute_l2_sudt_script_hash(
     Ok(to_jsonh256(l2_sudt_script.hash().into()))
 }
 
-async fn get_node_info(backend_info: Data<Vec<BackendInfo>>) -> Result<NodeInfo> {
+async fn get_node_info(generator: Data<Generator>) -> Result<NodeInfo> {
     Ok(NodeInfo {
         version: Version::current().to_string(),
-        backends: backend_info.clone(),
+        backends: get_backend_info(&generator),
     })
 }
 
+// validator_path, generator_path, validator_script_type_hash, activation_block_number
+type RegisterBackendParams = (String, String, JsonH256, GwUint64);
+
+/// Directory of backends which can be registered by admin RPCs
+struct BackendsDir(PathBuf);
+
+/// Resolve `path` relative to the backends dir, reject paths outside of it
+fn resolve_backend_path(backends_dir: &Path, path: &str) -> Result<PathBuf, RpcError> {
+    let backends_dir = backends_dir.canonicalize().map_err(|err| RpcError::Full {
+        code: INTERNAL_ERROR_ERR_CODE,
+        message: format!("backends dir: {}", err),
+        data: None,
+    })?;
+    let outside_err = || RpcError::Full {
+        code: INVALID_PARAM_ERR_CODE,
+        message: format!("backend path {} isn't in the backends dir", path),
+        data: None,
+    };
+    // canonicalize resolves `..` and symlinks, a missing file is rejected too
+    let resolved = backends_dir
+        .join(path)
+        .canonicalize()
+        .map_err(|_| outside_err())?;
+    if !resolved.starts_with(&backends_dir) || !resolved.is_file() {
+        return Err(outside_err());
+    }
+    Ok(resolved)
+}
+
+async fn admin_register_backend(
+    Params(params): Params<RegisterBackendParams>,
+    generator: Data<Generator>,
+    store: Data<Store>,
+    backends_dir: Data<BackendsDir>,
+) -> Result<(), RpcError> {
+    let (validator_path, generator_path, validator_script_type_hash, activation_block_number) =
+        params;
+    let validator_path = resolve_backend_path(&backends_dir.0, &validator_path)?;
+    let generator_path = resolve_backend_path(&backends_dir.0, &generator_path)?;
+    // executed blocks, including the mem block, must be replayed by the same backend
+    let mem_block_number: u64 = store.get_tip_block()?.raw().number().unpack() + 1;
+    let activation_block_number = activation_block_number.value();
+    if activation_block_number <= mem_block_number {
+        return Err(RpcError::Provided {
+            code: INVALID_PARAM_ERR_CODE,
+            message: "activation block number must be greater than the mem block number",
+        });
+    }
+
+    let config = BackendConfig {
+        validator_path,
+        generator_path,
+        validator_script_type_hash: validator_script_type_hash.clone(),
+        activation_block_number,
+        native_generator: None,
+    };
+    generator
+        .register_backend_config(config)
+        .map_err(|err| RpcError::Full {
+            code: INTERNAL_ERROR_ERR_CODE,
+            message: err.to_string(),
+            data: None,
+        })?;
+    log::info!(
+        "register backend {} at block {}",
+        validator_script_type_hash,
+        activation_block_number
+    );
+    Ok(())
+}
+
 async fn tests_produce_block(
     Params((payload,)): Params<(TestModePayload,)>,
     tests_rpc_impl: Data<BoxedTestsRPCImpl>,
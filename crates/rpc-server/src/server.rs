@@ -1,42 +1,116 @@
 // Taken and adapted from https://github.com/smol-rs/smol/blob/ad0839e1b3700dd33abb9bf23c1efd3c83b5bb2d/examples/hyper-server.rs
+use std::collections::HashSet;
 use std::net::SocketAddr;
 use std::net::{Shutdown, TcpListener, TcpStream};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Instant;
 
 use anyhow::{Error, Result};
+use gw_metrics::GLOBAL_METRICS;
 use hyper::service::{make_service_fn, service_fn};
 use hyper::{body::HttpBody, Body, Method, Request, Response, Server};
 use smol::{io, prelude::*, Async};
 
 use jsonrpc_v2::{RequestKind, ResponseObjects, Router, Server as JsonrpcServer};
 
-use crate::registry::Registry;
+use crate::registry::{AdminRegistry, RPCServer, Registry};
 
 pub async fn start_jsonrpc_server(listen_addr: SocketAddr, registry: Registry) -> Result<()> {
-    let rpc_server = registry.build_rpc_server()?;
+    serve_jsonrpc_server(listen_addr, registry.build_rpc_server()?, "JSONRPC").await
+}
+
+pub async fn start_admin_jsonrpc_server(
+    listen_addr: SocketAddr,
+    registry: AdminRegistry,
+) -> Result<()> {
+    serve_jsonrpc_server(listen_addr, registry.build_rpc_server()?, "Admin JSONRPC").await
+}
+
+async fn serve_jsonrpc_server(
+    listen_addr: SocketAddr,
+    server: RPCServer,
+    name: &str,
+) -> Result<()> {
+    let RPCServer {
+        server: rpc_server,
+        methods,
+    } = server;
     let listener = Async::<TcpListener>::bind(listen_addr)?;
 
     // Format the full address.
     let url = format!("http://{}", listener.get_ref().local_addr()?);
-    log::info!("JSONRPC server listening on {}", url);
+    log::info!("{} server listening on {}", name, url);
 
     // Start a hyper server.
     Server::builder(SmolListener::new(&listener))
         .executor(SmolExecutor)
         .serve(make_service_fn(move |_| {
             let rpc_server = Arc::clone(&rpc_server);
-            async { Ok::<_, Error>(service_fn(move |req| serve(Arc::clone(&rpc_server), req))) }
+            let methods = Arc::clone(&methods);
+            async {
+                Ok::<_, Error>(service_fn(move |req| {
+                    serve(Arc::clone(&rpc_server), Arc::clone(&methods), req)
+                }))
+            }
+        }))
+        .await?;
+
+    Ok(())
+}
+
+pub async fn start_metrics_server(listen_addr: SocketAddr) -> Result<()> {
+    let listener = Async::<TcpListener>::bind(listen_addr)?;
+
+    // Format the full address.
+    let url = format!("http://{}/metrics", listener.get_ref().local_addr()?);
+    log::info!("Metrics server listening on {}", url);
+
+    Server::builder(SmolListener::new(&listener))
+        .executor(SmolExecutor)
+        .serve(make_service_fn(|_| async {
+            Ok::<_, Error>(service_fn(serve_metrics))
         }))
         .await?;
 
     Ok(())
 }
 
+// Serves prometheus metrics.
+async fn serve_metrics(req: Request<Body>) -> Result<Response<Body>> {
+    if req.method() != Method::GET || req.uri().path() != "/metrics" {
+        return hyper::Response::builder()
+            .status(hyper::StatusCode::NOT_FOUND)
+            .body(Body::empty())
+            .map_err(|e| anyhow::anyhow!("Metrics Request error: {:?}", e));
+    }
+
+    hyper::Response::builder()
+        .status(hyper::StatusCode::OK)
+        .header("Content-Type", "text/plain; version=0.0.4")
+        .body(Body::from(GLOBAL_METRICS.encode()))
+        .map_err(|e| anyhow::anyhow!("Metrics Request error: {:?}", e))
+}
+
+// Method name for metrics, batch requests are recorded as a whole.
+// Unregistered methods share one label, so clients can't grow the label set.
+fn request_method(body: &[u8], methods: &HashSet<&'static str>) -> &'static str {
+    match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(serde_json::Value::Array(_)) => "batch",
+        Ok(req) => req
+            .get("method")
+            .and_then(|m| m.as_str())
+            .and_then(|m| methods.get(m).cloned())
+            .unwrap_or("unknown"),
+        Err(_) => "unknown",
+    }
+}
+
 // Serves a request and returns a response.
 async fn serve<R: Router + 'static>(
     rpc: Arc<JsonrpcServer<R>>,
+    methods: Arc<HashSet<&'static str>>,
     req: Request<Body>,
 ) -> Result<Response<Body>> {
     if req.method() == Method::OPTIONS {
@@ -66,7 +140,16 @@ async fn serve<R: Router + 'static>(
         buf.extend(chunk?);
     }
 
-    match rpc.handle(RequestKind::Bytes(buf.freeze())).await {
+    let method = request_method(&buf, &methods);
+    let now = Instant::now();
+    let response = rpc.handle(RequestKind::Bytes(buf.freeze())).await;
+    GLOBAL_METRICS.rpc_requests.with_label(method).inc();
+    GLOBAL_METRICS
+        .rpc_duration
+        .with_label(method)
+        .observe_duration(now.elapsed());
+
+    match response {
         ResponseObjects::Empty => hyper::Response::builder()
             .status(hyper::StatusCode::NO_CONTENT)
             .body(hyper::Body::from(Vec::<u8>::new()))
@@ -182,3 +265,26 @@ impl tokio::io::AsyncWrite for SmolStream {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_method_label() {
+        let methods: HashSet<&'static str> = vec!["gw_ping"].into_iter().collect();
+        let req = |method: &str| {
+            serde_json::to_vec(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+            }))
+            .unwrap()
+        };
+
+        assert_eq!(request_method(&req("gw_ping"), &methods), "gw_ping");
+        assert_eq!(request_method(&req("gw_random_123"), &methods), "unknown");
+        assert_eq!(request_method(b"[]", &methods), "batch");
+        assert_eq!(request_method(b"not json", &methods), "unknown");
+    }
+}
@@ -54,7 +54,7 @@ pub enum SubState {
 }
 
 impl SubState {
-    fn validate_in_block(&self, block: &L2Block) -> Result<()> {
+    pub fn validate_in_block(&self, block: &L2Block) -> Result<()> {
         match self {
             SubState::Withdrawal(index) => {
                 if *index as usize >= block.withdrawals().len() {
@@ -1,6 +1,6 @@
 //! Storage implementation
 
-use crate::transaction::StoreTransaction;
+use crate::transaction::{StoreTransaction, WithdrawalIndexMigration};
 use crate::write_batch::StoreWriteBatch;
 use anyhow::Result;
 use gw_common::{error::Error, smt::H256};
@@ -13,11 +13,14 @@ use gw_db::{
     DBPinnableSlice, RocksDB,
 };
 use gw_types::{
-    offchain::global_state_from_slice,
-    packed::{self, GlobalState, L2Block, L2Transaction},
+    offchain::{global_state_from_slice, tx_receipt_from_slice},
+    packed::{self, GlobalState, L2Block, L2Transaction, RollupConfig},
     prelude::*,
 };
 
+/// Blocks backfilled per committed transaction by the withdrawal index migration
+const WITHDRAWAL_INDEX_MIGRATION_BATCH_BLOCKS: u64 = 1000;
+
 #[derive(Clone)]
 pub struct Store {
     db: RocksDB,
@@ -63,6 +66,40 @@ impl<'a> Store {
         Ok(())
     }
 
+    /// Backfill withdrawal info and offers of main chain blocks attached before
+    /// they were indexed, returns the number of backfilled blocks.
+    ///
+    /// Blocks are committed in batches, an interrupted migration resumes from
+    /// the last committed batch.
+    pub fn migrate_withdrawal_index(&self, rollup_config: &RollupConfig) -> Result<u64> {
+        let mut total_migrated = 0;
+        loop {
+            let db = self.begin_transaction();
+            let migration = db.migrate_withdrawal_index_batch(
+                rollup_config,
+                WITHDRAWAL_INDEX_MIGRATION_BATCH_BLOCKS,
+            )?;
+            db.commit()?;
+            match migration {
+                WithdrawalIndexMigration::Progress {
+                    migrated,
+                    next_block_number,
+                    tip_block_number,
+                } => {
+                    total_migrated += migrated;
+                    log::info!(
+                        "migrating withdrawal index, block {}/{}",
+                        next_block_number,
+                        tip_block_number
+                    );
+                }
+                WithdrawalIndexMigration::Done { migrated } => {
+                    return Ok(total_migrated + migrated);
+                }
+            }
+        }
+    }
+
     pub fn has_genesis(&self) -> Result<bool> {
         let db = self.begin_transaction();
         Ok(db.get_block_hash_by_number(0)?.is_some())
@@ -143,7 +180,7 @@ impl<'a> Store {
     ) -> Result<Option<packed::TxReceipt>, Error> {
         match self.get(COLUMN_TRANSACTION_RECEIPT, tx_hash.as_slice()) {
             Some(slice) => Ok(Some(
-                packed::TxReceiptReader::from_slice_should_be_ok(slice.as_ref()).to_entity(),
+                tx_receipt_from_slice(slice.as_ref()).expect("tx receipt should be ok"),
             )),
             None => Ok(None),
         }
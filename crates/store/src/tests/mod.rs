@@ -1,3 +1,4 @@
 mod state_db;
 mod transaction;
 mod transaction_clear_block_state;
+mod withdrawal;
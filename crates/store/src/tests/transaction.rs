@@ -1,5 +1,15 @@
-use crate::{traits::KVStore, Store};
-use gw_db::{DBRawIterator, Direction::Forward, IteratorMode};
+use crate::{traits::KVStore, transaction::mem_pool_store::MemPoolStore, Store};
+use gw_common::{merkle_utils::calculate_state_checkpoint, H256};
+use gw_db::{
+    schema::COLUMN_MEM_POOL_TRANSACTION_RECEIPT, DBRawIterator, Direction::Forward, IteratorMode,
+};
+use gw_types::{
+    packed::{
+        FeePayment, L2Block, L2Transaction, LogItem, RawL2Block, SubmitTransactions, TxReceipt,
+        TxReceiptV0,
+    },
+    prelude::*,
+};
 use std::collections::HashMap;
 
 #[test]
@@ -369,3 +379,117 @@ fn range_search_and_delete() {
 
     assert!(store_txn.get(1, &[6, 0]).is_some());
 }
+
+#[test]
+fn get_tx_receipt_v0() {
+    let store = Store::open_tmp().unwrap();
+    let store_txn = store.begin_transaction();
+
+    let log = LogItem::new_builder().account_id(1u32.pack()).build();
+    let receipt_v0 = TxReceiptV0::new_builder()
+        .tx_witness_hash([1u8; 32].pack())
+        .logs(vec![log.clone()].pack())
+        .build();
+    let tx_hash_v0 = H256::from([1u8; 32]);
+    store_txn
+        .insert_raw(
+            COLUMN_MEM_POOL_TRANSACTION_RECEIPT,
+            tx_hash_v0.as_slice(),
+            receipt_v0.as_slice(),
+        )
+        .unwrap();
+
+    let fee_payment = FeePayment::new_builder()
+        .payer_id(2u32.pack())
+        .sudt_id(1u32.pack())
+        .amount(100u128.pack())
+        .build();
+    let receipt = TxReceipt::new_builder()
+        .tx_witness_hash([2u8; 32].pack())
+        .fee_payments(vec![fee_payment.clone()].pack())
+        .build();
+    let tx_hash = H256::from([2u8; 32]);
+    store_txn
+        .insert_mem_pool_transaction_receipt(&tx_hash, receipt.clone())
+        .unwrap();
+
+    let got_v0 = store_txn
+        .get_mem_pool_transaction_receipt(&tx_hash_v0)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        got_v0.tx_witness_hash().as_slice(),
+        receipt_v0.tx_witness_hash().as_slice()
+    );
+    assert_eq!(got_v0.logs().get(0).unwrap().as_slice(), log.as_slice());
+    assert!(got_v0.fee_payments().is_empty());
+
+    let got = store_txn
+        .get_mem_pool_transaction_receipt(&tx_hash)
+        .unwrap()
+        .unwrap();
+    assert_eq!(got.as_slice(), receipt.as_slice());
+    let got_fee_payment = got.fee_payments().get(0).unwrap();
+    assert_eq!(got_fee_payment.as_slice(), fee_payment.as_slice());
+}
+
+#[test]
+fn insert_block_collected_fees() {
+    let store = Store::open_tmp().unwrap();
+    let store_txn = store.begin_transaction();
+
+    let fee_payment = |payer_id: u32, sudt_id: u32, amount: u128| {
+        FeePayment::new_builder()
+            .payer_id(payer_id.pack())
+            .sudt_id(sudt_id.pack())
+            .amount(amount.pack())
+            .build()
+    };
+    let receipts = vec![
+        TxReceipt::new_builder()
+            .fee_payments(vec![fee_payment(2, 1, 100), fee_payment(2, 3, 7)].pack())
+            .build(),
+        TxReceipt::new_builder()
+            .fee_payments(vec![fee_payment(4, 1, 50)].pack())
+            .build(),
+    ];
+    let block = {
+        let prev_state_checkpoint: [u8; 32] = calculate_state_checkpoint(&H256::zero(), 0).into();
+        let submit_txs = SubmitTransactions::new_builder()
+            .tx_count(2u32.pack())
+            .prev_state_checkpoint(prev_state_checkpoint.pack())
+            .build();
+        let raw = RawL2Block::new_builder()
+            .submit_transactions(submit_txs)
+            .state_checkpoint_list(vec![[1u8; 32], [2u8; 32]].pack())
+            .build();
+        let txs = vec![L2Transaction::default(), L2Transaction::default()];
+        L2Block::new_builder()
+            .raw(raw)
+            .transactions(txs.pack())
+            .build()
+    };
+    store_txn
+        .insert_block(
+            block.clone(),
+            Default::default(),
+            Default::default(),
+            Vec::new(),
+            Default::default(),
+            receipts,
+            Vec::new(),
+        )
+        .unwrap();
+
+    let collected_fees = store_txn
+        .get_block_collected_fees(&block.hash().into())
+        .unwrap();
+    let expected: Vec<(u32, u128)> = vec![(1, 150), (3, 7)];
+    assert_eq!(collected_fees.into_iter().collect::<Vec<_>>(), expected);
+
+    // no fee paid
+    let collected_fees = store_txn
+        .get_block_collected_fees(&H256::from([3u8; 32]))
+        .unwrap();
+    assert!(collected_fees.is_empty());
+}
@@ -0,0 +1,197 @@
+use crate::{traits::KVStore, transaction::WithdrawalIndexMigration, Store};
+use gw_common::{merkle_utils::calculate_state_checkpoint, H256};
+use gw_db::{
+    schema::{COLUMN_META, COLUMN_WITHDRAWAL_INFO, COLUMN_WITHDRAWAL_OFFER, MIGRATION_VERSION_KEY},
+    IteratorMode,
+};
+use gw_types::{
+    packed::{
+        L2Block, RawL2Block, RawWithdrawalRequest, RollupConfig, SubmitTransactions,
+        WithdrawalRequest,
+    },
+    prelude::*,
+};
+
+fn build_withdrawal(nonce: u32, payment_lock_hash: [u8; 32]) -> WithdrawalRequest {
+    let raw = RawWithdrawalRequest::new_builder()
+        .nonce(nonce.pack())
+        .capacity(100_00000000u64.pack())
+        .payment_lock_hash(payment_lock_hash.pack())
+        .build();
+    WithdrawalRequest::new_builder().raw(raw).build()
+}
+
+fn build_block(number: u64, withdrawals: Vec<WithdrawalRequest>) -> L2Block {
+    let prev_state_checkpoint: [u8; 32] = calculate_state_checkpoint(&H256::zero(), 0).into();
+    let submit_txs = SubmitTransactions::new_builder()
+        .prev_state_checkpoint(prev_state_checkpoint.pack())
+        .build();
+    let raw = RawL2Block::new_builder()
+        .number(number.pack())
+        .submit_transactions(submit_txs)
+        .build();
+    L2Block::new_builder()
+        .raw(raw)
+        .withdrawals(withdrawals.pack())
+        .build()
+}
+
+fn insert_and_attach_block(store: &Store, block: L2Block, rollup_config: &RollupConfig) {
+    let db = store.begin_transaction();
+    db.insert_block(
+        block.clone(),
+        Default::default(),
+        Default::default(),
+        Vec::new(),
+        Default::default(),
+        Vec::new(),
+        Vec::new(),
+    )
+    .unwrap();
+    db.attach_block(block, rollup_config).unwrap();
+    db.commit().unwrap();
+}
+
+#[test]
+fn test_withdrawal_info_round_trip() {
+    let store = Store::open_tmp().unwrap();
+    let rollup_config = RollupConfig::new_builder()
+        .finality_blocks(1u64.pack())
+        .build();
+    let plain = build_withdrawal(0, [0u8; 32]);
+    let offer = build_withdrawal(1, [42u8; 32]);
+    let block = build_block(0, vec![plain.clone(), offer.clone()]);
+    insert_and_attach_block(&store, block.clone(), &rollup_config);
+
+    let db = store.begin_transaction();
+    for (index, withdrawal) in vec![plain, offer.clone()].into_iter().enumerate() {
+        let info = db
+            .get_withdrawal_info(&withdrawal.hash().into())
+            .unwrap()
+            .expect("withdrawal info");
+        let block_number: u64 = info.block_number().unpack();
+        assert_eq!(block_number, 0);
+        assert_eq!(&info.key().as_slice()[..32], &block.hash());
+        assert_eq!(&info.key().as_slice()[32..], &(index as u32).to_be_bytes());
+        let stored = db.get_withdrawal_by_key(&info.key()).unwrap();
+        assert_eq!(stored.map(|w| w.as_bytes()), Some(withdrawal.as_bytes()));
+    }
+    assert!(db
+        .get_withdrawal_info(&H256::from([1u8; 32]))
+        .unwrap()
+        .is_none());
+    let offers = db.get_withdrawal_offers(0).unwrap();
+    assert_eq!(offers.len(), 1);
+    assert_eq!(offers[0].0, 0);
+    assert_eq!(offers[0].1.as_bytes(), offer.as_bytes());
+    drop(db);
+
+    // block 1 finalizes block 0, its offers are removed
+    let next_withdrawal = build_withdrawal(2, [0u8; 32]);
+    let next_block = build_block(1, vec![next_withdrawal.clone()]);
+    insert_and_attach_block(&store, next_block.clone(), &rollup_config);
+    let db = store.begin_transaction();
+    assert!(db.get_withdrawal_offers(0).unwrap().is_empty());
+    assert!(db
+        .get_withdrawal_info(&next_withdrawal.hash().into())
+        .unwrap()
+        .is_some());
+
+    // detaching block 1 removes its withdrawal info and restores offers of block 0
+    db.detach_block(&next_block, &rollup_config).unwrap();
+    db.commit().unwrap();
+    let db = store.begin_transaction();
+    assert!(db
+        .get_withdrawal_info(&next_withdrawal.hash().into())
+        .unwrap()
+        .is_none());
+    let offers = db.get_withdrawal_offers(0).unwrap();
+    assert_eq!(offers.len(), 1);
+    assert_eq!(offers[0].1.as_bytes(), offer.as_bytes());
+}
+
+#[test]
+fn test_migrate_withdrawal_index() {
+    let store = Store::open_tmp().unwrap();
+    let rollup_config = RollupConfig::new_builder()
+        .finality_blocks(1u64.pack())
+        .build();
+    let finalized_offer = build_withdrawal(0, [42u8; 32]);
+    let offer = build_withdrawal(1, [42u8; 32]);
+    insert_and_attach_block(
+        &store,
+        build_block(0, vec![finalized_offer.clone()]),
+        &rollup_config,
+    );
+    insert_and_attach_block(&store, build_block(1, vec![offer.clone()]), &rollup_config);
+
+    // simulate a db written before withdrawals were indexed
+    let db = store.begin_transaction();
+    for withdrawal in &[&finalized_offer, &offer] {
+        db.delete(COLUMN_WITHDRAWAL_INFO, &withdrawal.hash())
+            .unwrap();
+    }
+    let offer_keys: Vec<_> = db
+        .get_iter(COLUMN_WITHDRAWAL_OFFER, IteratorMode::Start)
+        .map(|(key, _)| key)
+        .collect();
+    for key in offer_keys {
+        db.delete(COLUMN_WITHDRAWAL_OFFER, &key).unwrap();
+    }
+    db.commit().unwrap();
+
+    // an interrupted migration only committed its first batch
+    let db = store.begin_transaction();
+    assert_eq!(db.get_db_version().unwrap(), 0);
+    assert_eq!(
+        db.migrate_withdrawal_index_batch(&rollup_config, 1)
+            .unwrap(),
+        WithdrawalIndexMigration::Progress {
+            migrated: 1,
+            next_block_number: 1,
+            tip_block_number: 1,
+        }
+    );
+    db.commit().unwrap();
+    let db = store.begin_transaction();
+    assert_eq!(db.get_db_version().unwrap(), 0);
+    assert!(db
+        .get_withdrawal_info(&finalized_offer.hash().into())
+        .unwrap()
+        .is_some());
+    assert!(db
+        .get_withdrawal_info(&offer.hash().into())
+        .unwrap()
+        .is_none());
+
+    // resume from the recorded progress
+    assert_eq!(store.migrate_withdrawal_index(&rollup_config).unwrap(), 1);
+
+    let db = store.begin_transaction();
+    assert!(db.get_db_version().unwrap() > 0);
+    for withdrawal in &[&finalized_offer, &offer] {
+        let info = db
+            .get_withdrawal_info(&withdrawal.hash().into())
+            .unwrap()
+            .expect("backfilled withdrawal info");
+        let stored = db.get_withdrawal_by_key(&info.key()).unwrap();
+        assert_eq!(stored.map(|w| w.as_bytes()), Some(withdrawal.as_bytes()));
+    }
+    // only the unfinalized block has offers
+    let offers = db.get_withdrawal_offers(0).unwrap();
+    assert_eq!(offers.len(), 1);
+    assert_eq!(offers[0].0, 1);
+    assert_eq!(offers[0].1.as_bytes(), offer.as_bytes());
+
+    // migration runs once
+    assert_eq!(store.migrate_withdrawal_index(&rollup_config).unwrap(), 0);
+}
+
+#[test]
+fn test_invalid_db_version() {
+    let store = Store::open_tmp().unwrap();
+    let db = store.begin_transaction();
+    db.insert_raw(COLUMN_META, MIGRATION_VERSION_KEY, &[1u8])
+        .unwrap();
+    assert!(db.get_db_version().is_err());
+}
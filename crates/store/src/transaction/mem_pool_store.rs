@@ -4,9 +4,10 @@ use gw_db::{
     schema::{
         COLUMN_MEM_POOL_TRANSACTION, COLUMN_MEM_POOL_TRANSACTION_RECEIPT,
         COLUMN_MEM_POOL_WITHDRAWAL, COLUMN_META, META_MEM_POOL_BLOCK_INFO,
+        META_MEM_POOL_DEFERRED_TXS,
     },
 };
-use gw_types::{packed, prelude::*};
+use gw_types::{offchain::tx_receipt_from_slice, packed, prelude::*};
 
 use super::StoreTransaction;
 use crate::{constant::MEMORY_BLOCK_NUMBER, traits::KVStore};
@@ -53,6 +54,11 @@ pub trait MemPoolStore {
 
     fn get_mem_pool_block_info(&self) -> Result<Option<packed::BlockInfo>, Error>;
 
+    /// Txs are stored by `insert_mem_pool_transaction`, only hashes are updated here
+    fn update_mem_pool_deferred_txs(&self, tx_hashes: &[H256]) -> Result<(), Error>;
+
+    fn get_mem_pool_deferred_txs(&self) -> Result<Vec<H256>, Error>;
+
     fn clear_mem_block_state(&self) -> Result<(), Error>;
 }
 
@@ -108,9 +114,7 @@ impl MemPoolStore for StoreTransaction {
     ) -> Result<Option<packed::TxReceipt>, Error> {
         Ok(self
             .get(COLUMN_MEM_POOL_TRANSACTION_RECEIPT, tx_hash.as_slice())
-            .map(|slice| {
-                packed::TxReceiptReader::from_slice_should_be_ok(slice.as_ref()).to_entity()
-            }))
+            .map(|slice| tx_receipt_from_slice(slice.as_ref()).expect("tx receipt should be ok")))
     }
 
     fn insert_mem_pool_withdrawal(
@@ -152,4 +156,27 @@ impl MemPoolStore for StoreTransaction {
                 packed::BlockInfoReader::from_slice_should_be_ok(slice.as_ref()).to_entity()
             }))
     }
+
+    fn update_mem_pool_deferred_txs(&self, tx_hashes: &[H256]) -> Result<(), Error> {
+        let tx_hashes = packed::Byte32Vec::new_builder()
+            .set(tx_hashes.iter().map(|tx_hash| tx_hash.pack()).collect())
+            .build();
+        self.insert_raw(
+            COLUMN_META,
+            META_MEM_POOL_DEFERRED_TXS,
+            tx_hashes.as_slice(),
+        )
+    }
+
+    fn get_mem_pool_deferred_txs(&self) -> Result<Vec<H256>, Error> {
+        Ok(self
+            .get(COLUMN_META, META_MEM_POOL_DEFERRED_TXS)
+            .map(|slice| {
+                packed::Byte32VecReader::from_slice_should_be_ok(slice.as_ref())
+                    .iter()
+                    .map(|tx_hash| tx_hash.unpack())
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
 }
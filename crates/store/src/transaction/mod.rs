@@ -1,4 +1,4 @@
 pub mod mem_pool_store;
 mod store_transaction;
 
-pub use store_transaction::StoreTransaction;
+pub use store_transaction::{StoreTransaction, WithdrawalIndexMigration};
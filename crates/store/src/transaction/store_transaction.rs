@@ -4,19 +4,22 @@ use crate::{smt_store_impl::SMTStore, traits::KVStore};
 use gw_common::h256_ext::H256Ext;
 use gw_common::{merkle_utils::calculate_state_checkpoint, smt::SMT, H256};
 use gw_db::schema::{
-    Col, COLUMN_ASSET_SCRIPT, COLUMN_BAD_BLOCK_CHALLENGE_TARGET, COLUMN_BLOCK,
-    COLUMN_BLOCK_DEPOSIT_REQUESTS, COLUMN_BLOCK_GLOBAL_STATE, COLUMN_BLOCK_SMT_BRANCH,
-    COLUMN_BLOCK_SMT_LEAF, COLUMN_BLOCK_STATE_RECORD, COLUMN_CHECKPOINT, COLUMN_INDEX,
+    Col, COLUMN_ASSET_SCRIPT, COLUMN_BAD_BLOCK_CHALLENGE_TARGET, COLUMN_BAD_BLOCK_CONFIRMATION,
+    COLUMN_BLOCK, COLUMN_BLOCK_COLLECTED_FEES, COLUMN_BLOCK_DEPOSIT_REQUESTS,
+    COLUMN_BLOCK_GLOBAL_STATE, COLUMN_BLOCK_SMT_BRANCH, COLUMN_BLOCK_SMT_LEAF,
+    COLUMN_BLOCK_STATE_RECORD, COLUMN_CHECKPOINT, COLUMN_CLEANER_VERIFIER, COLUMN_INDEX,
     COLUMN_L2BLOCK_COMMITTED_INFO, COLUMN_META, COLUMN_REVERTED_BLOCK_SMT_BRANCH,
     COLUMN_REVERTED_BLOCK_SMT_LEAF, COLUMN_REVERTED_BLOCK_SMT_ROOT, COLUMN_TRANSACTION,
-    COLUMN_TRANSACTION_INFO, COLUMN_TRANSACTION_RECEIPT, META_BLOCK_SMT_ROOT_KEY,
-    META_CHAIN_ID_KEY, META_LAST_VALID_TIP_BLOCK_HASH_KEY, META_MEM_BLOCK_ACCOUNT_SMT_COUNT_KEY,
+    COLUMN_TRANSACTION_INFO, COLUMN_TRANSACTION_RECEIPT, COLUMN_WITHDRAWAL_INFO,
+    COLUMN_WITHDRAWAL_OFFER, META_BLOCK_SMT_ROOT_KEY, META_CHAIN_ID_KEY,
+    META_LAST_VALID_TIP_BLOCK_HASH_KEY, META_MEM_BLOCK_ACCOUNT_SMT_COUNT_KEY,
     META_MEM_BLOCK_ACCOUNT_SMT_ROOT_KEY, META_REVERTED_BLOCK_SMT_ROOT_KEY, META_TIP_BLOCK_HASH_KEY,
+    META_WITHDRAWAL_INDEX_MIGRATION_KEY, MIGRATION_VERSION_KEY,
 };
 use gw_db::{
     error::Error, iter::DBIter, DBIterator, Direction::Forward, IteratorMode, RocksDBTransaction,
 };
-use gw_types::offchain::global_state_from_slice;
+use gw_types::offchain::{collect_fees, global_state_from_slice, tx_receipt_from_slice};
 use gw_types::packed::Script;
 use gw_types::{
     packed::{
@@ -25,15 +28,32 @@ use gw_types::{
     },
     prelude::*,
 };
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
 /// TODO use a variable instead of hardcode
 const NUMBER_OF_CONFIRMATION: u64 = 10000;
+/// Since this version withdrawal info and offers are indexed on block attach
+const WITHDRAWAL_INDEX_DB_VERSION: u32 = 1;
+/// sudt id (u32) | amount (u128)
+const COLLECTED_FEE_SIZE: usize = 20;
 
 pub struct StoreTransaction {
     pub(crate) inner: RocksDBTransaction,
 }
 
+/// Result of a withdrawal index migration batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WithdrawalIndexMigration {
+    Progress {
+        migrated: u64,
+        next_block_number: u64,
+        tip_block_number: u64,
+    },
+    Done {
+        migrated: u64,
+    },
+}
+
 impl KVStore for StoreTransaction {
     fn get(&self, col: Col, key: &[u8]) -> Option<Box<[u8]>> {
         self.inner
@@ -276,6 +296,64 @@ impl StoreTransaction {
             }))
     }
 
+    /// Withdrawal info shares the same structure with transaction info,
+    /// the key is block hash | withdrawal index
+    pub fn get_withdrawal_info(
+        &self,
+        withdrawal_hash: &H256,
+    ) -> Result<Option<packed::TransactionInfo>, Error> {
+        let withdrawal_info_opt = self
+            .get(COLUMN_WITHDRAWAL_INFO, withdrawal_hash.as_slice())
+            .map(|slice| {
+                packed::TransactionInfoReader::from_slice_should_be_ok(slice.as_ref()).to_entity()
+            });
+        Ok(withdrawal_info_opt)
+    }
+
+    /// Unfinalized withdrawals with a payment lock, from `from_block_number`
+    /// to the tip, ordered by block number and withdrawal index
+    pub fn get_withdrawal_offers(
+        &self,
+        from_block_number: u64,
+    ) -> Result<Vec<(u64, packed::WithdrawalRequest)>, Error> {
+        let start_key = withdrawal_offer_key(from_block_number, 0);
+        let offers = self
+            .get_iter(
+                COLUMN_WITHDRAWAL_OFFER,
+                IteratorMode::From(&start_key, Forward),
+            )
+            .map(|(key, value)| {
+                let mut buf = [0u8; 8];
+                buf.copy_from_slice(&key[..8]);
+                let withdrawal =
+                    packed::WithdrawalRequestReader::from_slice_should_be_ok(value.as_ref())
+                        .to_entity();
+                (u64::from_be_bytes(buf), withdrawal)
+            })
+            .collect();
+        Ok(offers)
+    }
+
+    pub fn get_withdrawal_by_key(
+        &self,
+        withdrawal_key: &TransactionKey,
+    ) -> Result<Option<packed::WithdrawalRequest>, Error> {
+        let key = withdrawal_key.as_slice();
+        let block_hash = {
+            let mut buf = [0u8; 32];
+            buf.copy_from_slice(&key[..32]);
+            H256::from(buf)
+        };
+        let index = {
+            let mut buf = [0u8; 4];
+            buf.copy_from_slice(&key[32..]);
+            u32::from_be_bytes(buf)
+        };
+
+        let block = self.get_block(&block_hash)?;
+        Ok(block.and_then(|b| b.withdrawals().get(index as usize)))
+    }
+
     pub fn get_transaction_receipt(
         &self,
         tx_hash: &H256,
@@ -296,9 +374,7 @@ impl StoreTransaction {
     ) -> Result<Option<packed::TxReceipt>, Error> {
         Ok(self
             .get(COLUMN_TRANSACTION_RECEIPT, key.as_slice())
-            .map(|slice| {
-                packed::TxReceiptReader::from_slice_should_be_ok(slice.as_ref()).to_entity()
-            }))
+            .map(|slice| tx_receipt_from_slice(slice.as_ref()).expect("tx receipt should be ok")))
     }
 
     pub fn get_checkpoint_post_state(
@@ -378,6 +454,71 @@ impl StoreTransaction {
         )
     }
 
+    /// Fees paid by block txs, sudt id -> amount. Fee payments are recorded by
+    /// `SYS_PAY_FEE`, the fee itself is transferred to block producer by the sudt contract.
+    pub fn get_block_collected_fees(
+        &self,
+        block_hash: &H256,
+    ) -> Result<BTreeMap<u32, u128>, Error> {
+        let fees = match self.get(COLUMN_BLOCK_COLLECTED_FEES, block_hash.as_slice()) {
+            Some(slice) => slice,
+            None => return Ok(BTreeMap::new()),
+        };
+        if fees.len() % COLLECTED_FEE_SIZE != 0 {
+            return Err(Error::from("invalid block collected fees".to_owned()));
+        }
+        let to_fee = |entry: &[u8]| {
+            let mut sudt_id = [0u8; 4];
+            sudt_id.copy_from_slice(&entry[..4]);
+            let mut amount = [0u8; 16];
+            amount.copy_from_slice(&entry[4..]);
+            (u32::from_le_bytes(sudt_id), u128::from_le_bytes(amount))
+        };
+        Ok(fees.chunks(COLLECTED_FEE_SIZE).map(to_fee).collect())
+    }
+
+    /// Offchain confirmation result of a bad block, none if it isn't confirmed
+    pub fn get_bad_block_confirmed(&self, block_hash: &H256) -> Result<Option<bool>, Error> {
+        let confirmed = self.get(COLUMN_BAD_BLOCK_CONFIRMATION, block_hash.as_slice());
+        Ok(confirmed.map(|slice| slice.as_ref() == [1u8]))
+    }
+
+    pub fn set_bad_block_confirmed(&self, block_hash: &H256, confirmed: bool) -> Result<(), Error> {
+        self.insert_raw(
+            COLUMN_BAD_BLOCK_CONFIRMATION,
+            block_hash.as_slice(),
+            &[confirmed as u8],
+        )
+    }
+
+    pub fn get_cleaner_verifiers(&self) -> Result<Vec<packed::ConsumedVerifier>, Error> {
+        let iter = self.get_iter(COLUMN_CLEANER_VERIFIER, IteratorMode::Start);
+        let to_verifier = iter.map(|(_key, value)| {
+            packed::ConsumedVerifierReader::from_slice_should_be_ok(value.as_ref()).to_entity()
+        });
+
+        Ok(to_verifier.collect())
+    }
+
+    pub fn set_cleaner_verifier(
+        &self,
+        verifier_out_point: &packed::OutPoint,
+        verifier: &packed::ConsumedVerifier,
+    ) -> Result<(), Error> {
+        self.insert_raw(
+            COLUMN_CLEANER_VERIFIER,
+            verifier_out_point.as_slice(),
+            verifier.as_slice(),
+        )
+    }
+
+    pub fn delete_cleaner_verifier(
+        &self,
+        verifier_out_point: &packed::OutPoint,
+    ) -> Result<(), Error> {
+        self.delete(COLUMN_CLEANER_VERIFIER, verifier_out_point.as_slice())
+    }
+
     // TODO: prune db state
     pub fn get_reverted_block_hashes(&self) -> Result<HashSet<H256>, Error> {
         let iter = self.get_iter(COLUMN_REVERTED_BLOCK_SMT_LEAF, IteratorMode::End);
@@ -585,6 +726,16 @@ impl StoreTransaction {
             )?;
         }
 
+        let collected_fees = collect_fees(tx_receipts.iter());
+        if !collected_fees.is_empty() {
+            let mut fees = Vec::with_capacity(collected_fees.len() * COLLECTED_FEE_SIZE);
+            for (sudt_id, amount) in collected_fees {
+                fees.extend_from_slice(&sudt_id.to_le_bytes());
+                fees.extend_from_slice(&amount.to_le_bytes());
+            }
+            self.insert_raw(COLUMN_BLOCK_COLLECTED_FEES, &block_hash, &fees)?;
+        }
+
         let post_states: Vec<AccountMerkleState> = {
             let withdrawal_post_states = withdrawal_receipts.into_iter().map(|w| w.post_state());
             let tx_post_states = tx_receipts.iter().map(|t| t.post_state());
@@ -627,7 +778,7 @@ impl StoreTransaction {
     pub fn attach_block(
         &self,
         block: packed::L2Block,
-        _rollup_config: &RollupConfig,
+        rollup_config: &RollupConfig,
     ) -> Result<(), Error> {
         let raw = block.raw();
         let raw_number = raw.number();
@@ -644,6 +795,17 @@ impl StoreTransaction {
             self.insert_raw(COLUMN_TRANSACTION_INFO, &tx_hash, info.as_slice())?;
         }
 
+        // build withdrawal info
+        self.insert_withdrawal_info(&block)?;
+
+        // index withdrawal offers, offers of the block finalized by this one are removed
+        let block_number: u64 = raw_number.unpack();
+        self.insert_withdrawal_offers(block_number, &block)?;
+        let finality_blocks: u64 = rollup_config.finality_blocks().unpack();
+        if let Some(finalized_number) = block_number.checked_sub(finality_blocks) {
+            self.delete_withdrawal_offers(finalized_number)?;
+        }
+
         // build main chain index
         self.insert_raw(COLUMN_INDEX, raw_number.as_slice(), &block_hash)?;
         self.insert_raw(COLUMN_INDEX, &block_hash, raw_number.as_slice())?;
@@ -667,7 +829,7 @@ impl StoreTransaction {
     pub fn detach_block(
         &self,
         block: &packed::L2Block,
-        _rollup_config: &RollupConfig,
+        rollup_config: &RollupConfig,
     ) -> Result<(), Error> {
         // remove transaction info
         for tx in block.transactions().into_iter() {
@@ -675,6 +837,26 @@ impl StoreTransaction {
             self.delete(COLUMN_TRANSACTION_INFO, &tx_hash)?;
         }
 
+        // remove withdrawal info
+        for withdrawal in block.withdrawals().into_iter() {
+            let withdrawal_hash = withdrawal.hash();
+            self.delete(COLUMN_WITHDRAWAL_INFO, &withdrawal_hash)?;
+        }
+
+        // remove withdrawal offers, offers of the block unfinalized by this detachment are restored
+        let number: u64 = block.raw().number().unpack();
+        self.delete_withdrawal_offers(number)?;
+        let finality_blocks: u64 = rollup_config.finality_blocks().unpack();
+        if let Some(unfinalized_number) = number.checked_sub(finality_blocks) {
+            let unfinalized_block = match self.get_block_hash_by_number(unfinalized_number)? {
+                Some(block_hash) => self.get_block(&block_hash)?,
+                None => None,
+            };
+            if let Some(unfinalized_block) = unfinalized_block {
+                self.insert_withdrawal_offers(unfinalized_number, &unfinalized_block)?;
+            }
+        }
+
         let block_hash: H256 = block.hash().into();
 
         // remove index
@@ -708,6 +890,94 @@ impl StoreTransaction {
         Ok(())
     }
 
+    pub fn get_db_version(&self) -> Result<u32, Error> {
+        match self.get(COLUMN_META, MIGRATION_VERSION_KEY) {
+            Some(slice) => {
+                let mut buf = [0u8; 4];
+                if slice.len() != buf.len() {
+                    return Err(Error::from(format!(
+                        "invalid db version length {}",
+                        slice.len()
+                    )));
+                }
+                buf.copy_from_slice(slice.as_ref());
+                Ok(u32::from_be_bytes(buf))
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// Backfill withdrawal info and offers of at most `batch_blocks` main chain
+    /// blocks attached before they were indexed, resuming from the recorded
+    /// progress. Commit after each batch, see `Store::migrate_withdrawal_index`.
+    pub fn migrate_withdrawal_index_batch(
+        &self,
+        rollup_config: &RollupConfig,
+        batch_blocks: u64,
+    ) -> Result<WithdrawalIndexMigration, Error> {
+        if self.get_db_version()? >= WITHDRAWAL_INDEX_DB_VERSION {
+            return Ok(WithdrawalIndexMigration::Done { migrated: 0 });
+        }
+
+        let mut migrated = 0;
+        // no tip means a fresh db, blocks will be indexed on attach
+        if self.get(COLUMN_META, META_TIP_BLOCK_HASH_KEY).is_some() {
+            let tip_number: u64 = self.get_tip_block()?.raw().number().unpack();
+            let finality_blocks: u64 = rollup_config.finality_blocks().unpack();
+            let start = match self.get(COLUMN_META, META_WITHDRAWAL_INDEX_MIGRATION_KEY) {
+                Some(slice) => {
+                    let mut buf = [0u8; 8];
+                    if slice.len() != buf.len() {
+                        return Err(Error::from(format!(
+                            "invalid withdrawal index migration progress length {}",
+                            slice.len()
+                        )));
+                    }
+                    buf.copy_from_slice(slice.as_ref());
+                    u64::from_be_bytes(buf)
+                }
+                None => 0,
+            };
+            let end = start.saturating_add(batch_blocks).min(tip_number + 1);
+            for block_number in start..end {
+                let block = match self.get_block_hash_by_number(block_number)? {
+                    Some(block_hash) => self.get_block(&block_hash)?,
+                    None => None,
+                };
+                let block = match block {
+                    Some(block) => block,
+                    None => continue,
+                };
+                self.insert_withdrawal_info(&block)?;
+                if block_number + finality_blocks > tip_number {
+                    self.insert_withdrawal_offers(block_number, &block)?;
+                }
+                migrated += 1;
+            }
+
+            if end <= tip_number {
+                self.insert_raw(
+                    COLUMN_META,
+                    META_WITHDRAWAL_INDEX_MIGRATION_KEY,
+                    &end.to_be_bytes(),
+                )?;
+                return Ok(WithdrawalIndexMigration::Progress {
+                    migrated,
+                    next_block_number: end,
+                    tip_block_number: tip_number,
+                });
+            }
+            self.delete(COLUMN_META, META_WITHDRAWAL_INDEX_MIGRATION_KEY)?;
+        }
+
+        self.insert_raw(
+            COLUMN_META,
+            MIGRATION_VERSION_KEY,
+            &WITHDRAWAL_INDEX_DB_VERSION.to_be_bytes(),
+        )?;
+        Ok(WithdrawalIndexMigration::Done { migrated })
+    }
+
     pub fn record_block_state(
         &self,
         block_number: u64,
@@ -739,6 +1009,52 @@ impl StoreTransaction {
         Ok(())
     }
 
+    fn insert_withdrawal_info(&self, block: &packed::L2Block) -> Result<(), Error> {
+        let block_hash = block.hash();
+        let block_number = block.raw().number();
+        for (index, withdrawal) in block.withdrawals().into_iter().enumerate() {
+            let key = TransactionKey::build_transaction_key(block_hash.pack(), index as u32);
+            let info = packed::TransactionInfo::new_builder()
+                .key(key)
+                .block_number(block_number.clone())
+                .build();
+            let withdrawal_hash = withdrawal.hash();
+            self.insert_raw(COLUMN_WITHDRAWAL_INFO, &withdrawal_hash, info.as_slice())?;
+        }
+        Ok(())
+    }
+
+    fn insert_withdrawal_offers(
+        &self,
+        block_number: u64,
+        block: &packed::L2Block,
+    ) -> Result<(), Error> {
+        for (index, withdrawal) in block.withdrawals().into_iter().enumerate() {
+            if withdrawal.raw().payment_lock_hash().as_slice() == [0u8; 32] {
+                continue;
+            }
+            let key = withdrawal_offer_key(block_number, index as u32);
+            self.insert_raw(COLUMN_WITHDRAWAL_OFFER, &key, withdrawal.as_slice())?;
+        }
+        Ok(())
+    }
+
+    fn delete_withdrawal_offers(&self, block_number: u64) -> Result<(), Error> {
+        let start_key = withdrawal_offer_key(block_number, 0);
+        let keys: Vec<Box<[u8]>> = self
+            .get_iter(
+                COLUMN_WITHDRAWAL_OFFER,
+                IteratorMode::From(&start_key, Forward),
+            )
+            .map(|(key, _value)| key)
+            .take_while(|key| key[..8] == block_number.to_be_bytes())
+            .collect();
+        for key in keys {
+            self.delete(COLUMN_WITHDRAWAL_OFFER, &key)?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn clear_block_state(&self, block_number: u64) -> Result<(), Error> {
         let iter = self.iter_block_state_record(block_number);
         for record_key in iter {
@@ -763,6 +1079,14 @@ impl StoreTransaction {
     }
 }
 
+// block_number(8 bytes) | withdrawal_index(4 bytes)
+fn withdrawal_offer_key(block_number: u64, index: u32) -> [u8; 12] {
+    let mut key = [0u8; 12];
+    key[..8].copy_from_slice(&block_number.to_be_bytes());
+    key[8..].copy_from_slice(&index.to_be_bytes());
+    key
+}
+
 // block_number(8 bytes) | tx_index(4 bytes) | col (1 byte) | key (n bytes)
 struct BlockStateRecordKey(Vec<u8>);
 
@@ -3,7 +3,7 @@
 use gw_block_producer::produce_block::{produce_block, ProduceBlockParam, ProduceBlockResult};
 use gw_chain::chain::{Chain, L1Action, L1ActionContext, SyncParam};
 use gw_common::{blake2b::new_blake2b, H256};
-use gw_config::{BackendConfig, ChainConfig, GenesisConfig};
+use gw_config::{BackendConfig, ChainConfig, GenesisConfig, MemPoolConfig};
 use gw_generator::{
     account_lock_manage::{always_success::AlwaysSuccess, AccountLockManage},
     backend_manage::BackendManage,
@@ -73,17 +73,28 @@ pub fn build_backend_manage(rollup_config: &RollupConfig) -> BackendManage {
             validator_path: META_VALIDATOR_PATH.into(),
             generator_path: META_GENERATOR_PATH.into(),
             validator_script_type_hash: META_VALIDATOR_SCRIPT_TYPE_HASH.into(),
+            activation_block_number: 0,
+            native_generator: None,
         },
         BackendConfig {
             validator_path: SUDT_VALIDATOR_PATH.into(),
             generator_path: SUDT_GENERATOR_PATH.into(),
             validator_script_type_hash: sudt_validator_script_type_hash.into(),
+            activation_block_number: 0,
+            native_generator: None,
         },
     ];
     BackendManage::from_config(configs).expect("default backend")
 }
 
 pub fn setup_chain(rollup_type_script: Script) -> Chain {
+    setup_chain_with_mem_pool_config(rollup_type_script, Default::default())
+}
+
+pub fn setup_chain_with_mem_pool_config(
+    rollup_type_script: Script,
+    mem_pool_config: MemPoolConfig,
+) -> Chain {
     let mut account_lock_manage = AccountLockManage::default();
     let rollup_config = RollupConfig::new_builder()
         .allowed_eoa_type_hashes(vec![*ALWAYS_SUCCESS_CODE_HASH].pack())
@@ -91,10 +102,11 @@ pub fn setup_chain(rollup_type_script: Script) -> Chain {
         .build();
     account_lock_manage
         .register_lock_algorithm((*ALWAYS_SUCCESS_CODE_HASH).into(), Box::new(AlwaysSuccess));
-    let mut chain = setup_chain_with_account_lock_manage(
+    let mut chain = build_chain(
         rollup_type_script,
         rollup_config,
         account_lock_manage,
+        mem_pool_config,
     );
     chain.complete_initial_syncing().unwrap();
     chain
@@ -104,6 +116,20 @@ pub fn setup_chain_with_account_lock_manage(
     rollup_type_script: Script,
     rollup_config: RollupConfig,
     account_lock_manage: AccountLockManage,
+) -> Chain {
+    build_chain(
+        rollup_type_script,
+        rollup_config,
+        account_lock_manage,
+        Default::default(),
+    )
+}
+
+fn build_chain(
+    rollup_type_script: Script,
+    rollup_config: RollupConfig,
+    account_lock_manage: AccountLockManage,
+    mem_pool_config: MemPoolConfig,
 ) -> Chain {
     let store = Store::open_tmp().unwrap();
     let rollup_script_hash = rollup_type_script.hash();
@@ -113,6 +139,7 @@ pub fn setup_chain_with_account_lock_manage(
         rollup_config: rollup_config.clone().into(),
         rollup_type_hash: rollup_script_hash.into(),
         secp_data_dep: Default::default(),
+        sudt_bond: None,
     };
     let genesis_committed_info = L2BlockCommittedInfo::default();
     let backend_manage = build_backend_manage(&rollup_config);
@@ -125,6 +152,7 @@ pub fn setup_chain_with_account_lock_manage(
         account_lock_manage,
         rollup_context,
         Default::default(),
+        Default::default(),
     ));
     init_genesis(
         &store,
@@ -139,7 +167,7 @@ pub fn setup_chain_with_account_lock_manage(
         Arc::clone(&generator),
         Box::new(provider),
         None,
-        Default::default(),
+        mem_pool_config,
     )
     .unwrap();
     Chain::create(
@@ -160,6 +188,7 @@ pub fn build_sync_tx(
     let ProduceBlockResult {
         block,
         global_state,
+        ..
     } = produce_block_result;
     let rollup_action = {
         let submit_block = RollupSubmitBlock::new_builder().block(block).build();
@@ -258,6 +287,7 @@ pub fn construct_block(
                 data: Default::default(),
             },
             request: deposit,
+            block_number: 0,
         })
         .collect();
     let provider = DummyMemPoolProvider {
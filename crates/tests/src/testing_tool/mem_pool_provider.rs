@@ -4,7 +4,7 @@ use anyhow::Result;
 use gw_mem_pool::traits::MemPoolProvider;
 use gw_types::{
     offchain::{CollectedCustodianCells, DepositInfo, RollupContext},
-    packed::WithdrawalRequest,
+    packed::{OutPoint, WithdrawalRequest},
 };
 use smol::Task;
 
@@ -24,6 +24,12 @@ impl MemPoolProvider for DummyMemPoolProvider {
         let deposit_cells = self.deposit_cells.clone();
         smol::spawn(async move { Ok(deposit_cells) })
     }
+    fn query_cancel_timeout_deposits(
+        &self,
+        _deposits: Vec<DepositInfo>,
+    ) -> Task<Result<Vec<OutPoint>>> {
+        smol::spawn(async move { Ok(vec![]) })
+    }
     fn query_available_custodians(
         &self,
         _withdrawals: Vec<WithdrawalRequest>,
@@ -1,2 +1,4 @@
 pub mod chain;
 pub mod mem_pool_provider;
+pub mod node_rpc;
+pub mod rpc_server;
@@ -0,0 +1,75 @@
+//! Serve node RPC requests in process, without a HTTP server
+
+use gw_block_producer::test_mode_control::TestModeControl;
+use gw_chain::chain::Chain;
+use gw_config::{DebugConfig, MemPoolConfig, NodeMode, RPCConfig};
+use gw_rpc_server::registry::{AdminRegistry, RPCServer, Registry};
+use jsonrpc_v2::RequestKind;
+use serde_json::{json, Value};
+use smol::lock::Mutex;
+
+use std::{path::PathBuf, sync::Arc};
+
+/// Build the RPC server of a node running `chain`
+pub fn build_node_rpc_server(
+    chain: &Arc<Mutex<Chain>>,
+    rpc_config: RPCConfig,
+    node_mode: NodeMode,
+) -> RPCServer {
+    let (store, mem_pool, generator) = {
+        let chain = smol::block_on(chain.lock());
+        // read-only nodes have no mem-pool
+        let mem_pool = match node_mode {
+            NodeMode::ReadOnly => None,
+            _ => chain.mem_pool().clone(),
+        };
+        (
+            chain.store().clone(),
+            mem_pool,
+            Arc::clone(chain.generator()),
+        )
+    };
+    let rollup_config = generator.rollup_context().rollup_config.clone();
+    Registry::new(
+        store,
+        mem_pool,
+        generator,
+        None::<Box<TestModeControl>>,
+        rollup_config,
+        DebugConfig::default(),
+        Arc::clone(chain),
+        None,
+        MemPoolConfig::default(),
+        rpc_config,
+        node_mode,
+    )
+    .build_rpc_server()
+    .expect("build rpc server")
+}
+
+/// Build the admin RPC server of a node running `chain`
+pub fn build_admin_rpc_server(chain: &Arc<Mutex<Chain>>, backends_dir: PathBuf) -> RPCServer {
+    let (store, generator) = {
+        let chain = smol::block_on(chain.lock());
+        (chain.store().clone(), Arc::clone(chain.generator()))
+    };
+    AdminRegistry::new(store, generator, backends_dir)
+        .build_rpc_server()
+        .expect("build admin rpc server")
+}
+
+/// Call `method`, returns the result or the error object of the response
+pub fn call(server: &RPCServer, method: &str, params: Value) -> Result<Value, Value> {
+    let req = json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params});
+    let req = serde_json::to_vec(&req).expect("serialize request");
+    let resp = smol::block_on(
+        server
+            .server
+            .handle(RequestKind::Bytes(bytes_v10::Bytes::from(req))),
+    );
+    let resp = serde_json::to_value(&resp).expect("serialize response");
+    match resp.get("error") {
+        Some(err) => Err(err.clone()),
+        None => Ok(resp["result"].clone()),
+    }
+}
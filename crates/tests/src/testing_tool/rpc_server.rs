@@ -0,0 +1,144 @@
+//! A minimal JSONRPC server to stub CKB node / indexer / signer RPCs in tests
+
+use async_jsonrpc_client::HttpClient;
+use gw_rpc_client::rpc_client::RPCClient;
+use gw_types::{offchain::RollupContext, packed::RollupConfig};
+use serde_json::{json, Value};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+type Handler = dyn Fn(&str, &Value) -> Option<Value> + Send + Sync;
+
+pub struct MockRPCServer {
+    url: String,
+    requests: Arc<Mutex<Vec<(String, Value)>>>,
+}
+
+impl MockRPCServer {
+    /// Start a server on a random local port, `handler` returns the result
+    /// of a method call or `None` for method not found
+    pub fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&str, &Value) -> Option<Value> + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let url = format!("http://{}", listener.local_addr().expect("addr"));
+        let requests: Arc<Mutex<Vec<(String, Value)>>> = Default::default();
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        {
+            let requests = Arc::clone(&requests);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let stream = match stream {
+                        Ok(stream) => stream,
+                        Err(_) => continue,
+                    };
+                    let requests = Arc::clone(&requests);
+                    let handler = Arc::clone(&handler);
+                    thread::spawn(move || {
+                        let _ = serve(stream, &requests, handler.as_ref());
+                    });
+                }
+            });
+        }
+
+        MockRPCServer { url, requests }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// A client using this server as both the CKB node and the indexer
+    pub fn rpc_client(&self) -> RPCClient {
+        let rollup_context = RollupContext {
+            rollup_script_hash: Default::default(),
+            rollup_config: RollupConfig::default(),
+        };
+        RPCClient::new(
+            Default::default(),
+            rollup_context,
+            HttpClient::new(self.url.clone()).expect("ckb client"),
+            HttpClient::new(self.url.clone()).expect("indexer client"),
+        )
+    }
+
+    /// Called methods in order
+    pub fn methods(&self) -> Vec<String> {
+        let requests = self.requests.lock().unwrap();
+        requests.iter().map(|(method, _)| method.clone()).collect()
+    }
+
+    /// Params of all calls to `method`
+    pub fn params_of(&self, method: &str) -> Vec<Value> {
+        let requests = self.requests.lock().unwrap();
+        requests
+            .iter()
+            .filter(|(m, _)| m == method)
+            .map(|(_, params)| params.clone())
+            .collect()
+    }
+}
+
+fn serve(
+    stream: TcpStream,
+    requests: &Mutex<Vec<(String, Value)>>,
+    handler: &Handler,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    loop {
+        // headers
+        let mut content_length = 0usize;
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        loop {
+            line.clear();
+            reader.read_line(&mut line)?;
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+
+        // body
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body)?;
+        let req: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+        let method = req["method"].as_str().unwrap_or_default().to_string();
+        let params = req["params"].clone();
+        requests
+            .lock()
+            .unwrap()
+            .push((method.clone(), params.clone()));
+
+        let resp = match handler(&method, &params) {
+            Some(result) => json!({"jsonrpc": "2.0", "id": req["id"], "result": result}),
+            None => json!({
+                "jsonrpc": "2.0",
+                "id": req["id"],
+                "error": {"code": -32601, "message": "Method not found"},
+            }),
+        };
+        let resp = serde_json::to_vec(&resp)?;
+        write!(
+            writer,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+            resp.len()
+        )?;
+        writer.write_all(&resp)?;
+        writer.flush()?;
+    }
+}
@@ -0,0 +1,72 @@
+use crate::testing_tool::{
+    chain::setup_chain,
+    node_rpc::{build_admin_rpc_server, build_node_rpc_server, call},
+};
+
+use gw_config::NodeMode;
+use gw_types::packed::Script;
+use serde_json::json;
+use smol::lock::Mutex;
+
+use std::{fs, sync::Arc};
+
+const BACKEND_TYPE_HASH: &str =
+    "0x0101010101010101010101010101010101010101010101010101010101010101";
+
+#[test]
+fn test_public_rpc_server_does_not_expose_admin_rpc() {
+    let chain = Arc::new(Mutex::new(setup_chain(Script::default())));
+    let rpc_server = build_node_rpc_server(&chain, Default::default(), NodeMode::FullNode);
+
+    assert!(!rpc_server.methods.contains("admin_register_backend"));
+    let params = json!(["validator", "generator", BACKEND_TYPE_HASH, "0x64"]);
+    let err = call(&rpc_server, "admin_register_backend", params).unwrap_err();
+    assert_eq!(err["code"], json!(-32601));
+}
+
+#[test]
+fn test_admin_register_backend_paths() {
+    let chain = Arc::new(Mutex::new(setup_chain(Script::default())));
+    let root = tempfile::tempdir().expect("tempdir");
+    let backends_dir = root.path().join("backends");
+    fs::create_dir(&backends_dir).unwrap();
+    fs::write(backends_dir.join("validator"), b"validator").unwrap();
+    fs::write(backends_dir.join("generator"), b"generator").unwrap();
+    fs::write(root.path().join("outside"), b"outside").unwrap();
+    let admin_server = build_admin_rpc_server(&chain, backends_dir.clone());
+
+    // paths outside of the backends dir
+    let outside = root.path().join("outside");
+    for path in vec![
+        outside.to_str().unwrap(),
+        "../outside",
+        "missing",
+        "/etc/passwd",
+    ] {
+        let params = json!([path, "generator", BACKEND_TYPE_HASH, "0x64"]);
+        let err = call(&admin_server, "admin_register_backend", params).unwrap_err();
+        assert_eq!(err["code"], json!(-32602), "path {}", path);
+        let params = json!(["validator", path, BACKEND_TYPE_HASH, "0x64"]);
+        let err = call(&admin_server, "admin_register_backend", params).unwrap_err();
+        assert_eq!(err["code"], json!(-32602), "path {}", path);
+    }
+
+    // relative and absolute paths in the backends dir
+    let validator = backends_dir.join("validator");
+    let params = json!([
+        validator.to_str().unwrap(),
+        "generator",
+        BACKEND_TYPE_HASH,
+        "0x64"
+    ]);
+    assert_eq!(
+        call(&admin_server, "admin_register_backend", params),
+        Ok(json!(null))
+    );
+    let generator = smol::block_on(chain.lock()).generator().clone();
+    let registered = generator
+        .get_backends()
+        .iter()
+        .any(|b| b.activation_block_number == 100 && &b.generator[..] == b"generator");
+    assert!(registered);
+}
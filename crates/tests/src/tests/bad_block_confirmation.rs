@@ -0,0 +1,107 @@
+use gw_chain::chain::{BadBlockConfirmation, CancelChallengeAttempt, CancelChallengeResult};
+use gw_challenge::cancel_challenge::LoadDataStrategy;
+use gw_common::H256;
+use gw_store::Store;
+use gw_types::{
+    core::ChallengeTargetType,
+    packed::{ChallengeTarget, L2Block},
+    prelude::*,
+};
+
+fn target() -> ChallengeTarget {
+    ChallengeTarget::new_builder()
+        .block_hash(L2Block::default().hash().pack())
+        .target_index(1u32.pack())
+        .target_type((ChallengeTargetType::TxExecution as u8).into())
+        .build()
+}
+
+fn attempt(
+    load_data_strategy: LoadDataStrategy,
+    result: CancelChallengeResult,
+) -> CancelChallengeAttempt {
+    CancelChallengeAttempt {
+        load_data_strategy,
+        result,
+        dump_path: None,
+    }
+}
+
+#[test]
+fn test_confirm_bad_block_if_all_cancel_txs_rejected() {
+    let attempts = vec![
+        attempt(
+            LoadDataStrategy::Witness,
+            CancelChallengeResult::Rejected("script error".to_owned()),
+        ),
+        attempt(
+            LoadDataStrategy::CellDep,
+            CancelChallengeResult::Rejected("cancel tx max bytes exceeded".to_owned()),
+        ),
+    ];
+    let confirmation = BadBlockConfirmation::from_attempts(target(), attempts);
+    assert!(confirmation.confirmed);
+    assert_eq!(confirmation.target.as_slice(), target().as_slice());
+    assert_eq!(confirmation.attempts.len(), 2);
+}
+
+#[test]
+fn test_reject_bad_block_if_a_cancel_tx_verified() {
+    let attempts = vec![
+        attempt(
+            LoadDataStrategy::Witness,
+            CancelChallengeResult::Rejected("script error".to_owned()),
+        ),
+        attempt(
+            LoadDataStrategy::CellDep,
+            CancelChallengeResult::Verified(100),
+        ),
+    ];
+    let confirmation = BadBlockConfirmation::from_attempts(target(), attempts);
+    assert!(!confirmation.confirmed);
+}
+
+#[test]
+fn test_errors_never_confirm_bad_block() {
+    // mock tx build error isn't a rejection
+    let attempts = vec![
+        attempt(
+            LoadDataStrategy::Witness,
+            CancelChallengeResult::MockTxError("no verifier".to_owned()),
+        ),
+        attempt(
+            LoadDataStrategy::CellDep,
+            CancelChallengeResult::Rejected("script error".to_owned()),
+        ),
+    ];
+    let confirmation = BadBlockConfirmation::from_attempts(target(), attempts);
+    assert!(!confirmation.confirmed);
+
+    // nothing verified
+    let confirmation = BadBlockConfirmation::from_attempts(target(), vec![]);
+    assert!(!confirmation.confirmed);
+}
+
+#[test]
+fn test_persist_bad_block_confirmation() {
+    let store = Store::open_tmp().unwrap();
+    let confirmed_block: H256 = [1u8; 32].into();
+    let rejected_block: H256 = [2u8; 32].into();
+    let unknown_block: H256 = [3u8; 32].into();
+
+    let db = store.begin_transaction();
+    db.set_bad_block_confirmed(&confirmed_block, true).unwrap();
+    db.set_bad_block_confirmed(&rejected_block, false).unwrap();
+    db.commit().unwrap();
+
+    let db = store.begin_transaction();
+    assert_eq!(
+        db.get_bad_block_confirmed(&confirmed_block).unwrap(),
+        Some(true)
+    );
+    assert_eq!(
+        db.get_bad_block_confirmed(&rejected_block).unwrap(),
+        Some(false)
+    );
+    assert_eq!(db.get_bad_block_confirmed(&unknown_block).unwrap(), None);
+}
@@ -0,0 +1,138 @@
+#![allow(clippy::mutable_key_type)]
+
+use crate::testing_tool::chain::{
+    apply_block_result, construct_block, setup_chain, ALWAYS_SUCCESS_CODE_HASH,
+    DEFAULT_FINALITY_BLOCKS,
+};
+
+use gw_block_producer::challenge_simulator::ChallengeSimulator;
+use gw_chain::chain::Chain;
+use gw_common::H256;
+use gw_types::{
+    core::{ChallengeTargetType, ScriptHashType},
+    packed::{CellOutput, DepositRequest, RawWithdrawalRequest, Script, WithdrawalRequest},
+    prelude::*,
+};
+
+use std::{collections::HashSet, sync::Arc};
+
+fn produce_block(
+    chain: &mut Chain,
+    rollup_cell: CellOutput,
+    deposits: Vec<DepositRequest>,
+    withdrawals: Vec<WithdrawalRequest>,
+) {
+    let block_result = {
+        let mem_pool = chain.mem_pool().as_ref().unwrap();
+        let mut mem_pool = smol::block_on(mem_pool.lock());
+        for withdrawal in withdrawals {
+            mem_pool.push_withdrawal_request(withdrawal).unwrap();
+        }
+        construct_block(chain, &mut mem_pool, deposits.clone()).unwrap()
+    };
+    apply_block_result(chain, rollup_cell, block_result, deposits, HashSet::new());
+}
+
+#[test]
+fn test_simulate_withdrawal_challenge_offline() {
+    let rollup_type_script = Script::default();
+    let rollup_script_hash = rollup_type_script.hash();
+    let mut chain = setup_chain(rollup_type_script.clone());
+    let rollup_cell = CellOutput::new_builder()
+        .type_(Some(rollup_type_script).pack())
+        .build();
+    let user_script = Script::new_builder()
+        .code_hash(ALWAYS_SUCCESS_CODE_HASH.pack())
+        .hash_type(ScriptHashType::Type.into())
+        .args({
+            let mut args = rollup_script_hash.to_vec();
+            args.push(42);
+            args.pack()
+        })
+        .build();
+    let user_script_hash: H256 = user_script.hash().into();
+
+    // deposit and wait for it to be finalized
+    let deposit = DepositRequest::new_builder()
+        .capacity(1000_00000000u64.pack())
+        .sudt_script_hash(H256::zero().pack())
+        .script(user_script)
+        .build();
+    produce_block(&mut chain, rollup_cell.clone(), vec![deposit], vec![]);
+    for _ in 0..DEFAULT_FINALITY_BLOCKS {
+        produce_block(&mut chain, rollup_cell.clone(), vec![], vec![]);
+    }
+
+    // withdrawal block followed by two blocks
+    let withdrawal = {
+        let raw = RawWithdrawalRequest::new_builder()
+            .capacity(500_00000000u64.pack())
+            .account_script_hash(user_script_hash.pack())
+            .sudt_script_hash(H256::zero().pack())
+            .build();
+        WithdrawalRequest::new_builder().raw(raw).build()
+    };
+    produce_block(&mut chain, rollup_cell.clone(), vec![], vec![withdrawal]);
+    let withdrawal_block_number: u64 = chain.local_state().tip().raw().number().unpack();
+    produce_block(&mut chain, rollup_cell.clone(), vec![], vec![]);
+    produce_block(&mut chain, rollup_cell, vec![], vec![]);
+    let tip_block_number: u64 = chain.local_state().tip().raw().number().unpack();
+
+    let simulator = ChallengeSimulator {
+        rollup_context: chain.generator().rollup_context().clone(),
+        generator: Arc::clone(chain.generator()),
+        sudt_bond: None,
+        burn_lock: Script::default(),
+        verify_max_cycles: u64::MAX,
+        mock_ctx: None,
+    };
+    let reverted_block_smt_root = {
+        let db = chain.store().begin_transaction();
+        db.get_reverted_block_smt_root().unwrap()
+    };
+
+    let report = simulator
+        .simulate(
+            chain.store(),
+            withdrawal_block_number,
+            0,
+            ChallengeTargetType::Withdrawal,
+        )
+        .expect("simulate");
+    assert_eq!(report.tip_block_number, tip_block_number);
+    assert!(report.enter_challenge_witness_size > 0);
+    assert!(report.challenge_capacity > 0);
+
+    // cancel challenge isn't verified offline, so revert is always built
+    assert!(report.cancel_attempts.is_none());
+    assert_eq!(report.cancelable(), None);
+    let revert = report.revert.expect("revert");
+    assert_eq!(revert.reverted_blocks, 3);
+    assert!(revert.rollup_witness_size > 0);
+
+    // nothing is written to store
+    let db = chain.store().begin_transaction();
+    assert_eq!(
+        db.get_reverted_block_smt_root().unwrap(),
+        reverted_block_smt_root
+    );
+    assert!(db.get_reverted_block_hashes().unwrap().is_empty());
+    let tip_block_hash: H256 = chain.local_state().tip().hash().into();
+    assert_eq!(chain.store().get_tip_block_hash().unwrap(), tip_block_hash);
+
+    // withdrawal block has only one withdrawal and no tx
+    let err = simulator.simulate(
+        chain.store(),
+        withdrawal_block_number,
+        1,
+        ChallengeTargetType::Withdrawal,
+    );
+    assert!(err.is_err());
+    let err = simulator.simulate(
+        chain.store(),
+        withdrawal_block_number,
+        0,
+        ChallengeTargetType::TxExecution,
+    );
+    assert!(err.is_err());
+}
@@ -0,0 +1,260 @@
+use crate::testing_tool::chain::{ALWAYS_SUCCESS_CODE_HASH, ALWAYS_SUCCESS_PROGRAM};
+
+use anyhow::Result;
+use gw_block_producer::runner::build_generator;
+use gw_challenge::offchain::verify_tx::{verify_tx, RollupCellDeps, TxWithContext};
+use gw_common::H256;
+use gw_config::{AccountLockConfig, Config, LockAlgorithmConfig};
+use gw_generator::{
+    account_lock_manage::{ckb_vm_lock::CkbVmLock, LockAlgorithm},
+    error::LockAlgorithmError,
+};
+use gw_types::{
+    bytes::Bytes,
+    core::{DepType, ScriptHashType},
+    offchain::{CellInfo, InputCellInfo, RollupContext},
+    packed::{
+        CellDep, CellInput, CellOutput, OutPoint, RawTransaction, RollupConfig, Script,
+        Transaction, WitnessArgs,
+    },
+    prelude::*,
+};
+use secp256k1::{recovery::RecoverableSignature, Message, PublicKey, Secp256k1, SecretKey};
+use sha3::{Digest, Keccak256};
+
+const LOCK_CODE_HASH: [u8; 32] = [7u8; 32];
+const ETH_ACCOUNT_LOCK_PATH: &str =
+    "../../tests-deps/godwoken-scripts/build/debug/eth-account-lock";
+const SECP256K1_DATA_PATH: &str = "../../tests-deps/godwoken-scripts/c/build/secp256k1_data";
+const MAX_CYCLES: u64 = 70_000_000;
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(data);
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(&hasher.finalize());
+    buf
+}
+
+fn eth_address(privkey: &SecretKey) -> [u8; 20] {
+    let pubkey = PublicKey::from_secret_key(&Secp256k1::new(), privkey);
+    let hash = keccak256(&pubkey.serialize_uncompressed()[1..]);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// Ethereum personal sign
+fn eth_sign(privkey: &SecretKey, message: &H256) -> Bytes {
+    let mut data = b"\x19Ethereum Signed Message:\n32".to_vec();
+    data.extend_from_slice(message.as_slice());
+    let msg = Message::from_slice(&keccak256(&data)).unwrap();
+    let signature: RecoverableSignature = Secp256k1::new().sign_recoverable(&msg, privkey);
+    let (recid, compact) = signature.serialize_compact();
+    let mut signature = compact.to_vec();
+    signature.push(recid.to_i32() as u8);
+    signature.into()
+}
+
+fn cell(n: u8, output: CellOutput, data: Bytes) -> InputCellInfo {
+    let out_point = OutPoint::new_builder().tx_hash([n; 32].pack()).build();
+    InputCellInfo {
+        input: CellInput::new_builder()
+            .previous_output(out_point.clone())
+            .build(),
+        cell: CellInfo {
+            out_point,
+            output,
+            data,
+        },
+    }
+}
+
+/// The cancel challenge tx part which unlocks the verifier cell
+struct VerifierTx {
+    lock_program: Bytes,
+    secp256k1_data: Bytes,
+    lock_type: Script,
+    owner_lock: Script,
+}
+
+impl VerifierTx {
+    fn verify(&self, account_script: Script, message: &H256, signature: Bytes) -> Result<u64> {
+        // owner_lock_hash(32 bytes) | message(32 bytes)
+        let mut verifier_data = self.owner_lock.hash().to_vec();
+        verifier_data.extend_from_slice(message.as_slice());
+
+        let capacity = 1000_00000000u64.pack();
+        let cell_deps = vec![
+            cell(
+                1,
+                CellOutput::new_builder()
+                    .capacity(capacity.clone())
+                    .type_(Some(self.lock_type.clone()).pack())
+                    .build(),
+                self.lock_program.clone(),
+            ),
+            cell(
+                2,
+                CellOutput::new_builder().capacity(capacity.clone()).build(),
+                self.secp256k1_data.clone(),
+            ),
+            cell(
+                3,
+                CellOutput::new_builder().capacity(capacity.clone()).build(),
+                ALWAYS_SUCCESS_PROGRAM.clone(),
+            ),
+        ];
+        let inputs = vec![
+            cell(
+                4,
+                CellOutput::new_builder()
+                    .capacity(capacity.clone())
+                    .lock(account_script)
+                    .build(),
+                verifier_data.into(),
+            ),
+            cell(
+                5,
+                CellOutput::new_builder()
+                    .capacity(capacity)
+                    .lock(self.owner_lock.clone())
+                    .build(),
+                Bytes::new(),
+            ),
+        ];
+
+        let raw_tx = RawTransaction::new_builder()
+            .cell_deps(
+                cell_deps
+                    .iter()
+                    .map(|dep| {
+                        CellDep::new_builder()
+                            .out_point(dep.cell.out_point.clone())
+                            .dep_type(DepType::Code.into())
+                            .build()
+                    })
+                    .pack(),
+            )
+            .inputs(inputs.iter().map(|input| input.input.clone()).pack())
+            .build();
+        let witness = WitnessArgs::new_builder()
+            .lock(Some(signature).pack())
+            .build();
+        let tx = Transaction::new_builder()
+            .raw(raw_tx)
+            .witnesses(vec![witness.as_bytes(), Bytes::new()].pack())
+            .build();
+
+        let tx_with_context = TxWithContext {
+            cell_deps,
+            inputs,
+            tx,
+        };
+        verify_tx(&RollupCellDeps::new(vec![]), tx_with_context, MAX_CYCLES)
+    }
+}
+
+#[test]
+fn test_ckb_vm_lock_with_eth_account_lock() {
+    let lock_program: Bytes = std::fs::read(ETH_ACCOUNT_LOCK_PATH)
+        .expect("read eth account lock")
+        .into();
+    let secp256k1_data: Bytes = std::fs::read(SECP256K1_DATA_PATH)
+        .expect("read secp256k1 data")
+        .into();
+    let lock_type = Script::new_builder()
+        .code_hash([1u8; 32].pack())
+        .hash_type(ScriptHashType::Type.into())
+        .build();
+    let code_hash: H256 = lock_type.hash().into();
+    let owner_lock = Script::new_builder()
+        .code_hash(ALWAYS_SUCCESS_CODE_HASH.pack())
+        .hash_type(ScriptHashType::Data.into())
+        .build();
+
+    let lock = CkbVmLock::new(
+        code_hash,
+        lock_program.clone(),
+        vec![secp256k1_data.clone()],
+        owner_lock.clone(),
+        MAX_CYCLES,
+    );
+    let verifier_tx = VerifierTx {
+        lock_program: lock_program.clone(),
+        secp256k1_data,
+        lock_type,
+        owner_lock: owner_lock.clone(),
+    };
+
+    // rollup_script_hash(32 bytes) | eth_address(20 bytes)
+    let privkey = SecretKey::from_slice(&[42u8; 32]).unwrap();
+    let mut lock_args = [42u8; 32].to_vec();
+    lock_args.extend_from_slice(&eth_address(&privkey));
+    let lock_args = Bytes::from(lock_args);
+    let account_script = Script::new_builder()
+        .code_hash(code_hash.pack())
+        .hash_type(ScriptHashType::Type.into())
+        .args(lock_args.pack())
+        .build();
+    let message = H256::from([3u8; 32]);
+
+    // signed by the account
+    let signature = eth_sign(&privkey, &message);
+    let valid = lock
+        .verify_message(lock_args.clone(), signature.clone(), message)
+        .expect("verify message");
+    assert!(valid);
+    verifier_tx
+        .verify(account_script.clone(), &message, signature.clone())
+        .expect("verify on-chain");
+
+    // signed by another key
+    let other_privkey = SecretKey::from_slice(&[43u8; 32]).unwrap();
+    let other_signature = eth_sign(&other_privkey, &message);
+    let valid = lock
+        .verify_message(lock_args.clone(), other_signature.clone(), message)
+        .expect("verify message");
+    assert!(!valid);
+    verifier_tx
+        .verify(account_script, &message, other_signature)
+        .unwrap_err();
+
+    // exceeded max cycles
+    let err = CkbVmLock::new(code_hash, lock_program, vec![], owner_lock, 0)
+        .verify_message(lock_args, signature.clone(), message)
+        .unwrap_err();
+    assert!(matches!(err, LockAlgorithmError::VM(_)));
+
+    let err = lock.recover(message, &signature).unwrap_err();
+    assert_eq!(err, LockAlgorithmError::UnsupportedRecover);
+}
+
+#[test]
+fn test_disallowed_account_lock_code_hash() {
+    let rollup_config = RollupConfig::new_builder()
+        .allowed_eoa_type_hashes(vec![[1u8; 32]].pack())
+        .build();
+    let mut config = Config::default();
+    config.genesis.rollup_config = rollup_config.clone().into();
+    config.account_locks = vec![AccountLockConfig {
+        code_hash: LOCK_CODE_HASH.into(),
+        lock_algorithm: LockAlgorithmConfig::Secp256k1,
+    }];
+    let rollup_context = RollupContext {
+        rollup_config,
+        rollup_script_hash: [42u8; 32].into(),
+    };
+
+    let err = build_generator(&config, &rollup_context).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("isn't in the allowed eoa type hashes"),
+        "{}",
+        err
+    );
+
+    // allowed code hash
+    config.account_locks[0].code_hash = [1u8; 32].into();
+    build_generator(&config, &rollup_context).expect("build generator");
+}
@@ -0,0 +1,105 @@
+use gw_block_producer::cleaner::{delete_verifiers, load_verifiers, persist_verifier, Verifier};
+use gw_challenge::cancel_challenge::RecoverAccountsContext;
+use gw_common::H256;
+use gw_db::{config::Config as DBConfig, schema::COLUMNS, RocksDB};
+use gw_store::Store;
+use gw_types::{
+    bytes::Bytes,
+    offchain::{CellInfo, InputCellInfo},
+    packed::{CellDep, CellInput, CellOutput, OutPoint, WitnessArgs},
+    prelude::*,
+};
+use std::path::Path;
+
+fn open_store(path: &Path) -> Store {
+    let config = DBConfig {
+        path: path.to_path_buf(),
+        ..Default::default()
+    };
+    Store::new(RocksDB::open(&config, COLUMNS))
+}
+
+fn input_cell_info(tx_hash: [u8; 32], index: u32, data: &'static [u8]) -> InputCellInfo {
+    let out_point = OutPoint::new_builder()
+        .tx_hash(tx_hash.pack())
+        .index(index.pack())
+        .build();
+    let output = CellOutput::new_builder().capacity(1000u64.pack()).build();
+    InputCellInfo {
+        input: CellInput::new_builder()
+            .previous_output(out_point.clone())
+            .build(),
+        cell: CellInfo {
+            out_point,
+            output,
+            data: Bytes::from_static(data),
+        },
+    }
+}
+
+fn verifier(tx_hash: [u8; 32], with_context: bool) -> Verifier {
+    let load_data_inputs = vec![input_cell_info([9u8; 32], 0, b"load data")];
+    let witness = WitnessArgs::new_builder()
+        .lock(Some(Bytes::from_static(b"signature")).pack())
+        .build();
+    let recover_accounts_context = if with_context {
+        Some(RecoverAccountsContext {
+            cell_deps: vec![CellDep::default()],
+            inputs: vec![input_cell_info([8u8; 32], 1, b"recover account")],
+            witnesses: vec![witness.clone()],
+        })
+    } else {
+        None
+    };
+
+    Verifier::new(
+        load_data_inputs,
+        recover_accounts_context,
+        CellDep::default(),
+        input_cell_info(tx_hash, 0, b"verifier"),
+        Some(witness),
+    )
+}
+
+fn sorted(mut verifiers: Vec<(Verifier, Option<H256>)>) -> Vec<(Bytes, Option<H256>)> {
+    verifiers.sort_by_key(|(verifier, _)| verifier.out_point().as_bytes());
+    let to_bytes = verifiers.into_iter();
+    to_bytes
+        .map(|(verifier, consumed)| (verifier.pack().as_bytes(), consumed))
+        .collect()
+}
+
+#[test]
+fn test_cleaner_verifiers_persist_and_reload() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let unconsumed = verifier([1u8; 32], false);
+    let consumed = verifier([2u8; 32], true);
+    let consumed_tx = H256::from([3u8; 32]);
+    {
+        let store = open_store(dir.path());
+        assert!(load_verifiers(&store).unwrap().is_empty());
+
+        persist_verifier(&store, &unconsumed, None).unwrap();
+        persist_verifier(&store, &consumed, None).unwrap();
+        // Update consumed tx hash after reclaiming
+        persist_verifier(&store, &consumed, Some(consumed_tx)).unwrap();
+    }
+
+    // Restart
+    let store = open_store(dir.path());
+    let reloaded = load_verifiers(&store).unwrap();
+    let expected = vec![
+        (unconsumed.clone(), None),
+        (consumed.clone(), Some(consumed_tx)),
+    ];
+    assert_eq!(sorted(reloaded), sorted(expected));
+
+    // Pruned verifiers are gone after restart
+    delete_verifiers(&store, &[consumed.out_point()]).unwrap();
+    drop(store);
+
+    let store = open_store(dir.path());
+    let reloaded = load_verifiers(&store).unwrap();
+    assert_eq!(sorted(reloaded), sorted(vec![(unconsumed, None)]));
+}
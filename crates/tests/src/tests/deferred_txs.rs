@@ -0,0 +1,274 @@
+#![allow(clippy::mutable_key_type)]
+
+use crate::testing_tool::{
+    chain::{
+        apply_block_result, construct_block, setup_chain_with_mem_pool_config,
+        ALWAYS_SUCCESS_CODE_HASH,
+    },
+    mem_pool_provider::DummyMemPoolProvider,
+};
+
+use gw_chain::chain::Chain;
+use gw_common::{
+    builtins::CKB_SUDT_ACCOUNT_ID,
+    state::{to_short_address, State},
+    H256,
+};
+use gw_config::MemPoolConfig;
+use gw_mem_pool::pool::MemPool;
+use gw_store::{
+    state_db::{CheckPoint, StateDBMode, StateDBTransaction, SubState},
+    transaction::mem_pool_store::MemPoolStore,
+};
+use gw_types::{
+    core::ScriptHashType,
+    packed::{
+        CellOutput, DepositRequest, L2Transaction, RawL2Transaction, SUDTArgs, SUDTTransfer, Script,
+    },
+    prelude::*,
+};
+
+use std::{collections::HashSet, sync::Arc};
+
+const CAPACITY: u64 = 1000_00000000;
+
+fn user_script(rollup_script_hash: &[u8; 32], n: u8) -> Script {
+    Script::new_builder()
+        .code_hash(ALWAYS_SUCCESS_CODE_HASH.pack())
+        .hash_type(ScriptHashType::Type.into())
+        .args({
+            let mut args = rollup_script_hash.to_vec();
+            args.push(n);
+            args.pack()
+        })
+        .build()
+}
+
+fn produce_block(chain: &mut Chain, deposit_requests: Vec<DepositRequest>) -> usize {
+    let rollup_cell = CellOutput::new_builder()
+        .type_(Some(Script::default()).pack())
+        .build();
+    let block_result = {
+        let mem_pool = chain.mem_pool().as_ref().unwrap();
+        let mut mem_pool = smol::block_on(mem_pool.lock());
+        construct_block(chain, &mut mem_pool, deposit_requests.clone()).unwrap()
+    };
+    let txs_count = block_result.block.transactions().len();
+    apply_block_result(
+        chain,
+        rollup_cell,
+        block_result,
+        deposit_requests,
+        HashSet::new(),
+    );
+    txs_count
+}
+
+fn tip_nonce(chain: &Chain, account_id: u32) -> u32 {
+    let db = chain.store().begin_transaction();
+    let tip_block_hash = db.get_tip_block_hash().unwrap();
+    let state_db = StateDBTransaction::from_checkpoint(
+        &db,
+        CheckPoint::from_block_hash(&db, tip_block_hash, SubState::Block).unwrap(),
+        StateDBMode::ReadOnly,
+    )
+    .unwrap();
+    state_db
+        .state_tree()
+        .unwrap()
+        .get_nonce(account_id)
+        .unwrap()
+}
+
+fn transfer_tx(from_id: u32, nonce: u32, to: &H256, amount: u128) -> L2Transaction {
+    let args = SUDTArgs::new_builder()
+        .set(
+            SUDTTransfer::new_builder()
+                .to(to_short_address(to).to_vec().pack())
+                .amount(amount.pack())
+                .fee(1u128.pack())
+                .build(),
+        )
+        .build();
+    let raw = RawL2Transaction::new_builder()
+        .from_id(from_id.pack())
+        .to_id(CKB_SUDT_ACCOUNT_ID.pack())
+        .nonce(nonce.pack())
+        .args(args.as_bytes().pack())
+        .build();
+    L2Transaction::new_builder().raw(raw).build()
+}
+
+fn push_tx(chain: &Chain, tx: L2Transaction) -> anyhow::Result<()> {
+    let mem_pool = chain.mem_pool().as_ref().unwrap();
+    let mut mem_pool = smol::block_on(mem_pool.lock());
+    mem_pool.push_transaction(tx)
+}
+
+fn mem_block_txs_count(chain: &Chain) -> usize {
+    let mem_pool = chain.mem_pool().as_ref().unwrap();
+    let mem_pool = smol::block_on(mem_pool.lock());
+    mem_pool.mem_block().txs().len()
+}
+
+#[test]
+fn test_deferred_txs() {
+    let rollup_script_hash = Script::default().hash();
+    let mem_pool_config = MemPoolConfig {
+        max_mem_block_txs: 1,
+        ..Default::default()
+    };
+    let mut chain = setup_chain_with_mem_pool_config(Script::default(), mem_pool_config);
+
+    // deposit to alice and bob
+    let alice_script = user_script(&rollup_script_hash, 1);
+    let bob_script = user_script(&rollup_script_hash, 2);
+    let deposit_requests = vec![alice_script.clone(), bob_script.clone()]
+        .into_iter()
+        .map(|script| {
+            DepositRequest::new_builder()
+                .capacity(CAPACITY.pack())
+                .script(script)
+                .build()
+        })
+        .collect();
+    produce_block(&mut chain, deposit_requests);
+    let (alice_id, bob_id) = {
+        let mem_pool = chain.mem_pool().as_ref().unwrap();
+        let mem_pool = smol::block_on(mem_pool.lock());
+        let db = chain.store().begin_transaction();
+        let state_db = mem_pool.fetch_state_db(&db).unwrap();
+        let state = state_db.state_tree().unwrap();
+        let get_id = |script: &Script| {
+            state
+                .get_account_id_by_script_hash(&script.hash().into())
+                .unwrap()
+                .expect("account exists")
+        };
+        (get_id(&alice_script), get_id(&bob_script))
+    };
+    let alice_script_hash: H256 = alice_script.hash().into();
+    let bob_script_hash: H256 = bob_script.hash().into();
+
+    // the mem block is full after alice's first tx, the next tx is deferred
+    push_tx(&chain, transfer_tx(alice_id, 0, &bob_script_hash, 1)).unwrap();
+    assert_eq!(mem_block_txs_count(&chain), 1);
+    push_tx(&chain, transfer_tx(alice_id, 1, &bob_script_hash, 1)).unwrap();
+    assert_eq!(mem_block_txs_count(&chain), 1);
+
+    // invalid txs aren't deferred
+    // nonce must follow the deferred tx of alice
+    let err = push_tx(&chain, transfer_tx(alice_id, 1, &bob_script_hash, 2)).unwrap_err();
+    assert!(err.to_string().contains("nonce"), "{}", err);
+    push_tx(&chain, transfer_tx(alice_id, 3, &bob_script_hash, 1)).unwrap_err();
+    push_tx(&chain, transfer_tx(bob_id, 1, &alice_script_hash, 1)).unwrap_err();
+    // bob can't afford the amount
+    let amount = CAPACITY as u128 + 1;
+    push_tx(&chain, transfer_tx(bob_id, 0, &alice_script_hash, amount)).unwrap_err();
+    // the sender has no known lock, so the signature can't be verified
+    push_tx(
+        &chain,
+        transfer_tx(CKB_SUDT_ACCOUNT_ID, 0, &alice_script_hash, 1),
+    )
+    .unwrap_err();
+
+    // a valid tx of bob is deferred
+    push_tx(&chain, transfer_tx(bob_id, 0, &alice_script_hash, 1)).unwrap();
+    assert_eq!(mem_block_txs_count(&chain), 1);
+
+    // deferred txs are included by later blocks, one tx per block
+    assert_eq!(produce_block(&mut chain, vec![]), 1);
+    assert_eq!(tip_nonce(&chain, alice_id), 1);
+    assert_eq!(produce_block(&mut chain, vec![]), 1);
+    assert_eq!(tip_nonce(&chain, alice_id), 2);
+    assert_eq!(produce_block(&mut chain, vec![]), 1);
+    assert_eq!(tip_nonce(&chain, bob_id), 1);
+    assert_eq!(produce_block(&mut chain, vec![]), 0);
+}
+
+fn is_stored_in_mem_pool(chain: &Chain, tx: &L2Transaction) -> bool {
+    let db = chain.store().begin_transaction();
+    db.get_mem_pool_transaction(&tx.hash().into())
+        .unwrap()
+        .is_some()
+}
+
+#[test]
+fn test_deferred_txs_executed_in_order() {
+    let rollup_script_hash = Script::default().hash();
+    let mem_pool_config = MemPoolConfig {
+        max_mem_block_txs: 1,
+        ..Default::default()
+    };
+    let mut chain = setup_chain_with_mem_pool_config(Script::default(), mem_pool_config.clone());
+
+    // deposit to alice and bob
+    let alice_script = user_script(&rollup_script_hash, 1);
+    let bob_script = user_script(&rollup_script_hash, 2);
+    let deposit_requests = vec![alice_script.clone(), bob_script.clone()]
+        .into_iter()
+        .map(|script| {
+            DepositRequest::new_builder()
+                .capacity(CAPACITY.pack())
+                .script(script)
+                .build()
+        })
+        .collect();
+    produce_block(&mut chain, deposit_requests);
+    // alice is the first created account after the builtin accounts
+    let (alice_id, bob_id) = (2, 3);
+    let alice_script_hash: H256 = alice_script.hash().into();
+    let bob_script_hash: H256 = bob_script.hash().into();
+
+    // bob fills the mem block, alice has CAPACITY + 1 on the mem block state
+    push_tx(&chain, transfer_tx(bob_id, 0, &alice_script_hash, 1)).unwrap();
+    // alice spends all but 10 in a deferred tx
+    let amount = CAPACITY as u128 - 10;
+    let first_tx = transfer_tx(alice_id, 0, &bob_script_hash, amount);
+    push_tx(&chain, first_tx.clone()).unwrap();
+    assert_eq!(mem_block_txs_count(&chain), 1);
+
+    // alice could afford 10 + fee on the mem block state, but not after the first tx
+    push_tx(&chain, transfer_tx(alice_id, 1, &bob_script_hash, 10)).unwrap_err();
+    let second_tx = transfer_tx(alice_id, 1, &bob_script_hash, 1);
+    push_tx(&chain, second_tx.clone()).unwrap();
+
+    // deferred txs are stored as pending txs
+    assert!(is_stored_in_mem_pool(&chain, &first_tx));
+    assert!(is_stored_in_mem_pool(&chain, &second_tx));
+
+    // deferred txs are kept by the recovery from invalid state, the mem block
+    // txs are dropped
+    {
+        let mem_pool = chain.mem_pool().as_ref().unwrap();
+        let mut mem_pool = smol::block_on(mem_pool.lock());
+        mem_pool.try_to_recovery_from_invalid_state().unwrap();
+        mem_pool.reset_mem_block().unwrap();
+    }
+    // the first deferred tx is in the mem block after reset
+    assert_eq!(mem_block_txs_count(&chain), 1);
+    assert!(is_stored_in_mem_pool(&chain, &second_tx));
+
+    assert_eq!(produce_block(&mut chain, vec![]), 1);
+    assert_eq!(tip_nonce(&chain, alice_id), 1);
+    assert_eq!(produce_block(&mut chain, vec![]), 1);
+    assert_eq!(tip_nonce(&chain, alice_id), 2);
+    assert_eq!(tip_nonce(&chain, bob_id), 0);
+
+    // deferred txs survive restarts
+    push_tx(&chain, transfer_tx(alice_id, 2, &bob_script_hash, 1)).unwrap();
+    let deferred_tx = transfer_tx(bob_id, 0, &alice_script_hash, 1);
+    push_tx(&chain, deferred_tx.clone()).unwrap();
+    assert_eq!(mem_block_txs_count(&chain), 1);
+    let mem_pool = MemPool::create(
+        chain.store().clone(),
+        Arc::clone(chain.generator()),
+        Box::new(DummyMemPoolProvider::default()),
+        None,
+        mem_pool_config,
+    )
+    .unwrap();
+    // mem block txs aren't restored, the deferred tx is re-injected
+    let mem_block_txs = mem_pool.mem_block().txs().to_vec();
+    assert_eq!(mem_block_txs, vec![deferred_tx.hash().into()]);
+}
@@ -0,0 +1,48 @@
+use crate::testing_tool::rpc_server::MockRPCServer;
+
+use gw_block_producer::dry_run::dry_run_block_tx;
+use gw_challenge::offchain::verify_tx::RollupCellDeps;
+use gw_types::{
+    packed::{RawTransaction, Transaction},
+    prelude::*,
+};
+use serde_json::json;
+
+#[test]
+fn test_dry_run_never_submits_block_tx() {
+    let server = MockRPCServer::start(|method, _params| match method {
+        "dry_run_transaction" => Some(json!({ "cycles": "0x64" })),
+        _ => None,
+    });
+    let rpc_client = server.rpc_client();
+
+    let dump_dir = std::env::temp_dir().join(format!("gw-dry-run-{}", std::process::id()));
+    let tx = Transaction::new_builder()
+        .raw(RawTransaction::new_builder().version(1u32.pack()).build())
+        .build();
+    let report = smol::block_on(dry_run_block_tx(
+        &rpc_client,
+        &RollupCellDeps::new(vec![]),
+        &dump_dir,
+        42,
+        tx.clone(),
+    ));
+
+    // the tx which would be submitted is reported and dumped
+    assert_eq!(report.block_number, 42);
+    assert_eq!(report.tx.as_slice(), tx.as_slice());
+    assert_eq!(report.dry_run_cycles.expect("dry run cycles"), 100);
+    let tx_hash = hex::encode(tx.hash());
+    let dumped = std::fs::read_dir(&dump_dir)
+        .expect("dump dir")
+        .filter_map(|entry| entry.ok())
+        .any(|entry| entry.file_name().to_string_lossy().contains(&tx_hash));
+    assert!(dumped);
+
+    // only dry run, nothing is sent to layer1
+    let methods = server.methods();
+    assert!(methods.iter().any(|m| m == "dry_run_transaction"));
+    assert!(!methods.iter().any(|m| m == "send_transaction"));
+
+    let _ = std::fs::remove_dir_all(dump_dir);
+}
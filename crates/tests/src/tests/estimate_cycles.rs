@@ -0,0 +1,114 @@
+#![allow(clippy::mutable_key_type)]
+
+use crate::testing_tool::{
+    chain::{apply_block_result, construct_block, setup_chain, ALWAYS_SUCCESS_CODE_HASH},
+    node_rpc::{build_node_rpc_server, call},
+};
+
+use gw_common::{builtins::CKB_SUDT_ACCOUNT_ID, state::to_short_address, H256};
+use gw_config::NodeMode;
+use gw_types::{
+    core::ScriptHashType,
+    packed::{
+        CellOutput, DepositRequest, L2Transaction, RawL2Transaction, SUDTArgs, SUDTTransfer, Script,
+    },
+    prelude::*,
+};
+use serde_json::json;
+use smol::lock::Mutex;
+
+use std::{collections::HashSet, sync::Arc};
+
+const CAPACITY: u64 = 1000_00000000;
+
+fn user_script(rollup_script_hash: &[u8; 32], n: u8) -> Script {
+    Script::new_builder()
+        .code_hash(ALWAYS_SUCCESS_CODE_HASH.pack())
+        .hash_type(ScriptHashType::Type.into())
+        .args({
+            let mut args = rollup_script_hash.to_vec();
+            args.push(n);
+            args.pack()
+        })
+        .build()
+}
+
+fn transfer_tx(from_id: u32, nonce: u32, to: &H256, amount: u128) -> RawL2Transaction {
+    let args = SUDTArgs::new_builder()
+        .set(
+            SUDTTransfer::new_builder()
+                .to(to_short_address(to).to_vec().pack())
+                .amount(amount.pack())
+                .fee(1u128.pack())
+                .build(),
+        )
+        .build();
+    RawL2Transaction::new_builder()
+        .from_id(from_id.pack())
+        .to_id(CKB_SUDT_ACCOUNT_ID.pack())
+        .nonce(nonce.pack())
+        .args(args.as_bytes().pack())
+        .build()
+}
+
+#[test]
+fn test_estimate_cycles_respects_mem_pool() {
+    let rollup_script_hash = Script::default().hash();
+    let mut chain = setup_chain(Script::default());
+
+    // deposit to alice and bob
+    let alice_script = user_script(&rollup_script_hash, 1);
+    let bob_script = user_script(&rollup_script_hash, 2);
+    let deposit_requests: Vec<_> = vec![alice_script.clone(), bob_script.clone()]
+        .into_iter()
+        .map(|script| {
+            DepositRequest::new_builder()
+                .capacity(CAPACITY.pack())
+                .script(script)
+                .build()
+        })
+        .collect();
+    let block_result = {
+        let mem_pool = chain.mem_pool().as_ref().unwrap();
+        let mut mem_pool = smol::block_on(mem_pool.lock());
+        construct_block(&chain, &mut mem_pool, deposit_requests.clone()).unwrap()
+    };
+    let rollup_cell = CellOutput::new_builder()
+        .type_(Some(Script::default()).pack())
+        .build();
+    apply_block_result(
+        &mut chain,
+        rollup_cell,
+        block_result,
+        deposit_requests,
+        HashSet::new(),
+    );
+    let mem_pool = chain.mem_pool().clone().unwrap();
+    // alice is the first created account after the builtin accounts
+    let alice_id = 2;
+    let bob_script_hash: H256 = bob_script.hash().into();
+
+    let chain = Arc::new(Mutex::new(chain));
+    let full_node = build_node_rpc_server(&chain, Default::default(), NodeMode::FullNode);
+    let read_only_node = build_node_rpc_server(&chain, Default::default(), NodeMode::ReadOnly);
+
+    // transfer all balance of alice
+    let raw_tx = transfer_tx(alice_id, 0, &bob_script_hash, CAPACITY as u128 - 1);
+    let params = json!([format!("0x{}", hex::encode(raw_tx.as_slice()))]);
+    for server in vec![&full_node, &read_only_node] {
+        let estimation = call(server, "gw_estimate_cycles", params.clone()).expect("estimate");
+        assert_ne!(estimation["cycles"], json!("0x0"));
+        assert_eq!(estimation["fee"], json!(null));
+    }
+
+    // spend in the mem block, alice can't afford the transfer on the mem block state
+    let tx = L2Transaction::new_builder()
+        .raw(transfer_tx(alice_id, 0, &bob_script_hash, 1))
+        .build();
+    smol::block_on(mem_pool.lock())
+        .push_transaction(tx)
+        .unwrap();
+    call(&full_node, "gw_estimate_cycles", params.clone()).unwrap_err();
+    // read-only nodes estimate on the tip block state
+    call(&read_only_node, "gw_estimate_cycles", params).expect("estimate on tip");
+}
@@ -0,0 +1,138 @@
+#![allow(clippy::mutable_key_type)]
+
+use crate::testing_tool::{
+    chain::{
+        apply_block_result, construct_block, setup_chain, ALWAYS_SUCCESS_CODE_HASH,
+        DEFAULT_FINALITY_BLOCKS,
+    },
+    node_rpc::{build_node_rpc_server, call},
+};
+
+use gw_chain::chain::Chain;
+use gw_common::H256;
+use gw_config::NodeMode;
+use gw_jsonrpc_types::godwoken::{WithdrawalStatus, WithdrawalWithStatus};
+use gw_rpc_server::registry::RPCServer;
+use gw_types::{
+    core::ScriptHashType,
+    packed::{CellOutput, DepositRequest, RawWithdrawalRequest, Script, WithdrawalRequest},
+    prelude::*,
+};
+use serde_json::json;
+use smol::lock::Mutex;
+
+use std::{collections::HashSet, sync::Arc};
+
+fn produce_block(
+    chain: &Arc<Mutex<Chain>>,
+    rollup_cell: CellOutput,
+    deposits: Vec<DepositRequest>,
+) {
+    let mut chain = smol::block_on(chain.lock());
+    let block_result = {
+        let mem_pool = chain.mem_pool().as_ref().unwrap();
+        let mut mem_pool = smol::block_on(mem_pool.lock());
+        construct_block(&chain, &mut mem_pool, deposits.clone()).unwrap()
+    };
+    apply_block_result(
+        &mut chain,
+        rollup_cell,
+        block_result,
+        deposits,
+        HashSet::new(),
+    );
+}
+
+fn get_withdrawal(server: &RPCServer, withdrawal_hash: [u8; 32]) -> Option<WithdrawalWithStatus> {
+    let params = json!([format!("0x{}", hex::encode(withdrawal_hash))]);
+    let result = call(server, "gw_get_withdrawal", params).expect("gw_get_withdrawal");
+    serde_json::from_value(result).expect("withdrawal with status")
+}
+
+#[test]
+fn test_get_withdrawal_rpc() {
+    let rollup_type_script = Script::default();
+    let rollup_script_hash = rollup_type_script.hash();
+    let chain = Arc::new(Mutex::new(setup_chain(rollup_type_script.clone())));
+    let rpc_server = build_node_rpc_server(&chain, Default::default(), NodeMode::FullNode);
+    let rollup_cell = CellOutput::new_builder()
+        .type_(Some(rollup_type_script).pack())
+        .build();
+    let user_script = Script::new_builder()
+        .code_hash(ALWAYS_SUCCESS_CODE_HASH.pack())
+        .hash_type(ScriptHashType::Type.into())
+        .args({
+            let mut args = rollup_script_hash.to_vec();
+            args.push(42);
+            args.pack()
+        })
+        .build();
+    let user_script_hash: H256 = user_script.hash().into();
+
+    // deposit and wait for it to be finalized
+    let deposit = DepositRequest::new_builder()
+        .capacity(1000_00000000u64.pack())
+        .sudt_script_hash(H256::zero().pack())
+        .script(user_script)
+        .build();
+    produce_block(&chain, rollup_cell.clone(), vec![deposit]);
+    for _ in 0..DEFAULT_FINALITY_BLOCKS {
+        produce_block(&chain, rollup_cell.clone(), vec![]);
+    }
+
+    let withdrawal = {
+        let raw = RawWithdrawalRequest::new_builder()
+            .capacity(300_00000000u64.pack())
+            .account_script_hash(user_script_hash.pack())
+            .sudt_script_hash(H256::zero().pack())
+            .build();
+        WithdrawalRequest::new_builder().raw(raw).build()
+    };
+    assert!(get_withdrawal(&rpc_server, withdrawal.hash()).is_none());
+
+    // pending in mem pool
+    {
+        let chain = smol::block_on(chain.lock());
+        let mem_pool = chain.mem_pool().as_ref().unwrap();
+        let mut mem_pool = smol::block_on(mem_pool.lock());
+        mem_pool
+            .push_withdrawal_request(withdrawal.clone())
+            .unwrap();
+    }
+    let pending = get_withdrawal(&rpc_server, withdrawal.hash()).expect("pending withdrawal");
+    assert_eq!(pending.status, WithdrawalStatus::Pending);
+    assert_eq!(pending.l2_block_number, None);
+    assert_eq!(
+        WithdrawalRequest::from(pending.withdrawal).as_bytes(),
+        withdrawal.as_bytes()
+    );
+
+    // committed in a block
+    produce_block(&chain, rollup_cell.clone(), vec![]);
+    let (block_number, block_hash) = {
+        let chain = smol::block_on(chain.lock());
+        let tip = chain.store().get_tip_block().unwrap();
+        let block_number: u64 = tip.raw().number().unpack();
+        (block_number, tip.hash())
+    };
+    let committed = get_withdrawal(&rpc_server, withdrawal.hash()).expect("committed withdrawal");
+    assert_eq!(committed.status, WithdrawalStatus::Committed);
+    assert_eq!(
+        committed.l2_block_number.map(|n| n.value()),
+        Some(block_number)
+    );
+    assert_eq!(committed.l2_block_hash.map(|h| h.0), Some(block_hash));
+    assert_eq!(committed.l2_withdrawal_index.map(|i| i.value()), Some(0));
+    assert!(committed.l1_committed_info.is_some());
+
+    // finalized
+    for _ in 0..DEFAULT_FINALITY_BLOCKS {
+        produce_block(&chain, rollup_cell.clone(), vec![]);
+    }
+    let finalized = get_withdrawal(&rpc_server, withdrawal.hash()).expect("finalized withdrawal");
+    assert_eq!(finalized.status, WithdrawalStatus::Finalized);
+    assert_eq!(
+        finalized.l2_block_number.map(|n| n.value()),
+        Some(block_number)
+    );
+}
@@ -1,2 +1,17 @@
+mod admin_rpc;
+mod bad_block_confirmation;
 mod chain;
+mod challenge_simulator;
+mod ckb_vm_lock;
+mod cleaner;
+mod deferred_txs;
 mod deposit_withdrawal;
+mod dry_run;
+mod estimate_cycles;
+mod get_withdrawal;
+mod native_sudt;
+mod proofs;
+mod signer;
+mod stake;
+mod state_checkpoint;
+mod withdrawal_offer;
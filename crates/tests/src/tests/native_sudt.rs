@@ -0,0 +1,269 @@
+use crate::testing_tool::chain::build_backend_manage;
+
+use gw_common::{
+    state::{to_short_address, State},
+    H256,
+};
+use gw_config::NativeGenerator;
+use gw_generator::{
+    account_lock_manage::AccountLockManage, backend_manage::BackendManage, dummy_state::DummyState,
+    traits::StateExt, Generator,
+};
+use gw_traits::ChainStore;
+use gw_types::{
+    core::ScriptHashType,
+    offchain::{RollupContext, RunResult},
+    packed::{
+        BlockInfo, RawL2Transaction, RollupConfig, SUDTArgs, SUDTQuery, SUDTTransfer, Script,
+    },
+    prelude::*,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const SUDT_VALIDATOR_SCRIPT_TYPE_HASH: [u8; 32] = [3u8; 32];
+const ACCOUNTS_COUNT: usize = 5;
+
+struct DummyChainStore;
+
+impl ChainStore for DummyChainStore {
+    fn get_block_hash_by_number(&self, _number: u64) -> Result<Option<H256>, gw_db::error::Error> {
+        Err("dummy chain store".to_string().into())
+    }
+}
+
+struct TestEnv {
+    tree: DummyState,
+    sudt_id: u32,
+    account_ids: Vec<u32>,
+    block_info: BlockInfo,
+}
+
+fn setup(rng: &mut StdRng) -> TestEnv {
+    let mut tree = DummyState::default();
+    let sudt_id = tree
+        .create_account_from_script(
+            Script::new_builder()
+                .code_hash(SUDT_VALIDATOR_SCRIPT_TYPE_HASH.pack())
+                .args([0u8; 64].to_vec().pack())
+                .hash_type(ScriptHashType::Type.into())
+                .build(),
+        )
+        .expect("create sudt account");
+    let account_ids: Vec<u32> = (0..ACCOUNTS_COUNT)
+        .map(|i| {
+            tree.create_account_from_script(
+                Script::new_builder()
+                    .code_hash([0u8; 32].pack())
+                    .args([i as u8; 20].to_vec().pack())
+                    .hash_type(ScriptHashType::Type.into())
+                    .build(),
+            )
+            .expect("create account")
+        })
+        .collect();
+    for id in account_ids.iter() {
+        let script_hash = tree.get_script_hash(*id).expect("get script hash");
+        let balance = rng.gen_range(0..100_000u128);
+        tree.mint_sudt(sudt_id, to_short_address(&script_hash), balance)
+            .expect("mint");
+    }
+    // the first account produces blocks
+    let block_info = BlockInfo::new_builder()
+        .block_producer_id(account_ids[0].pack())
+        .number(1u64.pack())
+        .timestamp(0u64.pack())
+        .build();
+
+    TestEnv {
+        tree,
+        sudt_id,
+        account_ids,
+        block_info,
+    }
+}
+
+fn build_generator(rollup_config: &RollupConfig, backend_manage: BackendManage) -> Generator {
+    let rollup_context = RollupContext {
+        rollup_config: rollup_config.clone(),
+        rollup_script_hash: [42u8; 32].into(),
+    };
+    Generator::new(
+        backend_manage,
+        AccountLockManage::default(),
+        rollup_context,
+        Default::default(),
+        Default::default(),
+    )
+}
+
+fn random_args(rng: &mut StdRng, env: &TestEnv) -> SUDTArgs {
+    let to_address = if rng.gen_bool(0.9) {
+        let to_id = env.account_ids[rng.gen_range(0..ACCOUNTS_COUNT)];
+        let script_hash = env.tree.get_script_hash(to_id).expect("get script hash");
+        to_short_address(&script_hash).to_vec()
+    } else {
+        // unknown address
+        rng.gen::<[u8; 20]>().to_vec()
+    };
+
+    if rng.gen_bool(0.2) {
+        return SUDTArgs::new_builder()
+            .set(
+                SUDTQuery::new_builder()
+                    .short_address(to_address.pack())
+                    .build(),
+            )
+            .build();
+    }
+    SUDTArgs::new_builder()
+        .set(
+            SUDTTransfer::new_builder()
+                .to(to_address.pack())
+                .amount(rng.gen_range(0..50_000u128).pack())
+                .fee(rng.gen_range(0..100u128).pack())
+                .build(),
+        )
+        .build()
+}
+
+fn assert_run_result_eq(vm: &RunResult, native: &RunResult) {
+    assert_eq!(vm.exit_code, native.exit_code, "exit code");
+    // native generators have no measured cycles
+    assert_eq!(native.used_cycles, 0, "native used cycles");
+    assert_eq!(vm.read_values, native.read_values, "read values");
+    assert_eq!(vm.write_values, native.write_values, "write values");
+    assert_eq!(vm.return_data, native.return_data, "return data");
+    assert_eq!(vm.account_count, native.account_count, "account count");
+    assert_eq!(vm.new_scripts, native.new_scripts, "new scripts");
+    assert_eq!(vm.get_scripts, native.get_scripts, "get scripts");
+    assert_eq!(vm.write_data, native.write_data, "write data");
+    assert_eq!(vm.read_data, native.read_data, "read data");
+    assert_eq!(
+        vm.logs.iter().map(|l| l.as_slice()).collect::<Vec<_>>(),
+        native.logs.iter().map(|l| l.as_slice()).collect::<Vec<_>>(),
+        "logs"
+    );
+    assert_eq!(
+        vm.fee_payments
+            .iter()
+            .map(|p| p.as_slice())
+            .collect::<Vec<_>>(),
+        native
+            .fee_payments
+            .iter()
+            .map(|p| p.as_slice())
+            .collect::<Vec<_>>(),
+        "fee payments"
+    );
+}
+
+fn build_native_generator(rollup_config: &RollupConfig) -> Generator {
+    let mut backend_manage = build_backend_manage(rollup_config);
+    let mut backend = backend_manage
+        .get_backend(&SUDT_VALIDATOR_SCRIPT_TYPE_HASH.into(), 0)
+        .expect("sudt backend")
+        .clone();
+    backend.native_generator = Some(NativeGenerator::Sudt);
+    backend_manage.register_backend(backend);
+    build_generator(rollup_config, backend_manage)
+}
+
+#[test]
+fn test_native_sudt_generator_matches_vm() {
+    let rollup_config = RollupConfig::new_builder()
+        .l2_sudt_validator_script_type_hash(SUDT_VALIDATOR_SCRIPT_TYPE_HASH.pack())
+        .build();
+    let vm_generator = build_generator(&rollup_config, build_backend_manage(&rollup_config));
+    let native_generator = build_native_generator(&rollup_config);
+
+    let seed: u64 = 42;
+    let mut rng = StdRng::seed_from_u64(seed);
+    // build the same state for both generators
+    let mut vm_env = setup(&mut StdRng::seed_from_u64(seed));
+    let mut native_env = setup(&mut StdRng::seed_from_u64(seed));
+
+    for _ in 0..200 {
+        let from_id = vm_env.account_ids[rng.gen_range(0..ACCOUNTS_COUNT)];
+        let args = random_args(&mut rng, &vm_env);
+        let raw_tx = RawL2Transaction::new_builder()
+            .from_id(from_id.pack())
+            .to_id(vm_env.sudt_id.pack())
+            .nonce(vm_env.tree.get_nonce(from_id).unwrap().pack())
+            .args(args.as_bytes().pack())
+            .build();
+
+        let vm_result = vm_generator
+            .unchecked_execute_transaction(
+                &DummyChainStore,
+                &vm_env.tree,
+                &vm_env.block_info,
+                &raw_tx,
+                vm_generator.execution_limits().l2tx_max_cycles,
+            )
+            .expect("vm execute");
+        let native_result = native_generator
+            .unchecked_execute_transaction(
+                &DummyChainStore,
+                &native_env.tree,
+                &native_env.block_info,
+                &raw_tx,
+                native_generator.execution_limits().l2tx_max_cycles,
+            )
+            .expect("native execute");
+        assert_run_result_eq(&vm_result, &native_result);
+
+        if vm_result.exit_code == 0 {
+            vm_env.tree.apply_run_result(&vm_result).unwrap();
+            native_env.tree.apply_run_result(&native_result).unwrap();
+        }
+    }
+
+    assert_eq!(
+        vm_env.tree.calculate_root().unwrap(),
+        native_env.tree.calculate_root().unwrap()
+    );
+}
+
+#[test]
+fn test_native_sudt_generator_reports_no_cycles() {
+    let rollup_config = RollupConfig::new_builder()
+        .l2_sudt_validator_script_type_hash(SUDT_VALIDATOR_SCRIPT_TYPE_HASH.pack())
+        .build();
+    let generator = build_native_generator(&rollup_config);
+    let env = setup(&mut StdRng::seed_from_u64(42));
+    let from_id = env.account_ids[1];
+    let to_script_hash = env.tree.get_script_hash(env.account_ids[2]).unwrap();
+    let args = SUDTArgs::new_builder()
+        .set(
+            SUDTTransfer::new_builder()
+                .to(to_short_address(&to_script_hash).to_vec().pack())
+                .amount(1u128.pack())
+                .fee(1u128.pack())
+                .build(),
+        )
+        .build();
+    let raw_tx = RawL2Transaction::new_builder()
+        .from_id(from_id.pack())
+        .to_id(env.sudt_id.pack())
+        .nonce(env.tree.get_nonce(from_id).unwrap().pack())
+        .args(args.as_bytes().pack())
+        .build();
+    let execute = |max_cycles| {
+        generator.unchecked_execute_transaction(
+            &DummyChainStore,
+            &env.tree,
+            &env.block_info,
+            &raw_tx,
+            max_cycles,
+        )
+    };
+
+    // native cycles never decide acceptance
+    let max_cycles = generator.execution_limits().l2tx_max_cycles;
+    let run_result = execute(max_cycles).expect("execute");
+    assert_eq!(run_result.exit_code, 0);
+    assert_eq!(run_result.used_cycles, 0);
+    let run_result = execute(0).expect("execute without cycles");
+    assert_eq!(run_result.exit_code, 0);
+    assert_eq!(run_result.used_cycles, 0);
+}
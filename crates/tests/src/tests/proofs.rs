@@ -0,0 +1,234 @@
+#![allow(clippy::mutable_key_type)]
+
+use crate::testing_tool::{
+    chain::{apply_block_result, construct_block, setup_chain, ALWAYS_SUCCESS_CODE_HASH},
+    node_rpc::{build_node_rpc_server, call},
+};
+
+use gw_chain::chain::Chain;
+use gw_common::{
+    builtins::CKB_SUDT_ACCOUNT_ID,
+    h256_ext::H256Ext,
+    merkle_utils::{verify_ckb_merkle_proof, verify_smt_proof},
+    state::{
+        build_account_field_key, build_account_key, build_sudt_key, to_short_address,
+        GW_ACCOUNT_NONCE_TYPE, SUDT_KEY_FLAG_BALANCE,
+    },
+    H256,
+};
+use gw_config::{NodeMode, RPCConfig};
+use gw_jsonrpc_types::godwoken::{AccountProof, TransactionProof};
+use gw_types::{
+    core::ScriptHashType,
+    packed::{
+        CellOutput, DepositRequest, L2Block, L2Transaction, RawL2Transaction, SUDTArgs,
+        SUDTTransfer, Script,
+    },
+    prelude::*,
+};
+use serde_json::{json, Value};
+use smol::lock::Mutex;
+
+use std::{collections::HashSet, sync::Arc};
+
+const CAPACITY: u64 = 1000_00000000;
+
+fn user_script(rollup_script_hash: &[u8; 32], n: u8) -> Script {
+    Script::new_builder()
+        .code_hash(ALWAYS_SUCCESS_CODE_HASH.pack())
+        .hash_type(ScriptHashType::Type.into())
+        .args({
+            let mut args = rollup_script_hash.to_vec();
+            args.push(n);
+            args.pack()
+        })
+        .build()
+}
+
+fn produce_block(chain: &mut Chain, deposit_requests: Vec<DepositRequest>) -> L2Block {
+    let rollup_cell = CellOutput::new_builder()
+        .type_(Some(Script::default()).pack())
+        .build();
+    let block_result = {
+        let mem_pool = chain.mem_pool().as_ref().unwrap();
+        let mut mem_pool = smol::block_on(mem_pool.lock());
+        construct_block(chain, &mut mem_pool, deposit_requests.clone()).unwrap()
+    };
+    let block = block_result.block.clone();
+    apply_block_result(
+        chain,
+        rollup_cell,
+        block_result,
+        deposit_requests,
+        HashSet::new(),
+    );
+    block
+}
+
+fn hex_h256(hash: &H256) -> String {
+    format!("0x{}", hex::encode(hash.as_slice()))
+}
+
+fn verify_account_proof(proof: &AccountProof, block: &L2Block) -> Vec<(H256, H256)> {
+    let post_account_root: H256 = block.raw().post_account().merkle_root().unpack();
+    assert_eq!(H256::from(proof.merkle_root.0), post_account_root);
+    assert_eq!(H256::from(proof.block_hash.0), block.hash().into());
+    let leaves: Vec<(H256, H256)> = proof
+        .leaves
+        .iter()
+        .map(|kv| (H256::from(kv.k.0), H256::from(kv.v.0)))
+        .collect();
+    let valid = verify_smt_proof(
+        &post_account_root,
+        proof.proof.as_bytes().to_vec(),
+        leaves.clone(),
+    )
+    .unwrap();
+    assert!(valid, "valid account proof");
+    leaves
+}
+
+fn error_code(err: Value) -> i64 {
+    err["code"].as_i64().expect("error code")
+}
+
+#[test]
+fn test_account_and_transaction_proofs() {
+    let rollup_script_hash = Script::default().hash();
+    let mut chain = setup_chain(Script::default());
+
+    // deposit to alice and bob
+    let alice_script = user_script(&rollup_script_hash, 1);
+    let bob_script = user_script(&rollup_script_hash, 2);
+    let deposit_requests = vec![alice_script, bob_script.clone()]
+        .into_iter()
+        .map(|script| {
+            DepositRequest::new_builder()
+                .capacity(CAPACITY.pack())
+                .script(script)
+                .build()
+        })
+        .collect();
+    produce_block(&mut chain, deposit_requests);
+    // alice is the first created account after the builtin accounts
+    let alice_id = 2;
+    let bob_script_hash: H256 = bob_script.hash().into();
+
+    // alice transfers to bob
+    let args = SUDTArgs::new_builder()
+        .set(
+            SUDTTransfer::new_builder()
+                .to(to_short_address(&bob_script_hash).to_vec().pack())
+                .amount(1u128.pack())
+                .fee(1u128.pack())
+                .build(),
+        )
+        .build();
+    let raw_tx = RawL2Transaction::new_builder()
+        .from_id(alice_id.pack())
+        .to_id(CKB_SUDT_ACCOUNT_ID.pack())
+        .nonce(0u32.pack())
+        .args(args.as_bytes().pack())
+        .build();
+    let tx = L2Transaction::new_builder().raw(raw_tx).build();
+    {
+        let mem_pool = chain.mem_pool().as_ref().unwrap();
+        smol::block_on(mem_pool.lock())
+            .push_transaction(tx.clone())
+            .unwrap();
+    }
+    let block = produce_block(&mut chain, vec![]);
+    assert_eq!(block.transactions().len(), 1);
+    let block_number: u64 = block.raw().number().unpack();
+
+    let chain = Arc::new(Mutex::new(chain));
+    let rpc_config = RPCConfig {
+        max_proof_keys: 2,
+        ..Default::default()
+    };
+    let rpc_server = build_node_rpc_server(&chain, rpc_config, NodeMode::FullNode);
+
+    // nonce of alice and balance of bob in the CKB sUDT account
+    let nonce_key = build_account_field_key(alice_id, GW_ACCOUNT_NONCE_TYPE);
+    let balance_key = {
+        let sudt_key = build_sudt_key(SUDT_KEY_FLAG_BALANCE, to_short_address(&bob_script_hash));
+        build_account_key(CKB_SUDT_ACCOUNT_ID, &sudt_key)
+    };
+    let params = json!([
+        [hex_h256(&nonce_key), hex_h256(&balance_key)],
+        format!("{:#x}", block_number)
+    ]);
+    let proof = call(&rpc_server, "gw_get_account_proof", params).expect("account proof");
+    let proof: AccountProof = serde_json::from_value(proof).unwrap();
+    let leaves = verify_account_proof(&proof, &block);
+    assert_eq!(leaves[0], (nonce_key, H256::from_u32(1)));
+    assert_eq!(
+        leaves[1],
+        (balance_key, H256::from_u128(CAPACITY as u128 + 1))
+    );
+
+    // the proof doesn't verify values other than the proved ones
+    let forged = vec![(nonce_key, H256::zero()), leaves[1]];
+    assert!(!verify_smt_proof(
+        &block.raw().post_account().merkle_root().unpack(),
+        proof.proof.as_bytes().to_vec(),
+        forged
+    )
+    .unwrap());
+
+    // a storage key never written is proved as zero, defaults to tip
+    let storage_key = H256::from_u32(42);
+    let params = json!([format!("{:#x}", alice_id), [hex_h256(&storage_key)]]);
+    let proof = call(&rpc_server, "gw_get_storage_proof", params).expect("storage proof");
+    let proof: AccountProof = serde_json::from_value(proof).unwrap();
+    let leaves = verify_account_proof(&proof, &block);
+    assert_eq!(
+        leaves[0],
+        (
+            build_account_key(alice_id, storage_key.as_slice()),
+            H256::zero()
+        )
+    );
+
+    // tx witness hash is proved against the block's tx_witness_root
+    let tx_hash: H256 = tx.hash().into();
+    let params = json!([hex_h256(&tx_hash)]);
+    let proof = call(&rpc_server, "gw_get_transaction_proof", params).expect("tx proof");
+    let proof: TransactionProof = serde_json::from_value(proof).unwrap();
+    let tx_witness_root: H256 = block.raw().submit_transactions().tx_witness_root().unpack();
+    let tx_index: u32 = proof.tx_index.into();
+    let witness_hash = H256::from(proof.witness_hash.0);
+    assert_eq!(witness_hash, tx.witness_hash().into());
+    let indices = proof
+        .tx_proof
+        .indices
+        .iter()
+        .map(|index| (*index).into())
+        .collect();
+    let lemmas = proof
+        .tx_proof
+        .lemmas
+        .iter()
+        .map(|lemma| H256::from(lemma.0))
+        .collect();
+    assert!(verify_ckb_merkle_proof(
+        &tx_witness_root,
+        indices,
+        lemmas,
+        &[(tx_index, witness_hash)]
+    ));
+
+    // empty keys and too many keys are rejected
+    let err = call(&rpc_server, "gw_get_account_proof", json!([[]])).unwrap_err();
+    assert_eq!(error_code(err), -32602);
+    let err = call(
+        &rpc_server,
+        "gw_get_storage_proof",
+        json!([format!("{:#x}", alice_id), []]),
+    )
+    .unwrap_err();
+    assert_eq!(error_code(err), -32602);
+    let too_many_keys: Vec<_> = (0..3u32).map(|n| hex_h256(&H256::from_u32(n))).collect();
+    let err = call(&rpc_server, "gw_get_account_proof", json!([too_many_keys])).unwrap_err();
+    assert_eq!(error_code(err), -32602);
+}
@@ -0,0 +1,83 @@
+use crate::testing_tool::rpc_server::MockRPCServer;
+
+use gw_utils::signer::{HttpSigner, PrivkeySigner, Signer};
+use serde_json::json;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+const PRIVKEY: &str = "0707070707070707070707070707070707070707070707070707070707070707";
+
+fn privkey_signer() -> PrivkeySigner {
+    let path = std::env::temp_dir().join(format!("gw-http-signer-{}.key", std::process::id()));
+    std::fs::write(&path, PRIVKEY).unwrap();
+    let signer = PrivkeySigner::from_file(&path).unwrap();
+    let _ = std::fs::remove_file(path);
+    signer
+}
+
+fn decode_h256(value: &serde_json::Value) -> [u8; 32] {
+    let mut buf = [0u8; 32];
+    let decoded = hex::decode(value.as_str().unwrap().trim_start_matches("0x")).unwrap();
+    buf.copy_from_slice(&decoded);
+    buf
+}
+
+#[test]
+fn test_http_signer() {
+    let stub = Arc::new(privkey_signer());
+    let server = {
+        let stub = Arc::clone(&stub);
+        MockRPCServer::start(move |method, params| match method {
+            "sign_message" => {
+                let msg = decode_h256(&params[1]);
+                let signature = stub.sign(msg).unwrap();
+                Some(json!(format!("0x{}", hex::encode(&signature[..]))))
+            }
+            _ => None,
+        })
+    };
+
+    let signer = HttpSigner::new(server.url(), Duration::from_secs(5)).unwrap();
+    let lock_hash = [1u8; 32];
+    let msg = [5u8; 32];
+    let signature = smol::block_on(signer.sign_message(lock_hash, msg)).unwrap();
+    assert_eq!(signature.to_vec(), stub.sign(msg).unwrap().to_vec());
+
+    // lock hash and message are sent as hex
+    let params = server.params_of("sign_message");
+    assert_eq!(params.len(), 1);
+    assert_eq!(decode_h256(&params[0][0]), lock_hash);
+    assert_eq!(decode_h256(&params[0][1]), msg);
+}
+
+#[test]
+fn test_http_signer_error() {
+    let server = MockRPCServer::start(|method, _params| match method {
+        // wrong signature length
+        "sign_message" => Some(json!("0x00")),
+        _ => None,
+    });
+    let signer = HttpSigner::new(server.url(), Duration::from_secs(5)).unwrap();
+    assert!(smol::block_on(signer.sign_message([0u8; 32], [5u8; 32])).is_err());
+
+    // method not found
+    let server = MockRPCServer::start(|_method, _params| None);
+    let signer = HttpSigner::new(server.url(), Duration::from_secs(5)).unwrap();
+    assert!(smol::block_on(signer.sign_message([0u8; 32], [5u8; 32])).is_err());
+}
+
+#[test]
+fn test_http_signer_timeout() {
+    let server = MockRPCServer::start(|_method, _params| {
+        std::thread::sleep(Duration::from_secs(3));
+        None
+    });
+    let signer = HttpSigner::new(server.url(), Duration::from_millis(200)).unwrap();
+
+    let now = Instant::now();
+    let err = smol::block_on(signer.sign_message([0u8; 32], [5u8; 32])).unwrap_err();
+    assert!(err.to_string().contains("timeout"));
+    assert!(now.elapsed() < Duration::from_secs(3));
+}
@@ -0,0 +1,93 @@
+use gw_block_producer::stake::verify_stake_cell;
+use gw_types::{
+    bytes::Bytes,
+    core::ScriptHashType,
+    offchain::RollupContext,
+    packed::{CellOutput, RollupConfig, Script},
+    prelude::*,
+};
+use gw_utils::sudt::SUDTBond;
+
+const REQUIRED_STAKING_CAPACITY: u64 = 1000_00000000;
+const STAKE_AMOUNT: u128 = 1000;
+
+fn rollup_context() -> RollupContext {
+    let rollup_config = RollupConfig::new_builder()
+        .l1_sudt_script_type_hash([1u8; 32].pack())
+        .required_staking_capacity(REQUIRED_STAKING_CAPACITY.pack())
+        .build();
+    RollupContext {
+        rollup_script_hash: [2u8; 32].into(),
+        rollup_config,
+    }
+}
+
+fn sudt_type(args: [u8; 32]) -> Script {
+    Script::new_builder()
+        .code_hash([1u8; 32].pack())
+        .hash_type(ScriptHashType::Type.into())
+        .args(Bytes::from(args.to_vec()).pack())
+        .build()
+}
+
+fn sudt_bond() -> SUDTBond {
+    SUDTBond {
+        sudt_type: sudt_type([3u8; 32]),
+        stake_amount: STAKE_AMOUNT,
+        challenge_amount: 100,
+    }
+}
+
+fn stake_cell(capacity: u64, sudt: Option<(Script, u128)>) -> (CellOutput, Bytes) {
+    let builder = CellOutput::new_builder().capacity(capacity.pack());
+    match sudt {
+        Some((sudt_type, amount)) => {
+            let output = builder.type_(Some(sudt_type).pack()).build();
+            (output, Bytes::from(amount.to_le_bytes().to_vec()))
+        }
+        None => (builder.build(), Bytes::new()),
+    }
+}
+
+#[test]
+fn test_verify_stake_cell_capacity() {
+    let rollup_context = rollup_context();
+
+    let (output, data) = stake_cell(REQUIRED_STAKING_CAPACITY, None);
+    verify_stake_cell(&rollup_context, None, &output, &data).unwrap();
+
+    let (output, data) = stake_cell(REQUIRED_STAKING_CAPACITY - 1, None);
+    let err = verify_stake_cell(&rollup_context, None, &output, &data).unwrap_err();
+    assert!(err.to_string().contains("less than required"));
+}
+
+#[test]
+fn test_verify_stake_cell_sudt_bond() {
+    let rollup_context = rollup_context();
+    let sudt_bond = sudt_bond();
+    let bonded = |amount| Some((sudt_bond.sudt_type.clone(), amount));
+
+    let (output, data) = stake_cell(REQUIRED_STAKING_CAPACITY, bonded(STAKE_AMOUNT));
+    verify_stake_cell(&rollup_context, Some(&sudt_bond), &output, &data).unwrap();
+
+    let (output, data) = stake_cell(REQUIRED_STAKING_CAPACITY, bonded(STAKE_AMOUNT + 1));
+    verify_stake_cell(&rollup_context, Some(&sudt_bond), &output, &data).unwrap();
+
+    // Under-bonded
+    let (output, data) = stake_cell(REQUIRED_STAKING_CAPACITY, bonded(STAKE_AMOUNT - 1));
+    let err = verify_stake_cell(&rollup_context, Some(&sudt_bond), &output, &data).unwrap_err();
+    assert!(err.to_string().contains("less than required"));
+
+    // Not bonded
+    let (output, data) = stake_cell(REQUIRED_STAKING_CAPACITY, None);
+    verify_stake_cell(&rollup_context, Some(&sudt_bond), &output, &data).unwrap_err();
+
+    // Bonded in another sudt
+    let another_sudt = Some((sudt_type([4u8; 32]), STAKE_AMOUNT));
+    let (output, data) = stake_cell(REQUIRED_STAKING_CAPACITY, another_sudt);
+    verify_stake_cell(&rollup_context, Some(&sudt_bond), &output, &data).unwrap_err();
+
+    // Bonded but the rollup doesn't require sudt bond
+    let (output, data) = stake_cell(REQUIRED_STAKING_CAPACITY, bonded(STAKE_AMOUNT));
+    verify_stake_cell(&rollup_context, None, &output, &data).unwrap_err();
+}
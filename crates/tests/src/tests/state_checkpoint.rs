@@ -0,0 +1,42 @@
+use crate::testing_tool::{
+    chain::setup_chain,
+    node_rpc::{build_node_rpc_server, call},
+};
+
+use gw_config::NodeMode;
+use gw_types::packed::Script;
+use serde_json::json;
+use smol::lock::Mutex;
+
+use std::sync::Arc;
+
+#[test]
+fn test_get_state_at_sub_state_checkpoint() {
+    let chain = Arc::new(Mutex::new(setup_chain(Script::default())));
+    let rpc_server = build_node_rpc_server(&chain, Default::default(), NodeMode::FullNode);
+
+    // valid checkpoint of genesis block
+    let checkpoint = json!({"block_number": "0x0", "sub_state": {"type": "block"}});
+    let nonce = call(&rpc_server, "gw_get_nonce", json!(["0x0", checkpoint]));
+    assert_eq!(nonce, Ok(json!("0x0")));
+    let checkpoint = json!({"block_number": "0x0", "sub_state": {"type": "prev_txs"}});
+    let nonce = call(&rpc_server, "gw_get_nonce", json!(["0x0", checkpoint]));
+    assert_eq!(nonce, Ok(json!("0x0")));
+
+    // genesis block has no tx and withdrawal
+    for sub_state in vec![
+        json!({"type": "tx", "index": "0x0"}),
+        json!({"type": "withdrawal", "index": "0x0"}),
+    ] {
+        let checkpoint = json!({"block_number": "0x0", "sub_state": sub_state});
+        let err = call(&rpc_server, "gw_get_nonce", json!(["0x0", checkpoint])).unwrap_err();
+        assert_eq!(err["code"], json!(-32602));
+        assert_eq!(err["message"], json!("invalid sub state index"));
+    }
+
+    // unknown block isn't reported as invalid sub state index
+    let checkpoint = json!({"block_number": "0x64", "sub_state": {"type": "block"}});
+    let err = call(&rpc_server, "gw_get_nonce", json!(["0x0", checkpoint])).unwrap_err();
+    assert_eq!(err["code"], json!(-32000));
+    assert_eq!(err["message"], json!("header not found"));
+}
@@ -0,0 +1,142 @@
+#![allow(clippy::mutable_key_type)]
+
+use crate::testing_tool::chain::{
+    apply_block_result, construct_block, setup_chain, ALWAYS_SUCCESS_CODE_HASH,
+    DEFAULT_FINALITY_BLOCKS,
+};
+
+use gw_chain::chain::Chain;
+use gw_common::H256;
+use gw_types::{
+    core::ScriptHashType,
+    packed::{CellOutput, DepositRequest, RawWithdrawalRequest, Script, WithdrawalRequest},
+    prelude::*,
+};
+
+use std::collections::HashSet;
+
+fn produce_block(
+    chain: &mut Chain,
+    rollup_cell: CellOutput,
+    deposit_requests: Vec<DepositRequest>,
+    withdrawals: Vec<WithdrawalRequest>,
+) {
+    let block_result = {
+        let mem_pool = chain.mem_pool().as_ref().unwrap();
+        let mut mem_pool = smol::block_on(mem_pool.lock());
+        for withdrawal in withdrawals {
+            mem_pool.push_withdrawal_request(withdrawal).unwrap();
+        }
+        construct_block(chain, &mut mem_pool, deposit_requests.clone()).unwrap()
+    };
+    apply_block_result(
+        chain,
+        rollup_cell,
+        block_result,
+        deposit_requests,
+        HashSet::new(),
+    );
+}
+
+fn build_withdrawal(
+    user_script_hash: H256,
+    capacity: u64,
+    nonce: u32,
+    payment_lock_hash: H256,
+) -> WithdrawalRequest {
+    let raw = RawWithdrawalRequest::new_builder()
+        .nonce(nonce.pack())
+        .capacity(capacity.pack())
+        .account_script_hash(user_script_hash.pack())
+        .sudt_script_hash(H256::zero().pack())
+        .sell_capacity(100_00000000u64.pack())
+        .payment_lock_hash(payment_lock_hash.pack())
+        .build();
+    WithdrawalRequest::new_builder().raw(raw).build()
+}
+
+fn tip_block_number(chain: &Chain) -> u64 {
+    let db = chain.store().begin_transaction();
+    db.get_tip_block().unwrap().raw().number().unpack()
+}
+
+#[test]
+fn test_create_and_list_withdrawal_offers() {
+    let rollup_type_script = Script::default();
+    let rollup_script_hash = rollup_type_script.hash();
+    let mut chain = setup_chain(rollup_type_script.clone());
+    let rollup_cell = CellOutput::new_builder()
+        .type_(Some(rollup_type_script).pack())
+        .build();
+    let user_script = Script::new_builder()
+        .code_hash(ALWAYS_SUCCESS_CODE_HASH.pack())
+        .hash_type(ScriptHashType::Type.into())
+        .args({
+            let mut args = rollup_script_hash.to_vec();
+            args.push(42);
+            args.pack()
+        })
+        .build();
+    let user_script_hash: H256 = user_script.hash().into();
+
+    // deposit and wait for it to be finalized
+    let deposit = DepositRequest::new_builder()
+        .capacity(1000_00000000u64.pack())
+        .sudt_script_hash(H256::zero().pack())
+        .script(user_script)
+        .build();
+    produce_block(&mut chain, rollup_cell.clone(), vec![deposit], vec![]);
+    for _ in 0..DEFAULT_FINALITY_BLOCKS {
+        produce_block(&mut chain, rollup_cell.clone(), vec![], vec![]);
+    }
+    assert!(chain
+        .store()
+        .begin_transaction()
+        .get_withdrawal_offers(0)
+        .unwrap()
+        .is_empty());
+
+    // withdrawal with a payment lock is an offer
+    let payment_lock_hash: H256 = [42u8; 32].into();
+    let offer = build_withdrawal(user_script_hash, 300_00000000, 0, payment_lock_hash);
+    produce_block(&mut chain, rollup_cell.clone(), vec![], vec![offer.clone()]);
+    let offer_block_number = tip_block_number(&chain);
+
+    // withdrawal without a payment lock isn't
+    let plain = build_withdrawal(user_script_hash, 300_00000000, 1, H256::zero());
+    produce_block(&mut chain, rollup_cell.clone(), vec![], vec![plain]);
+
+    let offers = {
+        let db = chain.store().begin_transaction();
+        db.get_withdrawal_offers(0).unwrap()
+    };
+    assert_eq!(offers.len(), 1);
+    let (block_number, withdrawal) = &offers[0];
+    assert_eq!(*block_number, offer_block_number);
+    assert_eq!(withdrawal.hash(), offer.hash());
+    assert_eq!(withdrawal.as_slice(), offer.as_slice());
+
+    // listing starts from the given block number
+    let db = chain.store().begin_transaction();
+    assert!(db
+        .get_withdrawal_offers(offer_block_number + 1)
+        .unwrap()
+        .is_empty());
+    drop(db);
+
+    // offers are removed once the withdrawal block is finalized
+    let tip_number = tip_block_number(&chain);
+    for _ in tip_number..offer_block_number + DEFAULT_FINALITY_BLOCKS {
+        produce_block(&mut chain, rollup_cell.clone(), vec![], vec![]);
+    }
+    assert_eq!(
+        tip_block_number(&chain),
+        offer_block_number + DEFAULT_FINALITY_BLOCKS
+    );
+    assert!(chain
+        .store()
+        .begin_transaction()
+        .get_withdrawal_offers(0)
+        .unwrap()
+        .is_empty());
+}
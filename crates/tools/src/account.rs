@@ -3,7 +3,7 @@ use crate::{deploy_scripts::ScriptsDeploymentResult, hasher::CkbHasher};
 use ckb_crypto::secp::Privkey;
 use ckb_fixed_hash::H256;
 use ckb_jsonrpc_types::JsonBytes;
-use ckb_sdk::SECP256K1;
+use ckb_sdk::{AddressPayload, SECP256K1};
 use ckb_types::{
     bytes::Bytes as CKBBytes, core::ScriptHashType, prelude::Builder as CKBBuilder,
     prelude::Entity as CKBEntity,
@@ -139,6 +139,17 @@ pub fn parse_account_short_address(
     Ok(short_address)
 }
 
+// secp256k1 sighash lock of privkey
+pub fn privkey_to_sighash_lock(privkey: &H256) -> Result<Script, String> {
+    let privkey = secp256k1::SecretKey::from_slice(privkey.as_bytes())
+        .map_err(|err| format!("Invalid secp256k1 secret key format, error: {}", err))?;
+    let pubkey = secp256k1::PublicKey::from_secret_key(&SECP256K1, &privkey);
+    let address_payload = AddressPayload::from_pubkey(&pubkey);
+    let lock = ckb_types::packed::Script::from(&address_payload);
+
+    Ok(Script::new_unchecked(lock.as_bytes()))
+}
+
 pub fn read_privkey(privkey_path: &Path) -> Result<H256, String> {
     let privkey_string = fs::read_to_string(privkey_path)
         .map_err(|err| err.to_string())?
@@ -0,0 +1,140 @@
+use crate::account::{privkey_to_sighash_lock, read_privkey};
+use crate::deploy_scripts::ScriptsDeploymentResult;
+use crate::utils::transaction::{build_rpc_client, read_config, wait_for_tx};
+use anyhow::{anyhow, bail, Result};
+use ckb_fixed_hash::H256;
+use ckb_sdk::HttpRpcClient;
+use gw_types::{
+    bytes::Bytes,
+    offchain::InputCellInfo,
+    packed::{CellDep, CellInput, DepositLockArgs, DepositLockArgsReader, OutPoint},
+    prelude::*,
+};
+use gw_utils::{
+    fee::fill_tx_fee, genesis_info::CKBGenesisInfo, signer::PrivkeySigner,
+    transaction_skeleton::TransactionSkeleton, wallet::Wallet,
+};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+const WAIT_TX_TIMEOUT_SECS: u64 = 180;
+
+/// Unlock a deposit cell which is not collected by godwoken after its cancel
+/// timeout, the deposited assets are returned to the owner lock.
+pub fn cancel_deposit(
+    privkey_path: &Path,
+    deployment_results_path: &Path,
+    config_path: &Path,
+    tx_hash: &str,
+    index: u32,
+) -> Result<()> {
+    let config = read_config(config_path).map_err(|err| anyhow!(err))?;
+    let deployment_result: ScriptsDeploymentResult = {
+        let content = std::fs::read_to_string(deployment_results_path)?;
+        serde_json::from_str(&content)?
+    };
+    let rpc_client = build_rpc_client(&config)?;
+
+    let owner_lock = {
+        let privkey = read_privkey(privkey_path).map_err(|err| anyhow!(err))?;
+        privkey_to_sighash_lock(&privkey).map_err(|err| anyhow!(err))?
+    };
+    let wallet = Wallet::with_signer(
+        Arc::new(PrivkeySigner::from_file(privkey_path)?),
+        owner_lock.clone(),
+    );
+
+    let deposit_out_point = {
+        let tx_hash = H256::from_str(tx_hash.trim_start_matches("0x"))
+            .map_err(|err| anyhow!("invalid tx hash: {}", err))?;
+        OutPoint::new_builder()
+            .tx_hash(tx_hash.pack())
+            .index(index.pack())
+            .build()
+    };
+    let deposit_cell = smol::block_on(rpc_client.get_cell(deposit_out_point))?
+        .ok_or_else(|| anyhow!("deposit cell not found or already consumed"))?;
+
+    // Deposit lock args: rollup type hash (32 bytes) | DepositLockArgs
+    let deposit_lock_args = {
+        let args: Bytes = deposit_cell.output.lock().args().unpack();
+        if args.len() < 32 {
+            bail!("invalid deposit lock args");
+        }
+        DepositLockArgsReader::verify(&args[32..], false)?;
+        DepositLockArgs::new_unchecked(args.slice(32..))
+    };
+    if deposit_lock_args.owner_lock_hash().as_slice() != owner_lock.hash() {
+        bail!("deposit cell isn't owned by privkey");
+    }
+    let cancel_timeout: u64 = deposit_lock_args.cancel_timeout().unpack();
+
+    let ckb_genesis_info = {
+        let ckb_genesis = smol::block_on(rpc_client.get_block_by_number(0))?;
+        let ckb_genesis = ckb_genesis.ok_or_else(|| anyhow!("can't found CKB genesis block"))?;
+        CKBGenesisInfo::from_block(&ckb_genesis)?
+    };
+    let owner_cell = smol::block_on(rpc_client.query_owner_cell(owner_lock.clone(), None))?
+        .ok_or_else(|| anyhow!("owner cell not found for cancel deposit"))?;
+
+    let mut tx_skeleton = TransactionSkeleton::default();
+    let deposit_lock_dep = {
+        let dep: ckb_types::packed::CellDep = deployment_result.deposit_lock.cell_dep.into();
+        CellDep::new_unchecked(dep.as_bytes())
+    };
+    tx_skeleton
+        .cell_deps_mut()
+        .extend(vec![deposit_lock_dep, ckb_genesis_info.sighash_dep()]);
+    if deposit_cell.output.type_().is_some() {
+        let block_producer_config = config
+            .block_producer
+            .as_ref()
+            .ok_or_else(|| anyhow!("block producer config not found"))?;
+        let sudt_type_dep = block_producer_config.watchtower.l1_sudt_type_dep.clone();
+        tx_skeleton.cell_deps_mut().push(sudt_type_dep.into());
+    }
+
+    // Deposit cell can only be unlocked by owner after cancel timeout, which
+    // requires an owner lock cell in inputs.
+    let deposit_output = deposit_cell
+        .output
+        .clone()
+        .as_builder()
+        .lock(owner_lock.clone())
+        .build();
+    let deposit_data = deposit_cell.data.clone();
+    tx_skeleton.inputs_mut().push(InputCellInfo {
+        input: CellInput::new_builder()
+            .previous_output(deposit_cell.out_point.clone())
+            .since(cancel_timeout.pack())
+            .build(),
+        cell: deposit_cell,
+    });
+    tx_skeleton.inputs_mut().push(InputCellInfo {
+        input: CellInput::new_builder()
+            .previous_output(owner_cell.out_point.clone())
+            .build(),
+        cell: owner_cell,
+    });
+    tx_skeleton
+        .outputs_mut()
+        .push((deposit_output, deposit_data));
+
+    smol::block_on(fill_tx_fee(
+        &mut tx_skeleton,
+        &rpc_client.indexer,
+        owner_lock,
+    ))?;
+    let tx = wallet.sign_tx_skeleton_blocking(tx_skeleton)?;
+
+    let tx_hash: [u8; 32] = smol::block_on(rpc_client.send_transaction(tx))?.into();
+    println!("Send tx 0x{}...", hex::encode(tx_hash));
+
+    let mut ckb_rpc_client = HttpRpcClient::new(config.rpc_client.ckb_url.to_owned());
+    wait_for_tx(&mut ckb_rpc_client, &H256(tx_hash), WAIT_TX_TIMEOUT_SECS)
+        .map_err(|err| anyhow!("{}", err))?;
+    println!("Deposit is cancelled in tx 0x{}", hex::encode(tx_hash));
+
+    Ok(())
+}
@@ -0,0 +1,450 @@
+use crate::account::{privkey_to_sighash_lock, read_privkey};
+use crate::fast_withdrawal::parse_withdrawal_lock_args;
+use crate::godwoken_rpc::GodwokenRpcClient;
+use crate::utils::transaction::{build_rpc_client, read_config, wait_for_tx};
+use anyhow::{anyhow, bail, Result};
+use ckb_fixed_hash::H256;
+use ckb_sdk::{HttpRpcClient, HumanCapacity};
+use gw_jsonrpc_types::godwoken::{L2BlockCommittedInfo, WithdrawalStatus, WithdrawalWithStatus};
+use gw_rpc_client::rpc_client::RPCClient;
+use gw_types::{
+    core::DepType,
+    offchain::{global_state_from_slice, CellInfo, InputCellInfo},
+    packed::{
+        CellDep, CellInput, Script, UnlockWithdrawalViaFinalize, UnlockWithdrawalWitness,
+        UnlockWithdrawalWitnessUnion, WithdrawalLockArgs, WitnessArgs,
+    },
+    prelude::*,
+};
+use gw_utils::{
+    fee::fill_tx_fee, genesis_info::CKBGenesisInfo, signer::PrivkeySigner,
+    transaction_skeleton::TransactionSkeleton, wallet::Wallet,
+};
+use std::collections::HashSet;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+const WAIT_TX_TIMEOUT_SECS: u64 = 180;
+
+/// Track a withdrawal through mem pool -> L2 block -> L1 withdrawal cell -> finalized
+pub fn withdrawal_status(
+    godwoken_rpc_url: &str,
+    config_path: &Path,
+    withdrawal_hash: &str,
+) -> Result<()> {
+    let config = read_config(config_path).map_err(|err| anyhow!(err))?;
+    let rpc_client = build_rpc_client(&config)?;
+    let withdrawal = get_withdrawal(godwoken_rpc_url, withdrawal_hash)?;
+
+    println!("withdrawal hash: {}", withdrawal_hash);
+    println!("status: {:?}", withdrawal.status);
+    if let (Some(number), Some(hash)) = (&withdrawal.l2_block_number, &withdrawal.l2_block_hash) {
+        println!(
+            "l2 block: {} 0x{}",
+            number.value(),
+            hex::encode(hash.as_bytes())
+        );
+    }
+    if let Some(ref committed_info) = withdrawal.l1_committed_info {
+        println!(
+            "l1 tx: 0x{}",
+            hex::encode(committed_info.transaction_hash.as_bytes())
+        );
+    }
+    if withdrawal.status == WithdrawalStatus::Pending {
+        return Ok(());
+    }
+
+    match smol::block_on(query_withdrawal_cell(&rpc_client, &withdrawal))? {
+        Some(cell) => {
+            let index: u32 = cell.out_point.index().unpack();
+            let capacity: u64 = cell.output.capacity().unpack();
+            let last_finalized_block_number =
+                smol::block_on(query_last_finalized_block_number(&rpc_client))?;
+            let lock_args = parse_withdrawal_lock_args(&cell)?;
+            let withdrawal_block_number: u64 = lock_args.withdrawal_block_number().unpack();
+            println!(
+                "withdrawal cell tx hash: 0x{} index: {} capacity: {}",
+                hex::encode(cell.out_point.tx_hash().as_slice()),
+                index,
+                HumanCapacity::from(capacity)
+            );
+            println!(
+                "unlockable: {} (last finalized block {})",
+                withdrawal_block_number <= last_finalized_block_number,
+                last_finalized_block_number
+            );
+        }
+        None => println!("withdrawal cell not found, it may be claimed, sold or reverted"),
+    }
+
+    Ok(())
+}
+
+/// Unlock a finalized withdrawal cell to its owner lock
+pub fn claim_withdrawal(
+    privkey_path: &Path,
+    godwoken_rpc_url: &str,
+    config_path: &Path,
+    withdrawal_hash: &str,
+) -> Result<()> {
+    let config = read_config(config_path).map_err(|err| anyhow!(err))?;
+    let block_producer_config = config
+        .block_producer
+        .clone()
+        .ok_or_else(|| anyhow!("block producer config not found"))?;
+    let rpc_client = build_rpc_client(&config)?;
+
+    let owner_lock = {
+        let privkey = read_privkey(privkey_path).map_err(|err| anyhow!(err))?;
+        privkey_to_sighash_lock(&privkey).map_err(|err| anyhow!(err))?
+    };
+    let wallet = Wallet::with_signer(
+        Arc::new(PrivkeySigner::from_file(privkey_path)?),
+        owner_lock.clone(),
+    );
+
+    let withdrawal = get_withdrawal(godwoken_rpc_url, withdrawal_hash)?;
+    if withdrawal.status == WithdrawalStatus::Pending {
+        bail!("withdrawal is pending in mem pool");
+    }
+    let withdrawal_cell = smol::block_on(query_withdrawal_cell(&rpc_client, &withdrawal))?
+        .ok_or_else(|| anyhow!("withdrawal cell not found, it may be claimed, sold or reverted"))?;
+
+    let lock_args = parse_withdrawal_lock_args(&withdrawal_cell)?;
+    let last_finalized_block_number =
+        smol::block_on(query_last_finalized_block_number(&rpc_client))?;
+    verify_claimable(&lock_args, &owner_lock.hash(), last_finalized_block_number)?;
+
+    let ckb_genesis_info = {
+        let ckb_genesis = smol::block_on(rpc_client.get_block_by_number(0))?;
+        let ckb_genesis = ckb_genesis.ok_or_else(|| anyhow!("can't found CKB genesis block"))?;
+        CKBGenesisInfo::from_block(&ckb_genesis)?
+    };
+    let rollup_cell = smol::block_on(rpc_client.query_rollup_cell())?
+        .ok_or_else(|| anyhow!("rollup cell not found"))?;
+    let owner_cell = smol::block_on(rpc_client.query_owner_cell(owner_lock.clone(), None))?
+        .ok_or_else(|| anyhow!("owner cell not found for claim withdrawal"))?;
+
+    let rollup_cell_dep = CellDep::new_builder()
+        .out_point(rollup_cell.out_point)
+        .dep_type(DepType::Code.into())
+        .build();
+    let mut cell_deps = vec![
+        block_producer_config
+            .withdrawal_cell_lock_dep
+            .clone()
+            .into(),
+        rollup_cell_dep,
+        ckb_genesis_info.sighash_dep(),
+    ];
+    if withdrawal_cell.output.type_().is_some() {
+        let sudt_type_dep = block_producer_config.watchtower.l1_sudt_type_dep.clone();
+        cell_deps.push(sudt_type_dep.into());
+    }
+
+    let mut tx_skeleton =
+        build_claim_tx_skeleton(cell_deps, withdrawal_cell, owner_cell, owner_lock.clone());
+    smol::block_on(fill_tx_fee(
+        &mut tx_skeleton,
+        &rpc_client.indexer,
+        owner_lock,
+    ))?;
+    let tx = wallet.sign_tx_skeleton_blocking(tx_skeleton)?;
+
+    let tx_hash: [u8; 32] = smol::block_on(rpc_client.send_transaction(tx))?.into();
+    println!("Send tx 0x{}...", hex::encode(tx_hash));
+
+    let mut ckb_rpc_client = HttpRpcClient::new(config.rpc_client.ckb_url.to_owned());
+    wait_for_tx(&mut ckb_rpc_client, &H256(tx_hash), WAIT_TX_TIMEOUT_SECS)
+        .map_err(|err| anyhow!("{}", err))?;
+    println!("Withdrawal is claimed in tx 0x{}", hex::encode(tx_hash));
+
+    Ok(())
+}
+
+// Withdrawal cell is claimable by its owner after the withdrawal block is finalized
+fn verify_claimable(
+    lock_args: &WithdrawalLockArgs,
+    owner_lock_hash: &[u8; 32],
+    last_finalized_block_number: u64,
+) -> Result<()> {
+    if lock_args.owner_lock_hash().as_slice() != owner_lock_hash {
+        bail!("withdrawal cell isn't owned by privkey");
+    }
+    let withdrawal_block_number: u64 = lock_args.withdrawal_block_number().unpack();
+    if withdrawal_block_number > last_finalized_block_number {
+        bail!(
+            "withdrawal block {} isn't finalized, last finalized block {}",
+            withdrawal_block_number,
+            last_finalized_block_number
+        );
+    }
+    Ok(())
+}
+
+// Finalized withdrawal cell can be unlocked by owner, which requires rollup cell
+// as cell dep and an owner lock cell in inputs.
+fn build_claim_tx_skeleton(
+    cell_deps: Vec<CellDep>,
+    withdrawal_cell: CellInfo,
+    owner_cell: CellInfo,
+    owner_lock: Script,
+) -> TransactionSkeleton {
+    let mut tx_skeleton = TransactionSkeleton::default();
+    tx_skeleton.cell_deps_mut().extend(cell_deps);
+
+    let unlock_via_finalize = UnlockWithdrawalWitness::new_builder()
+        .set(UnlockWithdrawalWitnessUnion::UnlockWithdrawalViaFinalize(
+            UnlockWithdrawalViaFinalize::default(),
+        ))
+        .build();
+    let withdrawal_witness = WitnessArgs::new_builder()
+        .lock(Some(unlock_via_finalize.as_bytes()).pack())
+        .build();
+
+    let claimed_output = withdrawal_cell
+        .output
+        .clone()
+        .as_builder()
+        .lock(owner_lock)
+        .build();
+    let claimed_data = withdrawal_cell.data.clone();
+    tx_skeleton.inputs_mut().push(InputCellInfo {
+        input: CellInput::new_builder()
+            .previous_output(withdrawal_cell.out_point.clone())
+            .build(),
+        cell: withdrawal_cell,
+    });
+    tx_skeleton.witnesses_mut().push(withdrawal_witness);
+    tx_skeleton.inputs_mut().push(InputCellInfo {
+        input: CellInput::new_builder()
+            .previous_output(owner_cell.out_point.clone())
+            .build(),
+        cell: owner_cell,
+    });
+    tx_skeleton
+        .outputs_mut()
+        .push((claimed_output, claimed_data));
+
+    tx_skeleton
+}
+
+fn get_withdrawal(godwoken_rpc_url: &str, withdrawal_hash: &str) -> Result<WithdrawalWithStatus> {
+    let withdrawal_hash = H256::from_str(withdrawal_hash.trim_start_matches("0x"))
+        .map_err(|err| anyhow!("invalid withdrawal hash: {}", err))?;
+    let mut godwoken_rpc_client = GodwokenRpcClient::new(godwoken_rpc_url);
+    let withdrawal = godwoken_rpc_client
+        .get_withdrawal(&withdrawal_hash)
+        .map_err(|err| anyhow!(err))?;
+
+    withdrawal.ok_or_else(|| anyhow!("withdrawal not found"))
+}
+
+// Withdrawal cell is created by the L1 tx which submits the withdrawal block
+async fn query_withdrawal_cell(
+    rpc_client: &RPCClient,
+    withdrawal: &WithdrawalWithStatus,
+) -> Result<Option<CellInfo>> {
+    let (block_hash, committed_info) =
+        match (&withdrawal.l2_block_hash, &withdrawal.l1_committed_info) {
+            (Some(block_hash), Some(committed_info)) => (block_hash, committed_info),
+            _ => return Ok(None),
+        };
+
+    let block_hashes: HashSet<gw_common::H256> = {
+        let block_hash: [u8; 32] = block_hash.clone().into();
+        vec![block_hash.into()].into_iter().collect()
+    };
+    let (cells, _) = rpc_client
+        .query_withdrawal_cells_by_block_hashes(&block_hashes)
+        .await?;
+
+    Ok(cells
+        .into_iter()
+        .find(|cell| is_withdrawal_cell(cell, withdrawal, committed_info)))
+}
+
+// Match the withdrawal cell created by the committed withdrawal request
+fn is_withdrawal_cell(
+    cell: &CellInfo,
+    withdrawal: &WithdrawalWithStatus,
+    committed_info: &L2BlockCommittedInfo,
+) -> bool {
+    let raw = &withdrawal.withdrawal.raw;
+    let lock_args = match parse_withdrawal_lock_args(cell) {
+        Ok(lock_args) => lock_args,
+        Err(_) => return false,
+    };
+    let capacity: u64 = cell.output.capacity().unpack();
+    cell.out_point.tx_hash().as_slice() == committed_info.transaction_hash.as_bytes()
+        && lock_args.account_script_hash().as_slice() == raw.account_script_hash.as_bytes()
+        && lock_args.owner_lock_hash().as_slice() == raw.owner_lock_hash.as_bytes()
+        && capacity == raw.capacity.value()
+}
+
+async fn query_last_finalized_block_number(rpc_client: &RPCClient) -> Result<u64> {
+    let rollup_cell = rpc_client
+        .query_rollup_cell()
+        .await?
+        .ok_or_else(|| anyhow!("rollup cell not found"))?;
+    let global_state = global_state_from_slice(&rollup_cell.data)?;
+    Ok(global_state.last_finalized_block_number().unpack())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gw_jsonrpc_types::godwoken::{RawWithdrawalRequest, WithdrawalRequest};
+    use gw_types::{
+        bytes::Bytes,
+        packed::{CellOutput, OutPoint},
+    };
+
+    const TX_HASH: [u8; 32] = [1u8; 32];
+    const ACCOUNT_SCRIPT_HASH: [u8; 32] = [2u8; 32];
+    const OWNER_LOCK_HASH: [u8; 32] = [3u8; 32];
+
+    fn withdrawal_lock_args(withdrawal_block_number: u64) -> WithdrawalLockArgs {
+        WithdrawalLockArgs::new_builder()
+            .account_script_hash(ACCOUNT_SCRIPT_HASH.pack())
+            .withdrawal_block_number(withdrawal_block_number.pack())
+            .owner_lock_hash(OWNER_LOCK_HASH.pack())
+            .build()
+    }
+
+    fn withdrawal_cell(lock_args: &WithdrawalLockArgs, capacity: u64) -> CellInfo {
+        let args = {
+            let mut args = vec![0u8; 32];
+            args.extend_from_slice(lock_args.as_slice());
+            Bytes::from(args)
+        };
+        let lock = Script::new_builder().args(args.pack()).build();
+        CellInfo {
+            out_point: OutPoint::new_builder().tx_hash(TX_HASH.pack()).build(),
+            output: CellOutput::new_builder()
+                .capacity(capacity.pack())
+                .lock(lock)
+                .build(),
+            data: Bytes::from(vec![42u8; 16]),
+        }
+    }
+
+    fn committed_withdrawal(capacity: u64) -> (WithdrawalWithStatus, L2BlockCommittedInfo) {
+        let withdrawal = WithdrawalWithStatus {
+            withdrawal: WithdrawalRequest {
+                raw: RawWithdrawalRequest {
+                    capacity: capacity.into(),
+                    account_script_hash: H256(ACCOUNT_SCRIPT_HASH),
+                    owner_lock_hash: H256(OWNER_LOCK_HASH),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            status: WithdrawalStatus::Committed,
+            ..Default::default()
+        };
+        let committed_info = L2BlockCommittedInfo {
+            transaction_hash: H256(TX_HASH),
+            ..Default::default()
+        };
+        (withdrawal, committed_info)
+    }
+
+    #[test]
+    fn test_verify_claimable() {
+        let lock_args = withdrawal_lock_args(10);
+        verify_claimable(&lock_args, &OWNER_LOCK_HASH, 10).expect("finalized");
+        verify_claimable(&lock_args, &OWNER_LOCK_HASH, 11).expect("finalized");
+
+        let err = verify_claimable(&lock_args, &OWNER_LOCK_HASH, 9).unwrap_err();
+        assert!(err.to_string().contains("isn't finalized"));
+        let err = verify_claimable(&lock_args, &[4u8; 32], 10).unwrap_err();
+        assert!(err.to_string().contains("isn't owned"));
+    }
+
+    #[test]
+    fn test_is_withdrawal_cell() {
+        let capacity = 100_00000000u64;
+        let (withdrawal, committed_info) = committed_withdrawal(capacity);
+        let cell = withdrawal_cell(&withdrawal_lock_args(10), capacity);
+        assert!(is_withdrawal_cell(&cell, &withdrawal, &committed_info));
+
+        // created by another tx
+        let mut other_tx = committed_info.clone();
+        other_tx.transaction_hash = H256([5u8; 32]);
+        assert!(!is_withdrawal_cell(&cell, &withdrawal, &other_tx));
+
+        // another withdrawal of the same account
+        let other_capacity = withdrawal_cell(&withdrawal_lock_args(10), capacity + 1);
+        assert!(!is_withdrawal_cell(
+            &other_capacity,
+            &withdrawal,
+            &committed_info
+        ));
+
+        // not a withdrawal cell
+        let mut not_withdrawal = cell;
+        not_withdrawal.output = not_withdrawal
+            .output
+            .as_builder()
+            .lock(Script::default())
+            .build();
+        assert!(!is_withdrawal_cell(
+            &not_withdrawal,
+            &withdrawal,
+            &committed_info
+        ));
+    }
+
+    #[test]
+    fn test_build_claim_tx_skeleton() {
+        let withdrawal_cell = withdrawal_cell(&withdrawal_lock_args(10), 100_00000000);
+        let owner_lock = Script::new_builder()
+            .args(Bytes::from(vec![3u8; 20]).pack())
+            .build();
+        let owner_cell = CellInfo {
+            out_point: OutPoint::new_builder().index(1u32.pack()).build(),
+            output: CellOutput::new_builder().lock(owner_lock.clone()).build(),
+            data: Bytes::default(),
+        };
+        let cell_deps = vec![CellDep::default(); 3];
+
+        let tx_skeleton = build_claim_tx_skeleton(
+            cell_deps,
+            withdrawal_cell.clone(),
+            owner_cell.clone(),
+            owner_lock.clone(),
+        );
+
+        let inputs = tx_skeleton.inputs();
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(
+            inputs[0].input.previous_output().as_slice(),
+            withdrawal_cell.out_point.as_slice()
+        );
+        assert_eq!(
+            inputs[1].input.previous_output().as_slice(),
+            owner_cell.out_point.as_slice()
+        );
+
+        // withdrawal cell is unlocked via finalize
+        let witness = tx_skeleton.witnesses().get(0).expect("withdrawal witness");
+        let lock: Bytes = witness.lock().to_opt().expect("witness lock").unpack();
+        let unlock = UnlockWithdrawalWitness::from_slice(&lock).expect("unlock witness");
+        assert!(matches!(
+            unlock.to_enum(),
+            UnlockWithdrawalWitnessUnion::UnlockWithdrawalViaFinalize(_)
+        ));
+
+        // claimed cell keeps capacity and data, and is locked by the owner
+        let (output, data) = tx_skeleton.outputs().get(0).expect("claimed output");
+        assert_eq!(output.lock().as_slice(), owner_lock.as_slice());
+        assert_eq!(
+            output.capacity().as_slice(),
+            withdrawal_cell.output.capacity().as_slice()
+        );
+        assert_eq!(data, &withdrawal_cell.data);
+    }
+}
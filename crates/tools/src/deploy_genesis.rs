@@ -435,6 +435,7 @@ pub fn deploy_genesis(
         rollup_type_hash: rollup_script_hash.clone(),
         rollup_config: rollup_config.clone().into(),
         secp_data_dep,
+        sudt_bond: None,
     };
     let genesis_with_global_state =
         build_genesis(&genesis_config, secp_data).map_err(|err| err.to_string())?;
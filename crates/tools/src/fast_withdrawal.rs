@@ -0,0 +1,301 @@
+//! Fast withdrawal via liquidity provider
+//!
+//! A withdrawal cell can't be unlocked by its owner before the withdrawal
+//! block is finalized. The owner can sell it by setting a payment lock in
+//! the withdrawal request, a liquidity provider then buys the cell by paying
+//! `sell_capacity` and `sell_amount` to the payment lock, and becomes the
+//! new owner of the withdrawal cell.
+
+#![allow(clippy::mutable_key_type)]
+
+use crate::account::{privkey_to_sighash_lock, read_privkey};
+use crate::godwoken_rpc::GodwokenRpcClient;
+use crate::utils::transaction::{build_rpc_client, read_config, wait_for_tx};
+use anyhow::{anyhow, bail, Result};
+use ckb_fixed_hash::H256;
+use ckb_sdk::{Address, HttpRpcClient, HumanCapacity};
+use gw_types::{
+    bytes::Bytes,
+    core::DepType,
+    offchain::{CellInfo, InputCellInfo},
+    packed::{
+        CellDep, CellInput, CellOutput, OutPoint, Script, UnlockWithdrawalViaTrade,
+        UnlockWithdrawalWitness, UnlockWithdrawalWitnessUnion, WithdrawalLockArgs,
+        WithdrawalLockArgsReader, WitnessArgs,
+    },
+    prelude::*,
+};
+use gw_utils::{
+    fee::fill_tx_fee,
+    genesis_info::CKBGenesisInfo,
+    signer::PrivkeySigner,
+    sudt::{build_sudt_cell, collect_sudt, parse_sudt},
+    transaction_skeleton::TransactionSkeleton,
+    wallet::Wallet,
+};
+use std::collections::HashSet;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+const WAIT_TX_TIMEOUT_SECS: u64 = 180;
+
+/// List withdrawal cells on sale in unfinalized blocks
+pub fn list_withdrawal_offers(godwoken_rpc_url: &str, config_path: &Path) -> Result<()> {
+    let config = read_config(config_path).map_err(|err| anyhow!(err))?;
+    let rpc_client = build_rpc_client(&config)?;
+
+    let mut godwoken_rpc_client = GodwokenRpcClient::new(godwoken_rpc_url);
+    let offers = godwoken_rpc_client
+        .get_withdrawal_offers()
+        .map_err(|err| anyhow!(err))?;
+    let block_hashes: HashSet<gw_common::H256> = offers
+        .iter()
+        .map(|offer| {
+            let block_hash: [u8; 32] = offer.block_hash.clone().into();
+            block_hash.into()
+        })
+        .collect();
+    if block_hashes.is_empty() {
+        println!("no withdrawal offer found");
+        return Ok(());
+    }
+
+    let query = rpc_client.query_withdrawal_cells_by_block_hashes(&block_hashes);
+    let (cells, _) = smol::block_on(query)?;
+    for cell in cells {
+        let lock_args = match parse_withdrawal_lock_args(&cell) {
+            Ok(lock_args) => lock_args,
+            Err(_) => continue,
+        };
+        if lock_args.payment_lock_hash().as_slice() == [0u8; 32] {
+            continue;
+        }
+
+        let index: u32 = cell.out_point.index().unpack();
+        let capacity: u64 = cell.output.capacity().unpack();
+        let withdrawal_block_number: u64 = lock_args.withdrawal_block_number().unpack();
+        let sell_capacity: u64 = lock_args.sell_capacity().unpack();
+        let sell_amount: u128 = lock_args.sell_amount().unpack();
+        let amount = parse_sudt(&cell).map(|(_, amount)| amount).unwrap_or(0);
+        println!(
+            "withdrawal cell tx hash: 0x{} index: {} block: {} capacity: {} amount: {} sudt script hash: 0x{} sell capacity: {} sell amount: {} payment lock hash: 0x{}",
+            hex::encode(cell.out_point.tx_hash().as_slice()),
+            index,
+            withdrawal_block_number,
+            HumanCapacity::from(capacity),
+            amount,
+            hex::encode(lock_args.sudt_script_hash().as_slice()),
+            HumanCapacity::from(sell_capacity),
+            sell_amount,
+            hex::encode(lock_args.payment_lock_hash().as_slice()),
+        );
+    }
+
+    Ok(())
+}
+
+/// Buy a withdrawal cell on sale, the buyer pays with cells of privkey's sighash lock
+/// and becomes the new owner of the withdrawal cell.
+pub fn buy_withdrawal(
+    privkey_path: &Path,
+    config_path: &Path,
+    tx_hash: &str,
+    index: u32,
+    payment_address: &str,
+) -> Result<()> {
+    let config = read_config(config_path).map_err(|err| anyhow!(err))?;
+    let block_producer_config = config
+        .block_producer
+        .clone()
+        .ok_or_else(|| anyhow!("block producer config not found"))?;
+    let rpc_client = build_rpc_client(&config)?;
+
+    let buyer_lock = {
+        let privkey = read_privkey(privkey_path).map_err(|err| anyhow!(err))?;
+        privkey_to_sighash_lock(&privkey).map_err(|err| anyhow!(err))?
+    };
+    let wallet = Wallet::with_signer(
+        Arc::new(PrivkeySigner::from_file(privkey_path)?),
+        buyer_lock.clone(),
+    );
+
+    let withdrawal_out_point = {
+        let tx_hash = H256::from_str(tx_hash.trim_start_matches("0x"))
+            .map_err(|err| anyhow!("invalid tx hash: {}", err))?;
+        OutPoint::new_builder()
+            .tx_hash(tx_hash.pack())
+            .index(index.pack())
+            .build()
+    };
+    let withdrawal_cell = smol::block_on(rpc_client.get_cell(withdrawal_out_point))?
+        .ok_or_else(|| anyhow!("withdrawal cell not found or already consumed"))?;
+    let lock_args = parse_withdrawal_lock_args(&withdrawal_cell)?;
+
+    let payment_lock = {
+        let address = Address::from_str(payment_address).map_err(|err| anyhow!(err))?;
+        let lock = ckb_types::packed::Script::from(address.payload());
+        Script::new_unchecked(lock.as_bytes())
+    };
+    if lock_args.payment_lock_hash().as_slice() == [0u8; 32] {
+        bail!("withdrawal isn't on sale");
+    }
+    if lock_args.payment_lock_hash().as_slice() != payment_lock.hash() {
+        bail!("payment address doesn't match withdrawal payment lock hash");
+    }
+    if lock_args.owner_lock_hash().as_slice() == buyer_lock.hash() {
+        bail!("withdrawal is already owned by privkey");
+    }
+
+    // New withdrawal cell is owned by buyer, payment is also redirected to buyer
+    // so that the cell can't be bought again by paying to the seller.
+    let new_withdrawal_output = {
+        let new_lock_args = lock_args
+            .clone()
+            .as_builder()
+            .owner_lock_hash(buyer_lock.hash().pack())
+            .payment_lock_hash(buyer_lock.hash().pack())
+            .build();
+        let args: Bytes = withdrawal_cell.output.lock().args().unpack();
+        let new_args: Bytes = args[..32]
+            .iter()
+            .chain(new_lock_args.as_slice().iter())
+            .cloned()
+            .collect();
+        let lock = withdrawal_cell
+            .output
+            .lock()
+            .as_builder()
+            .args(new_args.pack())
+            .build();
+        withdrawal_cell
+            .output
+            .clone()
+            .as_builder()
+            .lock(lock)
+            .build()
+    };
+
+    let sell_capacity: u64 = lock_args.sell_capacity().unpack();
+    let sell_amount: u128 = lock_args.sell_amount().unpack();
+    let payment_output = CellOutput::new_builder()
+        .capacity(sell_capacity.pack())
+        .lock(payment_lock)
+        .build();
+
+    let mut tx_skeleton = TransactionSkeleton::default();
+    let payment = if sell_amount > 0 {
+        let sudt_type = withdrawal_cell
+            .output
+            .type_()
+            .to_opt()
+            .ok_or_else(|| anyhow!("withdrawal cell has no sudt to pay sell amount"))?;
+        let collected = smol::block_on(collect_sudt(
+            &rpc_client,
+            buyer_lock.clone(),
+            sudt_type.clone(),
+            sell_amount,
+            &HashSet::new(),
+        ))?;
+        tx_skeleton.inputs_mut().extend(collected.inputs);
+        tx_skeleton.outputs_mut().extend(collected.change);
+        build_sudt_cell(payment_output, sudt_type, sell_amount)?
+    } else {
+        (payment_output, Bytes::new())
+    };
+
+    let ckb_genesis_info = {
+        let ckb_genesis = smol::block_on(rpc_client.get_block_by_number(0))?;
+        let ckb_genesis = ckb_genesis.ok_or_else(|| anyhow!("can't found CKB genesis block"))?;
+        CKBGenesisInfo::from_block(&ckb_genesis)?
+    };
+    let rollup_cell = smol::block_on(rpc_client.query_rollup_cell())?
+        .ok_or_else(|| anyhow!("rollup cell not found"))?;
+    let owner_cell = smol::block_on(rpc_client.query_owner_cell(buyer_lock.clone(), None))?
+        .ok_or_else(|| anyhow!("owner cell not found for buy withdrawal"))?;
+
+    let rollup_cell_dep = CellDep::new_builder()
+        .out_point(rollup_cell.out_point)
+        .dep_type(DepType::Code.into())
+        .build();
+    tx_skeleton.cell_deps_mut().extend(vec![
+        block_producer_config
+            .withdrawal_cell_lock_dep
+            .clone()
+            .into(),
+        rollup_cell_dep,
+        ckb_genesis_info.sighash_dep(),
+    ]);
+    if withdrawal_cell.output.type_().is_some() {
+        let sudt_type_dep = block_producer_config.watchtower.l1_sudt_type_dep.clone();
+        tx_skeleton.cell_deps_mut().push(sudt_type_dep.into());
+    }
+
+    let unlock_via_trade = {
+        let unlock_args = UnlockWithdrawalViaTrade::new_builder()
+            .owner_lock(buyer_lock.clone())
+            .build();
+        UnlockWithdrawalWitness::new_builder()
+            .set(UnlockWithdrawalWitnessUnion::UnlockWithdrawalViaTrade(
+                unlock_args,
+            ))
+            .build()
+    };
+    let withdrawal_witness = WitnessArgs::new_builder()
+        .lock(Some(unlock_via_trade.as_bytes()).pack())
+        .build();
+
+    // Withdrawal cell must be the first input, its witness is at the same index
+    let withdrawal_data = withdrawal_cell.data.clone();
+    tx_skeleton.inputs_mut().insert(
+        0,
+        InputCellInfo {
+            input: CellInput::new_builder()
+                .previous_output(withdrawal_cell.out_point.clone())
+                .build(),
+            cell: withdrawal_cell,
+        },
+    );
+    tx_skeleton.witnesses_mut().push(withdrawal_witness);
+    tx_skeleton.inputs_mut().push(InputCellInfo {
+        input: CellInput::new_builder()
+            .previous_output(owner_cell.out_point.clone())
+            .build(),
+        cell: owner_cell,
+    });
+    tx_skeleton
+        .outputs_mut()
+        .insert(0, (new_withdrawal_output, withdrawal_data));
+    tx_skeleton.outputs_mut().insert(1, payment);
+
+    smol::block_on(fill_tx_fee(
+        &mut tx_skeleton,
+        &rpc_client.indexer,
+        buyer_lock,
+    ))?;
+    let tx = wallet.sign_tx_skeleton_blocking(tx_skeleton)?;
+
+    let tx_hash: [u8; 32] = smol::block_on(rpc_client.send_transaction(tx))?.into();
+    println!("Send tx 0x{}...", hex::encode(tx_hash));
+
+    let mut ckb_rpc_client = HttpRpcClient::new(config.rpc_client.ckb_url.to_owned());
+    wait_for_tx(&mut ckb_rpc_client, &H256(tx_hash), WAIT_TX_TIMEOUT_SECS)
+        .map_err(|err| anyhow!("{}", err))?;
+    println!(
+        "Withdrawal is bought in tx 0x{}, new withdrawal cell index: 0",
+        hex::encode(tx_hash)
+    );
+
+    Ok(())
+}
+
+// Withdrawal lock args: rollup type hash (32 bytes) | WithdrawalLockArgs
+pub fn parse_withdrawal_lock_args(cell: &CellInfo) -> Result<WithdrawalLockArgs> {
+    let args: Bytes = cell.output.lock().args().unpack();
+    if args.len() < 32 {
+        bail!("invalid withdrawal lock args");
+    }
+    WithdrawalLockArgsReader::verify(&args[32..], false)?;
+
+    Ok(WithdrawalLockArgs::new_unchecked(args.slice(32..)))
+}
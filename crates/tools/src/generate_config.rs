@@ -6,7 +6,8 @@ use ckb_sdk::HttpRpcClient;
 use ckb_types::prelude::{Builder, Entity};
 use gw_config::{
     BackendConfig, BlockProducerConfig, ChainConfig, ChallengerConfig, Config, GenesisConfig,
-    NodeMode, RPCClientConfig, RPCServerConfig, StoreConfig, WalletConfig, Web3IndexerConfig,
+    NodeMode, RPCClientConfig, RPCServerConfig, StoreConfig, WalletConfig, WatchtowerConfig,
+    Web3IndexerConfig,
 };
 use gw_jsonrpc_types::godwoken::L2BlockCommittedInfo;
 use gw_types::{core::ScriptHashType, packed::Script, prelude::*};
@@ -214,6 +215,7 @@ pub fn generate_config(
     let wallet_config: WalletConfig = WalletConfig {
         privkey_path: privkey_path.into(),
         lock,
+        signer: None,
     };
 
     let backends: Vec<BackendConfig> = vec![
@@ -224,11 +226,15 @@ pub fn generate_config(
                 .meta_contract_validator
                 .script_type_hash
                 .clone(),
+            activation_block_number: 0,
+            native_generator: None,
         },
         BackendConfig {
             validator_path: scripts_built.get_path("l2_sudt_validator"),
             generator_path: scripts_built.get_path("l2_sudt_generator"),
             validator_script_type_hash: scripts_results.l2_sudt_validator.script_type_hash.clone(),
+            activation_block_number: 0,
+            native_generator: None,
         },
         BackendConfig {
             validator_path: scripts_built.get_path("polyjuice_validator"),
@@ -237,6 +243,8 @@ pub fn generate_config(
                 .polyjuice_validator
                 .script_type_hash
                 .clone(),
+            activation_block_number: 0,
+            native_generator: None,
         },
     ];
 
@@ -256,24 +264,31 @@ pub fn generate_config(
         indexer_url,
         ckb_url,
     };
-    let rpc_server = RPCServerConfig { listen: server_url };
+    let rpc_server = RPCServerConfig {
+        listen: server_url,
+        admin: None,
+    };
     let block_producer: Option<BlockProducerConfig> = Some(BlockProducerConfig {
         account_id,
         // cell deps
         poa_lock_dep,
         poa_state_dep,
-        rollup_cell_type_dep,
-        rollup_config_cell_dep,
         deposit_cell_lock_dep,
-        stake_cell_lock_dep,
         custodian_cell_lock_dep,
         withdrawal_cell_lock_dep,
-        challenge_cell_lock_dep,
-        l1_sudt_type_dep,
-        allowed_eoa_deps,
-        allowed_contract_deps,
-        challenger_config,
-        wallet_config,
+        watchtower: WatchtowerConfig {
+            rollup_cell_type_dep,
+            rollup_config_cell_dep,
+            stake_cell_lock_dep,
+            challenge_cell_lock_dep,
+            l1_sudt_type_dep,
+            allowed_eoa_deps,
+            allowed_contract_deps,
+            challenger_config,
+            wallet_config,
+        },
+        fee_config: Default::default(),
+        custodian_merge: Default::default(),
     });
     let genesis: GenesisConfig = GenesisConfig {
         timestamp: genesis.timestamp,
@@ -281,6 +296,7 @@ pub fn generate_config(
         meta_contract_validator_type_hash,
         rollup_config,
         secp_data_dep,
+        sudt_bond: None,
     };
     let eth_account_lock_hash = genesis
         .rollup_config
@@ -314,7 +330,11 @@ pub fn generate_config(
         debug: Default::default(),
         offchain_validator: Default::default(),
         mem_pool: Default::default(),
+        execution_limits: Default::default(),
+        account_locks: Default::default(),
         db_block_validator: Default::default(),
+        metrics: Default::default(),
+        watchtower: Default::default(),
     };
 
     let output_content = toml::to_string_pretty(&config).expect("serde toml to string pretty");
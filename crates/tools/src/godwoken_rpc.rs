@@ -3,7 +3,7 @@ use ckb_types::H256;
 use gw_jsonrpc_types::{
     ckb_jsonrpc_types::{JsonBytes, Uint128, Uint32},
     debugger::{DumpChallengeTarget, ReprMockTransaction},
-    godwoken::{RunResult, TxReceipt},
+    godwoken::{RunResult, TxReceipt, WithdrawalOffer, WithdrawalWithStatus},
 };
 use std::{u128, u32};
 
@@ -114,6 +114,19 @@ impl GodwokenRpcClient {
             .map(|opt| opt.map(Into::into))
     }
 
+    pub fn get_withdrawal(
+        &mut self,
+        withdrawal_hash: &H256,
+    ) -> Result<Option<WithdrawalWithStatus>, String> {
+        let params = serde_json::to_value((withdrawal_hash,)).map_err(|err| err.to_string())?;
+        self.rpc::<Option<WithdrawalWithStatus>>("get_withdrawal", params)
+    }
+
+    pub fn get_withdrawal_offers(&mut self) -> Result<Vec<WithdrawalOffer>, String> {
+        let params = serde_json::Value::Null;
+        self.rpc::<Vec<WithdrawalOffer>>("get_withdrawal_offers", params)
+    }
+
     pub fn debug_dump_cancel_challenge_tx(
         &mut self,
         challenge_target: DumpChallengeTarget,
@@ -1,10 +1,13 @@
 mod account;
 mod address;
+mod cancel_deposit;
+mod claim_withdrawal;
 mod create_creator_account;
 mod deploy_genesis;
 mod deploy_scripts;
 mod deposit_ckb;
 mod dump_tx;
+mod fast_withdrawal;
 mod generate_config;
 mod get_balance;
 pub mod godwoken_rpc;
@@ -12,6 +15,7 @@ mod hasher;
 mod polyjuice;
 mod prepare_scripts;
 mod setup;
+mod stake;
 mod transfer;
 mod update_cell;
 mod utils;
@@ -320,6 +324,28 @@ fn run_cli() -> Result<()> {
                             "0x0000000000000000000000000000000000000000000000000000000000000000",
                         )
                         .help("l1 sudt script hash, default for withdrawal CKB"),
+                )
+                .arg(
+                    Arg::with_name("sell-capacity")
+                        .long("sell-capacity")
+                        .takes_value(true)
+                        .default_value("100")
+                        .help("CKB capacity a buyer pays to buy the withdrawal cell"),
+                )
+                .arg(
+                    Arg::with_name("sell-amount")
+                        .long("sell-amount")
+                        .takes_value(true)
+                        .default_value("0")
+                        .help("sUDT amount a buyer pays to buy the withdrawal cell"),
+                )
+                .arg(
+                    Arg::with_name("payment-ckb-address")
+                        .short("p")
+                        .long("payment-ckb-address")
+                        .takes_value(true)
+                        .required(false)
+                        .help("ckb address to receive payment, withdrawal cell is on sale if set"),
                 ),
         )
         .subcommand(
@@ -688,6 +714,117 @@ fn run_cli() -> Result<()> {
                         .required(true)
                         .help("output file"),
                 ),
+        )
+        .subcommand(
+            SubCommand::with_name("list-stake")
+                .about("List stake cells of block producer wallet")
+                .arg(arg_config_path.clone()),
+        )
+        .subcommand(
+            SubCommand::with_name("top-up-stake")
+                .about("Add capacity to a finalized stake cell of block producer wallet")
+                .arg(arg_config_path.clone())
+                .arg(
+                    Arg::with_name("capacity")
+                        .short("c")
+                        .long("capacity")
+                        .takes_value(true)
+                        .required(true)
+                        .help("CKB capacity to top up"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("withdraw-stake")
+                .about("Unlock a finalized stake cell and return its capacity to block producer wallet")
+                .arg(arg_config_path.clone()),
+        )
+        .subcommand(
+            SubCommand::with_name("cancel-deposit")
+                .about("Cancel a deposit which isn't collected after its cancel timeout")
+                .arg(arg_privkey_path.clone())
+                .arg(arg_deployment_results_path.clone())
+                .arg(arg_config_path.clone())
+                .arg(
+                    Arg::with_name("tx-hash")
+                        .short("t")
+                        .long("tx-hash")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The tx hash of deposit cell"),
+                )
+                .arg(
+                    Arg::with_name("index")
+                        .short("i")
+                        .long("index")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The output index of deposit cell"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("list-withdrawal-offers")
+                .about("List withdrawal cells on sale in unfinalized blocks")
+                .arg(arg_config_path.clone())
+                .arg(arg_godwoken_rpc_url.clone()),
+        )
+        .subcommand(
+            SubCommand::with_name("buy-withdrawal")
+                .about("Buy a withdrawal cell on sale before its block is finalized")
+                .arg(arg_privkey_path.clone())
+                .arg(arg_config_path.clone())
+                .arg(
+                    Arg::with_name("tx-hash")
+                        .short("t")
+                        .long("tx-hash")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The tx hash of withdrawal cell"),
+                )
+                .arg(
+                    Arg::with_name("index")
+                        .short("i")
+                        .long("index")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The output index of withdrawal cell"),
+                )
+                .arg(
+                    Arg::with_name("payment-ckb-address")
+                        .short("p")
+                        .long("payment-ckb-address")
+                        .takes_value(true)
+                        .required(true)
+                        .help("ckb address of withdrawal payment lock"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("withdrawal-status")
+                .about("Query status and withdrawal cell of a withdrawal request")
+                .arg(arg_config_path.clone())
+                .arg(arg_godwoken_rpc_url.clone())
+                .arg(
+                    Arg::with_name("withdrawal-hash")
+                        .short("w")
+                        .long("withdrawal-hash")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The withdrawal request hash"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("claim-withdrawal")
+                .about("Unlock a finalized withdrawal cell to its owner")
+                .arg(arg_privkey_path.clone())
+                .arg(arg_config_path.clone())
+                .arg(arg_godwoken_rpc_url.clone())
+                .arg(
+                    Arg::with_name("withdrawal-hash")
+                        .short("w")
+                        .long("withdrawal-hash")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The withdrawal request hash"),
+                ),
         );
 
     let matches = app.clone().get_matches();
@@ -833,6 +970,9 @@ fn run_cli() -> Result<()> {
             let godwoken_rpc_url = m.value_of("godwoken-rpc-url").unwrap();
             let owner_ckb_address = m.value_of("owner-ckb-address").unwrap();
             let sudt_script_hash = m.value_of("sudt-script-hash").unwrap();
+            let sell_capacity = m.value_of("sell-capacity").unwrap();
+            let sell_amount = m.value_of("sell-amount").unwrap();
+            let payment_ckb_address = m.value_of("payment-ckb-address");
 
             if let Err(err) = withdraw::withdraw(
                 godwoken_rpc_url,
@@ -843,6 +983,9 @@ fn run_cli() -> Result<()> {
                 owner_ckb_address,
                 config_path,
                 deployment_results_path,
+                sell_capacity,
+                sell_amount,
+                payment_ckb_address,
             ) {
                 log::error!("Withdrawal error: {}", err);
                 std::process::exit(-1);
@@ -1102,6 +1245,113 @@ fn run_cli() -> Result<()> {
                 std::process::exit(-1);
             };
         }
+        ("list-stake", Some(m)) => {
+            let config_path = Path::new(m.value_of("config-path").unwrap());
+
+            if let Err(err) = stake::list_stake(config_path) {
+                log::error!("List stake error: {}", err);
+                std::process::exit(-1);
+            };
+        }
+        ("top-up-stake", Some(m)) => {
+            let config_path = Path::new(m.value_of("config-path").unwrap());
+            let capacity = m.value_of("capacity").unwrap();
+
+            if let Err(err) = stake::top_up_stake(config_path, capacity) {
+                log::error!("Top up stake error: {}", err);
+                std::process::exit(-1);
+            };
+        }
+        ("withdraw-stake", Some(m)) => {
+            let config_path = Path::new(m.value_of("config-path").unwrap());
+
+            if let Err(err) = stake::withdraw_stake(config_path) {
+                log::error!("Withdraw stake error: {}", err);
+                std::process::exit(-1);
+            };
+        }
+        ("cancel-deposit", Some(m)) => {
+            let privkey_path = Path::new(m.value_of("privkey-path").unwrap());
+            let deployment_results_path = Path::new(m.value_of("deployment-results-path").unwrap());
+            let config_path = Path::new(m.value_of("config-path").unwrap());
+            let tx_hash = m.value_of("tx-hash").unwrap();
+            let index = m
+                .value_of("index")
+                .unwrap()
+                .parse()
+                .expect("output index format error");
+
+            if let Err(err) = cancel_deposit::cancel_deposit(
+                privkey_path,
+                deployment_results_path,
+                config_path,
+                tx_hash,
+                index,
+            ) {
+                log::error!("Cancel deposit error: {}", err);
+                std::process::exit(-1);
+            };
+        }
+        ("list-withdrawal-offers", Some(m)) => {
+            let config_path = Path::new(m.value_of("config-path").unwrap());
+            let godwoken_rpc_url = m.value_of("godwoken-rpc-url").unwrap();
+
+            if let Err(err) = fast_withdrawal::list_withdrawal_offers(godwoken_rpc_url, config_path)
+            {
+                log::error!("List withdrawal offers error: {}", err);
+                std::process::exit(-1);
+            };
+        }
+        ("buy-withdrawal", Some(m)) => {
+            let privkey_path = Path::new(m.value_of("privkey-path").unwrap());
+            let config_path = Path::new(m.value_of("config-path").unwrap());
+            let tx_hash = m.value_of("tx-hash").unwrap();
+            let index = m
+                .value_of("index")
+                .unwrap()
+                .parse()
+                .expect("output index format error");
+            let payment_ckb_address = m.value_of("payment-ckb-address").unwrap();
+
+            if let Err(err) = fast_withdrawal::buy_withdrawal(
+                privkey_path,
+                config_path,
+                tx_hash,
+                index,
+                payment_ckb_address,
+            ) {
+                log::error!("Buy withdrawal error: {}", err);
+                std::process::exit(-1);
+            };
+        }
+        ("withdrawal-status", Some(m)) => {
+            let config_path = Path::new(m.value_of("config-path").unwrap());
+            let godwoken_rpc_url = m.value_of("godwoken-rpc-url").unwrap();
+            let withdrawal_hash = m.value_of("withdrawal-hash").unwrap();
+
+            if let Err(err) =
+                claim_withdrawal::withdrawal_status(godwoken_rpc_url, config_path, withdrawal_hash)
+            {
+                log::error!("Withdrawal status error: {}", err);
+                std::process::exit(-1);
+            };
+        }
+        ("claim-withdrawal", Some(m)) => {
+            let privkey_path = Path::new(m.value_of("privkey-path").unwrap());
+            let config_path = Path::new(m.value_of("config-path").unwrap());
+            let godwoken_rpc_url = m.value_of("godwoken-rpc-url").unwrap();
+            let withdrawal_hash = m.value_of("withdrawal-hash").unwrap();
+
+            if let Err(err) = claim_withdrawal::claim_withdrawal(
+                privkey_path,
+                godwoken_rpc_url,
+                config_path,
+                withdrawal_hash,
+            ) {
+                log::error!("Claim withdrawal error: {}", err);
+                std::process::exit(-1);
+            };
+        }
         _ => {
             app.print_help().expect("print help");
         }
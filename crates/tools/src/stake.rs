@@ -0,0 +1,283 @@
+use crate::utils::transaction::{build_rpc_client, read_config, wait_for_tx};
+use anyhow::{anyhow, bail, Context, Result};
+use ckb_fixed_hash::H256;
+use ckb_sdk::{HttpRpcClient, HumanCapacity};
+use gw_config::{BlockProducerConfig, Config};
+use gw_rpc_client::rpc_client::RPCClient;
+use gw_types::{
+    bytes::Bytes,
+    core::DepType,
+    offchain::{global_state_from_slice, CellInfo, InputCellInfo},
+    packed::{CellDep, CellInput, CellOutput, StakeLockArgs, StakeLockArgsReader},
+    prelude::*,
+};
+use gw_utils::{
+    fee::fill_tx_fee,
+    genesis_info::CKBGenesisInfo,
+    sudt::{build_sudt_cell, parse_sudt},
+    transaction_skeleton::TransactionSkeleton,
+    wallet::Wallet,
+};
+use std::path::Path;
+use std::str::FromStr;
+
+const WAIT_TX_TIMEOUT_SECS: u64 = 180;
+
+struct StakeContext {
+    config: Config,
+    block_producer_config: BlockProducerConfig,
+    rpc_client: RPCClient,
+    wallet: Wallet,
+}
+
+struct StakeCell {
+    cell: CellInfo,
+    stake_block_number: u64,
+}
+
+pub fn list_stake(config_path: &Path) -> Result<()> {
+    let ctx = StakeContext::from_config(config_path)?;
+    let last_finalized_block_number = smol::block_on(ctx.query_last_finalized_block_number())?;
+
+    let stake_cells = smol::block_on(ctx.query_stake_cells())?;
+    println!("owner lock hash: 0x{}", hex::encode(ctx.owner_lock_hash()));
+    println!("last finalized block: {}", last_finalized_block_number);
+    for stake in stake_cells {
+        let capacity: u64 = stake.cell.output.capacity().unpack();
+        let index: u32 = stake.cell.out_point.index().unpack();
+        println!(
+            "stake cell tx hash: 0x{} index: {} capacity: {} stake block: {} finalized: {}",
+            hex::encode(stake.cell.out_point.tx_hash().as_slice()),
+            index,
+            HumanCapacity::from(capacity),
+            stake.stake_block_number,
+            stake.stake_block_number <= last_finalized_block_number
+        );
+    }
+
+    Ok(())
+}
+
+pub fn top_up_stake(config_path: &Path, capacity: &str) -> Result<()> {
+    let ctx = StakeContext::from_config(config_path)?;
+    let top_up_capacity: u64 = HumanCapacity::from_str(capacity)
+        .map_err(|err| anyhow!(err))?
+        .into();
+
+    let stake = smol::block_on(ctx.query_finalized_stake_cell())?;
+    let stake_capacity: u64 = stake.cell.output.capacity().unpack();
+    let new_capacity = stake_capacity
+        .checked_add(top_up_capacity)
+        .ok_or_else(|| anyhow!("stake capacity overflow"))?;
+    let stake_output = stake
+        .cell
+        .output
+        .clone()
+        .as_builder()
+        .capacity(new_capacity.pack())
+        .build();
+    let stake_data = stake.cell.data.clone();
+
+    let mut tx_skeleton = smol::block_on(ctx.unlock_stake_tx_skeleton(stake))?;
+    tx_skeleton.outputs_mut().push((stake_output, stake_data));
+
+    let tx_hash = ctx.sign_and_send(tx_skeleton)?;
+    println!(
+        "Stake capacity is topped up to {} in tx 0x{}",
+        HumanCapacity::from(new_capacity),
+        hex::encode(tx_hash)
+    );
+
+    Ok(())
+}
+
+pub fn withdraw_stake(config_path: &Path) -> Result<()> {
+    let ctx = StakeContext::from_config(config_path)?;
+
+    let stake = smol::block_on(ctx.query_finalized_stake_cell())?;
+    let stake_capacity: u64 = stake.cell.output.capacity().unpack();
+
+    // Stake capacity goes to the change cell of owner lock, bonded sudt is returned to owner
+    let bonded_sudt = parse_sudt(&stake.cell);
+    let mut tx_skeleton = smol::block_on(ctx.unlock_stake_tx_skeleton(stake))?;
+    if let Some((sudt_type, amount)) = bonded_sudt {
+        let owner_lock = ctx.wallet.lock_script().to_owned();
+        let output = CellOutput::new_builder().lock(owner_lock).build();
+        let sudt_cell = build_sudt_cell(output, sudt_type, amount)?;
+        tx_skeleton.outputs_mut().push(sudt_cell);
+    }
+    let tx_hash = ctx.sign_and_send(tx_skeleton)?;
+    println!(
+        "Stake capacity {} is withdrawn in tx 0x{}",
+        HumanCapacity::from(stake_capacity),
+        hex::encode(tx_hash)
+    );
+
+    Ok(())
+}
+
+impl StakeContext {
+    fn from_config(config_path: &Path) -> Result<Self> {
+        let config = read_config(config_path).map_err(|err| anyhow!(err))?;
+        let block_producer_config = config
+            .block_producer
+            .clone()
+            .ok_or_else(|| anyhow!("block producer config not found"))?;
+        let wallet = Wallet::from_config(&block_producer_config.watchtower.wallet_config)?;
+
+        let rpc_client = build_rpc_client(&config)?;
+
+        Ok(StakeContext {
+            config,
+            block_producer_config,
+            rpc_client,
+            wallet,
+        })
+    }
+
+    fn owner_lock_hash(&self) -> [u8; 32] {
+        self.wallet.lock_script().hash()
+    }
+
+    async fn query_rollup_cell(&self) -> Result<CellInfo> {
+        let query = self.rpc_client.query_rollup_cell().await?;
+        query.ok_or_else(|| anyhow!("rollup cell not found"))
+    }
+
+    async fn query_last_finalized_block_number(&self) -> Result<u64> {
+        let rollup_cell = self.query_rollup_cell().await?;
+        let global_state = global_state_from_slice(&rollup_cell.data)?;
+        Ok(global_state.last_finalized_block_number().unpack())
+    }
+
+    async fn query_stake_cells(&self) -> Result<Vec<StakeCell>> {
+        let owner_lock_hash = self.owner_lock_hash();
+        let query = self
+            .rpc_client
+            .query_stake_cells_by_owner_lock_hash(owner_lock_hash);
+        let cells = query.await.with_context(|| {
+            format!(
+                "query stake cells of owner lock 0x{}",
+                hex::encode(owner_lock_hash)
+            )
+        })?;
+
+        let to_stake = cells.into_iter().map(|cell| {
+            let stake_block_number = parse_stake_block_number(&cell)?;
+            Ok(StakeCell {
+                cell,
+                stake_block_number,
+            })
+        });
+        to_stake.collect()
+    }
+
+    async fn query_finalized_stake_cell(&self) -> Result<StakeCell> {
+        let last_finalized_block_number = self.query_last_finalized_block_number().await?;
+        let stake_cells = self.query_stake_cells().await?;
+
+        let mut finalized = stake_cells
+            .into_iter()
+            .filter(|stake| stake.stake_block_number <= last_finalized_block_number);
+        match finalized.next() {
+            Some(stake) => Ok(stake),
+            None => bail!(
+                "no finalized stake cell, last finalized block {}",
+                last_finalized_block_number
+            ),
+        }
+    }
+
+    // Finalized stake cell can be unlocked by owner, which requires rollup cell as cell dep
+    // and an owner lock cell in inputs.
+    async fn unlock_stake_tx_skeleton(&self, stake: StakeCell) -> Result<TransactionSkeleton> {
+        let rpc_client = &self.rpc_client;
+        let rollup_cell = self.query_rollup_cell().await?;
+        let ckb_genesis_info = {
+            let ckb_genesis = rpc_client.get_block_by_number(0).await?;
+            let ckb_genesis =
+                ckb_genesis.ok_or_else(|| anyhow!("can't found CKB genesis block"))?;
+            CKBGenesisInfo::from_block(&ckb_genesis)?
+        };
+
+        let owner_lock = self.wallet.lock_script().to_owned();
+        let owner_cell = {
+            let query = rpc_client
+                .query_owner_cell(owner_lock.clone(), None)
+                .await?;
+            query.ok_or_else(|| anyhow!("owner cell not found for unlock stake"))?
+        };
+
+        let rollup_cell_dep = CellDep::new_builder()
+            .out_point(rollup_cell.out_point)
+            .dep_type(DepType::Code.into())
+            .build();
+
+        let mut tx_skeleton = TransactionSkeleton::default();
+        tx_skeleton.cell_deps_mut().extend(vec![
+            self.block_producer_config
+                .watchtower
+                .stake_cell_lock_dep
+                .clone()
+                .into(),
+            rollup_cell_dep,
+            ckb_genesis_info.sighash_dep(),
+        ]);
+        if stake.cell.output.type_().is_some() {
+            let sudt_type_dep = self
+                .block_producer_config
+                .watchtower
+                .l1_sudt_type_dep
+                .clone();
+            tx_skeleton.cell_deps_mut().push(sudt_type_dep.into());
+        }
+        tx_skeleton
+            .inputs_mut()
+            .push(to_input_cell_info(stake.cell));
+        tx_skeleton
+            .inputs_mut()
+            .push(to_input_cell_info(owner_cell));
+
+        Ok(tx_skeleton)
+    }
+
+    fn sign_and_send(&self, mut tx_skeleton: TransactionSkeleton) -> Result<[u8; 32]> {
+        let owner_lock = self.wallet.lock_script().to_owned();
+        smol::block_on(fill_tx_fee(
+            &mut tx_skeleton,
+            &self.rpc_client.indexer,
+            owner_lock,
+        ))?;
+        let tx = self.wallet.sign_tx_skeleton_blocking(tx_skeleton)?;
+
+        let tx_hash: [u8; 32] = smol::block_on(self.rpc_client.send_transaction(tx))?.into();
+        println!("Send tx 0x{}...", hex::encode(tx_hash));
+
+        let mut ckb_rpc_client = HttpRpcClient::new(self.config.rpc_client.ckb_url.to_owned());
+        wait_for_tx(&mut ckb_rpc_client, &H256(tx_hash), WAIT_TX_TIMEOUT_SECS)
+            .map_err(|err| anyhow!("{}", err))?;
+
+        Ok(tx_hash)
+    }
+}
+
+// Stake lock args: rollup type hash (32 bytes) | StakeLockArgs
+fn parse_stake_block_number(cell: &CellInfo) -> Result<u64> {
+    let args: Bytes = cell.output.lock().args().unpack();
+    if args.len() < 32 {
+        bail!("invalid stake lock args");
+    }
+    StakeLockArgsReader::verify(&args[32..], false)?;
+    let stake_lock_args = StakeLockArgs::new_unchecked(args.slice(32..));
+
+    Ok(stake_lock_args.stake_block_number().unpack())
+}
+
+fn to_input_cell_info(cell_info: CellInfo) -> InputCellInfo {
+    InputCellInfo {
+        input: CellInput::new_builder()
+            .previous_output(cell_info.out_point.clone())
+            .build(),
+        cell: cell_info,
+    }
+}
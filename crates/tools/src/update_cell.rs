@@ -113,8 +113,9 @@ pub fn update_cell<P: AsRef<Path>>(
     let wallet = Wallet::from_config(&WalletConfig {
         privkey_path: pk_path,
         lock: payment_lock.into(),
+        signer: None,
     })?;
-    let tx = wallet.sign_tx_skeleton(tx_skeleton)?;
+    let tx = wallet.sign_tx_skeleton_blocking(tx_skeleton)?;
     let update_message = format!(
         "tx hash: {} cell index: 0 size: {}",
         hex::encode(tx.hash()),
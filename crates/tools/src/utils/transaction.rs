@@ -2,6 +2,7 @@
 //! NOTICE: Some functions should be moved to a more proper module than this.
 
 use anyhow::Result;
+use async_jsonrpc_client::HttpClient;
 use ckb_fixed_hash::{h256, H256};
 use ckb_jsonrpc_types::Status;
 use ckb_sdk::rpc::TransactionView;
@@ -9,6 +10,8 @@ use ckb_sdk::HttpRpcClient;
 use ckb_sdk::NetworkType;
 use gw_config::Config;
 use gw_jsonrpc_types::godwoken::TxReceipt;
+use gw_rpc_client::rpc_client::RPCClient;
+use gw_types::{offchain::RollupContext, packed::Script, prelude::Entity};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
@@ -105,6 +108,28 @@ pub fn wait_for_tx(
     Err(format!("Timeout: {:?}", retry_timeout))
 }
 
+pub fn build_rpc_client(config: &Config) -> Result<RPCClient> {
+    let rollup_context = RollupContext {
+        rollup_config: config.genesis.rollup_config.clone().into(),
+        rollup_script_hash: {
+            let rollup_script_hash: [u8; 32] = config.genesis.rollup_type_hash.clone().into();
+            rollup_script_hash.into()
+        },
+    };
+    let rollup_type_script: Script = config.chain.rollup_type_script.clone().into();
+    let rollup_type_script =
+        ckb_types::packed::Script::new_unchecked(rollup_type_script.as_bytes());
+    let indexer_client = HttpClient::new(config.rpc_client.indexer_url.to_owned())?;
+    let ckb_client = HttpClient::new(config.rpc_client.ckb_url.to_owned())?;
+
+    Ok(RPCClient::new(
+        rollup_type_script,
+        rollup_context,
+        ckb_client,
+        indexer_client,
+    ))
+}
+
 pub fn get_network_type(rpc_client: &mut HttpRpcClient) -> Result<NetworkType, String> {
     let chain_info = rpc_client.get_blockchain_info()?;
     NetworkType::from_raw_str(chain_info.chain.as_str())
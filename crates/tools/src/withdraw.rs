@@ -29,11 +29,16 @@ pub fn withdraw(
     owner_ckb_address: &str,
     config_path: &Path,
     deployment_results_path: &Path,
+    sell_capacity: &str,
+    sell_amount: &str,
+    payment_ckb_address: Option<&str>,
 ) -> Result<(), String> {
     let sudt_script_hash =
         H256::from_str(&sudt_script_hash.trim()[2..]).map_err(|err| err.to_string())?;
     let capacity = parse_capacity(capacity)?;
     let amount: u128 = amount.parse().expect("sUDT amount format error");
+    let sell_capacity = parse_capacity(sell_capacity)?;
+    let sell_amount: u128 = sell_amount.parse().expect("sell amount format error");
 
     let deployment_result_string =
         fs::read_to_string(deployment_results_path).map_err(|err| err.to_string())?;
@@ -42,22 +47,16 @@ pub fn withdraw(
 
     let mut godwoken_rpc_client = GodwokenRpcClient::new(godwoken_rpc_url);
 
-    let sell_capacity = 100u64 * 10u64.pow(8);
-
     let config = read_config(&config_path)?;
     let rollup_type_hash = &config.genesis.rollup_type_hash;
 
-    let payment_lock_hash = H256::from([0u8; 32]);
-
     // owner_ckb_address -> owner_lock_hash
-    let owner_lock_hash: H256 = {
-        let address = Address::from_str(owner_ckb_address)?;
-        let payload = address.payload();
-        let owner_lock_script = ckb_types::packed::Script::from(payload);
-
-        CkbHasher::new()
-            .update(owner_lock_script.as_slice())
-            .finalize()
+    let owner_lock_hash: H256 = address_to_lock_hash(owner_ckb_address)?;
+
+    // withdrawal is on sale if payment address is set, zero hash means not for sale
+    let payment_lock_hash = match payment_ckb_address {
+        Some(address) => address_to_lock_hash(address)?,
+        None => H256::from([0u8; 32]),
     };
 
     let privkey = read_privkey(privkey_path)?;
@@ -79,7 +78,7 @@ pub fn withdraw(
         &sudt_script_hash,
         &account_script_hash,
         &sell_capacity,
-        &0u128,
+        &sell_amount,
         &owner_lock_hash,
         &payment_lock_hash,
     )?;
@@ -99,12 +98,24 @@ pub fn withdraw(
 
     let bytes = JsonBytes::from_bytes(withdrawal_request.as_bytes());
     godwoken_rpc_client.submit_withdrawal_request(bytes)?;
+    println!(
+        "withdrawal hash: 0x{}",
+        hex::encode(withdrawal_request.hash())
+    );
 
     wait_for_balance_change(&mut godwoken_rpc_client, from_address, init_balance, 180u64)?;
 
     Ok(())
 }
 
+fn address_to_lock_hash(ckb_address: &str) -> Result<H256, String> {
+    let address = Address::from_str(ckb_address)?;
+    let payload = address.payload();
+    let lock_script = ckb_types::packed::Script::from(payload);
+
+    Ok(CkbHasher::new().update(lock_script.as_slice()).finalize())
+}
+
 #[allow(clippy::too_many_arguments)]
 fn create_raw_withdrawal_request(
     nonce: &u32,
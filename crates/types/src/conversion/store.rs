@@ -1,3 +1,5 @@
+use crate::core::H256;
+use crate::offchain::{CellInfo, InputCellInfo};
 use crate::{packed, prelude::*};
 use molecule::prelude::Byte;
 use sparse_merkle_tree::merge::MergeValue;
@@ -109,3 +111,50 @@ impl<'r> Unpack<BranchKey> for packed::SMTBranchKeyReader<'r> {
 impl_conversion_for_entity_unpack!(BranchKey, SMTBranchKey);
 
 impl_conversion_for_packed_iterator_pack!(LogItem, LogItemVec);
+impl_conversion_for_packed_iterator_pack!(FeePayment, FeePaymentVec);
+
+impl Pack<packed::CellInfo> for CellInfo {
+    fn pack(&self) -> packed::CellInfo {
+        packed::CellInfo::new_builder()
+            .out_point(self.out_point.clone())
+            .output(self.output.clone())
+            .data(self.data.pack())
+            .build()
+    }
+}
+
+impl<'r> Unpack<CellInfo> for packed::CellInfoReader<'r> {
+    fn unpack(&self) -> CellInfo {
+        CellInfo {
+            out_point: self.out_point().to_entity(),
+            output: self.output().to_entity(),
+            data: self.data().unpack(),
+        }
+    }
+}
+impl_conversion_for_entity_unpack!(CellInfo, CellInfo);
+
+impl Pack<packed::InputCellInfo> for InputCellInfo {
+    fn pack(&self) -> packed::InputCellInfo {
+        packed::InputCellInfo::new_builder()
+            .input(self.input.clone())
+            .cell(self.cell.pack())
+            .build()
+    }
+}
+
+impl<'r> Unpack<InputCellInfo> for packed::InputCellInfoReader<'r> {
+    fn unpack(&self) -> InputCellInfo {
+        InputCellInfo {
+            input: self.input().to_entity(),
+            cell: self.cell().unpack(),
+        }
+    }
+}
+impl_conversion_for_entity_unpack!(InputCellInfo, InputCellInfo);
+impl_conversion_for_vector!(InputCellInfo, InputCellInfoVec, InputCellInfoVecReader);
+
+impl_conversion_for_option_pack!(H256, Byte32Opt);
+impl_conversion_for_packed_optional_pack!(WitnessArgs, WitnessArgsOpt);
+impl_conversion_for_packed_optional_pack!(RecoverAccountsContext, RecoverAccountsContextOpt);
+impl_conversion_for_packed_iterator_pack!(WitnessArgs, WitnessArgsVec);
@@ -1,7 +1,7 @@
 use molecule::prelude::Byte;
 
 use crate::packed;
-use crate::packed::{GlobalState, GlobalStateV0};
+use crate::packed::{GlobalState, GlobalStateV0, TxReceipt, TxReceiptV0};
 use crate::prelude::{Builder, Entity, Pack};
 use core::convert::TryFrom;
 use core::convert::TryInto;
@@ -196,3 +196,14 @@ impl From<GlobalStateV0> for GlobalState {
             .build()
     }
 }
+
+impl From<TxReceiptV0> for TxReceipt {
+    fn from(tx_receipt_v0: TxReceiptV0) -> TxReceipt {
+        TxReceipt::new_builder()
+            .tx_witness_hash(tx_receipt_v0.tx_witness_hash())
+            .post_state(tx_receipt_v0.post_state())
+            .read_data_hashes(tx_receipt_v0.read_data_hashes())
+            .logs(tx_receipt_v0.logs())
+            .build()
+    }
+}
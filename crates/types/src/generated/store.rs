@@ -2033,8 +2033,8 @@ impl<'t: 'r, 'r> ::core::iter::ExactSizeIterator for LogItemVecReaderIterator<'t
     }
 }
 #[derive(Clone)]
-pub struct TxReceipt(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for TxReceipt {
+pub struct FeePayment(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for FeePayment {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -2043,91 +2043,50 @@ impl ::core::fmt::LowerHex for TxReceipt {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl ::core::fmt::Debug for TxReceipt {
+impl ::core::fmt::Debug for FeePayment {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl ::core::fmt::Display for TxReceipt {
+impl ::core::fmt::Display for FeePayment {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "tx_witness_hash", self.tx_witness_hash())?;
-        write!(f, ", {}: {}", "post_state", self.post_state())?;
-        write!(f, ", {}: {}", "read_data_hashes", self.read_data_hashes())?;
-        write!(f, ", {}: {}", "logs", self.logs())?;
-        let extra_count = self.count_extra_fields();
-        if extra_count != 0 {
-            write!(f, ", .. ({} fields)", extra_count)?;
-        }
+        write!(f, "{}: {}", "payer_id", self.payer_id())?;
+        write!(f, ", {}: {}", "sudt_id", self.sudt_id())?;
+        write!(f, ", {}: {}", "amount", self.amount())?;
         write!(f, " }}")
     }
 }
-impl ::core::default::Default for TxReceipt {
+impl ::core::default::Default for FeePayment {
     fn default() -> Self {
         let v: Vec<u8> = vec![
-            96, 0, 0, 0, 20, 0, 0, 0, 52, 0, 0, 0, 88, 0, 0, 0, 92, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         ];
-        TxReceipt::new_unchecked(v.into())
+        FeePayment::new_unchecked(v.into())
     }
 }
-impl TxReceipt {
-    pub const FIELD_COUNT: usize = 4;
-    pub fn total_size(&self) -> usize {
-        molecule::unpack_number(self.as_slice()) as usize
-    }
-    pub fn field_count(&self) -> usize {
-        if self.total_size() == molecule::NUMBER_SIZE {
-            0
-        } else {
-            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
-        }
-    }
-    pub fn count_extra_fields(&self) -> usize {
-        self.field_count() - Self::FIELD_COUNT
-    }
-    pub fn has_extra_fields(&self) -> bool {
-        Self::FIELD_COUNT != self.field_count()
-    }
-    pub fn tx_witness_hash(&self) -> Byte32 {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[4..]) as usize;
-        let end = molecule::unpack_number(&slice[8..]) as usize;
-        Byte32::new_unchecked(self.0.slice(start..end))
-    }
-    pub fn post_state(&self) -> AccountMerkleState {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[8..]) as usize;
-        let end = molecule::unpack_number(&slice[12..]) as usize;
-        AccountMerkleState::new_unchecked(self.0.slice(start..end))
+impl FeePayment {
+    pub const TOTAL_SIZE: usize = 24;
+    pub const FIELD_SIZES: [usize; 3] = [4, 4, 16];
+    pub const FIELD_COUNT: usize = 3;
+    pub fn payer_id(&self) -> Uint32 {
+        Uint32::new_unchecked(self.0.slice(0..4))
     }
-    pub fn read_data_hashes(&self) -> Byte32Vec {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[12..]) as usize;
-        let end = molecule::unpack_number(&slice[16..]) as usize;
-        Byte32Vec::new_unchecked(self.0.slice(start..end))
+    pub fn sudt_id(&self) -> Uint32 {
+        Uint32::new_unchecked(self.0.slice(4..8))
     }
-    pub fn logs(&self) -> LogItemVec {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[16..]) as usize;
-        if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[20..]) as usize;
-            LogItemVec::new_unchecked(self.0.slice(start..end))
-        } else {
-            LogItemVec::new_unchecked(self.0.slice(start..))
-        }
+    pub fn amount(&self) -> Uint128 {
+        Uint128::new_unchecked(self.0.slice(8..24))
     }
-    pub fn as_reader<'r>(&'r self) -> TxReceiptReader<'r> {
-        TxReceiptReader::new_unchecked(self.as_slice())
+    pub fn as_reader<'r>(&'r self) -> FeePaymentReader<'r> {
+        FeePaymentReader::new_unchecked(self.as_slice())
     }
 }
-impl molecule::prelude::Entity for TxReceipt {
-    type Builder = TxReceiptBuilder;
-    const NAME: &'static str = "TxReceipt";
+impl molecule::prelude::Entity for FeePayment {
+    type Builder = FeePaymentBuilder;
+    const NAME: &'static str = "FeePayment";
     fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        TxReceipt(data)
+        FeePayment(data)
     }
     fn as_bytes(&self) -> molecule::bytes::Bytes {
         self.0.clone()
@@ -2136,25 +2095,24 @@ impl molecule::prelude::Entity for TxReceipt {
         &self.0[..]
     }
     fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        TxReceiptReader::from_slice(slice).map(|reader| reader.to_entity())
+        FeePaymentReader::from_slice(slice).map(|reader| reader.to_entity())
     }
     fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        TxReceiptReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+        FeePaymentReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
     }
     fn new_builder() -> Self::Builder {
         ::core::default::Default::default()
     }
     fn as_builder(self) -> Self::Builder {
         Self::new_builder()
-            .tx_witness_hash(self.tx_witness_hash())
-            .post_state(self.post_state())
-            .read_data_hashes(self.read_data_hashes())
-            .logs(self.logs())
+            .payer_id(self.payer_id())
+            .sudt_id(self.sudt_id())
+            .amount(self.amount())
     }
 }
 #[derive(Clone, Copy)]
-pub struct TxReceiptReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for TxReceiptReader<'r> {
+pub struct FeePaymentReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for FeePaymentReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -2163,195 +2121,100 @@ impl<'r> ::core::fmt::LowerHex for TxReceiptReader<'r> {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Debug for TxReceiptReader<'r> {
+impl<'r> ::core::fmt::Debug for FeePaymentReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for TxReceiptReader<'r> {
+impl<'r> ::core::fmt::Display for FeePaymentReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "tx_witness_hash", self.tx_witness_hash())?;
-        write!(f, ", {}: {}", "post_state", self.post_state())?;
-        write!(f, ", {}: {}", "read_data_hashes", self.read_data_hashes())?;
-        write!(f, ", {}: {}", "logs", self.logs())?;
-        let extra_count = self.count_extra_fields();
-        if extra_count != 0 {
-            write!(f, ", .. ({} fields)", extra_count)?;
-        }
+        write!(f, "{}: {}", "payer_id", self.payer_id())?;
+        write!(f, ", {}: {}", "sudt_id", self.sudt_id())?;
+        write!(f, ", {}: {}", "amount", self.amount())?;
         write!(f, " }}")
     }
 }
-impl<'r> TxReceiptReader<'r> {
-    pub const FIELD_COUNT: usize = 4;
-    pub fn total_size(&self) -> usize {
-        molecule::unpack_number(self.as_slice()) as usize
-    }
-    pub fn field_count(&self) -> usize {
-        if self.total_size() == molecule::NUMBER_SIZE {
-            0
-        } else {
-            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
-        }
-    }
-    pub fn count_extra_fields(&self) -> usize {
-        self.field_count() - Self::FIELD_COUNT
-    }
-    pub fn has_extra_fields(&self) -> bool {
-        Self::FIELD_COUNT != self.field_count()
-    }
-    pub fn tx_witness_hash(&self) -> Byte32Reader<'r> {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[4..]) as usize;
-        let end = molecule::unpack_number(&slice[8..]) as usize;
-        Byte32Reader::new_unchecked(&self.as_slice()[start..end])
-    }
-    pub fn post_state(&self) -> AccountMerkleStateReader<'r> {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[8..]) as usize;
-        let end = molecule::unpack_number(&slice[12..]) as usize;
-        AccountMerkleStateReader::new_unchecked(&self.as_slice()[start..end])
+impl<'r> FeePaymentReader<'r> {
+    pub const TOTAL_SIZE: usize = 24;
+    pub const FIELD_SIZES: [usize; 3] = [4, 4, 16];
+    pub const FIELD_COUNT: usize = 3;
+    pub fn payer_id(&self) -> Uint32Reader<'r> {
+        Uint32Reader::new_unchecked(&self.as_slice()[0..4])
     }
-    pub fn read_data_hashes(&self) -> Byte32VecReader<'r> {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[12..]) as usize;
-        let end = molecule::unpack_number(&slice[16..]) as usize;
-        Byte32VecReader::new_unchecked(&self.as_slice()[start..end])
+    pub fn sudt_id(&self) -> Uint32Reader<'r> {
+        Uint32Reader::new_unchecked(&self.as_slice()[4..8])
     }
-    pub fn logs(&self) -> LogItemVecReader<'r> {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[16..]) as usize;
-        if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[20..]) as usize;
-            LogItemVecReader::new_unchecked(&self.as_slice()[start..end])
-        } else {
-            LogItemVecReader::new_unchecked(&self.as_slice()[start..])
-        }
+    pub fn amount(&self) -> Uint128Reader<'r> {
+        Uint128Reader::new_unchecked(&self.as_slice()[8..24])
     }
 }
-impl<'r> molecule::prelude::Reader<'r> for TxReceiptReader<'r> {
-    type Entity = TxReceipt;
-    const NAME: &'static str = "TxReceiptReader";
+impl<'r> molecule::prelude::Reader<'r> for FeePaymentReader<'r> {
+    type Entity = FeePayment;
+    const NAME: &'static str = "FeePaymentReader";
     fn to_entity(&self) -> Self::Entity {
         Self::Entity::new_unchecked(self.as_slice().to_owned().into())
     }
     fn new_unchecked(slice: &'r [u8]) -> Self {
-        TxReceiptReader(slice)
+        FeePaymentReader(slice)
     }
     fn as_slice(&self) -> &'r [u8] {
         self.0
     }
-    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
         use molecule::verification_error as ve;
         let slice_len = slice.len();
-        if slice_len < molecule::NUMBER_SIZE {
-            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
-        }
-        let total_size = molecule::unpack_number(slice) as usize;
-        if slice_len != total_size {
-            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
-        }
-        if slice_len == molecule::NUMBER_SIZE && Self::FIELD_COUNT == 0 {
-            return Ok(());
-        }
-        if slice_len < molecule::NUMBER_SIZE * 2 {
-            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
-        }
-        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
-        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
-            return ve!(Self, OffsetsNotMatch);
-        }
-        if slice_len < offset_first {
-            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
-        }
-        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
-        if field_count < Self::FIELD_COUNT {
-            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
-        } else if !compatible && field_count > Self::FIELD_COUNT {
-            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
-        };
-        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
-            .chunks_exact(molecule::NUMBER_SIZE)
-            .map(|x| molecule::unpack_number(x) as usize)
-            .collect();
-        offsets.push(total_size);
-        if offsets.windows(2).any(|i| i[0] > i[1]) {
-            return ve!(Self, OffsetsNotMatch);
+        if slice_len != Self::TOTAL_SIZE {
+            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
         }
-        Byte32Reader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
-        AccountMerkleStateReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
-        Byte32VecReader::verify(&slice[offsets[2]..offsets[3]], compatible)?;
-        LogItemVecReader::verify(&slice[offsets[3]..offsets[4]], compatible)?;
         Ok(())
     }
 }
 #[derive(Debug, Default)]
-pub struct TxReceiptBuilder {
-    pub(crate) tx_witness_hash: Byte32,
-    pub(crate) post_state: AccountMerkleState,
-    pub(crate) read_data_hashes: Byte32Vec,
-    pub(crate) logs: LogItemVec,
-}
-impl TxReceiptBuilder {
-    pub const FIELD_COUNT: usize = 4;
-    pub fn tx_witness_hash(mut self, v: Byte32) -> Self {
-        self.tx_witness_hash = v;
-        self
-    }
-    pub fn post_state(mut self, v: AccountMerkleState) -> Self {
-        self.post_state = v;
+pub struct FeePaymentBuilder {
+    pub(crate) payer_id: Uint32,
+    pub(crate) sudt_id: Uint32,
+    pub(crate) amount: Uint128,
+}
+impl FeePaymentBuilder {
+    pub const TOTAL_SIZE: usize = 24;
+    pub const FIELD_SIZES: [usize; 3] = [4, 4, 16];
+    pub const FIELD_COUNT: usize = 3;
+    pub fn payer_id(mut self, v: Uint32) -> Self {
+        self.payer_id = v;
         self
     }
-    pub fn read_data_hashes(mut self, v: Byte32Vec) -> Self {
-        self.read_data_hashes = v;
+    pub fn sudt_id(mut self, v: Uint32) -> Self {
+        self.sudt_id = v;
         self
     }
-    pub fn logs(mut self, v: LogItemVec) -> Self {
-        self.logs = v;
+    pub fn amount(mut self, v: Uint128) -> Self {
+        self.amount = v;
         self
     }
 }
-impl molecule::prelude::Builder for TxReceiptBuilder {
-    type Entity = TxReceipt;
-    const NAME: &'static str = "TxReceiptBuilder";
+impl molecule::prelude::Builder for FeePaymentBuilder {
+    type Entity = FeePayment;
+    const NAME: &'static str = "FeePaymentBuilder";
     fn expected_length(&self) -> usize {
-        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
-            + self.tx_witness_hash.as_slice().len()
-            + self.post_state.as_slice().len()
-            + self.read_data_hashes.as_slice().len()
-            + self.logs.as_slice().len()
+        Self::TOTAL_SIZE
     }
     fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
-        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
-        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
-        offsets.push(total_size);
-        total_size += self.tx_witness_hash.as_slice().len();
-        offsets.push(total_size);
-        total_size += self.post_state.as_slice().len();
-        offsets.push(total_size);
-        total_size += self.read_data_hashes.as_slice().len();
-        offsets.push(total_size);
-        total_size += self.logs.as_slice().len();
-        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
-        for offset in offsets.into_iter() {
-            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
-        }
-        writer.write_all(self.tx_witness_hash.as_slice())?;
-        writer.write_all(self.post_state.as_slice())?;
-        writer.write_all(self.read_data_hashes.as_slice())?;
-        writer.write_all(self.logs.as_slice())?;
+        writer.write_all(self.payer_id.as_slice())?;
+        writer.write_all(self.sudt_id.as_slice())?;
+        writer.write_all(self.amount.as_slice())?;
         Ok(())
     }
     fn build(&self) -> Self::Entity {
         let mut inner = Vec::with_capacity(self.expected_length());
         self.write(&mut inner)
             .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
-        TxReceipt::new_unchecked(inner.into())
+        FeePayment::new_unchecked(inner.into())
     }
 }
 #[derive(Clone)]
-pub struct WithdrawalReceipt(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for WithdrawalReceipt {
+pub struct FeePaymentVec(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for FeePaymentVec {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -2360,68 +2223,65 @@ impl ::core::fmt::LowerHex for WithdrawalReceipt {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl ::core::fmt::Debug for WithdrawalReceipt {
+impl ::core::fmt::Debug for FeePaymentVec {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl ::core::fmt::Display for WithdrawalReceipt {
+impl ::core::fmt::Display for FeePaymentVec {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "post_state", self.post_state())?;
-        let extra_count = self.count_extra_fields();
-        if extra_count != 0 {
-            write!(f, ", .. ({} fields)", extra_count)?;
+        write!(f, "{} [", Self::NAME)?;
+        for i in 0..self.len() {
+            if i == 0 {
+                write!(f, "{}", self.get_unchecked(i))?;
+            } else {
+                write!(f, ", {}", self.get_unchecked(i))?;
+            }
         }
-        write!(f, " }}")
+        write!(f, "]")
     }
 }
-impl ::core::default::Default for WithdrawalReceipt {
+impl ::core::default::Default for FeePaymentVec {
     fn default() -> Self {
-        let v: Vec<u8> = vec![
-            44, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        ];
-        WithdrawalReceipt::new_unchecked(v.into())
+        let v: Vec<u8> = vec![0, 0, 0, 0];
+        FeePaymentVec::new_unchecked(v.into())
     }
 }
-impl WithdrawalReceipt {
-    pub const FIELD_COUNT: usize = 1;
+impl FeePaymentVec {
+    pub const ITEM_SIZE: usize = 24;
     pub fn total_size(&self) -> usize {
+        molecule::NUMBER_SIZE + Self::ITEM_SIZE * self.item_count()
+    }
+    pub fn item_count(&self) -> usize {
         molecule::unpack_number(self.as_slice()) as usize
     }
-    pub fn field_count(&self) -> usize {
-        if self.total_size() == molecule::NUMBER_SIZE {
-            0
-        } else {
-            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
-        }
-    }
-    pub fn count_extra_fields(&self) -> usize {
-        self.field_count() - Self::FIELD_COUNT
+    pub fn len(&self) -> usize {
+        self.item_count()
     }
-    pub fn has_extra_fields(&self) -> bool {
-        Self::FIELD_COUNT != self.field_count()
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
-    pub fn post_state(&self) -> AccountMerkleState {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[4..]) as usize;
-        if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[8..]) as usize;
-            AccountMerkleState::new_unchecked(self.0.slice(start..end))
+    pub fn get(&self, idx: usize) -> Option<FeePayment> {
+        if idx >= self.len() {
+            None
         } else {
-            AccountMerkleState::new_unchecked(self.0.slice(start..))
+            Some(self.get_unchecked(idx))
         }
     }
-    pub fn as_reader<'r>(&'r self) -> WithdrawalReceiptReader<'r> {
-        WithdrawalReceiptReader::new_unchecked(self.as_slice())
+    pub fn get_unchecked(&self, idx: usize) -> FeePayment {
+        let start = molecule::NUMBER_SIZE + Self::ITEM_SIZE * idx;
+        let end = start + Self::ITEM_SIZE;
+        FeePayment::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn as_reader<'r>(&'r self) -> FeePaymentVecReader<'r> {
+        FeePaymentVecReader::new_unchecked(self.as_slice())
     }
 }
-impl molecule::prelude::Entity for WithdrawalReceipt {
-    type Builder = WithdrawalReceiptBuilder;
-    const NAME: &'static str = "WithdrawalReceipt";
+impl molecule::prelude::Entity for FeePaymentVec {
+    type Builder = FeePaymentVecBuilder;
+    const NAME: &'static str = "FeePaymentVec";
     fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        WithdrawalReceipt(data)
+        FeePaymentVec(data)
     }
     fn as_bytes(&self) -> molecule::bytes::Bytes {
         self.0.clone()
@@ -2430,21 +2290,21 @@ impl molecule::prelude::Entity for WithdrawalReceipt {
         &self.0[..]
     }
     fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        WithdrawalReceiptReader::from_slice(slice).map(|reader| reader.to_entity())
+        FeePaymentVecReader::from_slice(slice).map(|reader| reader.to_entity())
     }
     fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        WithdrawalReceiptReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+        FeePaymentVecReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
     }
     fn new_builder() -> Self::Builder {
         ::core::default::Default::default()
     }
     fn as_builder(self) -> Self::Builder {
-        Self::new_builder().post_state(self.post_state())
+        Self::new_builder().extend(self.into_iter())
     }
 }
 #[derive(Clone, Copy)]
-pub struct WithdrawalReceiptReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for WithdrawalReceiptReader<'r> {
+pub struct FeePaymentVecReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for FeePaymentVecReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -2453,143 +2313,174 @@ impl<'r> ::core::fmt::LowerHex for WithdrawalReceiptReader<'r> {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Debug for WithdrawalReceiptReader<'r> {
+impl<'r> ::core::fmt::Debug for FeePaymentVecReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for WithdrawalReceiptReader<'r> {
+impl<'r> ::core::fmt::Display for FeePaymentVecReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "post_state", self.post_state())?;
-        let extra_count = self.count_extra_fields();
-        if extra_count != 0 {
-            write!(f, ", .. ({} fields)", extra_count)?;
+        write!(f, "{} [", Self::NAME)?;
+        for i in 0..self.len() {
+            if i == 0 {
+                write!(f, "{}", self.get_unchecked(i))?;
+            } else {
+                write!(f, ", {}", self.get_unchecked(i))?;
+            }
         }
-        write!(f, " }}")
+        write!(f, "]")
     }
 }
-impl<'r> WithdrawalReceiptReader<'r> {
-    pub const FIELD_COUNT: usize = 1;
+impl<'r> FeePaymentVecReader<'r> {
+    pub const ITEM_SIZE: usize = 24;
     pub fn total_size(&self) -> usize {
-        molecule::unpack_number(self.as_slice()) as usize
+        molecule::NUMBER_SIZE + Self::ITEM_SIZE * self.item_count()
     }
-    pub fn field_count(&self) -> usize {
-        if self.total_size() == molecule::NUMBER_SIZE {
-            0
-        } else {
-            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
-        }
+    pub fn item_count(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
     }
-    pub fn count_extra_fields(&self) -> usize {
-        self.field_count() - Self::FIELD_COUNT
+    pub fn len(&self) -> usize {
+        self.item_count()
     }
-    pub fn has_extra_fields(&self) -> bool {
-        Self::FIELD_COUNT != self.field_count()
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
-    pub fn post_state(&self) -> AccountMerkleStateReader<'r> {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[4..]) as usize;
-        if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[8..]) as usize;
-            AccountMerkleStateReader::new_unchecked(&self.as_slice()[start..end])
+    pub fn get(&self, idx: usize) -> Option<FeePaymentReader<'r>> {
+        if idx >= self.len() {
+            None
         } else {
-            AccountMerkleStateReader::new_unchecked(&self.as_slice()[start..])
+            Some(self.get_unchecked(idx))
         }
     }
+    pub fn get_unchecked(&self, idx: usize) -> FeePaymentReader<'r> {
+        let start = molecule::NUMBER_SIZE + Self::ITEM_SIZE * idx;
+        let end = start + Self::ITEM_SIZE;
+        FeePaymentReader::new_unchecked(&self.as_slice()[start..end])
+    }
 }
-impl<'r> molecule::prelude::Reader<'r> for WithdrawalReceiptReader<'r> {
-    type Entity = WithdrawalReceipt;
-    const NAME: &'static str = "WithdrawalReceiptReader";
+impl<'r> molecule::prelude::Reader<'r> for FeePaymentVecReader<'r> {
+    type Entity = FeePaymentVec;
+    const NAME: &'static str = "FeePaymentVecReader";
     fn to_entity(&self) -> Self::Entity {
         Self::Entity::new_unchecked(self.as_slice().to_owned().into())
     }
     fn new_unchecked(slice: &'r [u8]) -> Self {
-        WithdrawalReceiptReader(slice)
+        FeePaymentVecReader(slice)
     }
     fn as_slice(&self) -> &'r [u8] {
         self.0
     }
-    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
         use molecule::verification_error as ve;
         let slice_len = slice.len();
         if slice_len < molecule::NUMBER_SIZE {
             return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
         }
-        let total_size = molecule::unpack_number(slice) as usize;
-        if slice_len != total_size {
-            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
-        }
-        if slice_len == molecule::NUMBER_SIZE && Self::FIELD_COUNT == 0 {
+        let item_count = molecule::unpack_number(slice) as usize;
+        if item_count == 0 {
+            if slice_len != molecule::NUMBER_SIZE {
+                return ve!(Self, TotalSizeNotMatch, molecule::NUMBER_SIZE, slice_len);
+            }
             return Ok(());
         }
-        if slice_len < molecule::NUMBER_SIZE * 2 {
-            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
-        }
-        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
-        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
-            return ve!(Self, OffsetsNotMatch);
-        }
-        if slice_len < offset_first {
-            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
-        }
-        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
-        if field_count < Self::FIELD_COUNT {
-            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
-        } else if !compatible && field_count > Self::FIELD_COUNT {
-            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
-        };
-        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
-            .chunks_exact(molecule::NUMBER_SIZE)
-            .map(|x| molecule::unpack_number(x) as usize)
-            .collect();
-        offsets.push(total_size);
-        if offsets.windows(2).any(|i| i[0] > i[1]) {
-            return ve!(Self, OffsetsNotMatch);
+        let total_size = molecule::NUMBER_SIZE + Self::ITEM_SIZE * item_count;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
         }
-        AccountMerkleStateReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
         Ok(())
     }
 }
 #[derive(Debug, Default)]
-pub struct WithdrawalReceiptBuilder {
-    pub(crate) post_state: AccountMerkleState,
-}
-impl WithdrawalReceiptBuilder {
-    pub const FIELD_COUNT: usize = 1;
-    pub fn post_state(mut self, v: AccountMerkleState) -> Self {
-        self.post_state = v;
+pub struct FeePaymentVecBuilder(pub(crate) Vec<FeePayment>);
+impl FeePaymentVecBuilder {
+    pub const ITEM_SIZE: usize = 24;
+    pub fn set(mut self, v: Vec<FeePayment>) -> Self {
+        self.0 = v;
+        self
+    }
+    pub fn push(mut self, v: FeePayment) -> Self {
+        self.0.push(v);
+        self
+    }
+    pub fn extend<T: ::core::iter::IntoIterator<Item = FeePayment>>(mut self, iter: T) -> Self {
+        for elem in iter {
+            self.0.push(elem);
+        }
         self
     }
 }
-impl molecule::prelude::Builder for WithdrawalReceiptBuilder {
-    type Entity = WithdrawalReceipt;
-    const NAME: &'static str = "WithdrawalReceiptBuilder";
+impl molecule::prelude::Builder for FeePaymentVecBuilder {
+    type Entity = FeePaymentVec;
+    const NAME: &'static str = "FeePaymentVecBuilder";
     fn expected_length(&self) -> usize {
-        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1) + self.post_state.as_slice().len()
+        molecule::NUMBER_SIZE + Self::ITEM_SIZE * self.0.len()
     }
     fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
-        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
-        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
-        offsets.push(total_size);
-        total_size += self.post_state.as_slice().len();
-        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
-        for offset in offsets.into_iter() {
-            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        writer.write_all(&molecule::pack_number(self.0.len() as molecule::Number))?;
+        for inner in &self.0[..] {
+            writer.write_all(inner.as_slice())?;
         }
-        writer.write_all(self.post_state.as_slice())?;
         Ok(())
     }
     fn build(&self) -> Self::Entity {
         let mut inner = Vec::with_capacity(self.expected_length());
         self.write(&mut inner)
             .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
-        WithdrawalReceipt::new_unchecked(inner.into())
+        FeePaymentVec::new_unchecked(inner.into())
+    }
+}
+pub struct FeePaymentVecIterator(FeePaymentVec, usize, usize);
+impl ::core::iter::Iterator for FeePaymentVecIterator {
+    type Item = FeePayment;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.1 >= self.2 {
+            None
+        } else {
+            let ret = self.0.get_unchecked(self.1);
+            self.1 += 1;
+            Some(ret)
+        }
+    }
+}
+impl ::core::iter::ExactSizeIterator for FeePaymentVecIterator {
+    fn len(&self) -> usize {
+        self.2 - self.1
+    }
+}
+impl ::core::iter::IntoIterator for FeePaymentVec {
+    type Item = FeePayment;
+    type IntoIter = FeePaymentVecIterator;
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        FeePaymentVecIterator(self, 0, len)
+    }
+}
+impl<'r> FeePaymentVecReader<'r> {
+    pub fn iter<'t>(&'t self) -> FeePaymentVecReaderIterator<'t, 'r> {
+        FeePaymentVecReaderIterator(&self, 0, self.len())
+    }
+}
+pub struct FeePaymentVecReaderIterator<'t, 'r>(&'t FeePaymentVecReader<'r>, usize, usize);
+impl<'t: 'r, 'r> ::core::iter::Iterator for FeePaymentVecReaderIterator<'t, 'r> {
+    type Item = FeePaymentReader<'t>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.1 >= self.2 {
+            None
+        } else {
+            let ret = self.0.get_unchecked(self.1);
+            self.1 += 1;
+            Some(ret)
+        }
+    }
+}
+impl<'t: 'r, 'r> ::core::iter::ExactSizeIterator for FeePaymentVecReaderIterator<'t, 'r> {
+    fn len(&self) -> usize {
+        self.2 - self.1
     }
 }
 #[derive(Clone)]
-pub struct SMTBranchKey(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for SMTBranchKey {
+pub struct TxReceiptV0(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for TxReceiptV0 {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -2598,242 +2489,91 @@ impl ::core::fmt::LowerHex for SMTBranchKey {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl ::core::fmt::Debug for SMTBranchKey {
+impl ::core::fmt::Debug for TxReceiptV0 {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl ::core::fmt::Display for SMTBranchKey {
+impl ::core::fmt::Display for TxReceiptV0 {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "height", self.height())?;
-        write!(f, ", {}: {}", "node_key", self.node_key())?;
+        write!(f, "{}: {}", "tx_witness_hash", self.tx_witness_hash())?;
+        write!(f, ", {}: {}", "post_state", self.post_state())?;
+        write!(f, ", {}: {}", "read_data_hashes", self.read_data_hashes())?;
+        write!(f, ", {}: {}", "logs", self.logs())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
         write!(f, " }}")
     }
 }
-impl ::core::default::Default for SMTBranchKey {
+impl ::core::default::Default for TxReceiptV0 {
     fn default() -> Self {
         let v: Vec<u8> = vec![
+            96, 0, 0, 0, 20, 0, 0, 0, 52, 0, 0, 0, 88, 0, 0, 0, 92, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0,
         ];
-        SMTBranchKey::new_unchecked(v.into())
+        TxReceiptV0::new_unchecked(v.into())
     }
 }
-impl SMTBranchKey {
-    pub const TOTAL_SIZE: usize = 33;
-    pub const FIELD_SIZES: [usize; 2] = [1, 32];
-    pub const FIELD_COUNT: usize = 2;
-    pub fn height(&self) -> Byte {
-        Byte::new_unchecked(self.0.slice(0..1))
-    }
-    pub fn node_key(&self) -> Byte32 {
-        Byte32::new_unchecked(self.0.slice(1..33))
+impl TxReceiptV0 {
+    pub const FIELD_COUNT: usize = 4;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
     }
-    pub fn as_reader<'r>(&'r self) -> SMTBranchKeyReader<'r> {
-        SMTBranchKeyReader::new_unchecked(self.as_slice())
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
     }
-}
-impl molecule::prelude::Entity for SMTBranchKey {
-    type Builder = SMTBranchKeyBuilder;
-    const NAME: &'static str = "SMTBranchKey";
-    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        SMTBranchKey(data)
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
     }
-    fn as_bytes(&self) -> molecule::bytes::Bytes {
-        self.0.clone()
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
     }
-    fn as_slice(&self) -> &[u8] {
-        &self.0[..]
+    pub fn tx_witness_hash(&self) -> Byte32 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        Byte32::new_unchecked(self.0.slice(start..end))
     }
-    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        SMTBranchKeyReader::from_slice(slice).map(|reader| reader.to_entity())
-    }
-    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        SMTBranchKeyReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
-    }
-    fn new_builder() -> Self::Builder {
-        ::core::default::Default::default()
-    }
-    fn as_builder(self) -> Self::Builder {
-        Self::new_builder()
-            .height(self.height())
-            .node_key(self.node_key())
-    }
-}
-#[derive(Clone, Copy)]
-pub struct SMTBranchKeyReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for SMTBranchKeyReader<'r> {
-    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        use molecule::hex_string;
-        if f.alternate() {
-            write!(f, "0x")?;
-        }
-        write!(f, "{}", hex_string(self.as_slice()))
-    }
-}
-impl<'r> ::core::fmt::Debug for SMTBranchKeyReader<'r> {
-    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{}({:#x})", Self::NAME, self)
-    }
-}
-impl<'r> ::core::fmt::Display for SMTBranchKeyReader<'r> {
-    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "height", self.height())?;
-        write!(f, ", {}: {}", "node_key", self.node_key())?;
-        write!(f, " }}")
-    }
-}
-impl<'r> SMTBranchKeyReader<'r> {
-    pub const TOTAL_SIZE: usize = 33;
-    pub const FIELD_SIZES: [usize; 2] = [1, 32];
-    pub const FIELD_COUNT: usize = 2;
-    pub fn height(&self) -> ByteReader<'r> {
-        ByteReader::new_unchecked(&self.as_slice()[0..1])
-    }
-    pub fn node_key(&self) -> Byte32Reader<'r> {
-        Byte32Reader::new_unchecked(&self.as_slice()[1..33])
-    }
-}
-impl<'r> molecule::prelude::Reader<'r> for SMTBranchKeyReader<'r> {
-    type Entity = SMTBranchKey;
-    const NAME: &'static str = "SMTBranchKeyReader";
-    fn to_entity(&self) -> Self::Entity {
-        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
-    }
-    fn new_unchecked(slice: &'r [u8]) -> Self {
-        SMTBranchKeyReader(slice)
-    }
-    fn as_slice(&self) -> &'r [u8] {
-        self.0
-    }
-    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
-        use molecule::verification_error as ve;
-        let slice_len = slice.len();
-        if slice_len != Self::TOTAL_SIZE {
-            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
-        }
-        Ok(())
-    }
-}
-#[derive(Debug, Default)]
-pub struct SMTBranchKeyBuilder {
-    pub(crate) height: Byte,
-    pub(crate) node_key: Byte32,
-}
-impl SMTBranchKeyBuilder {
-    pub const TOTAL_SIZE: usize = 33;
-    pub const FIELD_SIZES: [usize; 2] = [1, 32];
-    pub const FIELD_COUNT: usize = 2;
-    pub fn height(mut self, v: Byte) -> Self {
-        self.height = v;
-        self
-    }
-    pub fn node_key(mut self, v: Byte32) -> Self {
-        self.node_key = v;
-        self
-    }
-}
-impl molecule::prelude::Builder for SMTBranchKeyBuilder {
-    type Entity = SMTBranchKey;
-    const NAME: &'static str = "SMTBranchKeyBuilder";
-    fn expected_length(&self) -> usize {
-        Self::TOTAL_SIZE
-    }
-    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
-        writer.write_all(self.height.as_slice())?;
-        writer.write_all(self.node_key.as_slice())?;
-        Ok(())
-    }
-    fn build(&self) -> Self::Entity {
-        let mut inner = Vec::with_capacity(self.expected_length());
-        self.write(&mut inner)
-            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
-        SMTBranchKey::new_unchecked(inner.into())
-    }
-}
-#[derive(Clone)]
-pub struct SMTBranchNode(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for SMTBranchNode {
-    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        use molecule::hex_string;
-        if f.alternate() {
-            write!(f, "0x")?;
-        }
-        write!(f, "{}", hex_string(self.as_slice()))
-    }
-}
-impl ::core::fmt::Debug for SMTBranchNode {
-    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{}({:#x})", Self::NAME, self)
-    }
-}
-impl ::core::fmt::Display for SMTBranchNode {
-    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "left", self.left())?;
-        write!(f, ", {}: {}", "right", self.right())?;
-        let extra_count = self.count_extra_fields();
-        if extra_count != 0 {
-            write!(f, ", .. ({} fields)", extra_count)?;
-        }
-        write!(f, " }}")
-    }
-}
-impl ::core::default::Default for SMTBranchNode {
-    fn default() -> Self {
-        let v: Vec<u8> = vec![
-            84, 0, 0, 0, 12, 0, 0, 0, 48, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        ];
-        SMTBranchNode::new_unchecked(v.into())
-    }
-}
-impl SMTBranchNode {
-    pub const FIELD_COUNT: usize = 2;
-    pub fn total_size(&self) -> usize {
-        molecule::unpack_number(self.as_slice()) as usize
-    }
-    pub fn field_count(&self) -> usize {
-        if self.total_size() == molecule::NUMBER_SIZE {
-            0
-        } else {
-            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
-        }
-    }
-    pub fn count_extra_fields(&self) -> usize {
-        self.field_count() - Self::FIELD_COUNT
-    }
-    pub fn has_extra_fields(&self) -> bool {
-        Self::FIELD_COUNT != self.field_count()
+    pub fn post_state(&self) -> AccountMerkleState {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
+        AccountMerkleState::new_unchecked(self.0.slice(start..end))
     }
-    pub fn left(&self) -> SMTMergeValue {
+    pub fn read_data_hashes(&self) -> Byte32Vec {
         let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[4..]) as usize;
-        let end = molecule::unpack_number(&slice[8..]) as usize;
-        SMTMergeValue::new_unchecked(self.0.slice(start..end))
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        let end = molecule::unpack_number(&slice[16..]) as usize;
+        Byte32Vec::new_unchecked(self.0.slice(start..end))
     }
-    pub fn right(&self) -> SMTMergeValue {
+    pub fn logs(&self) -> LogItemVec {
         let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let start = molecule::unpack_number(&slice[16..]) as usize;
         if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[12..]) as usize;
-            SMTMergeValue::new_unchecked(self.0.slice(start..end))
+            let end = molecule::unpack_number(&slice[20..]) as usize;
+            LogItemVec::new_unchecked(self.0.slice(start..end))
         } else {
-            SMTMergeValue::new_unchecked(self.0.slice(start..))
+            LogItemVec::new_unchecked(self.0.slice(start..))
         }
     }
-    pub fn as_reader<'r>(&'r self) -> SMTBranchNodeReader<'r> {
-        SMTBranchNodeReader::new_unchecked(self.as_slice())
+    pub fn as_reader<'r>(&'r self) -> TxReceiptV0Reader<'r> {
+        TxReceiptV0Reader::new_unchecked(self.as_slice())
     }
 }
-impl molecule::prelude::Entity for SMTBranchNode {
-    type Builder = SMTBranchNodeBuilder;
-    const NAME: &'static str = "SMTBranchNode";
+impl molecule::prelude::Entity for TxReceiptV0 {
+    type Builder = TxReceiptV0Builder;
+    const NAME: &'static str = "TxReceiptV0";
     fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        SMTBranchNode(data)
+        TxReceiptV0(data)
     }
     fn as_bytes(&self) -> molecule::bytes::Bytes {
         self.0.clone()
@@ -2842,21 +2582,25 @@ impl molecule::prelude::Entity for SMTBranchNode {
         &self.0[..]
     }
     fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        SMTBranchNodeReader::from_slice(slice).map(|reader| reader.to_entity())
+        TxReceiptV0Reader::from_slice(slice).map(|reader| reader.to_entity())
     }
     fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        SMTBranchNodeReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+        TxReceiptV0Reader::from_compatible_slice(slice).map(|reader| reader.to_entity())
     }
     fn new_builder() -> Self::Builder {
         ::core::default::Default::default()
     }
     fn as_builder(self) -> Self::Builder {
-        Self::new_builder().left(self.left()).right(self.right())
+        Self::new_builder()
+            .tx_witness_hash(self.tx_witness_hash())
+            .post_state(self.post_state())
+            .read_data_hashes(self.read_data_hashes())
+            .logs(self.logs())
     }
 }
 #[derive(Clone, Copy)]
-pub struct SMTBranchNodeReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for SMTBranchNodeReader<'r> {
+pub struct TxReceiptV0Reader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for TxReceiptV0Reader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -2865,16 +2609,18 @@ impl<'r> ::core::fmt::LowerHex for SMTBranchNodeReader<'r> {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Debug for SMTBranchNodeReader<'r> {
+impl<'r> ::core::fmt::Debug for TxReceiptV0Reader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for SMTBranchNodeReader<'r> {
+impl<'r> ::core::fmt::Display for TxReceiptV0Reader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "left", self.left())?;
-        write!(f, ", {}: {}", "right", self.right())?;
+        write!(f, "{}: {}", "tx_witness_hash", self.tx_witness_hash())?;
+        write!(f, ", {}: {}", "post_state", self.post_state())?;
+        write!(f, ", {}: {}", "read_data_hashes", self.read_data_hashes())?;
+        write!(f, ", {}: {}", "logs", self.logs())?;
         let extra_count = self.count_extra_fields();
         if extra_count != 0 {
             write!(f, ", .. ({} fields)", extra_count)?;
@@ -2882,8 +2628,8 @@ impl<'r> ::core::fmt::Display for SMTBranchNodeReader<'r> {
         write!(f, " }}")
     }
 }
-impl<'r> SMTBranchNodeReader<'r> {
-    pub const FIELD_COUNT: usize = 2;
+impl<'r> TxReceiptV0Reader<'r> {
+    pub const FIELD_COUNT: usize = 4;
     pub fn total_size(&self) -> usize {
         molecule::unpack_number(self.as_slice()) as usize
     }
@@ -2900,31 +2646,43 @@ impl<'r> SMTBranchNodeReader<'r> {
     pub fn has_extra_fields(&self) -> bool {
         Self::FIELD_COUNT != self.field_count()
     }
-    pub fn left(&self) -> SMTMergeValueReader<'r> {
+    pub fn tx_witness_hash(&self) -> Byte32Reader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[4..]) as usize;
         let end = molecule::unpack_number(&slice[8..]) as usize;
-        SMTMergeValueReader::new_unchecked(&self.as_slice()[start..end])
+        Byte32Reader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn right(&self) -> SMTMergeValueReader<'r> {
+    pub fn post_state(&self) -> AccountMerkleStateReader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
+        AccountMerkleStateReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn read_data_hashes(&self) -> Byte32VecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        let end = molecule::unpack_number(&slice[16..]) as usize;
+        Byte32VecReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn logs(&self) -> LogItemVecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[16..]) as usize;
         if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[12..]) as usize;
-            SMTMergeValueReader::new_unchecked(&self.as_slice()[start..end])
+            let end = molecule::unpack_number(&slice[20..]) as usize;
+            LogItemVecReader::new_unchecked(&self.as_slice()[start..end])
         } else {
-            SMTMergeValueReader::new_unchecked(&self.as_slice()[start..])
+            LogItemVecReader::new_unchecked(&self.as_slice()[start..])
         }
     }
 }
-impl<'r> molecule::prelude::Reader<'r> for SMTBranchNodeReader<'r> {
-    type Entity = SMTBranchNode;
-    const NAME: &'static str = "SMTBranchNodeReader";
+impl<'r> molecule::prelude::Reader<'r> for TxReceiptV0Reader<'r> {
+    type Entity = TxReceiptV0;
+    const NAME: &'static str = "TxReceiptV0Reader";
     fn to_entity(&self) -> Self::Entity {
         Self::Entity::new_unchecked(self.as_slice().to_owned().into())
     }
     fn new_unchecked(slice: &'r [u8]) -> Self {
-        SMTBranchNodeReader(slice)
+        TxReceiptV0Reader(slice)
     }
     fn as_slice(&self) -> &'r [u8] {
         self.0
@@ -2966,60 +2724,80 @@ impl<'r> molecule::prelude::Reader<'r> for SMTBranchNodeReader<'r> {
         if offsets.windows(2).any(|i| i[0] > i[1]) {
             return ve!(Self, OffsetsNotMatch);
         }
-        SMTMergeValueReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
-        SMTMergeValueReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        Byte32Reader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        AccountMerkleStateReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        Byte32VecReader::verify(&slice[offsets[2]..offsets[3]], compatible)?;
+        LogItemVecReader::verify(&slice[offsets[3]..offsets[4]], compatible)?;
         Ok(())
     }
 }
 #[derive(Debug, Default)]
-pub struct SMTBranchNodeBuilder {
-    pub(crate) left: SMTMergeValue,
-    pub(crate) right: SMTMergeValue,
+pub struct TxReceiptV0Builder {
+    pub(crate) tx_witness_hash: Byte32,
+    pub(crate) post_state: AccountMerkleState,
+    pub(crate) read_data_hashes: Byte32Vec,
+    pub(crate) logs: LogItemVec,
 }
-impl SMTBranchNodeBuilder {
-    pub const FIELD_COUNT: usize = 2;
-    pub fn left(mut self, v: SMTMergeValue) -> Self {
-        self.left = v;
-        self
+impl TxReceiptV0Builder {
+    pub const FIELD_COUNT: usize = 4;
+    pub fn tx_witness_hash(mut self, v: Byte32) -> Self {
+        self.tx_witness_hash = v;
+        self
     }
-    pub fn right(mut self, v: SMTMergeValue) -> Self {
-        self.right = v;
+    pub fn post_state(mut self, v: AccountMerkleState) -> Self {
+        self.post_state = v;
+        self
+    }
+    pub fn read_data_hashes(mut self, v: Byte32Vec) -> Self {
+        self.read_data_hashes = v;
+        self
+    }
+    pub fn logs(mut self, v: LogItemVec) -> Self {
+        self.logs = v;
         self
     }
 }
-impl molecule::prelude::Builder for SMTBranchNodeBuilder {
-    type Entity = SMTBranchNode;
-    const NAME: &'static str = "SMTBranchNodeBuilder";
+impl molecule::prelude::Builder for TxReceiptV0Builder {
+    type Entity = TxReceiptV0;
+    const NAME: &'static str = "TxReceiptV0Builder";
     fn expected_length(&self) -> usize {
         molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
-            + self.left.as_slice().len()
-            + self.right.as_slice().len()
+            + self.tx_witness_hash.as_slice().len()
+            + self.post_state.as_slice().len()
+            + self.read_data_hashes.as_slice().len()
+            + self.logs.as_slice().len()
     }
     fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
         let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
         let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
         offsets.push(total_size);
-        total_size += self.left.as_slice().len();
+        total_size += self.tx_witness_hash.as_slice().len();
         offsets.push(total_size);
-        total_size += self.right.as_slice().len();
+        total_size += self.post_state.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.read_data_hashes.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.logs.as_slice().len();
         writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
         for offset in offsets.into_iter() {
             writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
         }
-        writer.write_all(self.left.as_slice())?;
-        writer.write_all(self.right.as_slice())?;
+        writer.write_all(self.tx_witness_hash.as_slice())?;
+        writer.write_all(self.post_state.as_slice())?;
+        writer.write_all(self.read_data_hashes.as_slice())?;
+        writer.write_all(self.logs.as_slice())?;
         Ok(())
     }
     fn build(&self) -> Self::Entity {
         let mut inner = Vec::with_capacity(self.expected_length());
         self.write(&mut inner)
             .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
-        SMTBranchNode::new_unchecked(inner.into())
+        TxReceiptV0::new_unchecked(inner.into())
     }
 }
 #[derive(Clone)]
-pub struct SMTMergeValue(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for SMTMergeValue {
+pub struct TxReceipt(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for TxReceipt {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -3028,49 +2806,98 @@ impl ::core::fmt::LowerHex for SMTMergeValue {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl ::core::fmt::Debug for SMTMergeValue {
+impl ::core::fmt::Debug for TxReceipt {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl ::core::fmt::Display for SMTMergeValue {
+impl ::core::fmt::Display for TxReceipt {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{}(", Self::NAME)?;
-        self.to_enum().display_inner(f)?;
-        write!(f, ")")
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "tx_witness_hash", self.tx_witness_hash())?;
+        write!(f, ", {}: {}", "post_state", self.post_state())?;
+        write!(f, ", {}: {}", "read_data_hashes", self.read_data_hashes())?;
+        write!(f, ", {}: {}", "logs", self.logs())?;
+        write!(f, ", {}: {}", "fee_payments", self.fee_payments())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
     }
 }
-impl ::core::default::Default for SMTMergeValue {
+impl ::core::default::Default for TxReceipt {
     fn default() -> Self {
         let v: Vec<u8> = vec![
+            104, 0, 0, 0, 24, 0, 0, 0, 56, 0, 0, 0, 92, 0, 0, 0, 96, 0, 0, 0, 100, 0, 0, 0, 0, 0,
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0,
         ];
-        SMTMergeValue::new_unchecked(v.into())
+        TxReceipt::new_unchecked(v.into())
     }
 }
-impl SMTMergeValue {
-    pub const ITEMS_COUNT: usize = 2;
-    pub fn item_id(&self) -> molecule::Number {
-        molecule::unpack_number(self.as_slice())
+impl TxReceipt {
+    pub const FIELD_COUNT: usize = 5;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
     }
-    pub fn to_enum(&self) -> SMTMergeValueUnion {
-        let inner = self.0.slice(molecule::NUMBER_SIZE..);
-        match self.item_id() {
-            0 => SMTValue::new_unchecked(inner).into(),
-            1 => SMTMergeWithZero::new_unchecked(inner).into(),
-            _ => panic!("{}: invalid data", Self::NAME),
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
         }
     }
-    pub fn as_reader<'r>(&'r self) -> SMTMergeValueReader<'r> {
-        SMTMergeValueReader::new_unchecked(self.as_slice())
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn tx_witness_hash(&self) -> Byte32 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        Byte32::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn post_state(&self) -> AccountMerkleState {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
+        AccountMerkleState::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn read_data_hashes(&self) -> Byte32Vec {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        let end = molecule::unpack_number(&slice[16..]) as usize;
+        Byte32Vec::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn logs(&self) -> LogItemVec {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[16..]) as usize;
+        let end = molecule::unpack_number(&slice[20..]) as usize;
+        LogItemVec::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn fee_payments(&self) -> FeePaymentVec {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[20..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[24..]) as usize;
+            FeePaymentVec::new_unchecked(self.0.slice(start..end))
+        } else {
+            FeePaymentVec::new_unchecked(self.0.slice(start..))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> TxReceiptReader<'r> {
+        TxReceiptReader::new_unchecked(self.as_slice())
     }
 }
-impl molecule::prelude::Entity for SMTMergeValue {
-    type Builder = SMTMergeValueBuilder;
-    const NAME: &'static str = "SMTMergeValue";
+impl molecule::prelude::Entity for TxReceipt {
+    type Builder = TxReceiptBuilder;
+    const NAME: &'static str = "TxReceipt";
     fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        SMTMergeValue(data)
+        TxReceipt(data)
     }
     fn as_bytes(&self) -> molecule::bytes::Bytes {
         self.0.clone()
@@ -3079,21 +2906,26 @@ impl molecule::prelude::Entity for SMTMergeValue {
         &self.0[..]
     }
     fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        SMTMergeValueReader::from_slice(slice).map(|reader| reader.to_entity())
+        TxReceiptReader::from_slice(slice).map(|reader| reader.to_entity())
     }
     fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        SMTMergeValueReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+        TxReceiptReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
     }
     fn new_builder() -> Self::Builder {
         ::core::default::Default::default()
     }
     fn as_builder(self) -> Self::Builder {
-        Self::new_builder().set(self.to_enum())
+        Self::new_builder()
+            .tx_witness_hash(self.tx_witness_hash())
+            .post_state(self.post_state())
+            .read_data_hashes(self.read_data_hashes())
+            .logs(self.logs())
+            .fee_payments(self.fee_payments())
     }
 }
 #[derive(Clone, Copy)]
-pub struct SMTMergeValueReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for SMTMergeValueReader<'r> {
+pub struct TxReceiptReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for TxReceiptReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -3102,40 +2934,87 @@ impl<'r> ::core::fmt::LowerHex for SMTMergeValueReader<'r> {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Debug for SMTMergeValueReader<'r> {
+impl<'r> ::core::fmt::Debug for TxReceiptReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for SMTMergeValueReader<'r> {
+impl<'r> ::core::fmt::Display for TxReceiptReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{}(", Self::NAME)?;
-        self.to_enum().display_inner(f)?;
-        write!(f, ")")
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "tx_witness_hash", self.tx_witness_hash())?;
+        write!(f, ", {}: {}", "post_state", self.post_state())?;
+        write!(f, ", {}: {}", "read_data_hashes", self.read_data_hashes())?;
+        write!(f, ", {}: {}", "logs", self.logs())?;
+        write!(f, ", {}: {}", "fee_payments", self.fee_payments())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
     }
 }
-impl<'r> SMTMergeValueReader<'r> {
-    pub const ITEMS_COUNT: usize = 2;
-    pub fn item_id(&self) -> molecule::Number {
-        molecule::unpack_number(self.as_slice())
+impl<'r> TxReceiptReader<'r> {
+    pub const FIELD_COUNT: usize = 5;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
     }
-    pub fn to_enum(&self) -> SMTMergeValueUnionReader<'r> {
-        let inner = &self.as_slice()[molecule::NUMBER_SIZE..];
-        match self.item_id() {
-            0 => SMTValueReader::new_unchecked(inner).into(),
-            1 => SMTMergeWithZeroReader::new_unchecked(inner).into(),
-            _ => panic!("{}: invalid data", Self::NAME),
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn tx_witness_hash(&self) -> Byte32Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        Byte32Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn post_state(&self) -> AccountMerkleStateReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
+        AccountMerkleStateReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn read_data_hashes(&self) -> Byte32VecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        let end = molecule::unpack_number(&slice[16..]) as usize;
+        Byte32VecReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn logs(&self) -> LogItemVecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[16..]) as usize;
+        let end = molecule::unpack_number(&slice[20..]) as usize;
+        LogItemVecReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn fee_payments(&self) -> FeePaymentVecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[20..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[24..]) as usize;
+            FeePaymentVecReader::new_unchecked(&self.as_slice()[start..end])
+        } else {
+            FeePaymentVecReader::new_unchecked(&self.as_slice()[start..])
         }
     }
 }
-impl<'r> molecule::prelude::Reader<'r> for SMTMergeValueReader<'r> {
-    type Entity = SMTMergeValue;
-    const NAME: &'static str = "SMTMergeValueReader";
+impl<'r> molecule::prelude::Reader<'r> for TxReceiptReader<'r> {
+    type Entity = TxReceipt;
+    const NAME: &'static str = "TxReceiptReader";
     fn to_entity(&self) -> Self::Entity {
         Self::Entity::new_unchecked(self.as_slice().to_owned().into())
     }
     fn new_unchecked(slice: &'r [u8]) -> Self {
-        SMTMergeValueReader(slice)
+        TxReceiptReader(slice)
     }
     fn as_slice(&self) -> &'r [u8] {
         self.0
@@ -3146,177 +3025,3422 @@ impl<'r> molecule::prelude::Reader<'r> for SMTMergeValueReader<'r> {
         if slice_len < molecule::NUMBER_SIZE {
             return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
         }
-        let item_id = molecule::unpack_number(slice);
-        let inner_slice = &slice[molecule::NUMBER_SIZE..];
-        match item_id {
-            0 => SMTValueReader::verify(inner_slice, compatible),
-            1 => SMTMergeWithZeroReader::verify(inner_slice, compatible),
-            _ => ve!(Self, UnknownItem, Self::ITEMS_COUNT, item_id),
-        }?;
-        Ok(())
-    }
-}
-#[derive(Debug, Default)]
-pub struct SMTMergeValueBuilder(pub(crate) SMTMergeValueUnion);
-impl SMTMergeValueBuilder {
-    pub const ITEMS_COUNT: usize = 2;
-    pub fn set<I>(mut self, v: I) -> Self
-    where
-        I: ::core::convert::Into<SMTMergeValueUnion>,
-    {
-        self.0 = v.into();
-        self
-    }
-}
-impl molecule::prelude::Builder for SMTMergeValueBuilder {
-    type Entity = SMTMergeValue;
-    const NAME: &'static str = "SMTMergeValueBuilder";
-    fn expected_length(&self) -> usize {
-        molecule::NUMBER_SIZE + self.0.as_slice().len()
-    }
-    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
-        writer.write_all(&molecule::pack_number(self.0.item_id()))?;
-        writer.write_all(self.0.as_slice())
-    }
-    fn build(&self) -> Self::Entity {
-        let mut inner = Vec::with_capacity(self.expected_length());
-        self.write(&mut inner)
-            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len == molecule::NUMBER_SIZE && Self::FIELD_COUNT == 0 {
+            return Ok(());
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
+        if field_count < Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        } else if !compatible && field_count > Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        };
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        Byte32Reader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        AccountMerkleStateReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        Byte32VecReader::verify(&slice[offsets[2]..offsets[3]], compatible)?;
+        LogItemVecReader::verify(&slice[offsets[3]..offsets[4]], compatible)?;
+        FeePaymentVecReader::verify(&slice[offsets[4]..offsets[5]], compatible)?;
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct TxReceiptBuilder {
+    pub(crate) tx_witness_hash: Byte32,
+    pub(crate) post_state: AccountMerkleState,
+    pub(crate) read_data_hashes: Byte32Vec,
+    pub(crate) logs: LogItemVec,
+    pub(crate) fee_payments: FeePaymentVec,
+}
+impl TxReceiptBuilder {
+    pub const FIELD_COUNT: usize = 5;
+    pub fn tx_witness_hash(mut self, v: Byte32) -> Self {
+        self.tx_witness_hash = v;
+        self
+    }
+    pub fn post_state(mut self, v: AccountMerkleState) -> Self {
+        self.post_state = v;
+        self
+    }
+    pub fn read_data_hashes(mut self, v: Byte32Vec) -> Self {
+        self.read_data_hashes = v;
+        self
+    }
+    pub fn logs(mut self, v: LogItemVec) -> Self {
+        self.logs = v;
+        self
+    }
+    pub fn fee_payments(mut self, v: FeePaymentVec) -> Self {
+        self.fee_payments = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for TxReceiptBuilder {
+    type Entity = TxReceipt;
+    const NAME: &'static str = "TxReceiptBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
+            + self.tx_witness_hash.as_slice().len()
+            + self.post_state.as_slice().len()
+            + self.read_data_hashes.as_slice().len()
+            + self.logs.as_slice().len()
+            + self.fee_payments.as_slice().len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
+        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
+        offsets.push(total_size);
+        total_size += self.tx_witness_hash.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.post_state.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.read_data_hashes.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.logs.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.fee_payments.as_slice().len();
+        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+        for offset in offsets.into_iter() {
+            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        }
+        writer.write_all(self.tx_witness_hash.as_slice())?;
+        writer.write_all(self.post_state.as_slice())?;
+        writer.write_all(self.read_data_hashes.as_slice())?;
+        writer.write_all(self.logs.as_slice())?;
+        writer.write_all(self.fee_payments.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        TxReceipt::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct WithdrawalReceipt(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for WithdrawalReceipt {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for WithdrawalReceipt {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for WithdrawalReceipt {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "post_state", self.post_state())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for WithdrawalReceipt {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![
+            44, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        WithdrawalReceipt::new_unchecked(v.into())
+    }
+}
+impl WithdrawalReceipt {
+    pub const FIELD_COUNT: usize = 1;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn post_state(&self) -> AccountMerkleState {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[8..]) as usize;
+            AccountMerkleState::new_unchecked(self.0.slice(start..end))
+        } else {
+            AccountMerkleState::new_unchecked(self.0.slice(start..))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> WithdrawalReceiptReader<'r> {
+        WithdrawalReceiptReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for WithdrawalReceipt {
+    type Builder = WithdrawalReceiptBuilder;
+    const NAME: &'static str = "WithdrawalReceipt";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        WithdrawalReceipt(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        WithdrawalReceiptReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        WithdrawalReceiptReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().post_state(self.post_state())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct WithdrawalReceiptReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for WithdrawalReceiptReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for WithdrawalReceiptReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for WithdrawalReceiptReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "post_state", self.post_state())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> WithdrawalReceiptReader<'r> {
+    pub const FIELD_COUNT: usize = 1;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn post_state(&self) -> AccountMerkleStateReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[8..]) as usize;
+            AccountMerkleStateReader::new_unchecked(&self.as_slice()[start..end])
+        } else {
+            AccountMerkleStateReader::new_unchecked(&self.as_slice()[start..])
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for WithdrawalReceiptReader<'r> {
+    type Entity = WithdrawalReceipt;
+    const NAME: &'static str = "WithdrawalReceiptReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        WithdrawalReceiptReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len == molecule::NUMBER_SIZE && Self::FIELD_COUNT == 0 {
+            return Ok(());
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
+        if field_count < Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        } else if !compatible && field_count > Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        };
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        AccountMerkleStateReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct WithdrawalReceiptBuilder {
+    pub(crate) post_state: AccountMerkleState,
+}
+impl WithdrawalReceiptBuilder {
+    pub const FIELD_COUNT: usize = 1;
+    pub fn post_state(mut self, v: AccountMerkleState) -> Self {
+        self.post_state = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for WithdrawalReceiptBuilder {
+    type Entity = WithdrawalReceipt;
+    const NAME: &'static str = "WithdrawalReceiptBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1) + self.post_state.as_slice().len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
+        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
+        offsets.push(total_size);
+        total_size += self.post_state.as_slice().len();
+        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+        for offset in offsets.into_iter() {
+            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        }
+        writer.write_all(self.post_state.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        WithdrawalReceipt::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct SMTBranchKey(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for SMTBranchKey {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for SMTBranchKey {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for SMTBranchKey {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "height", self.height())?;
+        write!(f, ", {}: {}", "node_key", self.node_key())?;
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for SMTBranchKey {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0,
+        ];
+        SMTBranchKey::new_unchecked(v.into())
+    }
+}
+impl SMTBranchKey {
+    pub const TOTAL_SIZE: usize = 33;
+    pub const FIELD_SIZES: [usize; 2] = [1, 32];
+    pub const FIELD_COUNT: usize = 2;
+    pub fn height(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(0..1))
+    }
+    pub fn node_key(&self) -> Byte32 {
+        Byte32::new_unchecked(self.0.slice(1..33))
+    }
+    pub fn as_reader<'r>(&'r self) -> SMTBranchKeyReader<'r> {
+        SMTBranchKeyReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for SMTBranchKey {
+    type Builder = SMTBranchKeyBuilder;
+    const NAME: &'static str = "SMTBranchKey";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        SMTBranchKey(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        SMTBranchKeyReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        SMTBranchKeyReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+            .height(self.height())
+            .node_key(self.node_key())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct SMTBranchKeyReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for SMTBranchKeyReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for SMTBranchKeyReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for SMTBranchKeyReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "height", self.height())?;
+        write!(f, ", {}: {}", "node_key", self.node_key())?;
+        write!(f, " }}")
+    }
+}
+impl<'r> SMTBranchKeyReader<'r> {
+    pub const TOTAL_SIZE: usize = 33;
+    pub const FIELD_SIZES: [usize; 2] = [1, 32];
+    pub const FIELD_COUNT: usize = 2;
+    pub fn height(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[0..1])
+    }
+    pub fn node_key(&self) -> Byte32Reader<'r> {
+        Byte32Reader::new_unchecked(&self.as_slice()[1..33])
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for SMTBranchKeyReader<'r> {
+    type Entity = SMTBranchKey;
+    const NAME: &'static str = "SMTBranchKeyReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        SMTBranchKeyReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len != Self::TOTAL_SIZE {
+            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct SMTBranchKeyBuilder {
+    pub(crate) height: Byte,
+    pub(crate) node_key: Byte32,
+}
+impl SMTBranchKeyBuilder {
+    pub const TOTAL_SIZE: usize = 33;
+    pub const FIELD_SIZES: [usize; 2] = [1, 32];
+    pub const FIELD_COUNT: usize = 2;
+    pub fn height(mut self, v: Byte) -> Self {
+        self.height = v;
+        self
+    }
+    pub fn node_key(mut self, v: Byte32) -> Self {
+        self.node_key = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for SMTBranchKeyBuilder {
+    type Entity = SMTBranchKey;
+    const NAME: &'static str = "SMTBranchKeyBuilder";
+    fn expected_length(&self) -> usize {
+        Self::TOTAL_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(self.height.as_slice())?;
+        writer.write_all(self.node_key.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        SMTBranchKey::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct SMTBranchNode(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for SMTBranchNode {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for SMTBranchNode {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for SMTBranchNode {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "left", self.left())?;
+        write!(f, ", {}: {}", "right", self.right())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for SMTBranchNode {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![
+            84, 0, 0, 0, 12, 0, 0, 0, 48, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        SMTBranchNode::new_unchecked(v.into())
+    }
+}
+impl SMTBranchNode {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn left(&self) -> SMTMergeValue {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        SMTMergeValue::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn right(&self) -> SMTMergeValue {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[12..]) as usize;
+            SMTMergeValue::new_unchecked(self.0.slice(start..end))
+        } else {
+            SMTMergeValue::new_unchecked(self.0.slice(start..))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> SMTBranchNodeReader<'r> {
+        SMTBranchNodeReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for SMTBranchNode {
+    type Builder = SMTBranchNodeBuilder;
+    const NAME: &'static str = "SMTBranchNode";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        SMTBranchNode(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        SMTBranchNodeReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        SMTBranchNodeReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().left(self.left()).right(self.right())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct SMTBranchNodeReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for SMTBranchNodeReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for SMTBranchNodeReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for SMTBranchNodeReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "left", self.left())?;
+        write!(f, ", {}: {}", "right", self.right())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> SMTBranchNodeReader<'r> {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn left(&self) -> SMTMergeValueReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        SMTMergeValueReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn right(&self) -> SMTMergeValueReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[12..]) as usize;
+            SMTMergeValueReader::new_unchecked(&self.as_slice()[start..end])
+        } else {
+            SMTMergeValueReader::new_unchecked(&self.as_slice()[start..])
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for SMTBranchNodeReader<'r> {
+    type Entity = SMTBranchNode;
+    const NAME: &'static str = "SMTBranchNodeReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        SMTBranchNodeReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len == molecule::NUMBER_SIZE && Self::FIELD_COUNT == 0 {
+            return Ok(());
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
+        if field_count < Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        } else if !compatible && field_count > Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        };
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        SMTMergeValueReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        SMTMergeValueReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct SMTBranchNodeBuilder {
+    pub(crate) left: SMTMergeValue,
+    pub(crate) right: SMTMergeValue,
+}
+impl SMTBranchNodeBuilder {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn left(mut self, v: SMTMergeValue) -> Self {
+        self.left = v;
+        self
+    }
+    pub fn right(mut self, v: SMTMergeValue) -> Self {
+        self.right = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for SMTBranchNodeBuilder {
+    type Entity = SMTBranchNode;
+    const NAME: &'static str = "SMTBranchNodeBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
+            + self.left.as_slice().len()
+            + self.right.as_slice().len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
+        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
+        offsets.push(total_size);
+        total_size += self.left.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.right.as_slice().len();
+        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+        for offset in offsets.into_iter() {
+            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        }
+        writer.write_all(self.left.as_slice())?;
+        writer.write_all(self.right.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        SMTBranchNode::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct SMTMergeValue(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for SMTMergeValue {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for SMTMergeValue {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for SMTMergeValue {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}(", Self::NAME)?;
+        self.to_enum().display_inner(f)?;
+        write!(f, ")")
+    }
+}
+impl ::core::default::Default for SMTMergeValue {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0,
+        ];
+        SMTMergeValue::new_unchecked(v.into())
+    }
+}
+impl SMTMergeValue {
+    pub const ITEMS_COUNT: usize = 2;
+    pub fn item_id(&self) -> molecule::Number {
+        molecule::unpack_number(self.as_slice())
+    }
+    pub fn to_enum(&self) -> SMTMergeValueUnion {
+        let inner = self.0.slice(molecule::NUMBER_SIZE..);
+        match self.item_id() {
+            0 => SMTValue::new_unchecked(inner).into(),
+            1 => SMTMergeWithZero::new_unchecked(inner).into(),
+            _ => panic!("{}: invalid data", Self::NAME),
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> SMTMergeValueReader<'r> {
+        SMTMergeValueReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for SMTMergeValue {
+    type Builder = SMTMergeValueBuilder;
+    const NAME: &'static str = "SMTMergeValue";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        SMTMergeValue(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        SMTMergeValueReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        SMTMergeValueReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().set(self.to_enum())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct SMTMergeValueReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for SMTMergeValueReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for SMTMergeValueReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for SMTMergeValueReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}(", Self::NAME)?;
+        self.to_enum().display_inner(f)?;
+        write!(f, ")")
+    }
+}
+impl<'r> SMTMergeValueReader<'r> {
+    pub const ITEMS_COUNT: usize = 2;
+    pub fn item_id(&self) -> molecule::Number {
+        molecule::unpack_number(self.as_slice())
+    }
+    pub fn to_enum(&self) -> SMTMergeValueUnionReader<'r> {
+        let inner = &self.as_slice()[molecule::NUMBER_SIZE..];
+        match self.item_id() {
+            0 => SMTValueReader::new_unchecked(inner).into(),
+            1 => SMTMergeWithZeroReader::new_unchecked(inner).into(),
+            _ => panic!("{}: invalid data", Self::NAME),
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for SMTMergeValueReader<'r> {
+    type Entity = SMTMergeValue;
+    const NAME: &'static str = "SMTMergeValueReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        SMTMergeValueReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let item_id = molecule::unpack_number(slice);
+        let inner_slice = &slice[molecule::NUMBER_SIZE..];
+        match item_id {
+            0 => SMTValueReader::verify(inner_slice, compatible),
+            1 => SMTMergeWithZeroReader::verify(inner_slice, compatible),
+            _ => ve!(Self, UnknownItem, Self::ITEMS_COUNT, item_id),
+        }?;
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct SMTMergeValueBuilder(pub(crate) SMTMergeValueUnion);
+impl SMTMergeValueBuilder {
+    pub const ITEMS_COUNT: usize = 2;
+    pub fn set<I>(mut self, v: I) -> Self
+    where
+        I: ::core::convert::Into<SMTMergeValueUnion>,
+    {
+        self.0 = v.into();
+        self
+    }
+}
+impl molecule::prelude::Builder for SMTMergeValueBuilder {
+    type Entity = SMTMergeValue;
+    const NAME: &'static str = "SMTMergeValueBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE + self.0.as_slice().len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(&molecule::pack_number(self.0.item_id()))?;
+        writer.write_all(self.0.as_slice())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
         SMTMergeValue::new_unchecked(inner.into())
     }
 }
-#[derive(Debug, Clone)]
-pub enum SMTMergeValueUnion {
-    SMTValue(SMTValue),
-    SMTMergeWithZero(SMTMergeWithZero),
+#[derive(Debug, Clone)]
+pub enum SMTMergeValueUnion {
+    SMTValue(SMTValue),
+    SMTMergeWithZero(SMTMergeWithZero),
+}
+#[derive(Debug, Clone, Copy)]
+pub enum SMTMergeValueUnionReader<'r> {
+    SMTValue(SMTValueReader<'r>),
+    SMTMergeWithZero(SMTMergeWithZeroReader<'r>),
+}
+impl ::core::default::Default for SMTMergeValueUnion {
+    fn default() -> Self {
+        SMTMergeValueUnion::SMTValue(::core::default::Default::default())
+    }
+}
+impl ::core::fmt::Display for SMTMergeValueUnion {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            SMTMergeValueUnion::SMTValue(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, SMTValue::NAME, item)
+            }
+            SMTMergeValueUnion::SMTMergeWithZero(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, SMTMergeWithZero::NAME, item)
+            }
+        }
+    }
+}
+impl<'r> ::core::fmt::Display for SMTMergeValueUnionReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            SMTMergeValueUnionReader::SMTValue(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, SMTValue::NAME, item)
+            }
+            SMTMergeValueUnionReader::SMTMergeWithZero(ref item) => {
+                write!(f, "{}::{}({})", Self::NAME, SMTMergeWithZero::NAME, item)
+            }
+        }
+    }
+}
+impl SMTMergeValueUnion {
+    pub(crate) fn display_inner(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            SMTMergeValueUnion::SMTValue(ref item) => write!(f, "{}", item),
+            SMTMergeValueUnion::SMTMergeWithZero(ref item) => write!(f, "{}", item),
+        }
+    }
+}
+impl<'r> SMTMergeValueUnionReader<'r> {
+    pub(crate) fn display_inner(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        match self {
+            SMTMergeValueUnionReader::SMTValue(ref item) => write!(f, "{}", item),
+            SMTMergeValueUnionReader::SMTMergeWithZero(ref item) => write!(f, "{}", item),
+        }
+    }
+}
+impl ::core::convert::From<SMTValue> for SMTMergeValueUnion {
+    fn from(item: SMTValue) -> Self {
+        SMTMergeValueUnion::SMTValue(item)
+    }
+}
+impl ::core::convert::From<SMTMergeWithZero> for SMTMergeValueUnion {
+    fn from(item: SMTMergeWithZero) -> Self {
+        SMTMergeValueUnion::SMTMergeWithZero(item)
+    }
+}
+impl<'r> ::core::convert::From<SMTValueReader<'r>> for SMTMergeValueUnionReader<'r> {
+    fn from(item: SMTValueReader<'r>) -> Self {
+        SMTMergeValueUnionReader::SMTValue(item)
+    }
+}
+impl<'r> ::core::convert::From<SMTMergeWithZeroReader<'r>> for SMTMergeValueUnionReader<'r> {
+    fn from(item: SMTMergeWithZeroReader<'r>) -> Self {
+        SMTMergeValueUnionReader::SMTMergeWithZero(item)
+    }
+}
+impl SMTMergeValueUnion {
+    pub const NAME: &'static str = "SMTMergeValueUnion";
+    pub fn as_bytes(&self) -> molecule::bytes::Bytes {
+        match self {
+            SMTMergeValueUnion::SMTValue(item) => item.as_bytes(),
+            SMTMergeValueUnion::SMTMergeWithZero(item) => item.as_bytes(),
+        }
+    }
+    pub fn as_slice(&self) -> &[u8] {
+        match self {
+            SMTMergeValueUnion::SMTValue(item) => item.as_slice(),
+            SMTMergeValueUnion::SMTMergeWithZero(item) => item.as_slice(),
+        }
+    }
+    pub fn item_id(&self) -> molecule::Number {
+        match self {
+            SMTMergeValueUnion::SMTValue(_) => 0,
+            SMTMergeValueUnion::SMTMergeWithZero(_) => 1,
+        }
+    }
+    pub fn item_name(&self) -> &str {
+        match self {
+            SMTMergeValueUnion::SMTValue(_) => "SMTValue",
+            SMTMergeValueUnion::SMTMergeWithZero(_) => "SMTMergeWithZero",
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> SMTMergeValueUnionReader<'r> {
+        match self {
+            SMTMergeValueUnion::SMTValue(item) => item.as_reader().into(),
+            SMTMergeValueUnion::SMTMergeWithZero(item) => item.as_reader().into(),
+        }
+    }
+}
+impl<'r> SMTMergeValueUnionReader<'r> {
+    pub const NAME: &'r str = "SMTMergeValueUnionReader";
+    pub fn as_slice(&self) -> &'r [u8] {
+        match self {
+            SMTMergeValueUnionReader::SMTValue(item) => item.as_slice(),
+            SMTMergeValueUnionReader::SMTMergeWithZero(item) => item.as_slice(),
+        }
+    }
+    pub fn item_id(&self) -> molecule::Number {
+        match self {
+            SMTMergeValueUnionReader::SMTValue(_) => 0,
+            SMTMergeValueUnionReader::SMTMergeWithZero(_) => 1,
+        }
+    }
+    pub fn item_name(&self) -> &str {
+        match self {
+            SMTMergeValueUnionReader::SMTValue(_) => "SMTValue",
+            SMTMergeValueUnionReader::SMTMergeWithZero(_) => "SMTMergeWithZero",
+        }
+    }
+}
+#[derive(Clone)]
+pub struct SMTMergeWithZero(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for SMTMergeWithZero {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for SMTMergeWithZero {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for SMTMergeWithZero {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "base_node", self.base_node())?;
+        write!(f, ", {}: {}", "zero_bits", self.zero_bits())?;
+        write!(f, ", {}: {}", "zero_count", self.zero_count())?;
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for SMTMergeWithZero {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0,
+        ];
+        SMTMergeWithZero::new_unchecked(v.into())
+    }
+}
+impl SMTMergeWithZero {
+    pub const TOTAL_SIZE: usize = 65;
+    pub const FIELD_SIZES: [usize; 3] = [32, 32, 1];
+    pub const FIELD_COUNT: usize = 3;
+    pub fn base_node(&self) -> Byte32 {
+        Byte32::new_unchecked(self.0.slice(0..32))
+    }
+    pub fn zero_bits(&self) -> Byte32 {
+        Byte32::new_unchecked(self.0.slice(32..64))
+    }
+    pub fn zero_count(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(64..65))
+    }
+    pub fn as_reader<'r>(&'r self) -> SMTMergeWithZeroReader<'r> {
+        SMTMergeWithZeroReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for SMTMergeWithZero {
+    type Builder = SMTMergeWithZeroBuilder;
+    const NAME: &'static str = "SMTMergeWithZero";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        SMTMergeWithZero(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        SMTMergeWithZeroReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        SMTMergeWithZeroReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+            .base_node(self.base_node())
+            .zero_bits(self.zero_bits())
+            .zero_count(self.zero_count())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct SMTMergeWithZeroReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for SMTMergeWithZeroReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for SMTMergeWithZeroReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for SMTMergeWithZeroReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "base_node", self.base_node())?;
+        write!(f, ", {}: {}", "zero_bits", self.zero_bits())?;
+        write!(f, ", {}: {}", "zero_count", self.zero_count())?;
+        write!(f, " }}")
+    }
+}
+impl<'r> SMTMergeWithZeroReader<'r> {
+    pub const TOTAL_SIZE: usize = 65;
+    pub const FIELD_SIZES: [usize; 3] = [32, 32, 1];
+    pub const FIELD_COUNT: usize = 3;
+    pub fn base_node(&self) -> Byte32Reader<'r> {
+        Byte32Reader::new_unchecked(&self.as_slice()[0..32])
+    }
+    pub fn zero_bits(&self) -> Byte32Reader<'r> {
+        Byte32Reader::new_unchecked(&self.as_slice()[32..64])
+    }
+    pub fn zero_count(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[64..65])
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for SMTMergeWithZeroReader<'r> {
+    type Entity = SMTMergeWithZero;
+    const NAME: &'static str = "SMTMergeWithZeroReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        SMTMergeWithZeroReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len != Self::TOTAL_SIZE {
+            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct SMTMergeWithZeroBuilder {
+    pub(crate) base_node: Byte32,
+    pub(crate) zero_bits: Byte32,
+    pub(crate) zero_count: Byte,
+}
+impl SMTMergeWithZeroBuilder {
+    pub const TOTAL_SIZE: usize = 65;
+    pub const FIELD_SIZES: [usize; 3] = [32, 32, 1];
+    pub const FIELD_COUNT: usize = 3;
+    pub fn base_node(mut self, v: Byte32) -> Self {
+        self.base_node = v;
+        self
+    }
+    pub fn zero_bits(mut self, v: Byte32) -> Self {
+        self.zero_bits = v;
+        self
+    }
+    pub fn zero_count(mut self, v: Byte) -> Self {
+        self.zero_count = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for SMTMergeWithZeroBuilder {
+    type Entity = SMTMergeWithZero;
+    const NAME: &'static str = "SMTMergeWithZeroBuilder";
+    fn expected_length(&self) -> usize {
+        Self::TOTAL_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(self.base_node.as_slice())?;
+        writer.write_all(self.zero_bits.as_slice())?;
+        writer.write_all(self.zero_count.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        SMTMergeWithZero::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct SMTValue(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for SMTValue {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for SMTValue {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for SMTValue {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "value", self.value())?;
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for SMTValue {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0,
+        ];
+        SMTValue::new_unchecked(v.into())
+    }
+}
+impl SMTValue {
+    pub const TOTAL_SIZE: usize = 32;
+    pub const FIELD_SIZES: [usize; 1] = [32];
+    pub const FIELD_COUNT: usize = 1;
+    pub fn value(&self) -> Byte32 {
+        Byte32::new_unchecked(self.0.slice(0..32))
+    }
+    pub fn as_reader<'r>(&'r self) -> SMTValueReader<'r> {
+        SMTValueReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for SMTValue {
+    type Builder = SMTValueBuilder;
+    const NAME: &'static str = "SMTValue";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        SMTValue(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        SMTValueReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        SMTValueReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().value(self.value())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct SMTValueReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for SMTValueReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for SMTValueReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for SMTValueReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "value", self.value())?;
+        write!(f, " }}")
+    }
+}
+impl<'r> SMTValueReader<'r> {
+    pub const TOTAL_SIZE: usize = 32;
+    pub const FIELD_SIZES: [usize; 1] = [32];
+    pub const FIELD_COUNT: usize = 1;
+    pub fn value(&self) -> Byte32Reader<'r> {
+        Byte32Reader::new_unchecked(&self.as_slice()[0..32])
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for SMTValueReader<'r> {
+    type Entity = SMTValue;
+    const NAME: &'static str = "SMTValueReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        SMTValueReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len != Self::TOTAL_SIZE {
+            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct SMTValueBuilder {
+    pub(crate) value: Byte32,
+}
+impl SMTValueBuilder {
+    pub const TOTAL_SIZE: usize = 32;
+    pub const FIELD_SIZES: [usize; 1] = [32];
+    pub const FIELD_COUNT: usize = 1;
+    pub fn value(mut self, v: Byte32) -> Self {
+        self.value = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for SMTValueBuilder {
+    type Entity = SMTValue;
+    const NAME: &'static str = "SMTValueBuilder";
+    fn expected_length(&self) -> usize {
+        Self::TOTAL_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(self.value.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        SMTValue::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct CellInfo(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for CellInfo {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for CellInfo {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for CellInfo {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "out_point", self.out_point())?;
+        write!(f, ", {}: {}", "output", self.output())?;
+        write!(f, ", {}: {}", "data", self.data())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for CellInfo {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![
+            77, 0, 0, 0, 16, 0, 0, 0, 24, 0, 0, 0, 77, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 53, 0, 0,
+            0, 16, 0, 0, 0, 48, 0, 0, 0, 49, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        CellInfo::new_unchecked(v.into())
+    }
+}
+impl CellInfo {
+    pub const FIELD_COUNT: usize = 3;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn out_point(&self) -> OutPoint {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        OutPoint::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn output(&self) -> CellOutput {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
+        CellOutput::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn data(&self) -> Bytes {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[16..]) as usize;
+            Bytes::new_unchecked(self.0.slice(start..end))
+        } else {
+            Bytes::new_unchecked(self.0.slice(start..))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> CellInfoReader<'r> {
+        CellInfoReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for CellInfo {
+    type Builder = CellInfoBuilder;
+    const NAME: &'static str = "CellInfo";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        CellInfo(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        CellInfoReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        CellInfoReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+            .out_point(self.out_point())
+            .output(self.output())
+            .data(self.data())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct CellInfoReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for CellInfoReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for CellInfoReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for CellInfoReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "out_point", self.out_point())?;
+        write!(f, ", {}: {}", "output", self.output())?;
+        write!(f, ", {}: {}", "data", self.data())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> CellInfoReader<'r> {
+    pub const FIELD_COUNT: usize = 3;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn out_point(&self) -> OutPointReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        OutPointReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn output(&self) -> CellOutputReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
+        CellOutputReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn data(&self) -> BytesReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[16..]) as usize;
+            BytesReader::new_unchecked(&self.as_slice()[start..end])
+        } else {
+            BytesReader::new_unchecked(&self.as_slice()[start..])
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for CellInfoReader<'r> {
+    type Entity = CellInfo;
+    const NAME: &'static str = "CellInfoReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        CellInfoReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len == molecule::NUMBER_SIZE && Self::FIELD_COUNT == 0 {
+            return Ok(());
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
+        if field_count < Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        } else if !compatible && field_count > Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        };
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        OutPointReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        CellOutputReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        BytesReader::verify(&slice[offsets[2]..offsets[3]], compatible)?;
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct CellInfoBuilder {
+    pub(crate) out_point: OutPoint,
+    pub(crate) output: CellOutput,
+    pub(crate) data: Bytes,
+}
+impl CellInfoBuilder {
+    pub const FIELD_COUNT: usize = 3;
+    pub fn out_point(mut self, v: OutPoint) -> Self {
+        self.out_point = v;
+        self
+    }
+    pub fn output(mut self, v: CellOutput) -> Self {
+        self.output = v;
+        self
+    }
+    pub fn data(mut self, v: Bytes) -> Self {
+        self.data = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for CellInfoBuilder {
+    type Entity = CellInfo;
+    const NAME: &'static str = "CellInfoBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
+            + self.out_point.as_slice().len()
+            + self.output.as_slice().len()
+            + self.data.as_slice().len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
+        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
+        offsets.push(total_size);
+        total_size += self.out_point.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.output.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.data.as_slice().len();
+        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+        for offset in offsets.into_iter() {
+            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        }
+        writer.write_all(self.out_point.as_slice())?;
+        writer.write_all(self.output.as_slice())?;
+        writer.write_all(self.data.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        CellInfo::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct InputCellInfo(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for InputCellInfo {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for InputCellInfo {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for InputCellInfo {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "input", self.input())?;
+        write!(f, ", {}: {}", "cell", self.cell())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for InputCellInfo {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![
+            68, 0, 0, 0, 12, 0, 0, 0, 64, 0, 0, 0, 52, 0, 0, 0, 28, 0, 0, 0, 32, 0, 0, 0, 36, 0, 0,
+            0, 40, 0, 0, 0, 44, 0, 0, 0, 48, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 4, 0, 0, 0, 4, 0, 0, 0, 4, 0, 0, 0,
+        ];
+        InputCellInfo::new_unchecked(v.into())
+    }
+}
+impl InputCellInfo {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn input(&self) -> CellInput {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        CellInput::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn cell(&self) -> CellInfo {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[12..]) as usize;
+            CellInfo::new_unchecked(self.0.slice(start..end))
+        } else {
+            CellInfo::new_unchecked(self.0.slice(start..))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> InputCellInfoReader<'r> {
+        InputCellInfoReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for InputCellInfo {
+    type Builder = InputCellInfoBuilder;
+    const NAME: &'static str = "InputCellInfo";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        InputCellInfo(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        InputCellInfoReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        InputCellInfoReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().input(self.input()).cell(self.cell())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct InputCellInfoReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for InputCellInfoReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for InputCellInfoReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for InputCellInfoReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "input", self.input())?;
+        write!(f, ", {}: {}", "cell", self.cell())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> InputCellInfoReader<'r> {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn input(&self) -> CellInputReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        CellInputReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn cell(&self) -> CellInfoReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[12..]) as usize;
+            CellInfoReader::new_unchecked(&self.as_slice()[start..end])
+        } else {
+            CellInfoReader::new_unchecked(&self.as_slice()[start..])
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for InputCellInfoReader<'r> {
+    type Entity = InputCellInfo;
+    const NAME: &'static str = "InputCellInfoReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        InputCellInfoReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len == molecule::NUMBER_SIZE && Self::FIELD_COUNT == 0 {
+            return Ok(());
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
+        if field_count < Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        } else if !compatible && field_count > Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        };
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        CellInputReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        CellInfoReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct InputCellInfoBuilder {
+    pub(crate) input: CellInput,
+    pub(crate) cell: CellInfo,
+}
+impl InputCellInfoBuilder {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn input(mut self, v: CellInput) -> Self {
+        self.input = v;
+        self
+    }
+    pub fn cell(mut self, v: CellInfo) -> Self {
+        self.cell = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for InputCellInfoBuilder {
+    type Entity = InputCellInfo;
+    const NAME: &'static str = "InputCellInfoBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
+            + self.input.as_slice().len()
+            + self.cell.as_slice().len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
+        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
+        offsets.push(total_size);
+        total_size += self.input.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.cell.as_slice().len();
+        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+        for offset in offsets.into_iter() {
+            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        }
+        writer.write_all(self.input.as_slice())?;
+        writer.write_all(self.cell.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        InputCellInfo::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct InputCellInfoVec(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for InputCellInfoVec {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for InputCellInfoVec {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for InputCellInfoVec {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} [", Self::NAME)?;
+        for i in 0..self.len() {
+            if i == 0 {
+                write!(f, "{}", self.get_unchecked(i))?;
+            } else {
+                write!(f, ", {}", self.get_unchecked(i))?;
+            }
+        }
+        write!(f, "]")
+    }
+}
+impl ::core::default::Default for InputCellInfoVec {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![4, 0, 0, 0];
+        InputCellInfoVec::new_unchecked(v.into())
+    }
+}
+impl InputCellInfoVec {
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn item_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.item_count()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn get(&self, idx: usize) -> Option<InputCellInfo> {
+        if idx >= self.len() {
+            None
+        } else {
+            Some(self.get_unchecked(idx))
+        }
+    }
+    pub fn get_unchecked(&self, idx: usize) -> InputCellInfo {
+        let slice = self.as_slice();
+        let start_idx = molecule::NUMBER_SIZE * (1 + idx);
+        let start = molecule::unpack_number(&slice[start_idx..]) as usize;
+        if idx == self.len() - 1 {
+            InputCellInfo::new_unchecked(self.0.slice(start..))
+        } else {
+            let end_idx = start_idx + molecule::NUMBER_SIZE;
+            let end = molecule::unpack_number(&slice[end_idx..]) as usize;
+            InputCellInfo::new_unchecked(self.0.slice(start..end))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> InputCellInfoVecReader<'r> {
+        InputCellInfoVecReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for InputCellInfoVec {
+    type Builder = InputCellInfoVecBuilder;
+    const NAME: &'static str = "InputCellInfoVec";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        InputCellInfoVec(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        InputCellInfoVecReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        InputCellInfoVecReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().extend(self.into_iter())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct InputCellInfoVecReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for InputCellInfoVecReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for InputCellInfoVecReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for InputCellInfoVecReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} [", Self::NAME)?;
+        for i in 0..self.len() {
+            if i == 0 {
+                write!(f, "{}", self.get_unchecked(i))?;
+            } else {
+                write!(f, ", {}", self.get_unchecked(i))?;
+            }
+        }
+        write!(f, "]")
+    }
+}
+impl<'r> InputCellInfoVecReader<'r> {
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn item_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.item_count()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn get(&self, idx: usize) -> Option<InputCellInfoReader<'r>> {
+        if idx >= self.len() {
+            None
+        } else {
+            Some(self.get_unchecked(idx))
+        }
+    }
+    pub fn get_unchecked(&self, idx: usize) -> InputCellInfoReader<'r> {
+        let slice = self.as_slice();
+        let start_idx = molecule::NUMBER_SIZE * (1 + idx);
+        let start = molecule::unpack_number(&slice[start_idx..]) as usize;
+        if idx == self.len() - 1 {
+            InputCellInfoReader::new_unchecked(&self.as_slice()[start..])
+        } else {
+            let end_idx = start_idx + molecule::NUMBER_SIZE;
+            let end = molecule::unpack_number(&slice[end_idx..]) as usize;
+            InputCellInfoReader::new_unchecked(&self.as_slice()[start..end])
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for InputCellInfoVecReader<'r> {
+    type Entity = InputCellInfoVec;
+    const NAME: &'static str = "InputCellInfoVecReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        InputCellInfoVecReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len == molecule::NUMBER_SIZE {
+            return Ok(());
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(
+                Self,
+                TotalSizeNotMatch,
+                molecule::NUMBER_SIZE * 2,
+                slice_len
+            );
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        for pair in offsets.windows(2) {
+            let start = pair[0];
+            let end = pair[1];
+            InputCellInfoReader::verify(&slice[start..end], compatible)?;
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct InputCellInfoVecBuilder(pub(crate) Vec<InputCellInfo>);
+impl InputCellInfoVecBuilder {
+    pub fn set(mut self, v: Vec<InputCellInfo>) -> Self {
+        self.0 = v;
+        self
+    }
+    pub fn push(mut self, v: InputCellInfo) -> Self {
+        self.0.push(v);
+        self
+    }
+    pub fn extend<T: ::core::iter::IntoIterator<Item = InputCellInfo>>(mut self, iter: T) -> Self {
+        for elem in iter {
+            self.0.push(elem);
+        }
+        self
+    }
+}
+impl molecule::prelude::Builder for InputCellInfoVecBuilder {
+    type Entity = InputCellInfoVec;
+    const NAME: &'static str = "InputCellInfoVecBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE * (self.0.len() + 1)
+            + self
+                .0
+                .iter()
+                .map(|inner| inner.as_slice().len())
+                .sum::<usize>()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        let item_count = self.0.len();
+        if item_count == 0 {
+            writer.write_all(&molecule::pack_number(
+                molecule::NUMBER_SIZE as molecule::Number,
+            ))?;
+        } else {
+            let (total_size, offsets) = self.0.iter().fold(
+                (
+                    molecule::NUMBER_SIZE * (item_count + 1),
+                    Vec::with_capacity(item_count),
+                ),
+                |(start, mut offsets), inner| {
+                    offsets.push(start);
+                    (start + inner.as_slice().len(), offsets)
+                },
+            );
+            writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+            for offset in offsets.into_iter() {
+                writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+            }
+            for inner in self.0.iter() {
+                writer.write_all(inner.as_slice())?;
+            }
+        }
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        InputCellInfoVec::new_unchecked(inner.into())
+    }
+}
+pub struct InputCellInfoVecIterator(InputCellInfoVec, usize, usize);
+impl ::core::iter::Iterator for InputCellInfoVecIterator {
+    type Item = InputCellInfo;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.1 >= self.2 {
+            None
+        } else {
+            let ret = self.0.get_unchecked(self.1);
+            self.1 += 1;
+            Some(ret)
+        }
+    }
+}
+impl ::core::iter::ExactSizeIterator for InputCellInfoVecIterator {
+    fn len(&self) -> usize {
+        self.2 - self.1
+    }
+}
+impl ::core::iter::IntoIterator for InputCellInfoVec {
+    type Item = InputCellInfo;
+    type IntoIter = InputCellInfoVecIterator;
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        InputCellInfoVecIterator(self, 0, len)
+    }
+}
+impl<'r> InputCellInfoVecReader<'r> {
+    pub fn iter<'t>(&'t self) -> InputCellInfoVecReaderIterator<'t, 'r> {
+        InputCellInfoVecReaderIterator(&self, 0, self.len())
+    }
+}
+pub struct InputCellInfoVecReaderIterator<'t, 'r>(&'t InputCellInfoVecReader<'r>, usize, usize);
+impl<'t: 'r, 'r> ::core::iter::Iterator for InputCellInfoVecReaderIterator<'t, 'r> {
+    type Item = InputCellInfoReader<'t>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.1 >= self.2 {
+            None
+        } else {
+            let ret = self.0.get_unchecked(self.1);
+            self.1 += 1;
+            Some(ret)
+        }
+    }
+}
+impl<'t: 'r, 'r> ::core::iter::ExactSizeIterator for InputCellInfoVecReaderIterator<'t, 'r> {
+    fn len(&self) -> usize {
+        self.2 - self.1
+    }
+}
+#[derive(Clone)]
+pub struct WitnessArgsVec(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for WitnessArgsVec {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for WitnessArgsVec {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for WitnessArgsVec {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} [", Self::NAME)?;
+        for i in 0..self.len() {
+            if i == 0 {
+                write!(f, "{}", self.get_unchecked(i))?;
+            } else {
+                write!(f, ", {}", self.get_unchecked(i))?;
+            }
+        }
+        write!(f, "]")
+    }
+}
+impl ::core::default::Default for WitnessArgsVec {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![4, 0, 0, 0];
+        WitnessArgsVec::new_unchecked(v.into())
+    }
+}
+impl WitnessArgsVec {
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn item_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.item_count()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn get(&self, idx: usize) -> Option<WitnessArgs> {
+        if idx >= self.len() {
+            None
+        } else {
+            Some(self.get_unchecked(idx))
+        }
+    }
+    pub fn get_unchecked(&self, idx: usize) -> WitnessArgs {
+        let slice = self.as_slice();
+        let start_idx = molecule::NUMBER_SIZE * (1 + idx);
+        let start = molecule::unpack_number(&slice[start_idx..]) as usize;
+        if idx == self.len() - 1 {
+            WitnessArgs::new_unchecked(self.0.slice(start..))
+        } else {
+            let end_idx = start_idx + molecule::NUMBER_SIZE;
+            let end = molecule::unpack_number(&slice[end_idx..]) as usize;
+            WitnessArgs::new_unchecked(self.0.slice(start..end))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> WitnessArgsVecReader<'r> {
+        WitnessArgsVecReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for WitnessArgsVec {
+    type Builder = WitnessArgsVecBuilder;
+    const NAME: &'static str = "WitnessArgsVec";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        WitnessArgsVec(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        WitnessArgsVecReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        WitnessArgsVecReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().extend(self.into_iter())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct WitnessArgsVecReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for WitnessArgsVecReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for WitnessArgsVecReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for WitnessArgsVecReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} [", Self::NAME)?;
+        for i in 0..self.len() {
+            if i == 0 {
+                write!(f, "{}", self.get_unchecked(i))?;
+            } else {
+                write!(f, ", {}", self.get_unchecked(i))?;
+            }
+        }
+        write!(f, "]")
+    }
+}
+impl<'r> WitnessArgsVecReader<'r> {
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn item_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.item_count()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn get(&self, idx: usize) -> Option<WitnessArgsReader<'r>> {
+        if idx >= self.len() {
+            None
+        } else {
+            Some(self.get_unchecked(idx))
+        }
+    }
+    pub fn get_unchecked(&self, idx: usize) -> WitnessArgsReader<'r> {
+        let slice = self.as_slice();
+        let start_idx = molecule::NUMBER_SIZE * (1 + idx);
+        let start = molecule::unpack_number(&slice[start_idx..]) as usize;
+        if idx == self.len() - 1 {
+            WitnessArgsReader::new_unchecked(&self.as_slice()[start..])
+        } else {
+            let end_idx = start_idx + molecule::NUMBER_SIZE;
+            let end = molecule::unpack_number(&slice[end_idx..]) as usize;
+            WitnessArgsReader::new_unchecked(&self.as_slice()[start..end])
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for WitnessArgsVecReader<'r> {
+    type Entity = WitnessArgsVec;
+    const NAME: &'static str = "WitnessArgsVecReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        WitnessArgsVecReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len == molecule::NUMBER_SIZE {
+            return Ok(());
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(
+                Self,
+                TotalSizeNotMatch,
+                molecule::NUMBER_SIZE * 2,
+                slice_len
+            );
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        for pair in offsets.windows(2) {
+            let start = pair[0];
+            let end = pair[1];
+            WitnessArgsReader::verify(&slice[start..end], compatible)?;
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct WitnessArgsVecBuilder(pub(crate) Vec<WitnessArgs>);
+impl WitnessArgsVecBuilder {
+    pub fn set(mut self, v: Vec<WitnessArgs>) -> Self {
+        self.0 = v;
+        self
+    }
+    pub fn push(mut self, v: WitnessArgs) -> Self {
+        self.0.push(v);
+        self
+    }
+    pub fn extend<T: ::core::iter::IntoIterator<Item = WitnessArgs>>(mut self, iter: T) -> Self {
+        for elem in iter {
+            self.0.push(elem);
+        }
+        self
+    }
+}
+impl molecule::prelude::Builder for WitnessArgsVecBuilder {
+    type Entity = WitnessArgsVec;
+    const NAME: &'static str = "WitnessArgsVecBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE * (self.0.len() + 1)
+            + self
+                .0
+                .iter()
+                .map(|inner| inner.as_slice().len())
+                .sum::<usize>()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        let item_count = self.0.len();
+        if item_count == 0 {
+            writer.write_all(&molecule::pack_number(
+                molecule::NUMBER_SIZE as molecule::Number,
+            ))?;
+        } else {
+            let (total_size, offsets) = self.0.iter().fold(
+                (
+                    molecule::NUMBER_SIZE * (item_count + 1),
+                    Vec::with_capacity(item_count),
+                ),
+                |(start, mut offsets), inner| {
+                    offsets.push(start);
+                    (start + inner.as_slice().len(), offsets)
+                },
+            );
+            writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+            for offset in offsets.into_iter() {
+                writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+            }
+            for inner in self.0.iter() {
+                writer.write_all(inner.as_slice())?;
+            }
+        }
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        WitnessArgsVec::new_unchecked(inner.into())
+    }
+}
+pub struct WitnessArgsVecIterator(WitnessArgsVec, usize, usize);
+impl ::core::iter::Iterator for WitnessArgsVecIterator {
+    type Item = WitnessArgs;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.1 >= self.2 {
+            None
+        } else {
+            let ret = self.0.get_unchecked(self.1);
+            self.1 += 1;
+            Some(ret)
+        }
+    }
+}
+impl ::core::iter::ExactSizeIterator for WitnessArgsVecIterator {
+    fn len(&self) -> usize {
+        self.2 - self.1
+    }
+}
+impl ::core::iter::IntoIterator for WitnessArgsVec {
+    type Item = WitnessArgs;
+    type IntoIter = WitnessArgsVecIterator;
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        WitnessArgsVecIterator(self, 0, len)
+    }
+}
+impl<'r> WitnessArgsVecReader<'r> {
+    pub fn iter<'t>(&'t self) -> WitnessArgsVecReaderIterator<'t, 'r> {
+        WitnessArgsVecReaderIterator(&self, 0, self.len())
+    }
+}
+pub struct WitnessArgsVecReaderIterator<'t, 'r>(&'t WitnessArgsVecReader<'r>, usize, usize);
+impl<'t: 'r, 'r> ::core::iter::Iterator for WitnessArgsVecReaderIterator<'t, 'r> {
+    type Item = WitnessArgsReader<'t>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.1 >= self.2 {
+            None
+        } else {
+            let ret = self.0.get_unchecked(self.1);
+            self.1 += 1;
+            Some(ret)
+        }
+    }
+}
+impl<'t: 'r, 'r> ::core::iter::ExactSizeIterator for WitnessArgsVecReaderIterator<'t, 'r> {
+    fn len(&self) -> usize {
+        self.2 - self.1
+    }
+}
+#[derive(Clone)]
+pub struct WitnessArgsOpt(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for WitnessArgsOpt {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for WitnessArgsOpt {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for WitnessArgsOpt {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        if let Some(v) = self.to_opt() {
+            write!(f, "{}(Some({}))", Self::NAME, v)
+        } else {
+            write!(f, "{}(None)", Self::NAME)
+        }
+    }
+}
+impl ::core::default::Default for WitnessArgsOpt {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![];
+        WitnessArgsOpt::new_unchecked(v.into())
+    }
+}
+impl WitnessArgsOpt {
+    pub fn is_none(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn is_some(&self) -> bool {
+        !self.0.is_empty()
+    }
+    pub fn to_opt(&self) -> Option<WitnessArgs> {
+        if self.is_none() {
+            None
+        } else {
+            Some(WitnessArgs::new_unchecked(self.0.clone()))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> WitnessArgsOptReader<'r> {
+        WitnessArgsOptReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for WitnessArgsOpt {
+    type Builder = WitnessArgsOptBuilder;
+    const NAME: &'static str = "WitnessArgsOpt";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        WitnessArgsOpt(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        WitnessArgsOptReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        WitnessArgsOptReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().set(self.to_opt())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct WitnessArgsOptReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for WitnessArgsOptReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for WitnessArgsOptReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for WitnessArgsOptReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        if let Some(v) = self.to_opt() {
+            write!(f, "{}(Some({}))", Self::NAME, v)
+        } else {
+            write!(f, "{}(None)", Self::NAME)
+        }
+    }
+}
+impl<'r> WitnessArgsOptReader<'r> {
+    pub fn is_none(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn is_some(&self) -> bool {
+        !self.0.is_empty()
+    }
+    pub fn to_opt(&self) -> Option<WitnessArgsReader<'r>> {
+        if self.is_none() {
+            None
+        } else {
+            Some(WitnessArgsReader::new_unchecked(self.as_slice()))
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for WitnessArgsOptReader<'r> {
+    type Entity = WitnessArgsOpt;
+    const NAME: &'static str = "WitnessArgsOptReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        WitnessArgsOptReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        if !slice.is_empty() {
+            WitnessArgsReader::verify(&slice[..], compatible)?;
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct WitnessArgsOptBuilder(pub(crate) Option<WitnessArgs>);
+impl WitnessArgsOptBuilder {
+    pub fn set(mut self, v: Option<WitnessArgs>) -> Self {
+        self.0 = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for WitnessArgsOptBuilder {
+    type Entity = WitnessArgsOpt;
+    const NAME: &'static str = "WitnessArgsOptBuilder";
+    fn expected_length(&self) -> usize {
+        self.0
+            .as_ref()
+            .map(|ref inner| inner.as_slice().len())
+            .unwrap_or(0)
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        self.0
+            .as_ref()
+            .map(|ref inner| writer.write_all(inner.as_slice()))
+            .unwrap_or(Ok(()))
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        WitnessArgsOpt::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct Byte32Opt(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for Byte32Opt {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for Byte32Opt {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for Byte32Opt {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        if let Some(v) = self.to_opt() {
+            write!(f, "{}(Some({}))", Self::NAME, v)
+        } else {
+            write!(f, "{}(None)", Self::NAME)
+        }
+    }
+}
+impl ::core::default::Default for Byte32Opt {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![];
+        Byte32Opt::new_unchecked(v.into())
+    }
+}
+impl Byte32Opt {
+    pub fn is_none(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn is_some(&self) -> bool {
+        !self.0.is_empty()
+    }
+    pub fn to_opt(&self) -> Option<Byte32> {
+        if self.is_none() {
+            None
+        } else {
+            Some(Byte32::new_unchecked(self.0.clone()))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> Byte32OptReader<'r> {
+        Byte32OptReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for Byte32Opt {
+    type Builder = Byte32OptBuilder;
+    const NAME: &'static str = "Byte32Opt";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        Byte32Opt(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        Byte32OptReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        Byte32OptReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().set(self.to_opt())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct Byte32OptReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for Byte32OptReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for Byte32OptReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for Byte32OptReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        if let Some(v) = self.to_opt() {
+            write!(f, "{}(Some({}))", Self::NAME, v)
+        } else {
+            write!(f, "{}(None)", Self::NAME)
+        }
+    }
+}
+impl<'r> Byte32OptReader<'r> {
+    pub fn is_none(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn is_some(&self) -> bool {
+        !self.0.is_empty()
+    }
+    pub fn to_opt(&self) -> Option<Byte32Reader<'r>> {
+        if self.is_none() {
+            None
+        } else {
+            Some(Byte32Reader::new_unchecked(self.as_slice()))
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for Byte32OptReader<'r> {
+    type Entity = Byte32Opt;
+    const NAME: &'static str = "Byte32OptReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        Byte32OptReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        if !slice.is_empty() {
+            Byte32Reader::verify(&slice[..], compatible)?;
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct Byte32OptBuilder(pub(crate) Option<Byte32>);
+impl Byte32OptBuilder {
+    pub fn set(mut self, v: Option<Byte32>) -> Self {
+        self.0 = v;
+        self
+    }
 }
-#[derive(Debug, Clone, Copy)]
-pub enum SMTMergeValueUnionReader<'r> {
-    SMTValue(SMTValueReader<'r>),
-    SMTMergeWithZero(SMTMergeWithZeroReader<'r>),
+impl molecule::prelude::Builder for Byte32OptBuilder {
+    type Entity = Byte32Opt;
+    const NAME: &'static str = "Byte32OptBuilder";
+    fn expected_length(&self) -> usize {
+        self.0
+            .as_ref()
+            .map(|ref inner| inner.as_slice().len())
+            .unwrap_or(0)
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        self.0
+            .as_ref()
+            .map(|ref inner| writer.write_all(inner.as_slice()))
+            .unwrap_or(Ok(()))
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        Byte32Opt::new_unchecked(inner.into())
+    }
 }
-impl ::core::default::Default for SMTMergeValueUnion {
+#[derive(Clone)]
+pub struct RecoverAccountsContext(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for RecoverAccountsContext {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for RecoverAccountsContext {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for RecoverAccountsContext {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "cell_deps", self.cell_deps())?;
+        write!(f, ", {}: {}", "inputs", self.inputs())?;
+        write!(f, ", {}: {}", "witnesses", self.witnesses())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for RecoverAccountsContext {
     fn default() -> Self {
-        SMTMergeValueUnion::SMTValue(::core::default::Default::default())
+        let v: Vec<u8> = vec![
+            77, 0, 0, 0, 16, 0, 0, 0, 24, 0, 0, 0, 77, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 53, 0, 0,
+            0, 16, 0, 0, 0, 48, 0, 0, 0, 49, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        RecoverAccountsContext::new_unchecked(v.into())
     }
 }
-impl ::core::fmt::Display for SMTMergeValueUnion {
+impl RecoverAccountsContext {
+    pub const FIELD_COUNT: usize = 3;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn cell_deps(&self) -> CellDepVec {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        CellDepVec::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn inputs(&self) -> InputCellInfoVec {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
+        InputCellInfoVec::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn witnesses(&self) -> WitnessArgsVec {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[16..]) as usize;
+            WitnessArgsVec::new_unchecked(self.0.slice(start..end))
+        } else {
+            WitnessArgsVec::new_unchecked(self.0.slice(start..))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> RecoverAccountsContextReader<'r> {
+        RecoverAccountsContextReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for RecoverAccountsContext {
+    type Builder = RecoverAccountsContextBuilder;
+    const NAME: &'static str = "RecoverAccountsContext";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        RecoverAccountsContext(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        RecoverAccountsContextReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        RecoverAccountsContextReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+            .cell_deps(self.cell_deps())
+            .inputs(self.inputs())
+            .witnesses(self.witnesses())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct RecoverAccountsContextReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for RecoverAccountsContextReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        match self {
-            SMTMergeValueUnion::SMTValue(ref item) => {
-                write!(f, "{}::{}({})", Self::NAME, SMTValue::NAME, item)
-            }
-            SMTMergeValueUnion::SMTMergeWithZero(ref item) => {
-                write!(f, "{}::{}({})", Self::NAME, SMTMergeWithZero::NAME, item)
-            }
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for RecoverAccountsContextReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for RecoverAccountsContextReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "cell_deps", self.cell_deps())?;
+        write!(f, ", {}: {}", "inputs", self.inputs())?;
+        write!(f, ", {}: {}", "witnesses", self.witnesses())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> RecoverAccountsContextReader<'r> {
+    pub const FIELD_COUNT: usize = 3;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn cell_deps(&self) -> CellDepVecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        CellDepVecReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn inputs(&self) -> InputCellInfoVecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
+        InputCellInfoVecReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn witnesses(&self) -> WitnessArgsVecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[16..]) as usize;
+            WitnessArgsVecReader::new_unchecked(&self.as_slice()[start..end])
+        } else {
+            WitnessArgsVecReader::new_unchecked(&self.as_slice()[start..])
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for RecoverAccountsContextReader<'r> {
+    type Entity = RecoverAccountsContext;
+    const NAME: &'static str = "RecoverAccountsContextReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        RecoverAccountsContextReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len == molecule::NUMBER_SIZE && Self::FIELD_COUNT == 0 {
+            return Ok(());
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
+        if field_count < Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        } else if !compatible && field_count > Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        };
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        CellDepVecReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        InputCellInfoVecReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        WitnessArgsVecReader::verify(&slice[offsets[2]..offsets[3]], compatible)?;
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct RecoverAccountsContextBuilder {
+    pub(crate) cell_deps: CellDepVec,
+    pub(crate) inputs: InputCellInfoVec,
+    pub(crate) witnesses: WitnessArgsVec,
+}
+impl RecoverAccountsContextBuilder {
+    pub const FIELD_COUNT: usize = 3;
+    pub fn cell_deps(mut self, v: CellDepVec) -> Self {
+        self.cell_deps = v;
+        self
+    }
+    pub fn inputs(mut self, v: InputCellInfoVec) -> Self {
+        self.inputs = v;
+        self
+    }
+    pub fn witnesses(mut self, v: WitnessArgsVec) -> Self {
+        self.witnesses = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for RecoverAccountsContextBuilder {
+    type Entity = RecoverAccountsContext;
+    const NAME: &'static str = "RecoverAccountsContextBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
+            + self.cell_deps.as_slice().len()
+            + self.inputs.as_slice().len()
+            + self.witnesses.as_slice().len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
+        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
+        offsets.push(total_size);
+        total_size += self.cell_deps.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.inputs.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.witnesses.as_slice().len();
+        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+        for offset in offsets.into_iter() {
+            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        }
+        writer.write_all(self.cell_deps.as_slice())?;
+        writer.write_all(self.inputs.as_slice())?;
+        writer.write_all(self.witnesses.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        RecoverAccountsContext::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct RecoverAccountsContextOpt(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for RecoverAccountsContextOpt {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
         }
+        write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Display for SMTMergeValueUnionReader<'r> {
+impl ::core::fmt::Debug for RecoverAccountsContextOpt {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        match self {
-            SMTMergeValueUnionReader::SMTValue(ref item) => {
-                write!(f, "{}::{}({})", Self::NAME, SMTValue::NAME, item)
-            }
-            SMTMergeValueUnionReader::SMTMergeWithZero(ref item) => {
-                write!(f, "{}::{}({})", Self::NAME, SMTMergeWithZero::NAME, item)
-            }
-        }
+        write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl SMTMergeValueUnion {
-    pub(crate) fn display_inner(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        match self {
-            SMTMergeValueUnion::SMTValue(ref item) => write!(f, "{}", item),
-            SMTMergeValueUnion::SMTMergeWithZero(ref item) => write!(f, "{}", item),
+impl ::core::fmt::Display for RecoverAccountsContextOpt {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        if let Some(v) = self.to_opt() {
+            write!(f, "{}(Some({}))", Self::NAME, v)
+        } else {
+            write!(f, "{}(None)", Self::NAME)
         }
     }
 }
-impl<'r> SMTMergeValueUnionReader<'r> {
-    pub(crate) fn display_inner(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        match self {
-            SMTMergeValueUnionReader::SMTValue(ref item) => write!(f, "{}", item),
-            SMTMergeValueUnionReader::SMTMergeWithZero(ref item) => write!(f, "{}", item),
-        }
+impl ::core::default::Default for RecoverAccountsContextOpt {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![];
+        RecoverAccountsContextOpt::new_unchecked(v.into())
     }
 }
-impl ::core::convert::From<SMTValue> for SMTMergeValueUnion {
-    fn from(item: SMTValue) -> Self {
-        SMTMergeValueUnion::SMTValue(item)
+impl RecoverAccountsContextOpt {
+    pub fn is_none(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn is_some(&self) -> bool {
+        !self.0.is_empty()
+    }
+    pub fn to_opt(&self) -> Option<RecoverAccountsContext> {
+        if self.is_none() {
+            None
+        } else {
+            Some(RecoverAccountsContext::new_unchecked(self.0.clone()))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> RecoverAccountsContextOptReader<'r> {
+        RecoverAccountsContextOptReader::new_unchecked(self.as_slice())
     }
 }
-impl ::core::convert::From<SMTMergeWithZero> for SMTMergeValueUnion {
-    fn from(item: SMTMergeWithZero) -> Self {
-        SMTMergeValueUnion::SMTMergeWithZero(item)
+impl molecule::prelude::Entity for RecoverAccountsContextOpt {
+    type Builder = RecoverAccountsContextOptBuilder;
+    const NAME: &'static str = "RecoverAccountsContextOpt";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        RecoverAccountsContextOpt(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        RecoverAccountsContextOptReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        RecoverAccountsContextOptReader::from_compatible_slice(slice)
+            .map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().set(self.to_opt())
     }
 }
-impl<'r> ::core::convert::From<SMTValueReader<'r>> for SMTMergeValueUnionReader<'r> {
-    fn from(item: SMTValueReader<'r>) -> Self {
-        SMTMergeValueUnionReader::SMTValue(item)
+#[derive(Clone, Copy)]
+pub struct RecoverAccountsContextOptReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for RecoverAccountsContextOptReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::convert::From<SMTMergeWithZeroReader<'r>> for SMTMergeValueUnionReader<'r> {
-    fn from(item: SMTMergeWithZeroReader<'r>) -> Self {
-        SMTMergeValueUnionReader::SMTMergeWithZero(item)
+impl<'r> ::core::fmt::Debug for RecoverAccountsContextOptReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl SMTMergeValueUnion {
-    pub const NAME: &'static str = "SMTMergeValueUnion";
-    pub fn as_bytes(&self) -> molecule::bytes::Bytes {
-        match self {
-            SMTMergeValueUnion::SMTValue(item) => item.as_bytes(),
-            SMTMergeValueUnion::SMTMergeWithZero(item) => item.as_bytes(),
+impl<'r> ::core::fmt::Display for RecoverAccountsContextOptReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        if let Some(v) = self.to_opt() {
+            write!(f, "{}(Some({}))", Self::NAME, v)
+        } else {
+            write!(f, "{}(None)", Self::NAME)
         }
     }
-    pub fn as_slice(&self) -> &[u8] {
-        match self {
-            SMTMergeValueUnion::SMTValue(item) => item.as_slice(),
-            SMTMergeValueUnion::SMTMergeWithZero(item) => item.as_slice(),
-        }
+}
+impl<'r> RecoverAccountsContextOptReader<'r> {
+    pub fn is_none(&self) -> bool {
+        self.0.is_empty()
     }
-    pub fn item_id(&self) -> molecule::Number {
-        match self {
-            SMTMergeValueUnion::SMTValue(_) => 0,
-            SMTMergeValueUnion::SMTMergeWithZero(_) => 1,
-        }
+    pub fn is_some(&self) -> bool {
+        !self.0.is_empty()
     }
-    pub fn item_name(&self) -> &str {
-        match self {
-            SMTMergeValueUnion::SMTValue(_) => "SMTValue",
-            SMTMergeValueUnion::SMTMergeWithZero(_) => "SMTMergeWithZero",
+    pub fn to_opt(&self) -> Option<RecoverAccountsContextReader<'r>> {
+        if self.is_none() {
+            None
+        } else {
+            Some(RecoverAccountsContextReader::new_unchecked(self.as_slice()))
         }
     }
-    pub fn as_reader<'r>(&'r self) -> SMTMergeValueUnionReader<'r> {
-        match self {
-            SMTMergeValueUnion::SMTValue(item) => item.as_reader().into(),
-            SMTMergeValueUnion::SMTMergeWithZero(item) => item.as_reader().into(),
+}
+impl<'r> molecule::prelude::Reader<'r> for RecoverAccountsContextOptReader<'r> {
+    type Entity = RecoverAccountsContextOpt;
+    const NAME: &'static str = "RecoverAccountsContextOptReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        RecoverAccountsContextOptReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        if !slice.is_empty() {
+            RecoverAccountsContextReader::verify(&slice[..], compatible)?;
         }
+        Ok(())
     }
 }
-impl<'r> SMTMergeValueUnionReader<'r> {
-    pub const NAME: &'r str = "SMTMergeValueUnionReader";
-    pub fn as_slice(&self) -> &'r [u8] {
-        match self {
-            SMTMergeValueUnionReader::SMTValue(item) => item.as_slice(),
-            SMTMergeValueUnionReader::SMTMergeWithZero(item) => item.as_slice(),
-        }
+#[derive(Debug, Default)]
+pub struct RecoverAccountsContextOptBuilder(pub(crate) Option<RecoverAccountsContext>);
+impl RecoverAccountsContextOptBuilder {
+    pub fn set(mut self, v: Option<RecoverAccountsContext>) -> Self {
+        self.0 = v;
+        self
     }
-    pub fn item_id(&self) -> molecule::Number {
-        match self {
-            SMTMergeValueUnionReader::SMTValue(_) => 0,
-            SMTMergeValueUnionReader::SMTMergeWithZero(_) => 1,
-        }
+}
+impl molecule::prelude::Builder for RecoverAccountsContextOptBuilder {
+    type Entity = RecoverAccountsContextOpt;
+    const NAME: &'static str = "RecoverAccountsContextOptBuilder";
+    fn expected_length(&self) -> usize {
+        self.0
+            .as_ref()
+            .map(|ref inner| inner.as_slice().len())
+            .unwrap_or(0)
     }
-    pub fn item_name(&self) -> &str {
-        match self {
-            SMTMergeValueUnionReader::SMTValue(_) => "SMTValue",
-            SMTMergeValueUnionReader::SMTMergeWithZero(_) => "SMTMergeWithZero",
-        }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        self.0
+            .as_ref()
+            .map(|ref inner| writer.write_all(inner.as_slice()))
+            .unwrap_or(Ok(()))
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        RecoverAccountsContextOpt::new_unchecked(inner.into())
     }
 }
 #[derive(Clone)]
-pub struct SMTMergeWithZero(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for SMTMergeWithZero {
+pub struct Verifier(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for Verifier {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -3325,52 +6449,108 @@ impl ::core::fmt::LowerHex for SMTMergeWithZero {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl ::core::fmt::Debug for SMTMergeWithZero {
+impl ::core::fmt::Debug for Verifier {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl ::core::fmt::Display for SMTMergeWithZero {
+impl ::core::fmt::Display for Verifier {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "base_node", self.base_node())?;
-        write!(f, ", {}: {}", "zero_bits", self.zero_bits())?;
-        write!(f, ", {}: {}", "zero_count", self.zero_count())?;
+        write!(f, "{}: {}", "load_data_inputs", self.load_data_inputs())?;
+        write!(
+            f,
+            ", {}: {}",
+            "recover_accounts_context",
+            self.recover_accounts_context()
+        )?;
+        write!(f, ", {}: {}", "cell_dep", self.cell_dep())?;
+        write!(f, ", {}: {}", "input", self.input())?;
+        write!(f, ", {}: {}", "witness", self.witness())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
         write!(f, " }}")
     }
 }
-impl ::core::default::Default for SMTMergeWithZero {
+impl ::core::default::Default for Verifier {
     fn default() -> Self {
         let v: Vec<u8> = vec![
+            248, 0, 0, 0, 24, 0, 0, 0, 232, 0, 0, 0, 236, 0, 0, 0, 240, 0, 0, 0, 244, 0, 0, 0, 0,
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 4, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         ];
-        SMTMergeWithZero::new_unchecked(v.into())
+        Verifier::new_unchecked(v.into())
     }
 }
-impl SMTMergeWithZero {
-    pub const TOTAL_SIZE: usize = 65;
-    pub const FIELD_SIZES: [usize; 3] = [32, 32, 1];
-    pub const FIELD_COUNT: usize = 3;
-    pub fn base_node(&self) -> Byte32 {
-        Byte32::new_unchecked(self.0.slice(0..32))
+impl Verifier {
+    pub const FIELD_COUNT: usize = 5;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
     }
-    pub fn zero_bits(&self) -> Byte32 {
-        Byte32::new_unchecked(self.0.slice(32..64))
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
     }
-    pub fn zero_count(&self) -> Byte {
-        Byte::new_unchecked(self.0.slice(64..65))
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
     }
-    pub fn as_reader<'r>(&'r self) -> SMTMergeWithZeroReader<'r> {
-        SMTMergeWithZeroReader::new_unchecked(self.as_slice())
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn load_data_inputs(&self) -> InputCellInfoVec {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        InputCellInfoVec::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn recover_accounts_context(&self) -> RecoverAccountsContextOpt {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
+        RecoverAccountsContextOpt::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn cell_dep(&self) -> CellDep {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        let end = molecule::unpack_number(&slice[16..]) as usize;
+        CellDep::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn input(&self) -> InputCellInfo {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[16..]) as usize;
+        let end = molecule::unpack_number(&slice[20..]) as usize;
+        InputCellInfo::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn witness(&self) -> WitnessArgsOpt {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[20..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[24..]) as usize;
+            WitnessArgsOpt::new_unchecked(self.0.slice(start..end))
+        } else {
+            WitnessArgsOpt::new_unchecked(self.0.slice(start..))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> VerifierReader<'r> {
+        VerifierReader::new_unchecked(self.as_slice())
     }
 }
-impl molecule::prelude::Entity for SMTMergeWithZero {
-    type Builder = SMTMergeWithZeroBuilder;
-    const NAME: &'static str = "SMTMergeWithZero";
+impl molecule::prelude::Entity for Verifier {
+    type Builder = VerifierBuilder;
+    const NAME: &'static str = "Verifier";
     fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        SMTMergeWithZero(data)
+        Verifier(data)
     }
     fn as_bytes(&self) -> molecule::bytes::Bytes {
         self.0.clone()
@@ -3379,24 +6559,26 @@ impl molecule::prelude::Entity for SMTMergeWithZero {
         &self.0[..]
     }
     fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        SMTMergeWithZeroReader::from_slice(slice).map(|reader| reader.to_entity())
+        VerifierReader::from_slice(slice).map(|reader| reader.to_entity())
     }
     fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        SMTMergeWithZeroReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+        VerifierReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
     }
     fn new_builder() -> Self::Builder {
         ::core::default::Default::default()
     }
     fn as_builder(self) -> Self::Builder {
         Self::new_builder()
-            .base_node(self.base_node())
-            .zero_bits(self.zero_bits())
-            .zero_count(self.zero_count())
+            .load_data_inputs(self.load_data_inputs())
+            .recover_accounts_context(self.recover_accounts_context())
+            .cell_dep(self.cell_dep())
+            .input(self.input())
+            .witness(self.witness())
     }
 }
 #[derive(Clone, Copy)]
-pub struct SMTMergeWithZeroReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for SMTMergeWithZeroReader<'r> {
+pub struct VerifierReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for VerifierReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -3405,100 +6587,217 @@ impl<'r> ::core::fmt::LowerHex for SMTMergeWithZeroReader<'r> {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Debug for SMTMergeWithZeroReader<'r> {
+impl<'r> ::core::fmt::Debug for VerifierReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for SMTMergeWithZeroReader<'r> {
+impl<'r> ::core::fmt::Display for VerifierReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "base_node", self.base_node())?;
-        write!(f, ", {}: {}", "zero_bits", self.zero_bits())?;
-        write!(f, ", {}: {}", "zero_count", self.zero_count())?;
+        write!(f, "{}: {}", "load_data_inputs", self.load_data_inputs())?;
+        write!(
+            f,
+            ", {}: {}",
+            "recover_accounts_context",
+            self.recover_accounts_context()
+        )?;
+        write!(f, ", {}: {}", "cell_dep", self.cell_dep())?;
+        write!(f, ", {}: {}", "input", self.input())?;
+        write!(f, ", {}: {}", "witness", self.witness())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
         write!(f, " }}")
     }
 }
-impl<'r> SMTMergeWithZeroReader<'r> {
-    pub const TOTAL_SIZE: usize = 65;
-    pub const FIELD_SIZES: [usize; 3] = [32, 32, 1];
-    pub const FIELD_COUNT: usize = 3;
-    pub fn base_node(&self) -> Byte32Reader<'r> {
-        Byte32Reader::new_unchecked(&self.as_slice()[0..32])
+impl<'r> VerifierReader<'r> {
+    pub const FIELD_COUNT: usize = 5;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn load_data_inputs(&self) -> InputCellInfoVecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        InputCellInfoVecReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn recover_accounts_context(&self) -> RecoverAccountsContextOptReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
+        RecoverAccountsContextOptReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn cell_dep(&self) -> CellDepReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        let end = molecule::unpack_number(&slice[16..]) as usize;
+        CellDepReader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn zero_bits(&self) -> Byte32Reader<'r> {
-        Byte32Reader::new_unchecked(&self.as_slice()[32..64])
+    pub fn input(&self) -> InputCellInfoReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[16..]) as usize;
+        let end = molecule::unpack_number(&slice[20..]) as usize;
+        InputCellInfoReader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn zero_count(&self) -> ByteReader<'r> {
-        ByteReader::new_unchecked(&self.as_slice()[64..65])
+    pub fn witness(&self) -> WitnessArgsOptReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[20..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[24..]) as usize;
+            WitnessArgsOptReader::new_unchecked(&self.as_slice()[start..end])
+        } else {
+            WitnessArgsOptReader::new_unchecked(&self.as_slice()[start..])
+        }
     }
 }
-impl<'r> molecule::prelude::Reader<'r> for SMTMergeWithZeroReader<'r> {
-    type Entity = SMTMergeWithZero;
-    const NAME: &'static str = "SMTMergeWithZeroReader";
+impl<'r> molecule::prelude::Reader<'r> for VerifierReader<'r> {
+    type Entity = Verifier;
+    const NAME: &'static str = "VerifierReader";
     fn to_entity(&self) -> Self::Entity {
         Self::Entity::new_unchecked(self.as_slice().to_owned().into())
     }
     fn new_unchecked(slice: &'r [u8]) -> Self {
-        SMTMergeWithZeroReader(slice)
+        VerifierReader(slice)
     }
     fn as_slice(&self) -> &'r [u8] {
         self.0
     }
-    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
         use molecule::verification_error as ve;
         let slice_len = slice.len();
-        if slice_len != Self::TOTAL_SIZE {
-            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len == molecule::NUMBER_SIZE && Self::FIELD_COUNT == 0 {
+            return Ok(());
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
+        if field_count < Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        } else if !compatible && field_count > Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        };
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
         }
+        InputCellInfoVecReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        RecoverAccountsContextOptReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        CellDepReader::verify(&slice[offsets[2]..offsets[3]], compatible)?;
+        InputCellInfoReader::verify(&slice[offsets[3]..offsets[4]], compatible)?;
+        WitnessArgsOptReader::verify(&slice[offsets[4]..offsets[5]], compatible)?;
         Ok(())
     }
 }
 #[derive(Debug, Default)]
-pub struct SMTMergeWithZeroBuilder {
-    pub(crate) base_node: Byte32,
-    pub(crate) zero_bits: Byte32,
-    pub(crate) zero_count: Byte,
-}
-impl SMTMergeWithZeroBuilder {
-    pub const TOTAL_SIZE: usize = 65;
-    pub const FIELD_SIZES: [usize; 3] = [32, 32, 1];
-    pub const FIELD_COUNT: usize = 3;
-    pub fn base_node(mut self, v: Byte32) -> Self {
-        self.base_node = v;
+pub struct VerifierBuilder {
+    pub(crate) load_data_inputs: InputCellInfoVec,
+    pub(crate) recover_accounts_context: RecoverAccountsContextOpt,
+    pub(crate) cell_dep: CellDep,
+    pub(crate) input: InputCellInfo,
+    pub(crate) witness: WitnessArgsOpt,
+}
+impl VerifierBuilder {
+    pub const FIELD_COUNT: usize = 5;
+    pub fn load_data_inputs(mut self, v: InputCellInfoVec) -> Self {
+        self.load_data_inputs = v;
         self
     }
-    pub fn zero_bits(mut self, v: Byte32) -> Self {
-        self.zero_bits = v;
+    pub fn recover_accounts_context(mut self, v: RecoverAccountsContextOpt) -> Self {
+        self.recover_accounts_context = v;
         self
     }
-    pub fn zero_count(mut self, v: Byte) -> Self {
-        self.zero_count = v;
+    pub fn cell_dep(mut self, v: CellDep) -> Self {
+        self.cell_dep = v;
+        self
+    }
+    pub fn input(mut self, v: InputCellInfo) -> Self {
+        self.input = v;
+        self
+    }
+    pub fn witness(mut self, v: WitnessArgsOpt) -> Self {
+        self.witness = v;
         self
     }
 }
-impl molecule::prelude::Builder for SMTMergeWithZeroBuilder {
-    type Entity = SMTMergeWithZero;
-    const NAME: &'static str = "SMTMergeWithZeroBuilder";
+impl molecule::prelude::Builder for VerifierBuilder {
+    type Entity = Verifier;
+    const NAME: &'static str = "VerifierBuilder";
     fn expected_length(&self) -> usize {
-        Self::TOTAL_SIZE
+        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
+            + self.load_data_inputs.as_slice().len()
+            + self.recover_accounts_context.as_slice().len()
+            + self.cell_dep.as_slice().len()
+            + self.input.as_slice().len()
+            + self.witness.as_slice().len()
     }
     fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
-        writer.write_all(self.base_node.as_slice())?;
-        writer.write_all(self.zero_bits.as_slice())?;
-        writer.write_all(self.zero_count.as_slice())?;
+        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
+        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
+        offsets.push(total_size);
+        total_size += self.load_data_inputs.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.recover_accounts_context.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.cell_dep.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.input.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.witness.as_slice().len();
+        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+        for offset in offsets.into_iter() {
+            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        }
+        writer.write_all(self.load_data_inputs.as_slice())?;
+        writer.write_all(self.recover_accounts_context.as_slice())?;
+        writer.write_all(self.cell_dep.as_slice())?;
+        writer.write_all(self.input.as_slice())?;
+        writer.write_all(self.witness.as_slice())?;
         Ok(())
     }
     fn build(&self) -> Self::Entity {
         let mut inner = Vec::with_capacity(self.expected_length());
         self.write(&mut inner)
             .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
-        SMTMergeWithZero::new_unchecked(inner.into())
+        Verifier::new_unchecked(inner.into())
     }
 }
 #[derive(Clone)]
-pub struct SMTValue(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for SMTValue {
+pub struct ConsumedVerifier(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for ConsumedVerifier {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -3507,43 +6806,76 @@ impl ::core::fmt::LowerHex for SMTValue {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl ::core::fmt::Debug for SMTValue {
+impl ::core::fmt::Debug for ConsumedVerifier {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl ::core::fmt::Display for SMTValue {
+impl ::core::fmt::Display for ConsumedVerifier {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "value", self.value())?;
+        write!(f, "{}: {}", "verifier", self.verifier())?;
+        write!(f, ", {}: {}", "consumed_tx_hash", self.consumed_tx_hash())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
         write!(f, " }}")
     }
 }
-impl ::core::default::Default for SMTValue {
+impl ::core::default::Default for ConsumedVerifier {
     fn default() -> Self {
         let v: Vec<u8> = vec![
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0,
+            68, 0, 0, 0, 12, 0, 0, 0, 64, 0, 0, 0, 52, 0, 0, 0, 28, 0, 0, 0, 32, 0, 0, 0, 36, 0, 0,
+            0, 40, 0, 0, 0, 44, 0, 0, 0, 48, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 4, 0, 0, 0, 4, 0, 0, 0, 4, 0, 0, 0,
         ];
-        SMTValue::new_unchecked(v.into())
+        ConsumedVerifier::new_unchecked(v.into())
     }
 }
-impl SMTValue {
-    pub const TOTAL_SIZE: usize = 32;
-    pub const FIELD_SIZES: [usize; 1] = [32];
-    pub const FIELD_COUNT: usize = 1;
-    pub fn value(&self) -> Byte32 {
-        Byte32::new_unchecked(self.0.slice(0..32))
+impl ConsumedVerifier {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
     }
-    pub fn as_reader<'r>(&'r self) -> SMTValueReader<'r> {
-        SMTValueReader::new_unchecked(self.as_slice())
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn verifier(&self) -> Verifier {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        Verifier::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn consumed_tx_hash(&self) -> Byte32Opt {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[12..]) as usize;
+            Byte32Opt::new_unchecked(self.0.slice(start..end))
+        } else {
+            Byte32Opt::new_unchecked(self.0.slice(start..))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> ConsumedVerifierReader<'r> {
+        ConsumedVerifierReader::new_unchecked(self.as_slice())
     }
 }
-impl molecule::prelude::Entity for SMTValue {
-    type Builder = SMTValueBuilder;
-    const NAME: &'static str = "SMTValue";
+impl molecule::prelude::Entity for ConsumedVerifier {
+    type Builder = ConsumedVerifierBuilder;
+    const NAME: &'static str = "ConsumedVerifier";
     fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        SMTValue(data)
+        ConsumedVerifier(data)
     }
     fn as_bytes(&self) -> molecule::bytes::Bytes {
         self.0.clone()
@@ -3552,21 +6884,23 @@ impl molecule::prelude::Entity for SMTValue {
         &self.0[..]
     }
     fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        SMTValueReader::from_slice(slice).map(|reader| reader.to_entity())
+        ConsumedVerifierReader::from_slice(slice).map(|reader| reader.to_entity())
     }
     fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        SMTValueReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+        ConsumedVerifierReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
     }
     fn new_builder() -> Self::Builder {
         ::core::default::Default::default()
     }
     fn as_builder(self) -> Self::Builder {
-        Self::new_builder().value(self.value())
+        Self::new_builder()
+            .verifier(self.verifier())
+            .consumed_tx_hash(self.consumed_tx_hash())
     }
 }
 #[derive(Clone, Copy)]
-pub struct SMTValueReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for SMTValueReader<'r> {
+pub struct ConsumedVerifierReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for ConsumedVerifierReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -3575,74 +6909,155 @@ impl<'r> ::core::fmt::LowerHex for SMTValueReader<'r> {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Debug for SMTValueReader<'r> {
+impl<'r> ::core::fmt::Debug for ConsumedVerifierReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for SMTValueReader<'r> {
+impl<'r> ::core::fmt::Display for ConsumedVerifierReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "value", self.value())?;
+        write!(f, "{}: {}", "verifier", self.verifier())?;
+        write!(f, ", {}: {}", "consumed_tx_hash", self.consumed_tx_hash())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
         write!(f, " }}")
     }
 }
-impl<'r> SMTValueReader<'r> {
-    pub const TOTAL_SIZE: usize = 32;
-    pub const FIELD_SIZES: [usize; 1] = [32];
-    pub const FIELD_COUNT: usize = 1;
-    pub fn value(&self) -> Byte32Reader<'r> {
-        Byte32Reader::new_unchecked(&self.as_slice()[0..32])
+impl<'r> ConsumedVerifierReader<'r> {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn verifier(&self) -> VerifierReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        VerifierReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn consumed_tx_hash(&self) -> Byte32OptReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[12..]) as usize;
+            Byte32OptReader::new_unchecked(&self.as_slice()[start..end])
+        } else {
+            Byte32OptReader::new_unchecked(&self.as_slice()[start..])
+        }
     }
 }
-impl<'r> molecule::prelude::Reader<'r> for SMTValueReader<'r> {
-    type Entity = SMTValue;
-    const NAME: &'static str = "SMTValueReader";
+impl<'r> molecule::prelude::Reader<'r> for ConsumedVerifierReader<'r> {
+    type Entity = ConsumedVerifier;
+    const NAME: &'static str = "ConsumedVerifierReader";
     fn to_entity(&self) -> Self::Entity {
         Self::Entity::new_unchecked(self.as_slice().to_owned().into())
     }
     fn new_unchecked(slice: &'r [u8]) -> Self {
-        SMTValueReader(slice)
+        ConsumedVerifierReader(slice)
     }
     fn as_slice(&self) -> &'r [u8] {
         self.0
     }
-    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
         use molecule::verification_error as ve;
         let slice_len = slice.len();
-        if slice_len != Self::TOTAL_SIZE {
-            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len == molecule::NUMBER_SIZE && Self::FIELD_COUNT == 0 {
+            return Ok(());
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
+        if field_count < Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        } else if !compatible && field_count > Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        };
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
         }
+        VerifierReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        Byte32OptReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
         Ok(())
     }
 }
 #[derive(Debug, Default)]
-pub struct SMTValueBuilder {
-    pub(crate) value: Byte32,
+pub struct ConsumedVerifierBuilder {
+    pub(crate) verifier: Verifier,
+    pub(crate) consumed_tx_hash: Byte32Opt,
 }
-impl SMTValueBuilder {
-    pub const TOTAL_SIZE: usize = 32;
-    pub const FIELD_SIZES: [usize; 1] = [32];
-    pub const FIELD_COUNT: usize = 1;
-    pub fn value(mut self, v: Byte32) -> Self {
-        self.value = v;
+impl ConsumedVerifierBuilder {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn verifier(mut self, v: Verifier) -> Self {
+        self.verifier = v;
+        self
+    }
+    pub fn consumed_tx_hash(mut self, v: Byte32Opt) -> Self {
+        self.consumed_tx_hash = v;
         self
     }
 }
-impl molecule::prelude::Builder for SMTValueBuilder {
-    type Entity = SMTValue;
-    const NAME: &'static str = "SMTValueBuilder";
+impl molecule::prelude::Builder for ConsumedVerifierBuilder {
+    type Entity = ConsumedVerifier;
+    const NAME: &'static str = "ConsumedVerifierBuilder";
     fn expected_length(&self) -> usize {
-        Self::TOTAL_SIZE
+        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
+            + self.verifier.as_slice().len()
+            + self.consumed_tx_hash.as_slice().len()
     }
     fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
-        writer.write_all(self.value.as_slice())?;
+        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
+        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
+        offsets.push(total_size);
+        total_size += self.verifier.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.consumed_tx_hash.as_slice().len();
+        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+        for offset in offsets.into_iter() {
+            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        }
+        writer.write_all(self.verifier.as_slice())?;
+        writer.write_all(self.consumed_tx_hash.as_slice())?;
         Ok(())
     }
     fn build(&self) -> Self::Entity {
         let mut inner = Vec::with_capacity(self.expected_length());
         self.write(&mut inner)
             .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
-        SMTValue::new_unchecked(inner.into())
+        ConsumedVerifier::new_unchecked(inner.into())
     }
 }
@@ -1,9 +1,10 @@
 use crate::packed::{
-    AccountMerkleState, Byte32, GlobalState, GlobalStateV0, TransactionKey, TxReceipt,
+    AccountMerkleState, Byte32, GlobalState, GlobalStateV0, TransactionKey, TxReceipt, TxReceiptV0,
 };
 use crate::prelude::*;
 use ckb_types::error::VerificationError;
 use sparse_merkle_tree::H256;
+use std::collections::BTreeMap;
 
 use super::RunResult;
 
@@ -35,10 +36,32 @@ impl TxReceipt {
                     .pack(),
             )
             .logs(run_result.logs.pack())
+            .fee_payments(run_result.fee_payments.pack())
             .build()
     }
 }
 
+pub fn tx_receipt_from_slice(slice: &[u8]) -> Result<TxReceipt, VerificationError> {
+    match TxReceipt::from_slice(slice) {
+        Ok(receipt) => Ok(receipt),
+        Err(_) => TxReceiptV0::from_slice(slice).map(Into::into),
+    }
+}
+
+/// Sum up fees paid in tx receipts, sudt id -> amount
+pub fn collect_fees<'a, I: IntoIterator<Item = &'a TxReceipt>>(receipts: I) -> BTreeMap<u32, u128> {
+    let mut collected_fees = BTreeMap::new();
+    for receipt in receipts {
+        for fee_payment in receipt.fee_payments() {
+            let sudt_id: u32 = fee_payment.sudt_id().unpack();
+            let amount: u128 = fee_payment.amount().unpack();
+            let collected = collected_fees.entry(sudt_id).or_insert(0u128);
+            *collected = collected.saturating_add(amount);
+        }
+    }
+    collected_fees
+}
+
 pub fn global_state_from_slice(slice: &[u8]) -> Result<GlobalState, VerificationError> {
     match GlobalState::from_slice(slice) {
         Ok(state) => Ok(state),
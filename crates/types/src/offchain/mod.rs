@@ -4,7 +4,7 @@ mod rollup_context;
 mod rpc;
 mod run_result;
 
-pub use extension::global_state_from_slice;
+pub use extension::{collect_fees, global_state_from_slice, tx_receipt_from_slice};
 pub use pool::*;
 pub use rollup_context::*;
 pub use rpc::*;
@@ -1,4 +1,5 @@
 use sparse_merkle_tree::H256;
+use std::collections::BTreeMap;
 
 use crate::packed::{AccountMerkleState, L2Block, L2Transaction, WithdrawalRequest};
 
@@ -18,4 +19,6 @@ pub struct BlockParam {
     pub post_merkle_state: AccountMerkleState,
     pub kv_state: Vec<(H256, H256)>,
     pub kv_state_proof: Vec<u8>,
+    /// Fees paid to block producer by txs, sudt id -> amount
+    pub collected_fees: BTreeMap<u32, u128>,
 }
@@ -64,4 +64,6 @@ pub enum TxStatus {
 pub struct DepositInfo {
     pub request: DepositRequest,
     pub cell: CellInfo,
+    /// L1 block number of the deposit cell
+    pub block_number: u64,
 }
@@ -1,4 +1,4 @@
-use crate::packed::{CellOutput, LogItem, Script};
+use crate::packed::{CellOutput, FeePayment, LogItem, Script};
 use crate::prelude::*;
 use sparse_merkle_tree::H256;
 use std::collections::{HashMap, HashSet};
@@ -24,6 +24,8 @@ pub struct RunResult {
     pub read_data: HashMap<H256, Vec<u8>>,
     // log data
     pub logs: Vec<LogItem>,
+    // fee payments recorded by SYS_PAY_FEE
+    pub fee_payments: Vec<FeePayment>,
     // used cycles
     pub used_cycles: u64,
     pub exit_code: i8,
@@ -1,19 +1,136 @@
 #![allow(clippy::mutable_key_type)]
 
 use crate::transaction_skeleton::TransactionSkeleton;
-use anyhow::Result;
-use gw_rpc_client::indexer_client::CKBIndexerClient;
+use anyhow::{anyhow, Result};
+use gw_config::FeeConfig;
+use gw_rpc_client::{indexer_client::CKBIndexerClient, rpc_client::RPCClient};
 use gw_types::{
     offchain::InputCellInfo,
     packed::{CellInput, CellOutput, Script},
     prelude::*,
 };
 
+use std::{collections::BTreeMap, time::Duration};
+
+/// Min fee rate of CKB, shannons per KB
+pub const MIN_FEE_RATE: u64 = 1000;
+// Economic state (include txs fee) of a block is available after finalized
+const BLOCK_FINALIZATION_DELAY: u64 = 11;
+// Estimation is in the block producing path, don't wait for slow L1 RPCs
+const ESTIMATE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Median of fee rates bounded by [min, max], min if there are no fee rates
+fn median_fee_rate(mut fee_rates: Vec<u64>, min_fee_rate: u64, max_fee_rate: u64) -> u64 {
+    fee_rates.sort_unstable();
+    let median = fee_rates.get(fee_rates.len() / 2).cloned().unwrap_or(0);
+    median.max(min_fee_rate).min(max_fee_rate)
+}
+
 /// Calculate tx fee
-/// TODO accept fee rate args
-fn calculate_required_tx_fee(tx_size: usize) -> u64 {
-    // tx_size * KB / MIN_FEE_RATE
-    tx_size as u64
+pub fn calculate_required_tx_fee(tx_size: usize, fee_rate: u64) -> u64 {
+    // tx_size * fee_rate / KB, round up
+    (tx_size as u64 * fee_rate + 999) / 1000
+}
+
+/// Estimate fee rate from txs fee of recent L1 blocks
+pub struct FeeRateEstimator {
+    config: FeeConfig,
+    // (L1 tip number, fee rate)
+    last_estimated: Option<(u64, u64)>,
+    // fee rates of L1 blocks in the estimation window, by block number,
+    // so only new blocks are fetched when the tip moves
+    block_fee_rates: BTreeMap<u64, Option<u64>>,
+}
+
+impl FeeRateEstimator {
+    pub fn new(config: FeeConfig) -> Self {
+        FeeRateEstimator {
+            config,
+            last_estimated: None,
+            block_fee_rates: BTreeMap::new(),
+        }
+    }
+
+    fn min_fee_rate(&self) -> u64 {
+        self.config.fee_rate.max(MIN_FEE_RATE)
+    }
+
+    fn max_fee_rate(&self) -> u64 {
+        self.config.max_fee_rate.max(self.min_fee_rate())
+    }
+
+    /// Return median fee rate of recent L1 blocks, bounded by fee config.
+    ///
+    /// Missing block fee rates are fetched concurrently, fetched ones are kept
+    /// even if the estimation times out, so a cold cache is warmed up by
+    /// successive calls.
+    pub async fn estimate(&mut self, rpc_client: &RPCClient) -> Result<u64> {
+        if 0 == self.config.estimate_blocks {
+            return Ok(self.min_fee_rate());
+        }
+
+        let tip_number: u64 = rpc_client.get_tip().await?.number().unpack();
+        if let Some((number, fee_rate)) = self.last_estimated {
+            if number == tip_number {
+                return Ok(fee_rate);
+            }
+        }
+
+        let end = tip_number.saturating_sub(BLOCK_FINALIZATION_DELAY);
+        let start = end.saturating_sub(self.config.estimate_blocks) + 1;
+        self.block_fee_rates = self.block_fee_rates.split_off(&start);
+        let tasks: Vec<_> = (start..=end)
+            .filter(|number| !self.block_fee_rates.contains_key(number))
+            .map(|number| {
+                let rpc_client = rpc_client.clone();
+                smol::spawn(async move { (number, rpc_client.get_block_fee_rate(number).await) })
+            })
+            .collect();
+        let block_fee_rates = &mut self.block_fee_rates;
+        let fetch = async {
+            for task in tasks {
+                let (number, fee_rate) = task.await;
+                block_fee_rates.insert(number, fee_rate?);
+            }
+            Ok::<_, anyhow::Error>(())
+        };
+        let timeout = async {
+            smol::Timer::after(ESTIMATE_TIMEOUT).await;
+            Err(anyhow!("fetch l1 block fee rates timeout"))
+        };
+        // unfinished tasks are cancelled on drop
+        smol::future::or(fetch, timeout).await?;
+
+        let fee_rates: Vec<u64> = self.block_fee_rates.values().flatten().cloned().collect();
+        let fee_rate = median_fee_rate(fee_rates, self.min_fee_rate(), self.max_fee_rate());
+        log::debug!(
+            "estimated fee rate {} from {} l1 blocks",
+            fee_rate,
+            self.block_fee_rates.len()
+        );
+
+        self.last_estimated = Some((tip_number, fee_rate));
+        Ok(fee_rate)
+    }
+
+    /// Return last estimated fee rate, or the min fee rate if never estimated
+    pub fn last_estimated(&self) -> u64 {
+        self.last_estimated
+            .map(|(_, fee_rate)| fee_rate)
+            .unwrap_or_else(|| self.min_fee_rate())
+    }
+
+    /// Return bumped fee rate, none if max fee rate is reached
+    pub fn bump(&self, fee_rate: u64) -> Option<u64> {
+        let bumped = fee_rate
+            .saturating_add(self.config.bump_fee_rate.max(1))
+            .min(self.max_fee_rate());
+        if bumped > fee_rate {
+            Some(bumped)
+        } else {
+            None
+        }
+    }
 }
 
 /// Add fee cell to tx skeleton
@@ -21,6 +138,16 @@ pub async fn fill_tx_fee(
     tx_skeleton: &mut TransactionSkeleton,
     client: &CKBIndexerClient,
     lock_script: Script,
+) -> Result<()> {
+    fill_tx_fee_with_rate(tx_skeleton, client, lock_script, MIN_FEE_RATE).await
+}
+
+/// Add fee cell to tx skeleton, fee rate is in shannons per KB
+pub async fn fill_tx_fee_with_rate(
+    tx_skeleton: &mut TransactionSkeleton,
+    client: &CKBIndexerClient,
+    lock_script: Script,
+    fee_rate: u64,
 ) -> Result<()> {
     const CHANGE_CELL_CAPACITY: u64 = 61_00000000;
 
@@ -45,7 +172,7 @@ pub async fn fill_tx_fee(
     // so most of time, paid_fee should already cover tx_fee. The first thing we need to do
     // is try to generate a change output cell.
     let tx_size = estimate_tx_size_with_change(tx_skeleton)?;
-    let tx_fee = calculate_required_tx_fee(tx_size);
+    let tx_fee = calculate_required_tx_fee(tx_size, fee_rate);
     let max_paid_fee = tx_skeleton
         .calculate_fee()?
         .saturating_sub(CHANGE_CELL_CAPACITY);
@@ -88,7 +215,7 @@ pub async fn fill_tx_fee(
             }));
 
         let tx_size = estimate_tx_size_with_change(tx_skeleton)?;
-        let tx_fee = calculate_required_tx_fee(tx_size);
+        let tx_fee = calculate_required_tx_fee(tx_size, fee_rate);
         let max_paid_fee = tx_skeleton
             .calculate_fee()?
             .saturating_sub(CHANGE_CELL_CAPACITY);
@@ -108,3 +235,75 @@ pub async fn fill_tx_fee(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction_skeleton::SealedTransaction;
+    use gw_types::packed::Transaction;
+
+    #[test]
+    fn test_median_fee_rate() {
+        // odd and even number of samples, unsorted
+        assert_eq!(median_fee_rate(vec![3000, 1000, 2000], 1000, 10_000), 2000);
+        assert_eq!(
+            median_fee_rate(vec![4000, 1000, 3000, 2000], 1000, 10_000),
+            3000
+        );
+        // bounded by min and max
+        assert_eq!(median_fee_rate(vec![10, 20, 30], 1000, 10_000), 1000);
+        assert_eq!(median_fee_rate(vec![50_000, 60_000], 1000, 10_000), 10_000);
+        // fallback to min if no l1 block has txs
+        assert_eq!(median_fee_rate(vec![], 1000, 10_000), 1000);
+    }
+
+    #[test]
+    fn test_fee_rate_bounds_and_bump() {
+        let config = FeeConfig {
+            fee_rate: 500,
+            max_fee_rate: 5000,
+            estimate_blocks: 20,
+            bump_timeout_secs: 180,
+            bump_fee_rate: 1500,
+        };
+        let estimator = FeeRateEstimator::new(config);
+        // configured fee rate is lower than the CKB min fee rate
+        assert_eq!(estimator.min_fee_rate(), MIN_FEE_RATE);
+        assert_eq!(estimator.last_estimated(), MIN_FEE_RATE);
+
+        assert_eq!(estimator.bump(1000), Some(2500));
+        assert_eq!(estimator.bump(4000), Some(5000));
+        assert_eq!(estimator.bump(5000), None);
+    }
+
+    #[test]
+    fn test_calculate_required_tx_fee() {
+        assert_eq!(calculate_required_tx_fee(1000, 1000), 1000);
+        // round up
+        assert_eq!(calculate_required_tx_fee(1001, 1000), 1001);
+        assert_eq!(calculate_required_tx_fee(1, 1000), 1);
+        assert_eq!(calculate_required_tx_fee(0, 1000), 0);
+    }
+
+    #[test]
+    fn test_check_fee_rate() {
+        let sealed_tx = |fee| SealedTransaction {
+            transaction: Transaction::default(),
+            fee,
+        };
+        let tx_in_block_size = Transaction::default().as_slice().len() + 4;
+
+        let min_fee = calculate_required_tx_fee(tx_in_block_size, MIN_FEE_RATE);
+        sealed_tx(min_fee)
+            .check_fee_rate(MIN_FEE_RATE)
+            .expect("min fee rate");
+        sealed_tx(min_fee - 1)
+            .check_fee_rate(MIN_FEE_RATE)
+            .unwrap_err();
+
+        // estimated fee rate
+        let fee = calculate_required_tx_fee(tx_in_block_size, 3000);
+        sealed_tx(fee).check_fee_rate(3000).expect("fee rate");
+        sealed_tx(fee - 1).check_fee_rate(3000).unwrap_err();
+    }
+}
@@ -1,4 +1,6 @@
 pub mod fee;
 pub mod genesis_info;
+pub mod signer;
+pub mod sudt;
 pub mod transaction_skeleton;
 pub mod wallet;
@@ -0,0 +1,406 @@
+use aes_ctr::{
+    cipher::{
+        generic_array::GenericArray,
+        stream::{NewStreamCipher, SyncStreamCipher},
+    },
+    Aes128Ctr,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use async_jsonrpc_client::{HttpClient, Output, Params as ClientParams, Transport};
+use async_trait::async_trait;
+use ckb_crypto::secp::Privkey;
+use faster_hex::hex_decode;
+use gw_config::{SignerConfig, WalletConfig};
+use serde::Deserialize;
+use serde_json::json;
+use sha3::{Digest, Keccak256};
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    os::unix::net::UnixStream,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+/// Sign 32 bytes message with a secp256k1 key, returns recoverable signature
+#[async_trait]
+pub trait Signer: Send + Sync {
+    async fn sign_message(&self, lock_hash: [u8; 32], msg: [u8; 32]) -> Result<[u8; 65]>;
+}
+
+/// Build the signer once, keystore decryption is expensive
+pub fn build_signer(config: &WalletConfig) -> Result<Arc<dyn Signer>> {
+    let signer: Arc<dyn Signer> = match config.signer {
+        None => Arc::new(PrivkeySigner::from_file(&config.privkey_path)?),
+        Some(SignerConfig::Keystore {
+            ref path,
+            ref passphrase_env,
+        }) => {
+            let passphrase = std::env::var(passphrase_env)
+                .with_context(|| format!("read keystore passphrase from ${}", passphrase_env))?;
+            let signer = PrivkeySigner::from_keystore(path, passphrase.as_bytes())?;
+            Arc::new(signer)
+        }
+        Some(SignerConfig::Unix {
+            ref path,
+            timeout_secs,
+        }) => Arc::new(UnixSocketSigner::new(
+            path.to_owned(),
+            Duration::from_secs(timeout_secs),
+        )),
+        Some(SignerConfig::Http {
+            ref url,
+            timeout_secs,
+        }) => Arc::new(HttpSigner::new(url, Duration::from_secs(timeout_secs))?),
+    };
+    Ok(signer)
+}
+
+/// Sign with a private key hold in memory
+pub struct PrivkeySigner {
+    privkey: Privkey,
+}
+
+impl PrivkeySigner {
+    pub fn new(privkey: Privkey) -> Self {
+        PrivkeySigner { privkey }
+    }
+
+    /// Read hex encoded private key file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = std::fs::read_to_string(path).with_context(|| "read wallet privkey")?;
+        let content = content.trim_start_matches("0x").trim();
+        if content.as_bytes().len() != 64 {
+            bail!("invalid privkey length");
+        }
+        let mut decoded = [0u8; 32];
+        hex_decode(content.as_bytes(), &mut decoded)?;
+        Ok(Self::new(Privkey::from_slice(&decoded)))
+    }
+
+    /// Decrypt private key from keystore file
+    pub fn from_keystore<P: AsRef<Path>>(path: P, passphrase: &[u8]) -> Result<Self> {
+        let content = std::fs::read(path).with_context(|| "read wallet keystore")?;
+        let keystore: Keystore = serde_json::from_slice(&content)?;
+        let secret = keystore.decrypt(passphrase)?;
+        if secret.len() != 32 {
+            bail!("invalid keystore privkey length");
+        }
+        Ok(Self::new(Privkey::from_slice(&secret)))
+    }
+}
+
+impl PrivkeySigner {
+    pub fn sign(&self, msg: [u8; 32]) -> Result<[u8; 65]> {
+        let signature = self
+            .privkey
+            .sign_recoverable(&msg.into())
+            .map_err(|err| anyhow!("signing error: {}", err))?;
+        let mut inner = [0u8; 65];
+        inner.copy_from_slice(&signature.serialize());
+        Ok(inner)
+    }
+}
+
+#[async_trait]
+impl Signer for PrivkeySigner {
+    async fn sign_message(&self, _lock_hash: [u8; 32], msg: [u8; 32]) -> Result<[u8; 65]> {
+        self.sign(msg)
+    }
+}
+
+// Web3 Secret Storage Definition (version 3), only scrypt kdf is supported
+#[derive(Deserialize)]
+struct Keystore {
+    crypto: KeystoreCrypto,
+}
+
+#[derive(Deserialize)]
+struct KeystoreCrypto {
+    cipher: String,
+    cipherparams: CipherParams,
+    ciphertext: String,
+    kdf: String,
+    kdfparams: ScryptParams,
+    mac: String,
+}
+
+#[derive(Deserialize)]
+struct CipherParams {
+    iv: String,
+}
+
+#[derive(Deserialize)]
+struct ScryptParams {
+    dklen: usize,
+    n: u64,
+    r: u32,
+    p: u32,
+    salt: String,
+}
+
+impl Keystore {
+    fn decrypt(&self, passphrase: &[u8]) -> Result<Vec<u8>> {
+        let crypto = &self.crypto;
+        if crypto.cipher != "aes-128-ctr" {
+            bail!("unsupported keystore cipher {}", crypto.cipher);
+        }
+        if crypto.kdf != "scrypt" {
+            bail!("unsupported keystore kdf {}", crypto.kdf);
+        }
+
+        let kdfparams = &crypto.kdfparams;
+        if kdfparams.dklen < 32 || !kdfparams.n.is_power_of_two() {
+            bail!("invalid keystore kdf params");
+        }
+        let log_n = kdfparams.n.trailing_zeros() as u8;
+        let params = scrypt::ScryptParams::new(log_n, kdfparams.r, kdfparams.p)
+            .map_err(|err| anyhow!("invalid keystore kdf params: {:?}", err))?;
+        let mut derived_key = vec![0u8; kdfparams.dklen];
+        scrypt::scrypt(
+            passphrase,
+            &decode_hex(&kdfparams.salt)?,
+            &params,
+            &mut derived_key,
+        )
+        .map_err(|err| anyhow!("derive keystore key: {:?}", err))?;
+
+        let mut ciphertext = decode_hex(&crypto.ciphertext)?;
+        let mac = Keccak256::digest(&[&derived_key[16..32], &ciphertext].concat());
+        if mac.as_slice() != decode_hex(&crypto.mac)?.as_slice() {
+            bail!("incorrect keystore passphrase");
+        }
+
+        let iv = decode_hex(&crypto.cipherparams.iv)?;
+        if iv.len() != 16 {
+            bail!("invalid keystore iv length");
+        }
+        let mut cipher = Aes128Ctr::new(
+            GenericArray::from_slice(&derived_key[..16]),
+            GenericArray::from_slice(&iv),
+        );
+        cipher.apply_keystream(&mut ciphertext);
+
+        Ok(ciphertext)
+    }
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>> {
+    Ok(hex::decode(s.trim_start_matches("0x"))?)
+}
+
+fn sign_message_request(lock_hash: [u8; 32], msg: [u8; 32]) -> Vec<serde_json::Value> {
+    vec![
+        json!(format!("0x{}", hex::encode(lock_hash))),
+        json!(format!("0x{}", hex::encode(msg))),
+    ]
+}
+
+fn parse_signature(signature: &str) -> Result<[u8; 65]> {
+    let decoded = decode_hex(signature)?;
+    if decoded.len() != 65 {
+        bail!("invalid signature length {}", decoded.len());
+    }
+    let mut signature = [0u8; 65];
+    signature.copy_from_slice(&decoded);
+    Ok(signature)
+}
+
+fn parse_output(output: Output) -> Result<[u8; 65]> {
+    match output {
+        Output::Success(success) => {
+            let signature: String = serde_json::from_value(success.result)?;
+            parse_signature(&signature)
+        }
+        Output::Failure(failure) => Err(anyhow!("signer error: {}", failure.error)),
+    }
+}
+
+/// External signer process listening on a Unix domain socket.
+///
+/// Each request is a line of JSONRPC `sign_message` call with params `[lock_hash, message]`,
+/// the signer replies a line of JSONRPC response with the hex encoded 65 bytes signature.
+#[derive(Clone)]
+pub struct UnixSocketSigner {
+    path: PathBuf,
+    timeout: Duration,
+}
+
+impl UnixSocketSigner {
+    pub fn new(path: PathBuf, timeout: Duration) -> Self {
+        UnixSocketSigner { path, timeout }
+    }
+
+    fn request(&self, lock_hash: [u8; 32], msg: [u8; 32]) -> Result<[u8; 65]> {
+        let mut stream = UnixStream::connect(&self.path)
+            .with_context(|| format!("connect signer {:?}", self.path))?;
+        // a hung signer must not stall block production
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "method": "sign_message",
+            "params": sign_message_request(lock_hash, msg),
+        });
+        stream.write_all(format!("{}\n", request).as_bytes())?;
+
+        let mut line = String::new();
+        BufReader::new(stream)
+            .read_line(&mut line)
+            .with_context(|| format!("read signer {:?}", self.path))?;
+        parse_output(serde_json::from_str(&line)?)
+    }
+}
+
+#[async_trait]
+impl Signer for UnixSocketSigner {
+    async fn sign_message(&self, lock_hash: [u8; 32], msg: [u8; 32]) -> Result<[u8; 65]> {
+        // blocking socket io with timeouts, run it off the async executor
+        let signer = self.clone();
+        smol::unblock(move || signer.request(lock_hash, msg)).await
+    }
+}
+
+/// External signer process serving the same `sign_message` JSONRPC over HTTP
+pub struct HttpSigner {
+    client: HttpClient,
+    timeout: Duration,
+}
+
+impl HttpSigner {
+    pub fn new(url: &str, timeout: Duration) -> Result<Self> {
+        let client = HttpClient::new(url)?;
+        Ok(HttpSigner { client, timeout })
+    }
+}
+
+#[async_trait]
+impl Signer for HttpSigner {
+    async fn sign_message(&self, lock_hash: [u8; 32], msg: [u8; 32]) -> Result<[u8; 65]> {
+        let params = ClientParams::Array(sign_message_request(lock_hash, msg));
+        let request = async {
+            let output = self.client.request("sign_message", Some(params)).await?;
+            parse_output(output)
+        };
+        let timeout = async {
+            smol::Timer::after(self.timeout).await;
+            Err(anyhow!("signer timeout after {:?}", self.timeout))
+        };
+        smol::future::or(request, timeout).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixListener;
+
+    const PRIVKEY: [u8; 32] = [7u8; 32];
+
+    #[test]
+    fn test_keystore_signer() {
+        let passphrase = b"godwoken";
+        let salt = [1u8; 32];
+        let iv = [2u8; 16];
+        let params = scrypt::ScryptParams::new(10, 8, 1).unwrap();
+        let mut derived_key = [0u8; 32];
+        scrypt::scrypt(passphrase, &salt, &params, &mut derived_key).unwrap();
+
+        // aes ctr encryption is the same as decryption
+        let mut ciphertext = PRIVKEY.to_vec();
+        let mut cipher = Aes128Ctr::new(
+            GenericArray::from_slice(&derived_key[..16]),
+            GenericArray::from_slice(&iv),
+        );
+        cipher.apply_keystream(&mut ciphertext);
+        let mac = Keccak256::digest(&[&derived_key[16..32], &ciphertext].concat());
+
+        let keystore = json!({
+            "version": 3,
+            "crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": { "iv": hex::encode(&iv) },
+                "ciphertext": hex::encode(&ciphertext),
+                "kdf": "scrypt",
+                "kdfparams": { "dklen": 32, "n": 1024, "r": 8, "p": 1, "salt": hex::encode(&salt) },
+                "mac": hex::encode(mac.as_slice()),
+            }
+        });
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keystore.json");
+        std::fs::write(&path, keystore.to_string()).unwrap();
+
+        let expected = PrivkeySigner::new(Privkey::from_slice(&PRIVKEY));
+        let signer = PrivkeySigner::from_keystore(&path, passphrase).unwrap();
+        let msg = [3u8; 32];
+        assert_eq!(
+            signer.sign(msg).unwrap().to_vec(),
+            expected.sign(msg).unwrap().to_vec()
+        );
+
+        assert!(PrivkeySigner::from_keystore(&path, b"wrong").is_err());
+    }
+
+    #[test]
+    fn test_unix_socket_signer() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signer.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+
+        // stub external signer
+        let stub = std::thread::spawn(move || {
+            let signer = PrivkeySigner::new(Privkey::from_slice(&PRIVKEY));
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+
+            let request: serde_json::Value = serde_json::from_str(&line).unwrap();
+            assert_eq!(request["method"], "sign_message");
+            let mut msg = [0u8; 32];
+            let msg_hex = request["params"][1].as_str().unwrap();
+            hex_decode(msg_hex.trim_start_matches("0x").as_bytes(), &mut msg).unwrap();
+
+            let signature = signer.sign(msg).unwrap();
+            let response = json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": format!("0x{}", hex::encode(&signature)),
+            });
+            let mut stream = stream;
+            stream
+                .write_all(format!("{}\n", response).as_bytes())
+                .unwrap();
+        });
+
+        let expected = PrivkeySigner::new(Privkey::from_slice(&PRIVKEY));
+        let signer = UnixSocketSigner::new(path, Duration::from_secs(5));
+        let msg = [5u8; 32];
+        let signature = smol::block_on(signer.sign_message([0u8; 32], msg)).unwrap();
+        assert_eq!(signature.to_vec(), expected.sign(msg).unwrap().to_vec());
+        stub.join().unwrap();
+    }
+
+    #[test]
+    fn test_unix_socket_signer_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signer.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+
+        // stub external signer which never replies
+        let stub = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            std::thread::sleep(Duration::from_secs(2));
+            drop(stream);
+        });
+
+        let signer = UnixSocketSigner::new(path, Duration::from_millis(100));
+        let start = std::time::Instant::now();
+        let result = smol::block_on(signer.sign_message([0u8; 32], [5u8; 32]));
+        assert!(result.is_err());
+        assert!(start.elapsed() < Duration::from_secs(2));
+        stub.join().unwrap();
+    }
+}
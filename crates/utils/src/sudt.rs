@@ -0,0 +1,166 @@
+#![allow(clippy::mutable_key_type)]
+
+use anyhow::{anyhow, Result};
+use gw_config::SUDTBondConfig;
+use gw_rpc_client::rpc_client::RPCClient;
+use gw_types::{
+    bytes::Bytes,
+    core::ScriptHashType,
+    offchain::{CellInfo, InputCellInfo, RollupContext},
+    packed::{CellInput, CellOutput, OutPoint, Script},
+    prelude::*,
+};
+use std::collections::HashSet;
+
+/// sUDT bonded in stake and challenge cells
+#[derive(Clone, Debug)]
+pub struct SUDTBond {
+    pub sudt_type: Script,
+    pub stake_amount: u128,
+    pub challenge_amount: u128,
+}
+
+impl SUDTBond {
+    pub fn from_config(rollup_context: &RollupContext, config: &SUDTBondConfig) -> Self {
+        let sudt_type = Script::new_builder()
+            .code_hash(rollup_context.rollup_config.l1_sudt_script_type_hash())
+            .hash_type(ScriptHashType::Type.into())
+            .args(config.sudt_args.clone().into_bytes().pack())
+            .build();
+
+        SUDTBond {
+            sudt_type,
+            stake_amount: config.stake_amount.value(),
+            challenge_amount: config.challenge_amount.value(),
+        }
+    }
+}
+
+pub struct CollectedSUDT {
+    pub inputs: Vec<InputCellInfo>,
+    pub change: Option<(CellOutput, Bytes)>,
+}
+
+/// Return sudt type script and amount of cell, none if cell isn't a sudt cell
+pub fn parse_sudt(cell: &CellInfo) -> Option<(Script, u128)> {
+    let sudt_type = cell.output.type_().to_opt()?;
+    if cell.data.len() < 16 {
+        return None;
+    }
+
+    let mut buf = [0u8; 16];
+    buf.copy_from_slice(&cell.data[..16]);
+    Some((sudt_type, u128::from_le_bytes(buf)))
+}
+
+/// Attach sudt to output, capacity is raised to occupied capacity if it isn't enough
+pub fn build_sudt_cell(
+    output: CellOutput,
+    sudt_type: Script,
+    amount: u128,
+) -> Result<(CellOutput, Bytes)> {
+    let output = output.as_builder().type_(Some(sudt_type).pack()).build();
+    let data = Bytes::from(amount.to_le_bytes().to_vec());
+
+    let occupied_capacity = output
+        .occupied_capacity(data.len())
+        .map_err(|err| anyhow!("sudt cell capacity overflow {}", err))?;
+    let capacity: u64 = output.capacity().unpack();
+    let output = output
+        .as_builder()
+        .capacity(capacity.max(occupied_capacity).pack())
+        .build();
+
+    Ok((output, data))
+}
+
+/// Collect owner's sudt cells to pay amount, the rest is returned to owner in change cell
+pub async fn collect_sudt(
+    rpc_client: &RPCClient,
+    owner_lock: Script,
+    sudt_type: Script,
+    amount: u128,
+    taken_outpoints: &HashSet<OutPoint>,
+) -> Result<CollectedSUDT> {
+    if 0 == amount {
+        return Ok(CollectedSUDT {
+            inputs: vec![],
+            change: None,
+        });
+    }
+
+    let cells = rpc_client
+        .query_sudt_cells(
+            owner_lock.clone(),
+            sudt_type.clone(),
+            amount,
+            taken_outpoints,
+        )
+        .await?;
+    let collected_amount: u128 = {
+        let amounts = cells.iter().filter_map(parse_sudt);
+        amounts.map(|(_, amount)| amount).sum()
+    };
+
+    let change_amount = collected_amount.saturating_sub(amount);
+    let change = if change_amount > 0 {
+        let change = CellOutput::new_builder().lock(owner_lock).build();
+        Some(build_sudt_cell(change, sudt_type, change_amount)?)
+    } else {
+        None
+    };
+
+    let inputs = cells.into_iter().map(|cell| InputCellInfo {
+        input: CellInput::new_builder()
+            .previous_output(cell.out_point.clone())
+            .build(),
+        cell,
+    });
+
+    Ok(CollectedSUDT {
+        inputs: inputs.collect(),
+        change,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_sudt_cell, parse_sudt};
+    use gw_types::{
+        offchain::CellInfo,
+        packed::{CellOutput, Script},
+        prelude::*,
+    };
+
+    #[test]
+    fn test_build_sudt_cell() {
+        let lock = Script::new_builder().args(vec![1u8; 20].pack()).build();
+        let sudt_type = Script::new_builder().args(vec![2u8; 32].pack()).build();
+        let output = CellOutput::new_builder().lock(lock).build();
+
+        let (output, data) = build_sudt_cell(output, sudt_type.clone(), 1000).unwrap();
+        let capacity: u64 = output.capacity().unpack();
+        assert_eq!(capacity, output.occupied_capacity(16).unwrap());
+
+        let cell = CellInfo {
+            output,
+            data,
+            ..Default::default()
+        };
+        let (parsed_type, amount) = parse_sudt(&cell).unwrap();
+        assert_eq!(parsed_type.as_slice(), sudt_type.as_slice());
+        assert_eq!(amount, 1000);
+    }
+
+    #[test]
+    fn test_build_sudt_cell_keep_enough_capacity() {
+        let sudt_type = Script::new_builder().args(vec![2u8; 32].pack()).build();
+        let output = CellOutput::new_builder()
+            .capacity(1_000_000_000_000u64.pack())
+            .build();
+
+        let (output, _data) = build_sudt_cell(output, sudt_type, 1).unwrap();
+        let capacity: u64 = output.capacity().unpack();
+        assert_eq!(capacity, 1_000_000_000_000u64);
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(clippy::mutable_key_type)]
 
+use crate::fee::calculate_required_tx_fee;
 use anyhow::{anyhow, Result};
 use gw_poa::GeneratedPoA;
 use gw_types::{
@@ -22,10 +23,10 @@ pub struct SealedTransaction {
 }
 
 impl SealedTransaction {
-    pub fn check_fee_rate(&self) -> Result<()> {
+    /// Check tx fee against the fee rate in shannons per KB
+    pub fn check_fee_rate(&self, fee_rate: u64) -> Result<()> {
         let tx_in_block_size = self.transaction.as_slice().len() + 4;
-        // tx_in_block_size * 1000(min fee rate per KB) / 1000(KB)
-        let expected_fee = tx_in_block_size as u64;
+        let expected_fee = calculate_required_tx_fee(tx_in_block_size, fee_rate);
 
         if self.fee < expected_fee {
             return Err(anyhow!(
@@ -38,7 +39,7 @@ impl SealedTransaction {
     }
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct TransactionSkeleton {
     inputs: Vec<InputCellInfo>,
     cell_deps: Vec<CellDep>,
@@ -1,6 +1,5 @@
-use anyhow::{anyhow, Context, Result};
+use anyhow::Result;
 use ckb_crypto::secp::Privkey;
-use faster_hex::hex_decode;
 use gw_common::blake2b::new_blake2b;
 use gw_config::WalletConfig;
 use gw_types::{
@@ -8,31 +7,34 @@ use gw_types::{
     packed::{Script, Transaction},
     prelude::{Entity, Unpack},
 };
+use std::sync::Arc;
 
-use crate::transaction_skeleton::TransactionSkeleton;
+use crate::{
+    fee::MIN_FEE_RATE,
+    signer::{build_signer, PrivkeySigner, Signer},
+    transaction_skeleton::{SignatureEntry, TransactionSkeleton},
+};
 
+/// Wallet is cheap to clone, clones share the same signer
+#[derive(Clone)]
 pub struct Wallet {
-    privkey: Privkey,
+    signer: Arc<dyn Signer>,
     lock: Script,
 }
 
 impl Wallet {
     pub fn new(privkey: Privkey, lock: Script) -> Self {
-        Wallet { privkey, lock }
+        Self::with_signer(Arc::new(PrivkeySigner::new(privkey)), lock)
+    }
+
+    pub fn with_signer(signer: Arc<dyn Signer>, lock: Script) -> Self {
+        Wallet { signer, lock }
     }
 
     pub fn from_config(config: &WalletConfig) -> Result<Self> {
         let lock = config.lock.clone().into();
-        let privkey = {
-            let content = std::fs::read_to_string(&config.privkey_path)
-                .with_context(|| "read wallet privkey")?;
-            let content = content.trim_start_matches("0x").trim();
-            assert_eq!(content.as_bytes().len(), 64, "invalid privkey length");
-            let mut decoded = [0u8; 32];
-            hex_decode(content.as_bytes(), &mut decoded)?;
-            Privkey::from_slice(&decoded)
-        };
-        let wallet = Self::new(privkey, lock);
+        let signer = build_signer(config)?;
+        let wallet = Self::with_signer(signer, lock);
         Ok(wallet)
     }
 
@@ -41,17 +43,54 @@ impl Wallet {
     }
 
     // sign message
-    pub fn sign_message(&self, msg: [u8; 32]) -> Result<[u8; 65]> {
-        let signature = self
-            .privkey
-            .sign_recoverable(&msg.into())
-            .map_err(|err| anyhow!("signing error: {}", err))?;
-        let mut inner = [0u8; 65];
-        inner.copy_from_slice(&signature.serialize());
-        Ok(inner)
+    pub async fn sign_message(&self, msg: [u8; 32]) -> Result<[u8; 65]> {
+        self.signer.sign_message(self.lock.hash(), msg).await
+    }
+
+    pub async fn sign_tx_skeleton(&self, tx_skeleton: TransactionSkeleton) -> Result<Transaction> {
+        self.sign_tx_skeleton_with_fee_rate(tx_skeleton, MIN_FEE_RATE)
+            .await
+    }
+
+    /// Sign tx skeleton, the tx fee must meet the fee rate in shannons per KB
+    pub async fn sign_tx_skeleton_with_fee_rate(
+        &self,
+        tx_skeleton: TransactionSkeleton,
+        fee_rate: u64,
+    ) -> Result<Transaction> {
+        let (signature_entries, messages) = Self::signing_messages(&tx_skeleton)?;
+        let mut signatures = Vec::with_capacity(messages.len());
+        for message in messages {
+            signatures.push(self.sign_message(message).await?);
+        }
+        Self::seal(tx_skeleton, &signature_entries, signatures, fee_rate)
     }
 
-    pub fn sign_tx_skeleton(&self, tx_skeleton: TransactionSkeleton) -> Result<Transaction> {
+    /// Sign tx skeleton in sync context, e.g. offchain mock txs.
+    /// Async callers should use `sign_tx_skeleton`.
+    pub fn sign_tx_skeleton_blocking(
+        &self,
+        tx_skeleton: TransactionSkeleton,
+    ) -> Result<Transaction> {
+        smol::block_on(self.sign_tx_skeleton(tx_skeleton))
+    }
+
+    fn seal(
+        tx_skeleton: TransactionSkeleton,
+        signature_entries: &[SignatureEntry],
+        signatures: Vec<[u8; 65]>,
+        fee_rate: u64,
+    ) -> Result<Transaction> {
+        let sealed_tx = tx_skeleton.seal(signature_entries, signatures)?;
+        // check fee rate
+        sealed_tx.check_fee_rate(fee_rate)?;
+        Ok(sealed_tx.transaction)
+    }
+
+    // messages to sign for each signature entry
+    fn signing_messages(
+        tx_skeleton: &TransactionSkeleton,
+    ) -> Result<(Vec<SignatureEntry>, Vec<[u8; 32]>)> {
         let signature_entries = tx_skeleton.signature_entries();
         let dummy_signatures = {
             let mut sigs = Vec::new();
@@ -69,7 +108,7 @@ impl Wallet {
             hasher.finalize(&mut hash);
             hash
         };
-        let mut signatures = Vec::with_capacity(signature_entries.len());
+        let mut messages = Vec::with_capacity(signature_entries.len());
         for entry in &signature_entries {
             let mut hasher = new_blake2b();
             // hash tx_hash
@@ -96,14 +135,8 @@ impl Wallet {
             }
             let mut message = [0u8; 32];
             hasher.finalize(&mut message);
-            // sign tx
-            let signature = self.sign_message(message)?;
-            signatures.push(signature);
+            messages.push(message);
         }
-        // seal
-        let sealed_tx = tx_skeleton.seal(&signature_entries, signatures)?;
-        // check fee rate
-        sealed_tx.check_fee_rate()?;
-        Ok(sealed_tx.transaction)
+        Ok((signature_entries, messages))
     }
 }